@@ -150,6 +150,7 @@ mod tests {
             annotations: BTreeMap::new(),
             diff: "".to_string(),
             base_content: "".to_string(),
+            char_spans: Vec::new(),
         };
 
         let api_record = ApiFileRecord::from(&file_diff);
@@ -170,6 +171,7 @@ mod tests {
             annotations,
             diff: "diff content".to_string(),
             base_content: "base content".to_string(),
+            char_spans: Vec::new(),
         };
 
         let api_record = ApiFileRecord::from(&file_diff);
@@ -195,6 +197,7 @@ mod tests {
             annotations,
             diff: "diff".to_string(),
             base_content: "base".to_string(),
+            char_spans: Vec::new(),
         };
 
         let api_record = ApiFileRecord::from(&file_diff);
@@ -236,6 +239,7 @@ mod tests {
             annotations,
             diff: String::new(),
             base_content: String::new(),
+            char_spans: Vec::new(),
         };
 
         let api_record = ApiFileRecord::from(&file_diff);
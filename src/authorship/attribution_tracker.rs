@@ -3,6 +3,7 @@
 //! This library maintains attribution ranges as files are edited, preserving
 //! authorship information even through moves, edits, and whitespace changes.
 
+use crate::authorship::authorship_log::CharSpan;
 use crate::authorship::imara_diff_utils::{ByteDiff, ByteDiffOp, DiffOp, capture_diff_slices};
 use crate::authorship::move_detection::{DeletedLine, InsertedLine, detect_moves};
 use crate::authorship::working_log::CheckpointKind;
@@ -2001,6 +2002,85 @@ pub fn attributions_to_line_attributions(
     merged_line_authors
 }
 
+/// Split lines whose characters came from more than one author into per-author spans.
+/// Characters not covered by any attribution are human-authored, and where attributions
+/// overlap the newest one wins. Only lines where at least two authors wrote non-whitespace
+/// characters are returned; every other line is already exact at line granularity.
+pub fn attributions_to_mixed_line_spans(
+    attributions: &[Attribution],
+    content: &str,
+) -> Vec<CharSpan> {
+    let mut spans = Vec::new();
+    if content.is_empty() || attributions.is_empty() {
+        return spans;
+    }
+
+    let human = CheckpointKind::Human.to_str();
+    let boundaries = LineBoundaries::new(content);
+
+    let mut sorted_indices: Vec<usize> = (0..attributions.len()).collect();
+    sorted_indices.sort_by_key(|&idx| (attributions[idx].start, attributions[idx].end, idx));
+    let mut next_idx = 0usize;
+    let mut active_indices: Vec<usize> = Vec::new();
+
+    for line_num in 1..=boundaries.line_count() {
+        let Some((line_start, line_end)) = boundaries.get_line_range(line_num) else {
+            continue;
+        };
+
+        while next_idx < sorted_indices.len()
+            && attributions[sorted_indices[next_idx]].start < line_end
+        {
+            active_indices.push(sorted_indices[next_idx]);
+            next_idx += 1;
+        }
+        active_indices.retain(|&attr_idx| {
+            let attr = &attributions[attr_idx];
+            attr.start < line_end && attr.end > line_start
+        });
+        if active_indices.is_empty() {
+            continue;
+        }
+
+        let line = content[line_start..line_end].trim_end_matches(['\n', '\r']);
+        let mut line_spans: Vec<CharSpan> = Vec::new();
+        let mut authors_with_text: Vec<&str> = Vec::new();
+
+        for (column, (offset, ch)) in line.char_indices().enumerate() {
+            let pos = line_start + offset;
+            let author = active_indices
+                .iter()
+                .filter(|&&attr_idx| {
+                    let attr = &attributions[attr_idx];
+                    attr.start <= pos && pos < attr.end
+                })
+                .max_by_key(|&&attr_idx| (attributions[attr_idx].ts, attr_idx))
+                .map(|&attr_idx| attributions[attr_idx].author_id.as_str())
+                .unwrap_or(human.as_str());
+
+            if !ch.is_whitespace() && !authors_with_text.contains(&author) {
+                authors_with_text.push(author);
+            }
+            let column = column as u32;
+            match line_spans.last_mut() {
+                Some(span) if span.author == author => span.end = column + 1,
+                _ => line_spans.push(CharSpan {
+                    line: line_num,
+                    start: column,
+                    end: column + 1,
+                    author: author.to_string(),
+                }),
+            }
+        }
+
+        if authors_with_text.len() > 1 {
+            spans.extend(line_spans);
+        }
+    }
+
+    spans
+}
+
 /// Find the dominant author for a specific line from overlapping attribution candidates.
 fn find_dominant_author_for_line_candidates(
     line_start: usize,
@@ -2570,4 +2650,64 @@ mod tests {
         assert_eq!(ai_block.start_line, 2);
         assert_eq!(ai_block.end_line, 17);
     }
+
+    #[test]
+    fn test_attributions_to_mixed_line_spans() {
+        let content = "let total = compute(a);\nlet ai_only = 1;\n";
+        // AI wrote both lines, then a human replaced `compute` on the first line
+        let attributions = vec![
+            Attribution::new(0, 12, "ai1".to_string(), TEST_TS),
+            Attribution::new(12, 19, "human".to_string(), TEST_TS + 1),
+            Attribution::new(19, content.len(), "ai1".to_string(), TEST_TS),
+        ];
+
+        let spans = attributions_to_mixed_line_spans(&attributions, content);
+
+        // Single-author lines are left to line-level attestations
+        assert_eq!(
+            spans,
+            vec![
+                CharSpan {
+                    line: 1,
+                    start: 0,
+                    end: 12,
+                    author: "ai1".to_string(),
+                },
+                CharSpan {
+                    line: 1,
+                    start: 12,
+                    end: 19,
+                    author: "human".to_string(),
+                },
+                CharSpan {
+                    line: 1,
+                    start: 19,
+                    end: 23,
+                    author: "ai1".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attributions_to_mixed_line_spans_uses_char_columns_and_latest_author() {
+        // `é` is two bytes; uncovered text is human and later attributions win overlaps
+        let content = "é = ai(b)\n";
+        let attributions = vec![
+            Attribution::new(0, 11, "ai1".to_string(), TEST_TS),
+            Attribution::new(5, 7, "ai2".to_string(), TEST_TS + 1),
+        ];
+
+        let spans = attributions_to_mixed_line_spans(&attributions, content);
+
+        let summary: Vec<(u32, u32, &str)> = spans
+            .iter()
+            .map(|span| (span.start, span.end, span.author.as_str()))
+            .collect();
+        assert_eq!(summary, vec![(0, 4, "ai1"), (4, 6, "ai2"), (6, 9, "ai1")]);
+
+        // Whitespace-only differences don't make a line mixed
+        let attributions = vec![Attribution::new(0, 9, "ai1".to_string(), TEST_TS)];
+        assert!(attributions_to_mixed_line_spans(&attributions, "let x = 1 \n").is_empty());
+    }
 }
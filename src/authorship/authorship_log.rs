@@ -186,6 +186,18 @@ impl fmt::Display for LineRange {
     }
 }
 
+/// Intra-line authorship for a line whose characters came from more than one author.
+/// Columns are 0-indexed character (not byte) offsets within the line, end exclusive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharSpan {
+    /// 1-indexed line number
+    pub line: u32,
+    pub start: u32,
+    pub end: u32,
+    /// Prompt hash, or "human" for human-authored characters
+    pub author: String,
+}
+
 /// Prompt session details stored in the top-level prompts map keyed by short hash (agent_id + tool)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PromptRecord {
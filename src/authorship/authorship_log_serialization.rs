@@ -1,4 +1,4 @@
-use crate::authorship::authorship_log::{Author, LineRange, PromptRecord};
+use crate::authorship::authorship_log::{Author, CharSpan, LineRange, PromptRecord};
 use crate::authorship::working_log::CheckpointKind;
 use crate::git::repository::Repository;
 use serde::{Deserialize, Serialize};
@@ -9,8 +9,25 @@ use std::io::{BufRead, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Authorship log format version identifier
+///
+/// Stays at 3.0.0 even though logs may carry the 3.1.0 `line_states` and `char_spans`
+/// extensions: released readers compare this string for equality and would reject anything
+/// else, while they ignore the extra metadata fields.
 pub const AUTHORSHIP_LOG_VERSION: &str = "authorship/3.0.0";
 
+/// Whether logs with this schema version can be read. Minor versions within 3.x only add
+/// optional metadata fields, so every `authorship/3.x.y` log is readable.
+pub fn is_supported_schema_version(schema_version: &str) -> bool {
+    schema_version
+        .strip_prefix("authorship/3.")
+        .is_some_and(|rest| {
+            let mut parts = rest.split('.');
+            let minor_patch = (parts.next(), parts.next(), parts.next());
+            matches!(minor_patch, (Some(minor), Some(patch), None)
+                if minor.parse::<u32>().is_ok() && patch.parse::<u32>().is_ok())
+        })
+}
+
 #[cfg(all(debug_assertions, test))]
 pub const GIT_AI_VERSION: &str = "development";
 
@@ -27,6 +44,13 @@ pub struct AuthorshipMetadata {
     pub git_ai_version: Option<String>,
     pub base_commit_sha: String,
     pub prompts: BTreeMap<String, PromptRecord>,
+    /// Per-file character spans for lines with more than one author. Optional extension:
+    /// readers that ignore it still get the line-level attestations above.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub char_spans: BTreeMap<String, Vec<CharSpan>>,
+    /// Per-file provenance for AI-generated lines (3.1.0 extension)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub line_states: BTreeMap<String, Vec<LineStateEntry>>,
 }
 
 impl AuthorshipMetadata {
@@ -36,6 +60,8 @@ impl AuthorshipMetadata {
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
             base_commit_sha: String::new(),
             prompts: BTreeMap::new(),
+            char_spans: BTreeMap::new(),
+            line_states: BTreeMap::new(),
         }
     }
 }
@@ -91,6 +117,61 @@ impl AttestationEntry {
     }
 }
 
+/// Whether AI-generated lines were committed as written or after a human edited them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineState {
+    /// Committed as the AI wrote it. These lines are also in the attestation section.
+    #[serde(rename = "ai")]
+    Ai,
+    /// Edited by a human after the AI wrote it. These lines are not attested, so readers of
+    /// 3.0.0 logs keep treating them as human-authored.
+    #[serde(rename = "ai+human-edited")]
+    AiHumanEdited,
+    /// A state added by a later minor version
+    #[serde(other)]
+    Unknown,
+}
+
+/// Provenance for a set of lines generated by one prompt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineStateEntry {
+    /// Prompt hash of the session that generated the lines
+    pub hash: String,
+    /// Serialized like the attestation section, e.g. "1-4,9"
+    #[serde(with = "line_ranges_string")]
+    pub lines: Vec<LineRange>,
+    pub state: LineState,
+    /// Unix seconds of the session's last checkpoint that touched the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_timestamp: Option<u64>,
+    /// The human who edited the lines (`ai+human-edited` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub human_author: Option<String>,
+    /// Unix seconds of the last human checkpoint that touched the file (`ai+human-edited` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub human_edit_timestamp: Option<u64>,
+}
+
+mod line_ranges_string {
+    use super::{format_line_ranges, parse_line_ranges};
+    use crate::authorship::authorship_log::LineRange;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        ranges: &[LineRange],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_line_ranges(ranges))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<LineRange>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_line_ranges(&value).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Per-file attestation data
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileAttestation {
@@ -271,7 +352,7 @@ impl AuthorshipLog {
                                 .unwrap_or_default();
                         let result = if let Some(latest_sha) = shas.first() {
                             if let Some(authorship_log) =
-                                crate::git::refs::get_authorship(repo, latest_sha)
+                                crate::git::refs::get_authorship_merged(repo, latest_sha)
                             {
                                 authorship_log.metadata.prompts.get(&entry.hash).cloned()
                             } else {
@@ -734,6 +815,133 @@ mod tests {
         assert_debug_snapshot!(serialized);
     }
 
+    #[test]
+    fn test_char_spans_roundtrip_and_omitted_when_empty() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "abc123".to_string();
+
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(!serialized.contains("char_spans"));
+
+        log.metadata.char_spans.insert(
+            "src/main.rs".to_string(),
+            vec![
+                CharSpan {
+                    line: 3,
+                    start: 0,
+                    end: 12,
+                    author: "xyzAbc".to_string(),
+                },
+                CharSpan {
+                    line: 3,
+                    start: 12,
+                    end: 17,
+                    author: "human".to_string(),
+                },
+            ],
+        );
+
+        let serialized = log.serialize_to_string().unwrap();
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        assert_eq!(deserialized.metadata.char_spans, log.metadata.char_spans);
+    }
+
+    #[test]
+    fn test_deserialize_3_0_0_log() {
+        let content = r#"src/main.rs
+  abcd1234abcd1234 1-3
+---
+{
+  "schema_version": "authorship/3.0.0",
+  "git_ai_version": "1.0.23",
+  "base_commit_sha": "abc123",
+  "prompts": {}
+}"#;
+
+        let log = AuthorshipLog::deserialize_from_string(content).unwrap();
+        assert_eq!(log.metadata.schema_version, "authorship/3.0.0");
+        assert!(is_supported_schema_version(&log.metadata.schema_version));
+        assert!(log.metadata.line_states.is_empty());
+        assert_eq!(
+            log.attestations[0].entries[0].line_ranges,
+            vec![LineRange::Range(1, 3)]
+        );
+    }
+
+    #[test]
+    fn test_is_supported_schema_version() {
+        assert!(is_supported_schema_version(AUTHORSHIP_LOG_VERSION));
+        assert!(is_supported_schema_version("authorship/3.0.0"));
+        assert!(is_supported_schema_version("authorship/3.7.2"));
+        assert!(!is_supported_schema_version("authorship/2.0.0"));
+        assert!(!is_supported_schema_version("authorship/4.0.0"));
+        assert!(!is_supported_schema_version("authorship/3.1"));
+        assert!(!is_supported_schema_version("3"));
+    }
+
+    #[test]
+    fn test_line_states_roundtrip() {
+        let mut log = AuthorshipLog::new();
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(!serialized.contains("line_states"));
+
+        log.metadata.line_states.insert(
+            "src/main.rs".to_string(),
+            vec![
+                LineStateEntry {
+                    hash: "abcd1234abcd1234".to_string(),
+                    lines: vec![LineRange::Range(1, 10), LineRange::Single(15)],
+                    state: LineState::Ai,
+                    ai_timestamp: Some(1764897758),
+                    human_author: None,
+                    human_edit_timestamp: None,
+                },
+                LineStateEntry {
+                    hash: "abcd1234abcd1234".to_string(),
+                    lines: vec![LineRange::Range(11, 12)],
+                    state: LineState::AiHumanEdited,
+                    ai_timestamp: Some(1764897758),
+                    human_author: Some("Developer".to_string()),
+                    human_edit_timestamp: Some(1764898010),
+                },
+            ],
+        );
+
+        let serialized = log.serialize_to_string().unwrap();
+        let metadata: serde_json::Value =
+            serde_json::from_str(serialized.split("---\n").nth(1).unwrap()).unwrap();
+        // Released readers only accept 3.0.0, extensions or not
+        assert_eq!(metadata["schema_version"], "authorship/3.0.0");
+        let states = &metadata["line_states"]["src/main.rs"];
+        assert_eq!(states[0]["lines"], "1-10,15");
+        assert_eq!(states[0]["state"], "ai");
+        assert!(states[0].get("human_author").is_none());
+        assert_eq!(states[1]["state"], "ai+human-edited");
+        assert_eq!(states[1]["human_author"], "Developer");
+
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        assert_eq!(deserialized.metadata.line_states, log.metadata.line_states);
+    }
+
+    #[test]
+    fn test_unknown_line_state_is_tolerated() {
+        let content = r#"---
+{
+  "schema_version": "authorship/3.2.0",
+  "base_commit_sha": "abc123",
+  "prompts": {},
+  "line_states": {
+    "src/main.rs": [{ "hash": "abcd1234abcd1234", "lines": "4", "state": "ai+reviewed" }]
+  }
+}"#;
+
+        let log = AuthorshipLog::deserialize_from_string(content).unwrap();
+        assert_eq!(
+            log.metadata.line_states["src/main.rs"][0].state,
+            LineState::Unknown
+        );
+    }
+
     #[test]
     fn test_line_range_sorting() {
         // Test that ranges are sorted correctly: single ranges and ranges by lowest bound
@@ -79,7 +79,7 @@ pub fn diff_ai_accepted_stats(
     Ok(stats)
 }
 
-fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
+pub(crate) fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
     if lines.is_empty() {
         return Vec::new();
     }
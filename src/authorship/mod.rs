@@ -6,6 +6,8 @@ pub mod ignore;
 pub mod imara_diff_utils;
 pub mod internal_db;
 pub mod move_detection;
+pub mod note_signing;
+pub mod patch_authorship;
 pub mod post_commit;
 pub mod pre_commit;
 pub mod prompt_utils;
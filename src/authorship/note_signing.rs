@@ -0,0 +1,455 @@
+//! Signing and verification of authorship notes.
+//!
+//! When `git-ai.signNotes` is enabled, every note written is signed with the key git would
+//! use for commits (`gpg.format`, `user.signingkey`, `gpg.program`, `gpg.ssh.program`,
+//! `gpg.x509.program`). The signature covers the exact unsigned note text and is stored as
+//! a final `signature` field of the metadata JSON, on a single line:
+//!
+//! ```text
+//!   "prompts": { ... },
+//!   "signature": {"format":"ssh","signature":"-----BEGIN SSH SIGNATURE-----\n..."}
+//! }
+//! ```
+//!
+//! Readers that don't know about signatures ignore the field, and any log that is parsed
+//! and re-serialized (e.g. during a rebase) drops it, so a stale signature is never kept.
+//!
+//! A signature only vouches for the commit named in the note's `base_commit_sha`, so a
+//! signed note copied onto another commit verifies as invalid. SSH signatures use their own
+//! `git-ai-note` namespace, so commit and tag signatures can't be passed off as note
+//! signatures or the other way round.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use crate::utils::debug_log;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Git config key that turns on signing of new authorship notes
+pub const SIGN_NOTES_CONFIG_KEY: &str = "git-ai.signNotes";
+
+const SIGNATURE_FIELD_PREFIX: &str = ",\n  \"signature\": ";
+const SSH_SIGNATURE_NAMESPACE: &str = "git-ai-note";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteSignature {
+    /// `openpgp`, `x509` or `ssh`, as in git's `gpg.format`
+    pub format: String,
+    /// Armored detached signature over the unsigned note text
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Valid {
+        signer: String,
+    },
+    Invalid {
+        reason: String,
+    },
+    /// The signature matches the note, but the signer could not be checked against a
+    /// trusted key list
+    Untrusted {
+        reason: String,
+    },
+    Missing,
+}
+
+pub fn signing_enabled(repo: &Repository) -> bool {
+    repo.config_get_str(SIGN_NOTES_CONFIG_KEY)
+        .ok()
+        .flatten()
+        .is_some_and(|value| {
+            matches!(
+                value.trim().to_lowercase().as_str(),
+                "true" | "yes" | "on" | "1"
+            )
+        })
+}
+
+/// Sign `content` if signing is enabled. A signing failure is reported and the note is
+/// written unsigned, so `git-ai verify` will flag it as missing a signature.
+pub fn maybe_sign_note<'a>(repo: &Repository, content: &'a str) -> Cow<'a, str> {
+    if !signing_enabled(repo) {
+        return Cow::Borrowed(content);
+    }
+    match sign_note(repo, content) {
+        Ok(signed) => Cow::Owned(signed),
+        Err(e) => {
+            eprintln!("Warning: Failed to sign authorship note: {}", e);
+            Cow::Borrowed(content)
+        }
+    }
+}
+
+/// Sign an unsigned note and return the note with its signature attached
+pub fn sign_note(repo: &Repository, content: &str) -> Result<String, GitAiError> {
+    let payload = content.trim_end();
+    if split_signature(payload).is_some() {
+        return Ok(payload.to_string());
+    }
+
+    let format = signature_format(repo);
+    let signature = match format.as_str() {
+        "ssh" => ssh_sign(repo, payload)?,
+        _ => gpg_sign(repo, &format, payload)?,
+    };
+    attach_signature(payload, &NoteSignature { format, signature })
+}
+
+/// Check the signature attached to the note of `commit_sha`
+pub fn verify_note(repo: &Repository, commit_sha: &str, content: &str) -> SignatureStatus {
+    let Some((payload, signature)) = split_signature(content.trim_end()) else {
+        return SignatureStatus::Missing;
+    };
+    if let Some(reason) = commit_mismatch(commit_sha, &payload) {
+        return SignatureStatus::Invalid { reason };
+    }
+
+    let result = match signature.format.as_str() {
+        "ssh" => ssh_verify(repo, &payload, &signature.signature),
+        "openpgp" | "x509" => gpg_verify(repo, &signature.format, &payload, &signature.signature),
+        other => Ok(SignatureStatus::Invalid {
+            reason: format!("unknown signature format '{}'", other),
+        }),
+    };
+    result.unwrap_or_else(|e| SignatureStatus::Invalid {
+        reason: e.to_string(),
+    })
+}
+
+/// Why a signed note doesn't belong to `commit_sha`, if it doesn't
+fn commit_mismatch(commit_sha: &str, payload: &str) -> Option<String> {
+    match AuthorshipLog::deserialize_from_string(payload) {
+        Ok(log) if log.metadata.base_commit_sha == commit_sha => None,
+        Ok(log) if log.metadata.base_commit_sha.is_empty() => {
+            Some("signed note does not name its commit".to_string())
+        }
+        Ok(log) => Some(format!(
+            "note was signed for commit {}",
+            log.metadata.base_commit_sha
+        )),
+        Err(e) => Some(format!("signed note is not a valid authorship log: {}", e)),
+    }
+}
+
+/// Append the signature field to the metadata JSON of an unsigned note
+fn attach_signature(payload: &str, signature: &NoteSignature) -> Result<String, GitAiError> {
+    let body = payload.strip_suffix("\n}").ok_or_else(|| {
+        GitAiError::Generic("Authorship note does not end with a JSON object".to_string())
+    })?;
+    Ok(format!(
+        "{}{}{}\n}}",
+        body,
+        SIGNATURE_FIELD_PREFIX,
+        serde_json::to_string(signature)?
+    ))
+}
+
+/// Split a signed note into the unsigned note text and its signature
+fn split_signature(content: &str) -> Option<(String, NoteSignature)> {
+    let body = content.strip_suffix("\n}")?;
+    let idx = body.rfind(SIGNATURE_FIELD_PREFIX)?;
+    let signature_json = &body[idx + SIGNATURE_FIELD_PREFIX.len()..];
+    if signature_json.contains('\n') {
+        return None;
+    }
+    let signature = serde_json::from_str(signature_json).ok()?;
+    Some((format!("{}\n}}", &body[..idx]), signature))
+}
+
+fn signature_format(repo: &Repository) -> String {
+    repo.config_get_str("gpg.format")
+        .ok()
+        .flatten()
+        .map(|format| format.trim().to_lowercase())
+        .unwrap_or_else(|| "openpgp".to_string())
+}
+
+fn config_or(repo: &Repository, key: &str, default: &str) -> String {
+    repo.config_get_str(key)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| default.to_string())
+}
+
+fn gpg_program(repo: &Repository, format: &str) -> String {
+    if format == "x509" {
+        config_or(repo, "gpg.x509.program", "gpgsm")
+    } else {
+        repo.config_get_str("gpg.openpgp.program")
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| config_or(repo, "gpg.program", "gpg"))
+    }
+}
+
+fn run_with_stdin(program: &str, args: &[String], stdin: &[u8]) -> Result<Output, GitAiError> {
+    debug_log(&format!("Running {} {:?}", program, args));
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| GitAiError::Generic(format!("Failed to run {}: {}", program, e)))?;
+    if let Some(mut child_stdin) = child.stdin.take() {
+        child_stdin.write_all(stdin)?;
+    }
+    Ok(child.wait_with_output()?)
+}
+
+/// A temporary file that is removed when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn with_contents(contents: &str) -> Result<Self, GitAiError> {
+        let path = std::env::temp_dir().join(format!("git-ai-sig-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents)?;
+        Ok(Self(path))
+    }
+
+    fn arg(&self) -> String {
+        self.0.to_string_lossy().to_string()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn gpg_sign(repo: &Repository, format: &str, payload: &str) -> Result<String, GitAiError> {
+    let mut args = vec!["--status-fd=2".to_string(), "-bsa".to_string()];
+    if let Some(key) = repo.config_get_str("user.signingkey")? {
+        args.push("-u".to_string());
+        args.push(key);
+    }
+
+    let program = gpg_program(repo, format);
+    let output = run_with_stdin(&program, &args, payload.as_bytes())?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || !stderr.contains("[GNUPG:] SIG_CREATED ") {
+        return Err(GitAiError::Generic(format!(
+            "{} failed to sign the note: {}",
+            program,
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn gpg_verify(
+    repo: &Repository,
+    format: &str,
+    payload: &str,
+    signature: &str,
+) -> Result<SignatureStatus, GitAiError> {
+    let signature_file = TempFile::with_contents(signature)?;
+    let args = vec![
+        "--status-fd=1".to_string(),
+        "--verify".to_string(),
+        signature_file.arg(),
+        "-".to_string(),
+    ];
+    let output = run_with_stdin(&gpg_program(repo, format), &args, payload.as_bytes())?;
+    let status = String::from_utf8_lossy(&output.stdout);
+
+    for line in status.lines() {
+        if let Some(rest) = line.strip_prefix("[GNUPG:] GOODSIG ") {
+            let signer = rest.split_once(' ').map(|(_, uid)| uid).unwrap_or(rest);
+            return Ok(SignatureStatus::Valid {
+                signer: signer.to_string(),
+            });
+        }
+        if line.starts_with("[GNUPG:] BADSIG ") {
+            return Ok(SignatureStatus::Invalid {
+                reason: "signature does not match the note".to_string(),
+            });
+        }
+        if line.starts_with("[GNUPG:] NO_PUBKEY ") {
+            return Ok(SignatureStatus::Invalid {
+                reason: "no public key for the signing key".to_string(),
+            });
+        }
+    }
+    Ok(SignatureStatus::Invalid {
+        reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    })
+}
+
+/// Resolve `user.signingkey` for ssh: a key file path, or a literal public key whose
+/// private half lives in ssh-agent (the same two forms git accepts)
+fn ssh_signing_key(repo: &Repository) -> Result<(String, Option<TempFile>), GitAiError> {
+    let key = repo.config_get_str("user.signingkey")?.ok_or_else(|| {
+        GitAiError::Generic("user.signingkey must be set to sign with gpg.format=ssh".to_string())
+    })?;
+    let key = key.trim();
+
+    let literal = key.strip_prefix("key::").or_else(|| {
+        (key.starts_with("ssh-") || key.starts_with("ecdsa-") || key.starts_with("sk-"))
+            .then_some(key)
+    });
+    if let Some(literal) = literal {
+        let key_file = TempFile::with_contents(literal)?;
+        return Ok((key_file.arg(), Some(key_file)));
+    }
+
+    let path = match key.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest).to_string_lossy().to_string())
+            .unwrap_or_else(|| key.to_string()),
+        None => key.to_string(),
+    };
+    Ok((path, None))
+}
+
+fn ssh_sign(repo: &Repository, payload: &str) -> Result<String, GitAiError> {
+    let (key_path, literal_key) = ssh_signing_key(repo)?;
+    let mut args = vec![
+        "-Y".to_string(),
+        "sign".to_string(),
+        "-n".to_string(),
+        SSH_SIGNATURE_NAMESPACE.to_string(),
+        "-f".to_string(),
+        key_path,
+    ];
+    if literal_key.is_some() {
+        args.push("-U".to_string());
+    }
+
+    let program = config_or(repo, "gpg.ssh.program", "ssh-keygen");
+    let output = run_with_stdin(&program, &args, payload.as_bytes())?;
+    if !output.status.success() {
+        return Err(GitAiError::Generic(format!(
+            "{} failed to sign the note: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+fn ssh_verify(
+    repo: &Repository,
+    payload: &str,
+    signature: &str,
+) -> Result<SignatureStatus, GitAiError> {
+    let program = config_or(repo, "gpg.ssh.program", "ssh-keygen");
+    let signature_file = TempFile::with_contents(signature)?;
+
+    let Some(allowed_signers) = repo.config_get_str("gpg.ssh.allowedSignersFile")? else {
+        // Without an allowed signers file only the signature itself can be checked, which
+        // any key passes, so the signer stays unverified
+        let args = vec![
+            "-Y".to_string(),
+            "check-novalidate".to_string(),
+            "-n".to_string(),
+            SSH_SIGNATURE_NAMESPACE.to_string(),
+            "-s".to_string(),
+            signature_file.arg(),
+        ];
+        let output = run_with_stdin(&program, &args, payload.as_bytes())?;
+        return Ok(if output.status.success() {
+            SignatureStatus::Untrusted {
+                reason: "signer not checked: gpg.ssh.allowedSignersFile is not set".to_string(),
+            }
+        } else {
+            SignatureStatus::Invalid {
+                reason: "signature does not match the note".to_string(),
+            }
+        });
+    };
+
+    let find_args = vec![
+        "-Y".to_string(),
+        "find-principals".to_string(),
+        "-f".to_string(),
+        allowed_signers.clone(),
+        "-s".to_string(),
+        signature_file.arg(),
+    ];
+    let principals = run_with_stdin(&program, &find_args, b"")?;
+    let principals = String::from_utf8_lossy(&principals.stdout).to_string();
+
+    for principal in principals.lines().filter(|line| !line.trim().is_empty()) {
+        let args = vec![
+            "-Y".to_string(),
+            "verify".to_string(),
+            "-n".to_string(),
+            SSH_SIGNATURE_NAMESPACE.to_string(),
+            "-f".to_string(),
+            allowed_signers.clone(),
+            "-I".to_string(),
+            principal.to_string(),
+            "-s".to_string(),
+            signature_file.arg(),
+        ];
+        let output = run_with_stdin(&program, &args, payload.as_bytes())?;
+        if output.status.success() {
+            return Ok(SignatureStatus::Valid {
+                signer: principal.to_string(),
+            });
+        }
+    }
+
+    Ok(SignatureStatus::Invalid {
+        reason: if principals.trim().is_empty() {
+            "signing key is not in gpg.ssh.allowedSignersFile".to_string()
+        } else {
+            "signature does not match the note".to_string()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNSIGNED: &str = "src/lib.rs\n  abcd1234abcd1234 1-2\n---\n{\n  \"schema_version\": \"authorship/3.0.0\",\n  \"base_commit_sha\": \"\",\n  \"prompts\": {}\n}";
+
+    #[test]
+    fn test_attach_and_split_signature_round_trip() {
+        let signature = NoteSignature {
+            format: "ssh".to_string(),
+            signature: "-----BEGIN SSH SIGNATURE-----\nabc\n-----END SSH SIGNATURE-----\n"
+                .to_string(),
+        };
+        let signed = attach_signature(UNSIGNED, &signature).unwrap();
+
+        // The signed note is still a valid authorship log
+        let log = crate::authorship::authorship_log_serialization::AuthorshipLog::deserialize_from_string(&signed)
+            .unwrap();
+        assert_eq!(log.attestations.len(), 1);
+
+        let (payload, parsed) = split_signature(&signed).unwrap();
+        assert_eq!(payload, UNSIGNED);
+        assert_eq!(parsed, signature);
+    }
+
+    #[test]
+    fn test_commit_mismatch() {
+        let note = UNSIGNED.replace(
+            "\"base_commit_sha\": \"\"",
+            "\"base_commit_sha\": \"abc123\"",
+        );
+        assert_eq!(commit_mismatch("abc123", &note), None);
+        assert_eq!(
+            commit_mismatch("def456", &note),
+            Some("note was signed for commit abc123".to_string())
+        );
+        assert!(commit_mismatch("abc123", UNSIGNED).is_some());
+    }
+
+    #[test]
+    fn test_split_signature_on_unsigned_note() {
+        assert!(split_signature(UNSIGNED).is_none());
+        assert!(split_signature("not a note").is_none());
+    }
+}
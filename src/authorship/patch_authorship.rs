@@ -0,0 +1,425 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, FileAttestation,
+};
+use crate::error::GitAiError;
+use crate::git::refs::get_authorship;
+use crate::git::repository::Repository;
+use crate::git::rewrite_log::AmPatch;
+use std::collections::HashSet;
+
+/// Markers around the authorship payload in a patch. The payload sits between the `---`
+/// separator and the diff, a section `git am` drops from the commit message and
+/// `git apply` ignores.
+pub const PAYLOAD_BEGIN: &str = "Git-AI-Authorship-Begin";
+pub const PAYLOAD_END: &str = "Git-AI-Authorship-End";
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Build the patch payload for `commit_sha`, or None when the commit has no authorship note.
+///
+/// Line numbers in the payload are positions among the lines the commit adds to each file
+/// (1 = first added line), so they stay valid when the patch applies at an offset. Prompt
+/// transcripts are dropped to keep patches small.
+pub fn build_patch_payload(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<Option<String>, GitAiError> {
+    let Some(authorship_log) = get_authorship(repo, commit_sha) else {
+        return Ok(None);
+    };
+
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let parent = if commit.parent_count()? > 0 {
+        commit.parent(0)?.id()
+    } else {
+        EMPTY_TREE_HASH.to_string()
+    };
+    let added_lines = repo.diff_added_lines(&parent, commit_sha, None)?;
+
+    let mut payload_log = remap_attestations(&authorship_log, |file, line| {
+        added_lines
+            .get(file)
+            .and_then(|lines| lines.binary_search(&line).ok())
+            .map(|index| index as u32 + 1)
+    });
+    for prompt in payload_log.metadata.prompts.values_mut() {
+        prompt.messages.clear();
+    }
+
+    if payload_log.attestations.is_empty() && payload_log.metadata.prompts.is_empty() {
+        return Ok(None);
+    }
+
+    payload_log
+        .serialize_to_string()
+        .map(Some)
+        .map_err(|_| GitAiError::Generic("Failed to serialize patch authorship".to_string()))
+}
+
+/// Rebuild the authorship log for `commit_sha` from a patch payload by mapping each
+/// added-line position back onto the lines the applied commit added.
+pub fn authorship_from_patch_payload(
+    repo: &Repository,
+    payload: &str,
+    commit_sha: &str,
+) -> Result<AuthorshipLog, GitAiError> {
+    let payload_log = AuthorshipLog::deserialize_from_string(payload).map_err(|e| {
+        GitAiError::Generic(format!("Failed to parse patch authorship payload: {}", e))
+    })?;
+
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let parent = if commit.parent_count()? > 0 {
+        commit.parent(0)?.id()
+    } else {
+        EMPTY_TREE_HASH.to_string()
+    };
+    let added_lines = repo.diff_added_lines(&parent, commit_sha, None)?;
+
+    let mut authorship_log = remap_attestations(&payload_log, |file, position| {
+        added_lines
+            .get(file)
+            .and_then(|lines| lines.get(position.checked_sub(1)? as usize))
+            .copied()
+    });
+    authorship_log.metadata.base_commit_sha = commit_sha.to_string();
+    Ok(authorship_log)
+}
+
+/// Map every attested line through `map_line`, dropping lines that have no mapping,
+/// then drop prompts that no longer attest any line.
+fn remap_attestations(
+    authorship_log: &AuthorshipLog,
+    map_line: impl Fn(&str, u32) -> Option<u32>,
+) -> AuthorshipLog {
+    let mut remapped = AuthorshipLog {
+        attestations: Vec::new(),
+        metadata: authorship_log.metadata.clone(),
+    };
+
+    for file in &authorship_log.attestations {
+        let mut file_attestation = FileAttestation::new(file.file_path.clone());
+        for entry in &file.entries {
+            let mut lines: Vec<u32> = entry
+                .line_ranges
+                .iter()
+                .flat_map(|range| range.expand())
+                .filter_map(|line| map_line(&file.file_path, line))
+                .collect();
+            lines.sort_unstable();
+            lines.dedup();
+            if !lines.is_empty() {
+                file_attestation.add_entry(AttestationEntry::new(
+                    entry.hash.clone(),
+                    LineRange::compress_lines(&lines),
+                ));
+            }
+        }
+        if !file_attestation.entries.is_empty() {
+            remapped.attestations.push(file_attestation);
+        }
+    }
+
+    let attested: HashSet<&str> = remapped
+        .attestations
+        .iter()
+        .flat_map(|file| file.entries.iter().map(|entry| entry.hash.as_str()))
+        .collect();
+    let referenced_before: HashSet<&str> = authorship_log
+        .attestations
+        .iter()
+        .flat_map(|file| file.entries.iter().map(|entry| entry.hash.as_str()))
+        .collect();
+    // Keep metadata-only prompts (no attestations to begin with) as they were
+    remapped.metadata.prompts.retain(|hash, _| {
+        attested.contains(hash.as_str()) || !referenced_before.contains(hash.as_str())
+    });
+
+    remapped
+}
+
+/// Insert `payload` after the `---` separator of a format-patch message. Returns None
+/// when the message has no separator or already carries a payload.
+pub fn embed_payload(patch: &str, payload: &str) -> Option<String> {
+    if patch.lines().any(|line| line == PAYLOAD_BEGIN) {
+        return None;
+    }
+
+    let mut output = String::with_capacity(patch.len() + payload.len() + 64);
+    let mut inserted = false;
+    for line in patch.split_inclusive('\n') {
+        output.push_str(line);
+        if !inserted && line.trim_end_matches(['\r', '\n']) == "---" {
+            output.push_str(PAYLOAD_BEGIN);
+            output.push('\n');
+            output.push_str(payload.trim_end());
+            output.push('\n');
+            output.push_str(PAYLOAD_END);
+            output.push('\n');
+            inserted = true;
+        }
+    }
+
+    inserted.then_some(output)
+}
+
+/// Extract the payload embedded by `embed_payload`
+pub fn extract_payload(message: &str) -> Option<String> {
+    let mut lines = message.lines().map(|line| line.trim_end_matches('\r'));
+    lines.by_ref().find(|line| *line == PAYLOAD_BEGIN)?;
+
+    let mut payload = Vec::new();
+    for line in lines {
+        if line == PAYLOAD_END {
+            return Some(payload.join("\n"));
+        }
+        payload.push(line);
+    }
+    None
+}
+
+/// The sha of a format-patch mbox separator line:
+/// `From <sha> Mon Sep 17 00:00:00 2001`
+pub fn mbox_source_commit(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("From ")?;
+    let (sha, date) = rest.split_once(' ')?;
+    let is_sha = sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit());
+    (is_sha && date.trim_end() == "Mon Sep 17 00:00:00 2001").then_some(sha)
+}
+
+/// Strip the bracketed `[PATCH n/m]` style prefixes `git am` removes from subjects
+fn strip_subject_prefix(subject: &str) -> String {
+    let mut subject = subject.trim();
+    while let Some(rest) = subject.strip_prefix('[')
+        && let Some(end) = rest.find(']')
+    {
+        subject = rest[end + 1..].trim_start();
+    }
+    subject.to_string()
+}
+
+/// Split a format-patch mailbox into its messages
+pub fn parse_mbox(content: &str) -> Vec<AmPatch> {
+    let mut messages: Vec<(String, Vec<&str>)> = Vec::new();
+    for line in content.lines() {
+        if let Some(sha) = mbox_source_commit(line) {
+            messages.push((sha.to_string(), Vec::new()));
+        } else if let Some((_, lines)) = messages.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    messages
+        .into_iter()
+        .map(|(source_commit, lines)| {
+            let mut subject = String::new();
+            let mut in_subject = false;
+            for line in lines.iter().take_while(|line| !line.is_empty()) {
+                if let Some(value) = line.strip_prefix("Subject: ") {
+                    subject = value.to_string();
+                    in_subject = true;
+                } else if in_subject && line.starts_with([' ', '\t']) {
+                    // Folded header continuation
+                    subject.push(' ');
+                    subject.push_str(line.trim());
+                } else {
+                    in_subject = false;
+                }
+            }
+
+            AmPatch {
+                source_commit,
+                subject: strip_subject_prefix(&subject),
+                authorship: extract_payload(&lines.join("\n")),
+            }
+        })
+        .collect()
+}
+
+/// Pair applied commits with the patches they came from. Subjects are matched first since
+/// `--skip` drops patches; unmatched commits fall back to their position.
+pub fn pair_am_commits<'a>(
+    patches: &'a [AmPatch],
+    new_commits: &[(String, String)],
+) -> Vec<(&'a AmPatch, String)> {
+    let mut used = vec![false; patches.len()];
+    let mut pairs = Vec::new();
+
+    for (index, (commit_sha, summary)) in new_commits.iter().enumerate() {
+        let matched = patches
+            .iter()
+            .enumerate()
+            .position(|(i, patch)| !used[i] && patch.subject == *summary)
+            .or_else(|| (index < patches.len() && !used[index]).then_some(index));
+        if let Some(i) = matched {
+            used[i] = true;
+            pairs.push((&patches[i], commit_sha.clone()));
+        }
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::PromptRecord;
+    use crate::authorship::working_log::AgentId;
+    use std::collections::HashMap;
+
+    fn positions(lines: &[(&str, &[u32])]) -> HashMap<String, Vec<u32>> {
+        lines
+            .iter()
+            .map(|(file, lines)| (file.to_string(), lines.to_vec()))
+            .collect()
+    }
+
+    fn sample_log() -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "abc1234".to_string(),
+                vec![LineRange::Range(10, 12), LineRange::Single(20)],
+            ));
+        log.metadata.prompts.insert(
+            "abc1234".to_string(),
+            PromptRecord {
+                agent_id: AgentId {
+                    tool: "cursor".to_string(),
+                    id: "session".to_string(),
+                    model: "model".to_string(),
+                },
+                human_author: None,
+                messages: vec![],
+                total_additions: 4,
+                total_deletions: 0,
+                accepted_lines: 4,
+                overriden_lines: 0,
+                messages_url: None,
+            },
+        );
+        log
+    }
+
+    #[test]
+    fn test_remap_attestations_to_positions_and_back() {
+        let log = sample_log();
+        let source_added = positions(&[("src/lib.rs", &[10, 11, 12, 15, 20])]);
+        let relative = remap_attestations(&log, |file, line| {
+            source_added
+                .get(file)
+                .and_then(|lines| lines.binary_search(&line).ok())
+                .map(|i| i as u32 + 1)
+        });
+        assert_eq!(
+            relative.attestations[0].entries[0].line_ranges,
+            vec![LineRange::Range(1, 3), LineRange::Single(5)]
+        );
+
+        // The patch applied 7 lines further down
+        let target_added = positions(&[("src/lib.rs", &[17, 18, 19, 22, 27])]);
+        let restored = remap_attestations(&relative, |file, position| {
+            target_added
+                .get(file)
+                .and_then(|lines| lines.get(position.checked_sub(1)? as usize))
+                .copied()
+        });
+        assert_eq!(
+            restored.attestations[0].entries[0].line_ranges,
+            vec![LineRange::Range(17, 19), LineRange::Single(27)]
+        );
+        assert!(restored.metadata.prompts.contains_key("abc1234"));
+    }
+
+    #[test]
+    fn test_remap_attestations_drops_unmapped_prompts() {
+        let log = sample_log();
+        let restored = remap_attestations(&log, |_, _| None);
+        assert!(restored.attestations.is_empty());
+        assert!(restored.metadata.prompts.is_empty());
+    }
+
+    #[test]
+    fn test_embed_and_extract_payload() {
+        let patch = "From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001\n\
+                     From: Dev <dev@example.com>\n\
+                     Subject: [PATCH] Add parser\n\
+                     \n\
+                     ---\n \
+                     parser.rs | 2 ++\n\
+                     \n\
+                     diff --git a/parser.rs b/parser.rs\n";
+        let payload = sample_log().serialize_to_string().unwrap();
+
+        let embedded = embed_payload(patch, &payload).unwrap();
+        assert!(embedded.contains("---\nGit-AI-Authorship-Begin\nsrc/lib.rs\n"));
+        assert_eq!(extract_payload(&embedded).unwrap(), payload.trim_end());
+        assert!(embed_payload(&embedded, &payload).is_none());
+        assert!(embed_payload("Subject: no separator\n", &payload).is_none());
+    }
+
+    #[test]
+    fn test_parse_mbox() {
+        let mbox = "From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001\n\
+                    From: Dev <dev@example.com>\n\
+                    Subject: [PATCH 1/2] Add a rather long subject that git\n \
+                    folds over two lines\n\
+                    \n\
+                    ---\n\
+                    Git-AI-Authorship-Begin\n\
+                    payload\n\
+                    Git-AI-Authorship-End\n\
+                    From 89abcdef0123456789abcdef0123456789abcdef Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH 2/2] [RFC] Second\n\
+                    \n\
+                    ---\n";
+        let patches = parse_mbox(mbox);
+        assert_eq!(patches.len(), 2);
+        assert_eq!(
+            patches[0].subject,
+            "Add a rather long subject that git folds over two lines"
+        );
+        assert_eq!(patches[0].authorship.as_deref(), Some("payload"));
+        assert_eq!(
+            patches[1].source_commit,
+            "89abcdef0123456789abcdef0123456789abcdef"
+        );
+        assert_eq!(patches[1].subject, "Second");
+        assert!(patches[1].authorship.is_none());
+    }
+
+    #[test]
+    fn test_mbox_source_commit() {
+        assert_eq!(
+            mbox_source_commit(
+                "From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17 00:00:00 2001"
+            ),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert!(mbox_source_commit("From: Dev <dev@example.com>").is_none());
+        assert!(mbox_source_commit("From abc Mon Sep 17 00:00:00 2001").is_none());
+    }
+
+    #[test]
+    fn test_pair_am_commits_skipped_patch() {
+        let patch = |sha: &str, subject: &str| AmPatch {
+            source_commit: sha.to_string(),
+            subject: subject.to_string(),
+            authorship: None,
+        };
+        let patches = vec![
+            patch("a", "First"),
+            patch("b", "Second"),
+            patch("c", "Third"),
+        ];
+        let new_commits = vec![
+            ("n1".to_string(), "First".to_string()),
+            ("n3".to_string(), "Third".to_string()),
+        ];
+        let pairs = pair_am_commits(&patches, &new_commits);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].0.source_commit, "a");
+        assert_eq!(pairs[1].0.source_commit, "c");
+        assert_eq!(pairs[1].1, "n3");
+    }
+}
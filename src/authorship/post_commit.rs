@@ -10,6 +10,7 @@ use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::authorship::working_log::{Checkpoint, CheckpointKind, WorkingLogEntry};
 use crate::config::{Config, PromptStorageMode};
 use crate::error::GitAiError;
+use crate::git::prompt_refs::store_prompt_messages_as_blobs;
 use crate::git::refs::notes_add;
 use crate::git::repository::Repository;
 use crate::utils::debug_log;
@@ -145,9 +146,32 @@ pub fn post_commit(
         }
         PromptStorageMode::Notes => {
             // Store in notes: redact secrets but keep messages in notes
-            let count = redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
-            if count > 0 {
-                debug_log(&format!("Redacted {} secrets from prompts", count));
+            let report = redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
+            if report.total > 0 {
+                debug_log(&format!(
+                    "Redacted {} secrets from prompts ({})",
+                    report.total, report
+                ));
+            }
+        }
+        PromptStorageMode::Git => {
+            // Store redacted messages as blobs in refs/ai-prompts/blobs, referenced from notes
+            let report = redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
+            if report.total > 0 {
+                debug_log(&format!(
+                    "Redacted {} secrets from prompts ({})",
+                    report.total, report
+                ));
+            }
+            if let Err(e) =
+                store_prompt_messages_as_blobs(repo, &mut authorship_log.metadata.prompts)
+            {
+                debug_log(&format!(
+                    "[Warning] Failed to store prompt messages as blobs: {}",
+                    e
+                ));
+                // Never fall back to inlining messages in notes
+                strip_prompt_messages(&mut authorship_log.metadata.prompts);
             }
         }
         PromptStorageMode::Default => {
@@ -162,12 +186,11 @@ pub fn post_commit(
 
             if should_enqueue_cas {
                 // Redact secrets before uploading to CAS
-                let redaction_count =
-                    redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
-                if redaction_count > 0 {
+                let report = redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
+                if report.total > 0 {
                     debug_log(&format!(
-                        "Redacted {} secrets from prompts before CAS upload",
-                        redaction_count
+                        "Redacted {} secrets from prompts before CAS upload ({})",
+                        report.total, report
                     ));
                 }
 
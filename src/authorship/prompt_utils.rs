@@ -3,11 +3,16 @@ use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::transcript::AiTranscript;
 use crate::commands::checkpoint_agent::agent_presets::{
     ClaudePreset, CodexPreset, ContinueCliPreset, CursorPreset, DroidPreset, GeminiPreset,
-    GithubCopilotPreset,
+    GithubCopilotPreset, WindsurfPreset,
 };
+use crate::commands::checkpoint_agent::aider_preset::AiderPreset;
+use crate::commands::checkpoint_agent::amp_preset::AmpPreset;
+use crate::commands::checkpoint_agent::junie_preset::JuniePreset;
 use crate::commands::checkpoint_agent::opencode_preset::OpenCodePreset;
+use crate::commands::checkpoint_agent::rovodev_preset::RovoDevPreset;
 use crate::error::GitAiError;
-use crate::git::refs::{get_authorship, grep_ai_notes};
+use crate::git::prompt_refs::resolve_prompt_messages;
+use crate::git::refs::{get_authorship_merged, grep_ai_notes};
 use crate::git::repository::Repository;
 use crate::observability::log_error;
 use crate::utils::debug_log;
@@ -33,6 +38,8 @@ pub fn find_prompt(
 }
 
 /// Find a prompt in a specific commit
+///
+/// Messages stored as a prompt blob (`prompt_storage = "git"`) are loaded into the record.
 pub fn find_prompt_in_commit(
     repo: &Repository,
     prompt_id: &str,
@@ -43,7 +50,7 @@ pub fn find_prompt_in_commit(
     let commit_sha = commit.id();
 
     // Get the authorship log for this commit
-    let authorship_log = get_authorship(repo, &commit_sha).ok_or_else(|| {
+    let authorship_log = get_authorship_merged(repo, &commit_sha).ok_or_else(|| {
         GitAiError::Generic(format!(
             "No authorship data found for commit: {}",
             commit_rev
@@ -55,7 +62,11 @@ pub fn find_prompt_in_commit(
         .metadata
         .prompts
         .get(prompt_id)
-        .map(|prompt| (commit_sha, prompt.clone()))
+        .map(|prompt| {
+            let mut prompt = prompt.clone();
+            resolve_prompt_messages(repo, &mut prompt);
+            (commit_sha, prompt)
+        })
         .ok_or_else(|| {
             GitAiError::Generic(format!(
                 "Prompt '{}' not found in commit {}",
@@ -66,6 +77,7 @@ pub fn find_prompt_in_commit(
 
 /// Find a prompt in history, skipping `offset` occurrences
 /// Returns the (N+1)th occurrence where N = offset (0 = most recent)
+/// Messages stored as a prompt blob are loaded into the record.
 pub fn find_prompt_in_history(
     repo: &Repository,
     prompt_id: &str,
@@ -85,11 +97,13 @@ pub fn find_prompt_in_history(
     // Iterate through commits, looking for the prompt and counting occurrences
     let mut found_count = 0;
     for sha in &shas {
-        if let Some(authorship_log) = get_authorship(repo, sha)
+        if let Some(authorship_log) = get_authorship_merged(repo, sha)
             && let Some(prompt) = authorship_log.metadata.prompts.get(prompt_id)
         {
             if found_count == offset {
-                return Ok((sha.clone(), prompt.clone()));
+                let mut prompt = prompt.clone();
+                resolve_prompt_messages(repo, &mut prompt);
+                return Ok((sha.clone(), prompt));
             }
             found_count += 1;
         }
@@ -115,7 +129,8 @@ pub fn find_prompt_in_history(
 /// Find a prompt, trying the database first, then falling back to repository if provided
 ///
 /// Returns `(Option<commit_sha>, PromptRecord)` where commit_sha is None if found in DB
-/// and Some(sha) if found in repository.
+/// and Some(sha) if found in repository. A DB record without messages borrows them from its
+/// commit's prompt blob when the repository has one.
 pub fn find_prompt_with_db_fallback(
     prompt_id: &str,
     repo: Option<&Repository>,
@@ -128,7 +143,13 @@ pub fn find_prompt_with_db_fallback(
 
     if let Some(db_record) = db_guard.get_prompt(prompt_id)? {
         // Convert PromptDbRecord to PromptRecord
-        let prompt_record = db_record.to_prompt_record();
+        let mut prompt_record = db_record.to_prompt_record();
+        if prompt_record.messages.is_empty()
+            && let (Some(repo), Some(commit_sha)) = (repo, db_record.commit_sha.as_deref())
+            && let Ok((_, committed)) = find_prompt_in_commit(repo, prompt_id, commit_sha)
+        {
+            prompt_record.messages = committed.messages;
+        }
         return Ok((db_record.commit_sha, prompt_record));
     }
 
@@ -176,6 +197,11 @@ pub fn update_prompt_from_tool(
         "continue-cli" => update_continue_cli_prompt(agent_metadata, current_model),
         "droid" => update_droid_prompt(agent_metadata, current_model),
         "opencode" => update_opencode_prompt(external_thread_id, agent_metadata, current_model),
+        "aider" => update_aider_prompt(external_thread_id, agent_metadata, current_model),
+        "windsurf" => update_windsurf_prompt(agent_metadata, current_model),
+        "amp" => update_amp_prompt(agent_metadata, current_model),
+        "junie" => update_junie_prompt(agent_metadata, current_model),
+        "rovodev" => update_rovodev_prompt(agent_metadata, current_model),
         _ => {
             debug_log(&format!("Unknown tool: {}", tool));
             PromptUpdateResult::Unchanged
@@ -398,6 +424,176 @@ fn update_github_copilot_prompt(
     }
 }
 
+/// Update Windsurf prompt from the Cascade trajectory transcript
+fn update_windsurf_prompt(
+    metadata: Option<&HashMap<String, String>>,
+    current_model: &str,
+) -> PromptUpdateResult {
+    let Some(transcript_path) = metadata.and_then(|m| m.get("transcript_path")) else {
+        return PromptUpdateResult::Unchanged;
+    };
+
+    match WindsurfPreset::fetch_trajectory_payload(std::path::Path::new(transcript_path)) {
+        Ok(steps) => {
+            let (transcript, model) = WindsurfPreset::transcript_and_model_from_trajectory(&steps);
+            PromptUpdateResult::Updated(
+                transcript,
+                model.unwrap_or_else(|| current_model.to_string()),
+            )
+        }
+        Err(e) => {
+            debug_log(&format!(
+                "Failed to read Windsurf trajectory from {}: {}",
+                transcript_path, e
+            ));
+            log_error(
+                &e,
+                Some(serde_json::json!({
+                    "agent_tool": "windsurf",
+                    "operation": "fetch_trajectory_payload"
+                })),
+            );
+            PromptUpdateResult::Failed(e)
+        }
+    }
+}
+
+/// Update Amp prompt from its thread file
+fn update_amp_prompt(
+    metadata: Option<&HashMap<String, String>>,
+    current_model: &str,
+) -> PromptUpdateResult {
+    let Some(transcript_path) = metadata.and_then(|m| m.get("transcript_path")) else {
+        return PromptUpdateResult::Unchanged;
+    };
+
+    match AmpPreset::transcript_and_model_from_amp_thread(transcript_path) {
+        Ok((transcript, model)) => PromptUpdateResult::Updated(
+            transcript,
+            model.unwrap_or_else(|| current_model.to_string()),
+        ),
+        Err(e) => {
+            debug_log(&format!(
+                "Failed to parse Amp thread file from {}: {}",
+                transcript_path, e
+            ));
+            log_error(
+                &e,
+                Some(serde_json::json!({
+                    "agent_tool": "amp",
+                    "operation": "transcript_and_model_from_amp_thread"
+                })),
+            );
+            PromptUpdateResult::Failed(e)
+        }
+    }
+}
+
+/// Update Junie prompt from its session event log
+fn update_junie_prompt(
+    metadata: Option<&HashMap<String, String>>,
+    current_model: &str,
+) -> PromptUpdateResult {
+    let Some(transcript_path) = metadata.and_then(|m| m.get("transcript_path")) else {
+        return PromptUpdateResult::Unchanged;
+    };
+
+    match JuniePreset::transcript_and_model_from_junie_session(transcript_path) {
+        Ok((transcript, model)) => PromptUpdateResult::Updated(
+            transcript,
+            model.unwrap_or_else(|| current_model.to_string()),
+        ),
+        Err(e) => {
+            debug_log(&format!(
+                "Failed to parse Junie session event log from {}: {}",
+                transcript_path, e
+            ));
+            log_error(
+                &e,
+                Some(serde_json::json!({
+                    "agent_tool": "junie",
+                    "operation": "transcript_and_model_from_junie_session"
+                })),
+            );
+            PromptUpdateResult::Failed(e)
+        }
+    }
+}
+
+/// Update Rovo Dev prompt from its session context
+fn update_rovodev_prompt(
+    metadata: Option<&HashMap<String, String>>,
+    current_model: &str,
+) -> PromptUpdateResult {
+    let Some(transcript_path) = metadata.and_then(|m| m.get("transcript_path")) else {
+        return PromptUpdateResult::Unchanged;
+    };
+
+    match RovoDevPreset::transcript_and_model_from_rovodev_session(transcript_path) {
+        Ok((transcript, model)) => PromptUpdateResult::Updated(
+            transcript,
+            model.unwrap_or_else(|| current_model.to_string()),
+        ),
+        Err(e) => {
+            debug_log(&format!(
+                "Failed to parse Rovo Dev session context from {}: {}",
+                transcript_path, e
+            ));
+            log_error(
+                &e,
+                Some(serde_json::json!({
+                    "agent_tool": "rovodev",
+                    "operation": "transcript_and_model_from_rovodev_session"
+                })),
+            );
+            PromptUpdateResult::Failed(e)
+        }
+    }
+}
+
+/// Update Aider prompt from its chat history file
+fn update_aider_prompt(
+    session_id: &str,
+    metadata: Option<&HashMap<String, String>>,
+    current_model: &str,
+) -> PromptUpdateResult {
+    let Some(transcript_path) = metadata.and_then(|m| m.get("transcript_path")) else {
+        return PromptUpdateResult::Unchanged;
+    };
+    let input_history_path = metadata
+        .and_then(|m| m.get("input_history_path"))
+        .map(String::as_str);
+
+    match AiderPreset::transcript_and_model_from_aider_history(transcript_path, input_history_path)
+    {
+        // The history file only ever grows; a newer session must not replace this one
+        Ok((transcript, model, session_started_at))
+            if AiderPreset::session_id(transcript_path, session_started_at.as_deref())
+                == session_id =>
+        {
+            PromptUpdateResult::Updated(
+                transcript,
+                model.unwrap_or_else(|| current_model.to_string()),
+            )
+        }
+        Ok(_) => PromptUpdateResult::Unchanged,
+        Err(e) => {
+            debug_log(&format!(
+                "Failed to parse Aider chat history from {}: {}",
+                transcript_path, e
+            ));
+            log_error(
+                &e,
+                Some(serde_json::json!({
+                    "agent_tool": "aider",
+                    "operation": "transcript_and_model_from_aider_history"
+                })),
+            );
+            PromptUpdateResult::Failed(e)
+        }
+    }
+}
+
 /// Update Continue CLI prompt from transcript file
 fn update_continue_cli_prompt(
     metadata: Option<&HashMap<String, String>>,
@@ -537,11 +733,14 @@ fn update_opencode_prompt(
     }
 }
 
-/// Enrich prompts that have empty messages by falling back to the InternalDatabase (SQLite).
+/// Enrich prompts that have empty messages by falling back to the InternalDatabase (SQLite),
+/// then to prompt blobs in the repository.
 ///
 /// For each prompt in `prompts` whose ID is in `referenced_ids` and whose `messages` field
-/// is empty, attempts to load the messages from the database.
+/// is empty, attempts to load the messages from the database, then from the blob named by a
+/// `git-blob:` messages URL.
 pub fn enrich_prompt_messages(
+    repo: &Repository,
     prompts: &mut HashMap<String, PromptRecord>,
     referenced_ids: &HashSet<&String>,
 ) {
@@ -564,6 +763,12 @@ pub fn enrich_prompt_messages(
             }
         }
     }
+
+    for id in &ids_needing_messages {
+        if let Some(prompt) = prompts.get_mut(id) {
+            resolve_prompt_messages(repo, prompt);
+        }
+    }
 }
 
 /// Format a PromptRecord's messages into a human-readable transcript.
@@ -928,7 +1133,7 @@ mod tests {
         // Get prompt ID from first commit
         let head_oid = tmp_repo.gitai_repo().head().unwrap().target().unwrap();
         let head_sha = head_oid.to_string();
-        let authorship = get_authorship(tmp_repo.gitai_repo(), &head_sha).unwrap();
+        let authorship = get_authorship_merged(tmp_repo.gitai_repo(), &head_sha).unwrap();
         let prompt_id = authorship
             .metadata
             .prompts
@@ -1140,6 +1345,12 @@ mod tests {
         let result = update_prompt_from_tool("droid", "thread-123", None, "model");
         assert!(matches!(result, PromptUpdateResult::Unchanged));
 
+        // Test dispatch to amp, junie and rovodev
+        for tool in ["amp", "junie", "rovodev"] {
+            let result = update_prompt_from_tool(tool, "thread-123", None, "model");
+            assert!(matches!(result, PromptUpdateResult::Unchanged));
+        }
+
         // Test dispatch to opencode (behavior depends on whether default storage exists)
         let result = update_prompt_from_tool("opencode", "session-123", None, "model");
         // Can be Unchanged, Failed, or Updated depending on storage availability
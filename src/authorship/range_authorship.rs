@@ -205,6 +205,8 @@ fn create_authorship_log_for_range(
                     ),
                     base_commit_sha: end_sha.to_string(),
                     prompts: std::collections::BTreeMap::new(),
+                    char_spans: std::collections::BTreeMap::new(),
+                    line_states: std::collections::BTreeMap::new(),
                 },
             },
         );
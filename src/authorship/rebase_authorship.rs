@@ -8,7 +8,7 @@ use crate::git::refs::{
     commits_with_authorship_notes, get_reference_as_authorship_log_v3, note_blob_oids_for_commits,
 };
 use crate::git::repository::{CommitRange, Repository, exec_git, exec_git_stdin};
-use crate::git::rewrite_log::RewriteLogEvent;
+use crate::git::rewrite_log::{AmPatch, RewriteLogEvent};
 use crate::utils::{debug_log, debug_performance_log};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
@@ -119,6 +119,44 @@ pub fn rewrite_authorship_if_needed(
                 cherry_pick_complete.new_commits.len()
             ));
         }
+        RewriteLogEvent::RevertComplete { revert_complete } => {
+            rewrite_authorship_after_revert(
+                repo,
+                &revert_complete.reverted_commits,
+                &revert_complete.new_commits,
+                &commit_author,
+            )?;
+
+            debug_log(&format!(
+                "✓ Rewrote authorship for {} revert commits",
+                revert_complete.new_commits.len()
+            ));
+        }
+        RewriteLogEvent::AmComplete { am_complete } => {
+            rewrite_authorship_after_am(
+                repo,
+                &am_complete.patches,
+                &am_complete.new_commits,
+                &commit_author,
+            )?;
+
+            debug_log(&format!(
+                "✓ Rewrote authorship for {} applied patches",
+                am_complete.new_commits.len()
+            ));
+        }
+        RewriteLogEvent::RevertMixed { revert_mixed } if revert_mixed.success => {
+            prepare_working_log_after_revert(
+                repo,
+                &revert_mixed.base_head,
+                &revert_mixed.reverted_commits,
+            )?;
+
+            debug_log(&format!(
+                "✓ Prepared authorship attributions for revert --no-commit of {} commits",
+                revert_mixed.reverted_commits.len()
+            ));
+        }
         _ => {}
     }
 
@@ -961,6 +999,309 @@ pub fn rewrite_authorship_after_cherry_pick(
     Ok(())
 }
 
+/// Extract the reverted commit from git's default revert message
+/// ("This reverts commit <sha>.")
+pub fn reverted_commit_from_message(message: &str) -> Option<String> {
+    const MARKER: &str = "This reverts commit ";
+    let start = message.find(MARKER)? + MARKER.len();
+    let sha: String = message[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_hexdigit())
+        .collect();
+    if sha.len() >= 7 { Some(sha) } else { None }
+}
+
+/// Pair each revert commit with the commit it reverts. The revert message names the
+/// reverted commit; fall back to argument order when the message was edited.
+fn pair_revert_commits(
+    repo: &Repository,
+    reverted_commits: &[String],
+    new_commits: &[String],
+) -> Vec<(String, String)> {
+    new_commits
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, new_commit)| {
+            let from_message = repo
+                .find_commit(new_commit.clone())
+                .and_then(|commit| commit.body())
+                .ok()
+                .and_then(|body| reverted_commit_from_message(&body))
+                .and_then(|sha| {
+                    reverted_commits
+                        .iter()
+                        .find(|reverted| reverted.starts_with(&sha))
+                        .cloned()
+                });
+            from_message
+                .or_else(|| reverted_commits.get(idx).cloned())
+                .map(|reverted| (reverted, new_commit.clone()))
+        })
+        .collect()
+}
+
+/// Keep only prompts referenced by attestations and recount their accepted lines,
+/// since a revert restores a subset of the lines a prompt originally produced.
+fn retain_attested_prompts(authorship_log: &mut AuthorshipLog) {
+    let mut attested_lines: HashMap<String, u32> = HashMap::new();
+    for file in &authorship_log.attestations {
+        for entry in &file.entries {
+            let lines: u32 = entry
+                .line_ranges
+                .iter()
+                .map(|range| range.expand().len() as u32)
+                .sum();
+            *attested_lines.entry(entry.hash.clone()).or_default() += lines;
+        }
+    }
+
+    authorship_log
+        .metadata
+        .prompts
+        .retain(|prompt_id, _| attested_lines.contains_key(prompt_id));
+    for (prompt_id, record) in authorship_log.metadata.prompts.iter_mut() {
+        record.accepted_lines = attested_lines.get(prompt_id).copied().unwrap_or(0);
+        record.overriden_lines = 0;
+    }
+}
+
+/// Rewrite authorship logs after `git revert`
+///
+/// A revert removes the lines the reverted commit added and restores the lines it removed.
+/// Removed lines need no attestation, and restored lines take the attribution they had in
+/// the reverted commit's parent. Reverting a revert therefore brings back the original
+/// AI attribution.
+///
+/// # Arguments
+/// * `repo` - Git repository
+/// * `reverted_commits` - Commits that were reverted, in the order they were given
+/// * `new_commits` - Revert commits that were created, oldest first
+/// * `_human_author` - The human author identifier (unused in this implementation)
+pub fn rewrite_authorship_after_revert(
+    repo: &Repository,
+    reverted_commits: &[String],
+    new_commits: &[String],
+    _human_author: &str,
+) -> Result<(), GitAiError> {
+    use crate::authorship::virtual_attribution::VirtualAttributions;
+
+    if new_commits.is_empty() || reverted_commits.is_empty() {
+        debug_log("Revert resulted in no new commits");
+        return Ok(());
+    }
+
+    for (reverted_commit, new_commit) in pair_revert_commits(repo, reverted_commits, new_commits) {
+        let new_commit_obj = repo.find_commit(new_commit.clone())?;
+        let new_parent = if new_commit_obj.parent_count()? > 0 {
+            new_commit_obj.parent(0)?.id()
+        } else {
+            "initial".to_string()
+        };
+
+        let changed_files = repo.list_commit_files(&new_commit, None)?;
+        let pathspecs: Vec<String> = changed_files.iter().cloned().collect();
+
+        // Restored content comes from the parent of the reverted commit
+        let restored_from = repo
+            .find_commit(reverted_commit.clone())?
+            .parent(0)
+            .ok()
+            .map(|parent| parent.id());
+
+        let mut authorship_log = match restored_from {
+            Some(restored_from) if !pathspecs.is_empty() => {
+                let repo_clone = repo.clone();
+                let pathspecs_clone = pathspecs.clone();
+                let restored_va = smol::block_on(async {
+                    VirtualAttributions::new_for_base_commit(
+                        repo_clone,
+                        restored_from,
+                        &pathspecs_clone,
+                        None,
+                    )
+                    .await
+                })?;
+
+                let final_state = get_committed_files_content(repo, &new_commit, &pathspecs)?;
+                let mut new_va =
+                    transform_attributions_to_final_state(&restored_va, final_state, None)?;
+                new_va.prompts = restored_va.prompts;
+                new_va.to_authorship_log_index_only(
+                    repo,
+                    &new_parent,
+                    &new_commit,
+                    Some(&changed_files),
+                )?
+            }
+            _ => AuthorshipLog::new(),
+        };
+
+        authorship_log.metadata.base_commit_sha = new_commit.clone();
+        retain_attested_prompts(&mut authorship_log);
+
+        let authorship_json = authorship_log
+            .serialize_to_string()
+            .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+        crate::git::refs::notes_add(repo, &new_commit, &authorship_json)?;
+
+        debug_log(&format!(
+            "Saved authorship log for revert commit {} of {} ({} files)",
+            new_commit,
+            reverted_commit,
+            authorship_log.attestations.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Prepare working log after `git revert --no-commit` (before commit)
+///
+/// The inverse changes are staged on top of `base_head`. Restored lines take the attribution
+/// they had in each reverted commit's parent and are written to INITIAL so the next commit
+/// picks them up, like `prepare_working_log_after_squash`.
+pub fn prepare_working_log_after_revert(
+    repo: &Repository,
+    base_head: &str,
+    reverted_commits: &[String],
+) -> Result<(), GitAiError> {
+    use crate::authorship::virtual_attribution::{
+        VirtualAttributions, merge_attributions_favoring_first,
+    };
+
+    let mut changed_files: HashSet<String> = HashSet::new();
+    for reverted_commit in reverted_commits {
+        changed_files.extend(repo.list_commit_files(reverted_commit, None)?);
+    }
+    if changed_files.is_empty() {
+        return Ok(());
+    }
+    let changed_files: Vec<String> = changed_files.into_iter().collect();
+    let staged_files = repo.get_all_staged_files_content(&changed_files)?;
+
+    // Earlier reverted commits win when several restore the same line
+    let mut merged_va: Option<VirtualAttributions> = None;
+    for reverted_commit in reverted_commits {
+        let Ok(restored_from) = repo
+            .find_commit(reverted_commit.clone())
+            .and_then(|commit| commit.parent(0))
+            .map(|parent| parent.id())
+        else {
+            continue;
+        };
+
+        let repo_clone = repo.clone();
+        let changed_files_clone = changed_files.clone();
+        let restored_va = smol::block_on(async {
+            VirtualAttributions::new_for_base_commit(
+                repo_clone,
+                restored_from,
+                &changed_files_clone,
+                None,
+            )
+            .await
+        })?;
+
+        merged_va = Some(match merged_va {
+            None => {
+                let mut transformed = transform_attributions_to_final_state(
+                    &restored_va,
+                    staged_files.clone(),
+                    None,
+                )?;
+                transformed.prompts = restored_va.prompts;
+                transformed
+            }
+            Some(primary) => {
+                merge_attributions_favoring_first(primary, restored_va, staged_files.clone())?
+            }
+        });
+    }
+
+    let Some(merged_va) = merged_va else {
+        return Ok(());
+    };
+
+    // Everything is uncommitted after --no-commit, so diff base_head against itself
+    let (_authorship_log, initial_attributions) =
+        merged_va.to_authorship_log_and_initial_working_log(repo, base_head, base_head, None)?;
+
+    if !initial_attributions.files.is_empty() {
+        let working_log = repo.storage.working_log_for_base_commit(base_head);
+        // Keep INITIAL attributions for files the revert did not touch
+        let existing = working_log.read_initial_attributions();
+        let mut files = existing.files;
+        files.extend(initial_attributions.files);
+        let mut prompts = existing.prompts;
+        prompts.extend(initial_attributions.prompts);
+        working_log.write_initial_attributions(files, prompts)?;
+    }
+
+    Ok(())
+}
+
+/// Rewrite authorship logs after `git am`
+///
+/// Patches whose source commit has a note in this repository are handled like a
+/// cherry-pick of that commit. For the rest, the authorship payload `git format-patch`
+/// embedded in the patch is mapped onto the lines each applied commit added.
+pub fn rewrite_authorship_after_am(
+    repo: &Repository,
+    patches: &[AmPatch],
+    new_commits: &[String],
+    human_author: &str,
+) -> Result<(), GitAiError> {
+    use crate::authorship::patch_authorship::{authorship_from_patch_payload, pair_am_commits};
+
+    if new_commits.is_empty() {
+        debug_log("am resulted in no new commits");
+        return Ok(());
+    }
+
+    let commit_summaries = new_commits
+        .iter()
+        .map(|sha| Ok((sha.clone(), repo.find_commit(sha.clone())?.summary()?)))
+        .collect::<Result<Vec<_>, GitAiError>>()?;
+
+    let mut local_sources = Vec::new();
+    let mut local_new_commits = Vec::new();
+    let mut payload_notes = Vec::new();
+    for (patch, new_commit) in pair_am_commits(patches, &commit_summaries) {
+        let has_local_note = repo.find_commit(patch.source_commit.clone()).is_ok()
+            && crate::git::refs::show_authorship_note(repo, &patch.source_commit).is_some();
+        if has_local_note {
+            local_sources.push(patch.source_commit.clone());
+            local_new_commits.push(new_commit);
+        } else if let Some(payload) = &patch.authorship {
+            let authorship_log = authorship_from_patch_payload(repo, payload, &new_commit)?;
+            let authorship_json = authorship_log.serialize_to_string().map_err(|_| {
+                GitAiError::Generic("Failed to serialize authorship log".to_string())
+            })?;
+            payload_notes.push((new_commit, authorship_json));
+        }
+    }
+
+    debug_log(&format!(
+        "am: {} patches with local source notes, {} restored from patch payloads",
+        local_sources.len(),
+        payload_notes.len()
+    ));
+
+    if !local_sources.is_empty() {
+        rewrite_authorship_after_cherry_pick(
+            repo,
+            &local_sources,
+            &local_new_commits,
+            human_author,
+        )?;
+    }
+    if !payload_notes.is_empty() {
+        crate::git::refs::notes_add_batch(repo, &payload_notes)?;
+    }
+
+    Ok(())
+}
+
 /// Get file contents from a commit tree for specified pathspecs
 fn get_committed_files_content(
     repo: &Repository,
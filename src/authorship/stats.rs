@@ -2,7 +2,7 @@ use crate::authorship::authorship_log::LineRange;
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
 use crate::authorship::transcript::Message;
 use crate::error::GitAiError;
-use crate::git::refs::get_authorship;
+use crate::git::refs::get_authorship_merged;
 use crate::git::repository::Repository;
 use crate::utils::debug_log;
 use serde::{Deserialize, Serialize};
@@ -542,7 +542,7 @@ pub fn stats_for_commit_stats(
         get_git_diff_stats(repo, commit_sha, ignore_patterns)?;
 
     // Step 2: get the authorship log for this commit
-    let authorship_log = get_authorship(repo, commit_sha);
+    let authorship_log = get_authorship_merged(repo, commit_sha);
 
     // Step 3: get line numbers added by this specific commit, then intersect with attestations.
     // This keeps accepted stats scoped to the target commit while avoiding expensive blame traversal.
@@ -583,7 +583,7 @@ pub fn stats_for_commit_stats(
     ))
 }
 
-fn accepted_lines_from_attestations(
+pub(crate) fn accepted_lines_from_attestations(
     authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
     added_lines_by_file: &HashMap<String, Vec<u32>>,
     is_merge_commit: bool,
@@ -1,7 +1,9 @@
 use crate::authorship::attribution_tracker::{
-    Attribution, LineAttribution, line_attributions_to_attributions,
+    Attribution, LineAttribution, attributions_to_mixed_line_spans,
+    line_attributions_to_attributions,
 };
-use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log::{CharSpan, LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{LineState, LineStateEntry};
 use crate::authorship::working_log::CheckpointKind;
 use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::error::GitAiError;
@@ -23,6 +25,21 @@ pub struct VirtualAttributions {
     // Timestamp to use for attributions
     ts: u128,
     pub blame_start_commit: Option<String>,
+    // Maps file path -> char spans (working directory line numbers) for lines written by
+    // more than one author. Only known while the working log still has char-level data.
+    pub mixed_line_spans: HashMap<String, Vec<CharSpan>>,
+    // Maps file path -> author id (prompt hash or "human") -> latest checkpoint by that author
+    // that touched the file. Only known while the working log is still around.
+    pub checkpoint_activity: HashMap<String, HashMap<String, CheckpointActivity>>,
+}
+
+/// The latest checkpoint in which an author touched a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointActivity {
+    /// Unix seconds
+    pub timestamp: u64,
+    /// The `user.name` the checkpoint was recorded under
+    pub author: String,
 }
 
 impl VirtualAttributions {
@@ -46,6 +63,8 @@ impl VirtualAttributions {
             prompts: BTreeMap::new(),
             ts,
             blame_start_commit,
+            mixed_line_spans: HashMap::new(),
+            checkpoint_activity: HashMap::new(),
         };
 
         // Process all pathspecs concurrently
@@ -305,6 +324,9 @@ impl VirtualAttributions {
             HashMap::new();
         let mut prompts = BTreeMap::new();
         let mut file_contents: HashMap<String, String> = HashMap::new();
+        let mut mixed_line_spans: HashMap<String, Vec<CharSpan>> = HashMap::new();
+        let mut checkpoint_activity: HashMap<String, HashMap<String, CheckpointActivity>> =
+            HashMap::new();
 
         // Track additions and deletions per session_id for metrics
         let mut session_additions: HashMap<String, u32> = HashMap::new();
@@ -376,8 +398,32 @@ impl VirtualAttributions {
                     checkpoint.line_stats.deletions;
             }
 
+            let activity_author_id = if checkpoint.kind == CheckpointKind::Human {
+                Some(checkpoint.kind.to_str())
+            } else {
+                checkpoint.agent_id.as_ref().map(|agent_id| {
+                    crate::authorship::authorship_log_serialization::generate_short_hash(
+                        &agent_id.id,
+                        &agent_id.tool,
+                    )
+                })
+            };
+
             // Collect attributions from checkpoint entries
             for entry in &checkpoint.entries {
+                if let Some(author_id) = &activity_author_id {
+                    checkpoint_activity
+                        .entry(entry.file.clone())
+                        .or_default()
+                        .insert(
+                            author_id.clone(),
+                            CheckpointActivity {
+                                timestamp: checkpoint.timestamp,
+                                author: checkpoint.author.clone(),
+                            },
+                        );
+                }
+
                 // Most human-only pre-commit entries carry no attribution data and can be skipped.
                 // This keeps post-commit work proportional to AI-relevant files.
                 if entry.line_attributions.is_empty() && entry.attributions.is_empty() {
@@ -395,6 +441,21 @@ impl VirtualAttributions {
                     file_contents.insert(entry.file.clone(), file_content);
                 }
 
+                // Only the latest entry per file keeps char-level attributions, and they only
+                // describe the working copy if nothing changed since that checkpoint
+                mixed_line_spans.remove(&entry.file);
+                if !entry.attributions.is_empty()
+                    && let Some(file_content) = file_contents.get(&entry.file)
+                    && working_log
+                        .get_file_version(&entry.blob_sha)
+                        .is_ok_and(|checkpointed| &checkpointed == file_content)
+                {
+                    let spans = attributions_to_mixed_line_spans(&entry.attributions, file_content);
+                    if !spans.is_empty() {
+                        mixed_line_spans.insert(entry.file.clone(), spans);
+                    }
+                }
+
                 // Prefer persisted line attributions. Fall back to converting char attributions
                 // for compatibility with older checkpoint data.
                 let file_content = file_contents.get(&entry.file).cloned().unwrap_or_default();
@@ -433,6 +494,8 @@ impl VirtualAttributions {
             prompts,
             ts: 0,
             blame_start_commit: None,
+            mixed_line_spans,
+            checkpoint_activity,
         })
     }
 
@@ -499,6 +562,8 @@ impl VirtualAttributions {
             prompts: BTreeMap::new(),
             ts,
             blame_start_commit: None,
+            mixed_line_spans: HashMap::new(),
+            checkpoint_activity: HashMap::new(),
         }
     }
 
@@ -518,6 +583,8 @@ impl VirtualAttributions {
             prompts,
             ts,
             blame_start_commit: None,
+            mixed_line_spans: HashMap::new(),
+            checkpoint_activity: HashMap::new(),
         }
     }
 
@@ -607,6 +674,24 @@ impl VirtualAttributions {
     }
 }
 
+/// Convert a working directory line number to its line number in the commit, by subtracting
+/// the unstaged lines above it. Returns None unless the commit added that line.
+fn committed_line_number(
+    workdir_line_num: u32,
+    unstaged_lines: &[u32],
+    committed_hunks: Option<&Vec<LineRange>>,
+) -> Option<u32> {
+    let adjustment = unstaged_lines
+        .iter()
+        .filter(|&&l| l < workdir_line_num)
+        .count() as u32;
+    let commit_line_num = workdir_line_num - adjustment;
+
+    committed_hunks
+        .is_some_and(|hunks| hunks.iter().any(|hunk| hunk.contains(commit_line_num)))
+        .then_some(commit_line_num)
+}
+
 /// Helper function to collect committed line ranges from git diff
 fn collect_committed_hunks(
     repo: &Repository,
@@ -830,6 +915,8 @@ impl VirtualAttributions {
             // so we need to convert to commit coordinates before comparing with committed hunks
             let mut committed_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
             let mut uncommitted_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
+            // Prompt hash -> committed AI lines that a human edited afterwards
+            let mut edited_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
 
             // Get the committed hunks for this file (if any) - these are in commit coordinates
             let file_committed_hunks = committed_hunks.get(file_path);
@@ -847,35 +934,65 @@ impl VirtualAttributions {
                             .or_default()
                             .push(workdir_line_num);
                         referenced_prompts.insert(line_attr.author_id.clone());
-                    } else {
-                        // Convert working directory line number to commit line number
-                        // by subtracting the count of unstaged lines before this line
-                        let adjustment = unstaged_lines
-                            .iter()
-                            .filter(|&&l| l < workdir_line_num)
-                            .count() as u32;
-                        let commit_line_num = workdir_line_num - adjustment;
-
-                        // Check if this commit line number is in any committed hunk
-                        let is_committed = if let Some(hunks) = file_committed_hunks {
-                            hunks.iter().any(|hunk| hunk.contains(commit_line_num))
-                        } else {
-                            false
-                        };
-
-                        if is_committed {
-                            // Line was committed in this commit (use commit coordinates)
-                            committed_lines_map
-                                .entry(line_attr.author_id.clone())
+                    } else if let Some(commit_line_num) = committed_line_number(
+                        workdir_line_num,
+                        &unstaged_lines,
+                        file_committed_hunks,
+                    ) {
+                        // Line was committed in this commit (use commit coordinates)
+                        committed_lines_map
+                            .entry(line_attr.author_id.clone())
+                            .or_default()
+                            .push(commit_line_num);
+                        if line_attr.author_id == CheckpointKind::Human.to_str()
+                            && let Some(overrode) = &line_attr.overrode
+                        {
+                            edited_lines_map
+                                .entry(overrode.clone())
                                 .or_default()
                                 .push(commit_line_num);
                         }
-                        // Note: Lines that are neither unstaged nor in committed_hunks are lines that
-                        // already existed in the parent commit. They are discarded (not added to uncommitted).
                     }
+                    // Note: Lines that are neither unstaged nor in committed_hunks are lines that
+                    // already existed in the parent commit. They are discarded (not added to uncommitted).
                 }
             }
 
+            // Carry intra-line spans for committed lines into the note
+            if let Some(spans) = self.mixed_line_spans.get(file_path) {
+                let committed_spans: Vec<CharSpan> = spans
+                    .iter()
+                    .filter(|span| unstaged_lines.binary_search(&span.line).is_err())
+                    .filter_map(|span| {
+                        committed_line_number(span.line, &unstaged_lines, file_committed_hunks).map(
+                            |line| CharSpan {
+                                line,
+                                ..span.clone()
+                            },
+                        )
+                    })
+                    .collect();
+                if !committed_spans.is_empty() {
+                    authorship_log
+                        .metadata
+                        .char_spans
+                        .insert(file_path.clone(), committed_spans);
+                }
+            }
+
+            let line_states = self.line_states_for_file(
+                file_path,
+                &authorship_log.metadata.prompts,
+                &committed_lines_map,
+                &edited_lines_map,
+            );
+            if !line_states.is_empty() {
+                authorship_log
+                    .metadata
+                    .line_states
+                    .insert(file_path.clone(), line_states);
+            }
+
             // Add committed attributions to authorship log
             if !committed_lines_map.is_empty() {
                 // Create attestation entries from committed lines
@@ -1007,6 +1124,59 @@ impl VirtualAttributions {
         Ok((authorship_log, initial_attributions))
     }
 
+    /// Line states for the committed lines of one file: AI lines as written, and AI lines a
+    /// human edited afterwards, with the checkpoint activity that produced them
+    fn line_states_for_file(
+        &self,
+        file_path: &str,
+        prompts: &BTreeMap<String, PromptRecord>,
+        committed_lines_map: &HashMap<String, Vec<u32>>,
+        edited_lines_map: &HashMap<String, Vec<u32>>,
+    ) -> Vec<LineStateEntry> {
+        let human = CheckpointKind::Human.to_str();
+        let activity = self.checkpoint_activity.get(file_path);
+        let activity_for =
+            |author_id: &str| activity.and_then(|by_author| by_author.get(author_id));
+        let human_activity = activity_for(&human);
+
+        let ai_lines = committed_lines_map
+            .iter()
+            .filter(|(author_id, _)| **author_id != human)
+            .map(|(hash, lines)| (hash, lines, LineState::Ai));
+        let edited_lines = edited_lines_map
+            .iter()
+            .map(|(hash, lines)| (hash, lines, LineState::AiHumanEdited));
+
+        let mut line_states: Vec<LineStateEntry> = ai_lines
+            .chain(edited_lines)
+            .filter_map(|(hash, lines, state)| {
+                let prompt = prompts.get(hash)?;
+                let mut lines = lines.clone();
+                lines.sort_unstable();
+                lines.dedup();
+
+                let mut entry = LineStateEntry {
+                    hash: hash.clone(),
+                    lines: LineRange::compress_lines(&lines),
+                    state,
+                    ai_timestamp: activity_for(hash).map(|activity| activity.timestamp),
+                    human_author: None,
+                    human_edit_timestamp: None,
+                };
+                if state == LineState::AiHumanEdited {
+                    entry.human_author = human_activity
+                        .map(|activity| activity.author.clone())
+                        .or_else(|| prompt.human_author.clone());
+                    entry.human_edit_timestamp = human_activity.map(|activity| activity.timestamp);
+                }
+                Some(entry)
+            })
+            .collect();
+        line_states
+            .sort_by_key(|entry| (entry.hash.clone(), entry.state == LineState::AiHumanEdited));
+        line_states
+    }
+
     /// Convert VirtualAttributions to AuthorshipLog only (index-only mode)
     ///
     /// This is a simplified version of `to_authorship_log_and_initial_working_log` that:
@@ -1016,8 +1186,6 @@ impl VirtualAttributions {
     ///
     /// This is useful for retroactively generating authorship logs from working logs
     /// where we know the commit has landed and don't care about uncommitted work.
-    // only being used by stats-delta in a fork
-    #[allow(dead_code)]
     pub fn to_authorship_log_index_only(
         &self,
         repo: &Repository,
@@ -1060,6 +1228,8 @@ impl VirtualAttributions {
 
             // Map author_id -> line numbers (in commit coordinates)
             let mut committed_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
+            // Prompt hash -> committed AI lines that a human edited afterwards
+            let mut edited_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
 
             for line_attr in line_attrs {
                 // Since we're not dealing with unstaged hunks, the line numbers in VirtualAttributions
@@ -1075,10 +1245,31 @@ impl VirtualAttributions {
                             .entry(line_attr.author_id.clone())
                             .or_default()
                             .push(line_num);
+                        if line_attr.author_id == CheckpointKind::Human.to_str()
+                            && let Some(overrode) = &line_attr.overrode
+                        {
+                            edited_lines_map
+                                .entry(overrode.clone())
+                                .or_default()
+                                .push(line_num);
+                        }
                     }
                 }
             }
 
+            let line_states = self.line_states_for_file(
+                file_path,
+                &authorship_log.metadata.prompts,
+                &committed_lines_map,
+                &edited_lines_map,
+            );
+            if !line_states.is_empty() {
+                authorship_log
+                    .metadata
+                    .line_states
+                    .insert(file_path.clone(), line_states);
+            }
+
             // Add committed attributions to authorship log
             if !committed_lines_map.is_empty() {
                 // Create attestation entries from committed lines
@@ -1346,6 +1537,8 @@ pub fn merge_attributions_favoring_first(
         prompts: merged_prompts,
         ts,
         blame_start_commit: None,
+        mixed_line_spans: HashMap::new(),
+        checkpoint_activity: HashMap::new(),
     };
 
     // Get union of all files
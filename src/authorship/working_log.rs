@@ -37,6 +37,17 @@ impl WorkingLogEntry {
             line_attributions,
         }
     }
+
+    /// Whether any line or char-level attribution belongs to a non-human author
+    pub fn has_non_human_attribution(&self) -> bool {
+        self.line_attributions
+            .iter()
+            .any(|attr| attr.author_id != CheckpointKind::Human.to_str())
+            || self
+                .attributions
+                .iter()
+                .any(|attr| attr.author_id != CheckpointKind::Human.to_str())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
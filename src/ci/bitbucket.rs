@@ -0,0 +1,288 @@
+use crate::ci::ci_context::{CiContext, CiEvent};
+use crate::error::GitAiError;
+use crate::git::repository::exec_git;
+use crate::git::repository::find_repository_in_path;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+const BITBUCKET_CI_TEMPLATE_YAML: &str = include_str!("workflow_templates/bitbucket.yaml");
+
+const DEFAULT_BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Page of pull requests from the Bitbucket Cloud API
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketPullRequestPage {
+    #[serde(default)]
+    values: Vec<BitbucketPullRequest>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketPullRequest {
+    id: u64,
+    title: Option<String>,
+    source: BitbucketPullRequestEndpoint,
+    destination: BitbucketPullRequestEndpoint,
+    merge_commit: Option<BitbucketCommit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketPullRequestEndpoint {
+    branch: BitbucketBranch,
+    commit: Option<BitbucketCommit>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketBranch {
+    name: String,
+}
+
+/// Bitbucket returns abbreviated (12 character) hashes for pull request commits
+#[derive(Debug, Clone, Deserialize)]
+struct BitbucketCommit {
+    hash: String,
+}
+
+/// Find the merged pull request whose (abbreviated) merge commit is `commit_sha`
+fn find_merged_pull_request(
+    pull_requests: Vec<BitbucketPullRequest>,
+    commit_sha: &str,
+) -> Option<BitbucketPullRequest> {
+    pull_requests.into_iter().find(|pr| {
+        pr.merge_commit
+            .as_ref()
+            .is_some_and(|c| !c.hash.is_empty() && commit_sha.starts_with(&c.hash))
+    })
+}
+
+/// Query the Bitbucket API for recently merged PRs and find the one that produced the
+/// current commit. Returns None if the commit did not come from a merged PR.
+pub fn get_bitbucket_ci_context() -> Result<Option<CiContext>, GitAiError> {
+    let required = |name: &str| {
+        std::env::var(name)
+            .map_err(|_| GitAiError::Generic(format!("{} environment variable not set", name)))
+    };
+    let commit_sha = required("BITBUCKET_COMMIT")?;
+    let workspace = required("BITBUCKET_WORKSPACE")?;
+    let repo_slug = required("BITBUCKET_REPO_SLUG")?;
+    let origin_url = required("BITBUCKET_GIT_HTTP_ORIGIN")?;
+    let api_url = std::env::var("BITBUCKET_API_URL")
+        .unwrap_or_else(|_| DEFAULT_BITBUCKET_API_URL.to_string());
+    let access_token = std::env::var("BITBUCKET_ACCESS_TOKEN").ok();
+
+    println!("[Bitbucket Pipelines] Environment:");
+    println!("  BITBUCKET_COMMIT: {}", commit_sha);
+    println!("  BITBUCKET_REPO_FULL_NAME: {}/{}", workspace, repo_slug);
+
+    let endpoint = format!(
+        "{}/repositories/{}/{}/pullrequests?state=MERGED&sort=-updated_on&pagelen=50",
+        api_url.trim_end_matches('/'),
+        workspace,
+        repo_slug
+    );
+    println!("[Bitbucket Pipelines] Querying API: {}", endpoint);
+
+    let mut request = minreq::get(&endpoint)
+        .with_header(
+            "User-Agent",
+            format!("git-ai/{}", env!("CARGO_PKG_VERSION")),
+        )
+        .with_timeout(30);
+    match &access_token {
+        Some(token) => {
+            println!("  Auth: BITBUCKET_ACCESS_TOKEN");
+            request = request.with_header("Authorization", format!("Bearer {}", token));
+        }
+        None => {
+            println!("  Auth: none (set BITBUCKET_ACCESS_TOKEN for private repositories)");
+        }
+    }
+
+    let response = request
+        .send()
+        .map_err(|e| GitAiError::Generic(format!("Bitbucket API request failed: {}", e)))?;
+    if response.status_code != 200 {
+        return Err(GitAiError::Generic(format!(
+            "Bitbucket API returned status {}: {}",
+            response.status_code,
+            response.as_str().unwrap_or("unknown error")
+        )));
+    }
+
+    let page: BitbucketPullRequestPage = serde_json::from_str(response.as_str().unwrap_or("{}"))
+        .map_err(|e| {
+            GitAiError::Generic(format!("Failed to parse Bitbucket API response: {}", e))
+        })?;
+    println!(
+        "[Bitbucket Pipelines] Found {} recently merged PRs",
+        page.values.len()
+    );
+
+    let pr = match find_merged_pull_request(page.values, &commit_sha) {
+        Some(pr) => {
+            println!(
+                "[Bitbucket Pipelines] Found matching PR #{}: \"{}\"",
+                pr.id,
+                pr.title.as_deref().unwrap_or("(no title)")
+            );
+            pr
+        }
+        None => {
+            println!(
+                "[Bitbucket Pipelines] No recent PR found corresponding to this commit. Skipping..."
+            );
+            return Ok(None);
+        }
+    };
+
+    let clone_dir = "git-ai-ci-clone".to_string();
+    let authenticated_url = match &access_token {
+        Some(token) if origin_url.starts_with("https://") => {
+            origin_url.replacen("https://", &format!("https://x-token-auth:{}@", token), 1)
+        }
+        _ => origin_url.clone(),
+    };
+
+    exec_git(&[
+        "clone".to_string(),
+        "--branch".to_string(),
+        pr.destination.branch.name.clone(),
+        authenticated_url.clone(),
+        clone_dir.clone(),
+    ])?;
+
+    // Bitbucket has no pull request refs, so the PR commits are only reachable through the
+    // source branch. Keep them in a local ref so they survive the rewrite.
+    let local_ref = format!("refs/bitbucket/pr/{}", pr.id);
+    println!(
+        "[Bitbucket Pipelines] Fetching source branch {}...",
+        pr.source.branch.name
+    );
+    if let Err(e) = exec_git(&[
+        "-C".to_string(),
+        clone_dir.clone(),
+        "fetch".to_string(),
+        authenticated_url,
+        format!("refs/heads/{}:{}", pr.source.branch.name, local_ref),
+    ]) {
+        println!(
+            "[Bitbucket Pipelines] Source branch {} is no longer available ({}). Disable \"Delete source branch after merge\" to keep authorship for squash merges. Skipping...",
+            pr.source.branch.name, e
+        );
+        let _ = std::fs::remove_dir_all(&clone_dir);
+        return Ok(None);
+    }
+
+    let repo = find_repository_in_path(&clone_dir)?;
+    let resolve = |rev: &str| -> Option<String> {
+        let mut args = repo.global_args_for_exec();
+        args.push("rev-parse".to_string());
+        args.push("--verify".to_string());
+        args.push(format!("{}^{{commit}}", rev));
+        exec_git(&args)
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    // Expand the abbreviated hashes from the API. The PR head falls back to the fetched
+    // branch tip when the API omits it.
+    let head_sha = pr
+        .source
+        .commit
+        .as_ref()
+        .and_then(|c| resolve(&c.hash))
+        .or_else(|| resolve(&local_ref))
+        .ok_or_else(|| {
+            GitAiError::Generic(format!(
+                "Could not resolve the head commit of PR #{}",
+                pr.id
+            ))
+        })?;
+    let base_sha = pr
+        .destination
+        .commit
+        .as_ref()
+        .and_then(|c| resolve(&c.hash))
+        .unwrap_or_default();
+
+    println!(
+        "[Bitbucket Pipelines] Created CiContext: merge_commit_sha={}, head_sha={}, head_ref={}, base_ref={}",
+        commit_sha, head_sha, pr.source.branch.name, pr.destination.branch.name
+    );
+
+    Ok(Some(CiContext {
+        repo,
+        event: CiEvent::Merge {
+            merge_commit_sha: commit_sha,
+            head_ref: pr.source.branch.name.clone(),
+            head_sha,
+            base_ref: pr.destination.branch.name.clone(),
+            base_sha,
+        },
+        temp_dir: PathBuf::from(clone_dir),
+    }))
+}
+
+/// Print the Bitbucket Pipelines snippet for users to copy into their bitbucket-pipelines.yml
+pub fn print_bitbucket_ci_yaml() {
+    println!("Add the following to your bitbucket-pipelines.yml:");
+    println!();
+    println!("{}", BITBUCKET_CI_TEMPLATE_YAML);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE_JSON: &str = r#"{
+        "pagelen": 50,
+        "values": [
+            {
+                "id": 7,
+                "title": "Open PR",
+                "source": {"branch": {"name": "feature/open"}, "commit": {"hash": "111111111111"}},
+                "destination": {"branch": {"name": "main"}, "commit": {"hash": "222222222222"}},
+                "merge_commit": null
+            },
+            {
+                "id": 8,
+                "title": "Add parser",
+                "source": {"branch": {"name": "feature/parser"}, "commit": {"hash": "abcdef123456"}},
+                "destination": {"branch": {"name": "main"}, "commit": {"hash": "0123456789ab"}},
+                "merge_commit": {"hash": "fedcba987654"}
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_bitbucket_pull_request_page_deserialization() {
+        let page: BitbucketPullRequestPage = serde_json::from_str(PAGE_JSON).unwrap();
+        assert_eq!(page.values.len(), 2);
+        assert!(page.values[0].merge_commit.is_none());
+        let pr = &page.values[1];
+        assert_eq!(pr.id, 8);
+        assert_eq!(pr.source.branch.name, "feature/parser");
+        assert_eq!(pr.destination.branch.name, "main");
+        assert_eq!(pr.merge_commit.as_ref().unwrap().hash, "fedcba987654");
+    }
+
+    #[test]
+    fn test_find_merged_pull_request_matches_abbreviated_hash() {
+        let page: BitbucketPullRequestPage = serde_json::from_str(PAGE_JSON).unwrap();
+        let pr = find_merged_pull_request(
+            page.values.clone(),
+            "fedcba9876543210fedcba9876543210fedcba98",
+        )
+        .unwrap();
+        assert_eq!(pr.id, 8);
+
+        assert!(
+            find_merged_pull_request(page.values, "0000000000000000000000000000000000000000")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_bitbucket_ci_template_yaml_not_empty() {
+        assert!(BITBUCKET_CI_TEMPLATE_YAML.contains("git-ai ci bitbucket run"));
+    }
+}
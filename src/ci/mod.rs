@@ -1,3 +1,6 @@
+pub mod azure;
+pub mod bitbucket;
 pub mod ci_context;
 pub mod github;
 pub mod gitlab;
+pub mod policy;
@@ -0,0 +1,420 @@
+//! Authorship policy gate for CI
+//!
+//! `git-ai ci check` evaluates the AI authorship of a PR's commit range against the
+//! rules declared in `.git-ai-policy.toml`, for example:
+//!
+//! ```toml
+//! [[rule]]
+//! name = "crypto-ai-share"
+//! paths = ["src/crypto/**"]
+//! max_ai_share = 80.0
+//!
+//! [[rule]]
+//! name = "no-ai-migrations"
+//! paths = ["migrations/**"]
+//! max_ai_lines = 0
+//!
+//! [[rule]]
+//! name = "prompts-have-authors"
+//! require_human_author = true
+//! ```
+
+use crate::authorship::authorship_log::PromptRecord;
+use crate::authorship::diff_ai_accepted::lines_to_ranges;
+use crate::authorship::ignore::{
+    IgnoreMatcher, build_ignore_matcher, should_ignore_file_with_matcher,
+};
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Policy file looked up at the repository root when `--policy` is not given
+pub const DEFAULT_POLICY_FILE: &str = ".git-ai-policy.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// A single rule. Every constraint that is set must hold for the files matching `paths`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    pub name: String,
+    /// Globs selecting the files the rule applies to, all files when empty
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Maximum percentage of added lines that may be AI-authored
+    pub max_ai_share: Option<f64>,
+    /// Maximum number of AI-authored lines that may be added
+    pub max_ai_lines: Option<u32>,
+    /// Every prompt with lines in the matching files must record a human author
+    #[serde(default)]
+    pub require_human_author: bool,
+}
+
+impl Policy {
+    pub fn from_toml(content: &str) -> Result<Self, GitAiError> {
+        let policy: Policy = toml::from_str(content)
+            .map_err(|e| GitAiError::Generic(format!("Invalid policy file: {}", e)))?;
+
+        for rule in &policy.rules {
+            if rule.max_ai_share.is_none()
+                && rule.max_ai_lines.is_none()
+                && !rule.require_human_author
+            {
+                return Err(GitAiError::Generic(format!(
+                    "Policy rule '{}' has no constraint (max_ai_share, max_ai_lines or require_human_author)",
+                    rule.name
+                )));
+            }
+            if let Some(share) = rule.max_ai_share
+                && !(0.0..=100.0).contains(&share)
+            {
+                return Err(GitAiError::Generic(format!(
+                    "Policy rule '{}' has max_ai_share {} outside 0-100",
+                    rule.name, share
+                )));
+            }
+        }
+
+        Ok(policy)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, GitAiError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            GitAiError::Generic(format!(
+                "Failed to read policy file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_toml(&content)
+    }
+}
+
+/// Lines added to one file across the checked range
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FileAuthorship {
+    pub added_lines: u32,
+    pub ai_lines: u32,
+    /// Prompt hashes with accepted lines in this file
+    pub prompts: BTreeSet<String>,
+}
+
+/// Per-file AI authorship of a commit range
+#[derive(Debug, Clone, Default)]
+pub struct RangeFileAuthorship {
+    pub files: BTreeMap<String, FileAuthorship>,
+    pub prompts: BTreeMap<String, PromptRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PolicyViolation {
+    pub rule: String,
+    pub message: String,
+    pub files: Vec<String>,
+    pub prompts: Vec<String>,
+}
+
+/// Collect per-file AI authorship for the net diff of `base..head`.
+///
+/// Each line the range adds is blamed at `head`, so a line rewritten later in the range
+/// counts once, with its final author.
+pub fn collect_range_file_authorship(
+    repo: &Repository,
+    base: &str,
+    head: &str,
+    ignore_patterns: &[String],
+) -> Result<RangeFileAuthorship, GitAiError> {
+    let added_lines_by_file = repo.diff_added_lines(base, head, None)?;
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+
+    let mut result = RangeFileAuthorship::default();
+    for (file_path, mut added_lines) in added_lines_by_file {
+        if added_lines.is_empty() || should_ignore_file_with_matcher(&file_path, &ignore_matcher) {
+            continue;
+        }
+        added_lines.sort_unstable();
+        added_lines.dedup();
+
+        let mut options = GitAiBlameOptions::default();
+        #[allow(clippy::field_reassign_with_default)]
+        {
+            options.oldest_commit = Some(base.to_string());
+            options.newest_commit = Some(head.to_string());
+            options.line_ranges = lines_to_ranges(&added_lines);
+            options.no_output = true;
+            options.use_prompt_hashes_as_names = true;
+        }
+        let (line_authors, prompt_records) = repo.blame(&file_path, &options)?;
+
+        let file = result.files.entry(file_path).or_default();
+        file.added_lines = added_lines.len() as u32;
+        for line in &added_lines {
+            let Some((hash, prompt)) = line_authors
+                .get(line)
+                .and_then(|hash| prompt_records.get_key_value(hash))
+            else {
+                continue;
+            };
+            file.ai_lines += 1;
+            file.prompts.insert(hash.clone());
+            result
+                .prompts
+                .entry(hash.clone())
+                .or_insert_with(|| prompt.clone());
+        }
+    }
+
+    Ok(result)
+}
+
+/// Evaluate every rule of `policy`, returning the violations in rule order
+pub fn evaluate_policy(policy: &Policy, authorship: &RangeFileAuthorship) -> Vec<PolicyViolation> {
+    let mut violations = Vec::new();
+
+    for rule in &policy.rules {
+        let matcher = build_ignore_matcher(&rule.paths);
+        let files: Vec<(&String, &FileAuthorship)> = authorship
+            .files
+            .iter()
+            .filter(|(path, _)| rule_matches(rule, &matcher, path))
+            .collect();
+        let scope = if rule.paths.is_empty() {
+            "all files".to_string()
+        } else {
+            rule.paths.join(", ")
+        };
+
+        let added: u32 = files.iter().map(|(_, f)| f.added_lines).sum();
+        let ai: u32 = files.iter().map(|(_, f)| f.ai_lines).sum();
+        let ai_files: Vec<String> = files
+            .iter()
+            .filter(|(_, f)| f.ai_lines > 0)
+            .map(|(path, _)| (*path).clone())
+            .collect();
+
+        if let Some(max_lines) = rule.max_ai_lines
+            && ai > max_lines
+        {
+            violations.push(PolicyViolation {
+                rule: rule.name.clone(),
+                message: format!("{} AI lines in {} (max {})", ai, scope, max_lines),
+                files: ai_files.clone(),
+                prompts: Vec::new(),
+            });
+        }
+
+        if let Some(max_share) = rule.max_ai_share
+            && added > 0
+        {
+            let share = ai as f64 / added as f64 * 100.0;
+            if share > max_share {
+                violations.push(PolicyViolation {
+                    rule: rule.name.clone(),
+                    message: format!(
+                        "{:.1}% AI lines in {} ({} of {}, max {}%)",
+                        share, scope, ai, added, max_share
+                    ),
+                    files: ai_files.clone(),
+                    prompts: Vec::new(),
+                });
+            }
+        }
+
+        if rule.require_human_author {
+            let mut prompts = BTreeSet::new();
+            let mut prompt_files = BTreeSet::new();
+            for (path, file) in &files {
+                for hash in &file.prompts {
+                    let has_author = authorship
+                        .prompts
+                        .get(hash)
+                        .and_then(|p| p.human_author.as_deref())
+                        .is_some_and(|author| !author.trim().is_empty());
+                    if !has_author {
+                        prompts.insert(hash.clone());
+                        prompt_files.insert((*path).clone());
+                    }
+                }
+            }
+            if !prompts.is_empty() {
+                violations.push(PolicyViolation {
+                    rule: rule.name.clone(),
+                    message: format!(
+                        "{} prompt(s) without a human author in {}",
+                        prompts.len(),
+                        scope
+                    ),
+                    files: prompt_files.into_iter().collect(),
+                    prompts: prompts.into_iter().collect(),
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+fn rule_matches(rule: &PolicyRule, matcher: &IgnoreMatcher, path: &str) -> bool {
+    rule.paths.is_empty() || should_ignore_file_with_matcher(path, matcher)
+}
+
+/// Human-readable summary of the violations, listing offending files and prompts
+pub fn format_violations(
+    violations: &[PolicyViolation],
+    authorship: &RangeFileAuthorship,
+) -> String {
+    let mut output = String::new();
+
+    for violation in violations {
+        output.push_str(&format!("✗ {}: {}\n", violation.rule, violation.message));
+        for path in &violation.files {
+            if let Some(file) = authorship.files.get(path) {
+                output.push_str(&format!(
+                    "    {} ({} of {} added lines AI)\n",
+                    path, file.ai_lines, file.added_lines
+                ));
+            }
+        }
+        for hash in &violation.prompts {
+            match authorship.prompts.get(hash) {
+                Some(prompt) => output.push_str(&format!(
+                    "    prompt {} ({}::{})\n",
+                    hash, prompt.agent_id.tool, prompt.agent_id.model
+                )),
+                None => output.push_str(&format!("    prompt {} (missing from notes)\n", hash)),
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::working_log::AgentId;
+
+    fn prompt(human_author: Option<&str>) -> PromptRecord {
+        PromptRecord {
+            agent_id: AgentId {
+                tool: "cursor".to_string(),
+                id: "session".to_string(),
+                model: "gpt".to_string(),
+            },
+            human_author: human_author.map(str::to_string),
+            messages: vec![],
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+        }
+    }
+
+    fn file(added_lines: u32, ai_lines: u32, prompts: &[&str]) -> FileAuthorship {
+        FileAuthorship {
+            added_lines,
+            ai_lines,
+            prompts: prompts.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn authorship() -> RangeFileAuthorship {
+        RangeFileAuthorship {
+            files: BTreeMap::from([
+                ("src/crypto/aes.rs".to_string(), file(10, 9, &["p1"])),
+                ("src/main.rs".to_string(), file(10, 2, &["p2"])),
+                ("migrations/001.sql".to_string(), file(4, 1, &["p2"])),
+            ]),
+            prompts: BTreeMap::from([
+                ("p1".to_string(), prompt(Some("Alice <alice@example.com>"))),
+                ("p2".to_string(), prompt(None)),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let policy = Policy::from_toml(
+            r#"
+[[rule]]
+name = "crypto"
+paths = ["src/crypto/**"]
+max_ai_share = 80.0
+
+[[rule]]
+name = "authors"
+require_human_author = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(policy.rules.len(), 2);
+        assert_eq!(policy.rules[0].paths, vec!["src/crypto/**".to_string()]);
+        assert_eq!(policy.rules[0].max_ai_share, Some(80.0));
+        assert!(policy.rules[1].require_human_author);
+        assert!(policy.rules[1].paths.is_empty());
+    }
+
+    #[test]
+    fn test_parse_policy_rejects_invalid_rules() {
+        assert!(Policy::from_toml("[[rule]]\nname = \"empty\"\n").is_err());
+        assert!(Policy::from_toml("[[rule]]\nname = \"x\"\nmax_ai_share = 120.0\n").is_err());
+        assert!(Policy::from_toml("[[rule]]\nname = \"x\"\nmax_ai_lnes = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_max_ai_share() {
+        let policy = Policy::from_toml(
+            "[[rule]]\nname = \"crypto\"\npaths = [\"src/crypto/**\"]\nmax_ai_share = 80.0\n",
+        )
+        .unwrap();
+        let violations = evaluate_policy(&policy, &authorship());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, "crypto");
+        assert_eq!(violations[0].files, vec!["src/crypto/aes.rs".to_string()]);
+
+        let relaxed = Policy::from_toml(
+            "[[rule]]\nname = \"crypto\"\npaths = [\"src/crypto/**\"]\nmax_ai_share = 90.0\n",
+        )
+        .unwrap();
+        assert!(evaluate_policy(&relaxed, &authorship()).is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_max_ai_lines() {
+        let policy = Policy::from_toml(
+            "[[rule]]\nname = \"migrations\"\npaths = [\"migrations/**\"]\nmax_ai_lines = 0\n",
+        )
+        .unwrap();
+        let violations = evaluate_policy(&policy, &authorship());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].files, vec!["migrations/001.sql".to_string()]);
+        assert!(violations[0].message.contains("1 AI lines"));
+    }
+
+    #[test]
+    fn test_evaluate_require_human_author() {
+        let policy =
+            Policy::from_toml("[[rule]]\nname = \"authors\"\nrequire_human_author = true\n")
+                .unwrap();
+        let violations = evaluate_policy(&policy, &authorship());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].prompts, vec!["p2".to_string()]);
+        assert_eq!(
+            violations[0].files,
+            vec!["migrations/001.sql".to_string(), "src/main.rs".to_string()]
+        );
+
+        let output = format_violations(&violations, &authorship());
+        assert!(output.contains("✗ authors:"));
+        assert!(output.contains("prompt p2 (cursor::gpt)"));
+    }
+}
@@ -1,21 +1,50 @@
 use crate::auth::CredentialStore;
-use crate::authorship::authorship_log::PromptRecord;
+use crate::authorship::authorship_log::{CharSpan, PromptRecord};
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::imara_diff_utils::{LineChangeTag, compute_line_changes};
 use crate::authorship::prompt_utils::enrich_prompt_messages;
+use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::authorship::working_log::CheckpointKind;
+use crate::commands::blame_cache::{BlameCacheKey, CachedBlame};
 use crate::error::GitAiError;
-use crate::git::refs::get_reference_as_authorship_log_v3;
+use crate::git::refs::get_reference_as_authorship_log_v3_merged;
 use crate::git::repository::Repository;
 use crate::git::repository::{exec_git, exec_git_stdin};
 #[cfg(windows)]
 use crate::utils::normalize_to_posix;
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, IsTerminal, Write};
+use std::str::FromStr;
 use std::sync::LazyLock;
 
+/// How finely `--json` output attributes authorship
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Granularity {
+    /// One author per line
+    #[default]
+    Line,
+    /// Also emit intra-line spans for lines written by more than one author
+    Char,
+}
+
+impl FromStr for Granularity {
+    type Err = GitAiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "line" => Ok(Granularity::Line),
+            "char" => Ok(Granularity::Char),
+            other => Err(GitAiError::Generic(format!(
+                "Invalid granularity '{}'. Expected 'line' or 'char'",
+                other
+            ))),
+        }
+    }
+}
+
 //🐰🥚 @todo use actual date Git AI was installed in each repo
 pub static OLDEST_AI_BLAME_DATE: LazyLock<DateTime<FixedOffset>> = LazyLock::new(|| {
     FixedOffset::east_opt(0)
@@ -57,6 +86,13 @@ pub struct BlameHunk {
     pub is_boundary: bool,
 }
 
+impl BlameHunk {
+    /// Whether the lines in this hunk are not committed yet (git reports the all-zero SHA)
+    pub fn is_uncommitted(&self) -> bool {
+        !self.commit_sha.is_empty() && self.commit_sha.chars().all(|c| c == '0')
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitAiBlameOptions {
     // Line range options
@@ -136,6 +172,9 @@ pub struct GitAiBlameOptions {
     // JSON output format
     pub json: bool,
 
+    // Attribution granularity for JSON output
+    pub granularity: Granularity,
+
     // Mark lines from commits without authorship logs as "Unknown"
     pub mark_unknown: bool,
 
@@ -146,6 +185,13 @@ pub struct GitAiBlameOptions {
     // When true, a single git blame hunk may be split into multiple hunks
     // if different lines were authored by different humans working with AI
     pub split_hunks_by_ai_author: bool,
+
+    // Read and write the persistent blame cache under .git/ai/blame_cache
+    pub use_cache: bool,
+
+    // Blame the working tree for JSON output too, including pending AI lines, instead of
+    // defaulting to HEAD
+    pub include_uncommitted: bool,
 }
 
 impl Default for GitAiBlameOptions {
@@ -188,9 +234,12 @@ impl Default for GitAiBlameOptions {
             no_output: false,
             ignore_whitespace: false,
             json: false,
+            granularity: Granularity::Line,
             mark_unknown: false,
             show_prompt: false,
             split_hunks_by_ai_author: true,
+            use_cache: false,
+            include_uncommitted: false,
         }
     }
 }
@@ -201,6 +250,33 @@ impl Repository {
         &self,
         file_path: &str,
         options: &GitAiBlameOptions,
+    ) -> Result<(HashMap<u32, String>, HashMap<String, PromptRecord>), GitAiError> {
+        self.blame_with_json_out(file_path, options, None)
+    }
+
+    /// Blame `file_path` and return the `--json` document instead of printing it
+    pub fn blame_json(
+        &self,
+        file_path: &str,
+        options: &GitAiBlameOptions,
+    ) -> Result<serde_json::Value, GitAiError> {
+        let options = GitAiBlameOptions {
+            json: true,
+            no_output: false,
+            ..options.clone()
+        };
+        let mut json = None;
+        self.blame_with_json_out(file_path, &options, Some(&mut json))?;
+        json.ok_or_else(|| GitAiError::Generic("Blame produced no JSON output".to_string()))
+    }
+
+    /// Blame, handing JSON output to `json_out` rather than stdout when given
+    #[allow(clippy::type_complexity)]
+    fn blame_with_json_out(
+        &self,
+        file_path: &str,
+        options: &GitAiBlameOptions,
+        json_out: Option<&mut Option<serde_json::Value>>,
     ) -> Result<(HashMap<u32, String>, HashMap<String, PromptRecord>), GitAiError> {
         // Use repo root for file system operations
         let repo_root = self.workdir().map_err(|e| {
@@ -262,11 +338,15 @@ impl Repository {
                 .to_string()
         };
 
-        // For JSON output, default to HEAD to exclude uncommitted changes
-        // and use prompt hashes as names so we can correlate with prompt_records
+        // For JSON output, default to HEAD to exclude uncommitted changes unless the caller
+        // passed contents or asked for them, and use prompt hashes as names so we can correlate
+        // with prompt_records
         let options = if options.json {
             let mut opts = options.clone();
-            if opts.newest_commit.is_none() {
+            if opts.newest_commit.is_none()
+                && opts.contents_data.is_none()
+                && !opts.include_uncommitted
+            {
                 opts.newest_commit = Some("HEAD".to_string());
             }
             opts.use_prompt_hashes_as_names = true;
@@ -351,6 +431,30 @@ impl Repository {
             }
         }
 
+        // Results that don't need the blame hunks themselves can come from the cache
+        let cache_key = if options.use_cache
+            && (options.no_output || (options.json && options.granularity == Granularity::Line))
+        {
+            BlameCacheKey::for_blame(self, &relative_file_path, &file_content, &options)
+        } else {
+            None
+        };
+        if let Some(cached) = cache_key.as_ref().and_then(BlameCacheKey::load) {
+            if !options.no_output {
+                output_json_format(
+                    self,
+                    &cached.line_authors,
+                    &cached.prompt_records,
+                    &cached.prompt_other_files,
+                    &cached.prompt_commits,
+                    &PendingAttributions::default(),
+                    None,
+                    json_out,
+                )?;
+            }
+            return Ok((cached.line_authors, cached.prompt_records));
+        }
+
         // Step 1: Get Git's native blame for all ranges
         let mut all_blame_hunks = Vec::new();
         for (start_line, end_line) in &line_ranges {
@@ -358,9 +462,48 @@ impl Repository {
             all_blame_hunks.extend(hunks);
         }
 
-        // Step 2: Overlay AI authorship information
+        // Step 2: When blaming the working tree, collect AI attributions for uncommitted lines
+        // from the working log of HEAD
+        let pending = if options.newest_commit.is_none()
+            && all_blame_hunks.iter().any(BlameHunk::is_uncommitted)
+        {
+            pending_ai_attributions(self, &all_blame_hunks, &relative_file_path, &file_content)?
+        } else {
+            PendingAttributions::default()
+        };
+
+        // Step 3: Overlay AI authorship information
         let (line_authors, prompt_records, authorship_logs, prompt_commits) =
-            overlay_ai_authorship(self, &all_blame_hunks, &relative_file_path, &options)?;
+            overlay_ai_authorship(
+                self,
+                &all_blame_hunks,
+                &relative_file_path,
+                &options,
+                &pending,
+            )?;
+
+        let json_output = options.json && !options.no_output;
+        let char_spans = (json_output && options.granularity == Granularity::Char)
+            .then(|| collect_char_spans(self, &all_blame_hunks, &relative_file_path));
+        let prompt_other_files = if json_output || cache_key.is_some() {
+            let span_prompts = char_spans.iter().flat_map(|(_, prompts)| prompts.keys());
+            files_by_prompt_hash(
+                prompt_records.keys().chain(span_prompts),
+                &authorship_logs,
+                &relative_file_path,
+            )
+        } else {
+            HashMap::new()
+        };
+
+        if let Some(cache_key) = &cache_key {
+            cache_key.store(&CachedBlame {
+                line_authors: line_authors.clone(),
+                prompt_records: prompt_records.clone(),
+                prompt_commits: prompt_commits.clone(),
+                prompt_other_files: prompt_other_files.clone(),
+            });
+        }
 
         if options.no_output {
             return Ok((line_authors, prompt_records));
@@ -372,9 +515,11 @@ impl Repository {
                 self,
                 &line_authors,
                 &prompt_records,
-                &authorship_logs,
+                &prompt_other_files,
                 &prompt_commits,
-                &relative_file_path,
+                &pending,
+                char_spans,
+                json_out,
             )?;
         } else if options.porcelain || options.line_porcelain {
             output_porcelain_format(
@@ -727,7 +872,8 @@ impl Repository {
             {
                 cached.clone()
             } else {
-                let authorship = get_reference_as_authorship_log_v3(self, &hunk.commit_sha).ok();
+                let authorship =
+                    get_reference_as_authorship_log_v3_merged(self, &hunk.commit_sha).ok();
                 commit_authorship_cache.insert(hunk.commit_sha.clone(), authorship.clone());
                 authorship
             };
@@ -808,12 +954,113 @@ impl Repository {
     }
 }
 
+/// AI attributions from the working log for lines that are not committed yet
+#[derive(Debug, Default)]
+struct PendingAttributions {
+    /// Current line number -> prompt hash
+    lines: HashMap<u32, String>,
+    prompts: HashMap<String, PromptRecord>,
+}
+
+/// Load the working log for HEAD and map its AI line attributions for `file_path` onto the
+/// uncommitted lines of the blamed content. Checkpoint attributions describe the file as it was
+/// when the checkpoint ran, so they are carried through a diff against that version to account
+/// for edits made since.
+fn pending_ai_attributions(
+    repo: &Repository,
+    blame_hunks: &[BlameHunk],
+    file_path: &str,
+    file_content: &str,
+) -> Result<PendingAttributions, GitAiError> {
+    let mut pending = PendingAttributions::default();
+
+    // Without a HEAD commit there is no working log to read
+    let Ok(head_sha) = repo.head().and_then(|head| head.target()) else {
+        return Ok(pending);
+    };
+    let human_author = repo.config_get_str("user.name").ok().flatten();
+    let working_va =
+        VirtualAttributions::from_just_working_log(repo.clone(), head_sha.clone(), human_author)?;
+    let Some(line_attributions) = working_va.get_line_attributions(file_path) else {
+        return Ok(pending);
+    };
+
+    let mut checkpointed_authors: HashMap<u32, &str> = HashMap::new();
+    for attribution in line_attributions {
+        if !working_va.prompts().contains_key(&attribution.author_id) {
+            continue;
+        }
+        for line in attribution.start_line..=attribution.end_line {
+            checkpointed_authors.insert(line, &attribution.author_id);
+        }
+    }
+    if checkpointed_authors.is_empty() {
+        return Ok(pending);
+    }
+
+    // The attributions come from the latest checkpoint that recorded any for this file, or from
+    // INITIAL (which describes the working copy) when no checkpoint did
+    let working_log = repo.storage.working_log_for_base_commit(&head_sha);
+    let checkpointed_content = working_log
+        .read_all_checkpoints()
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|checkpoint| &checkpoint.entries)
+        .rfind(|entry| {
+            entry.file == file_path
+                && (!entry.line_attributions.is_empty() || !entry.attributions.is_empty())
+        })
+        .and_then(|entry| working_log.get_file_version(&entry.blob_sha).ok())
+        .or_else(|| working_va.get_file_content(file_path).cloned())
+        .unwrap_or_default();
+
+    let uncommitted_lines: HashSet<u32> = blame_hunks
+        .iter()
+        .filter(|hunk| hunk.is_uncommitted())
+        .flat_map(|hunk| hunk.range.0..=hunk.range.1)
+        .collect();
+
+    let mut old_line = 1;
+    let mut new_line = 1;
+    for change in compute_line_changes(&checkpointed_content, file_content) {
+        match change.tag() {
+            LineChangeTag::Equal => {
+                if let Some(author_id) = checkpointed_authors.get(&old_line)
+                    && uncommitted_lines.contains(&new_line)
+                {
+                    pending.lines.insert(new_line, author_id.to_string());
+                }
+                old_line += 1;
+                new_line += 1;
+            }
+            LineChangeTag::Delete => old_line += 1,
+            LineChangeTag::Insert => new_line += 1,
+        }
+    }
+
+    for author_id in pending.lines.values() {
+        if pending.prompts.contains_key(author_id) {
+            continue;
+        }
+        if let Some(prompt) = working_va
+            .prompts()
+            .get(author_id)
+            .and_then(|by_commit| by_commit.values().next())
+        {
+            pending.prompts.insert(author_id.clone(), prompt.clone());
+        }
+    }
+
+    Ok(pending)
+}
+
 #[allow(clippy::type_complexity)]
 fn overlay_ai_authorship(
     repo: &Repository,
     blame_hunks: &[BlameHunk],
     file_path: &str,
     options: &GitAiBlameOptions,
+    pending: &PendingAttributions,
 ) -> Result<
     (
         HashMap<u32, String>,
@@ -839,7 +1086,7 @@ fn overlay_ai_authorship(
             cached.clone()
         } else {
             // Try to get authorship log for this commit
-            let authorship = get_reference_as_authorship_log_v3(repo, &hunk.commit_sha).ok();
+            let authorship = get_reference_as_authorship_log_v3_merged(repo, &hunk.commit_sha).ok();
             commit_authorship_cache.insert(hunk.commit_sha.clone(), authorship.clone());
             authorship
         };
@@ -898,7 +1145,21 @@ fn overlay_ai_authorship(
         } else {
             // No authorship log for this commit
             for line_num in hunk.range.0..=hunk.range.1 {
-                if options.mark_unknown {
+                if let Some((prompt_hash, prompt_record)) = pending
+                    .lines
+                    .get(&line_num)
+                    .and_then(|hash| pending.prompts.get_key_value(hash))
+                {
+                    // Uncommitted line written by an agent according to the working log
+                    if options.use_prompt_hashes_as_names {
+                        line_authors.insert(line_num, prompt_hash.clone());
+                    } else {
+                        line_authors.insert(line_num, prompt_record.agent_id.tool.clone());
+                    }
+                    prompt_records
+                        .entry(prompt_hash.clone())
+                        .or_insert_with(|| prompt_record.clone());
+                } else if options.mark_unknown {
                     // User wants explicit distinction - mark as Unknown
                     line_authors.insert(line_num, "Unknown".to_string());
                 } else if options.return_human_authors_as_human {
@@ -934,6 +1195,66 @@ fn overlay_ai_authorship(
     ))
 }
 
+/// Collect the intra-line spans recorded in the notes of the commits that introduced each
+/// blamed line, renumbered to current line numbers, along with the prompts they reference
+fn collect_char_spans(
+    repo: &Repository,
+    blame_hunks: &[BlameHunk],
+    file_path: &str,
+) -> (Vec<CharSpan>, HashMap<String, PromptRecord>) {
+    let mut spans = Vec::new();
+    let mut prompts = HashMap::new();
+    let mut commit_authorship_cache: HashMap<String, Option<AuthorshipLog>> = HashMap::new();
+
+    for hunk in blame_hunks {
+        let authorship_log = commit_authorship_cache
+            .entry(hunk.commit_sha.clone())
+            .or_insert_with(|| {
+                get_reference_as_authorship_log_v3_merged(repo, &hunk.commit_sha).ok()
+            });
+        let Some(authorship_log) = authorship_log else {
+            continue;
+        };
+        let Some(file_spans) = authorship_log.metadata.char_spans.get(file_path) else {
+            continue;
+        };
+
+        for span in file_spans {
+            if span.line < hunk.orig_range.0 || span.line > hunk.orig_range.1 {
+                continue;
+            }
+            if let Some(prompt) = authorship_log.metadata.prompts.get(&span.author) {
+                prompts
+                    .entry(span.author.clone())
+                    .or_insert_with(|| prompt.clone());
+            }
+            spans.push(CharSpan {
+                line: hunk.range.0 + (span.line - hunk.orig_range.0),
+                ..span.clone()
+            });
+        }
+    }
+
+    spans.sort_by_key(|span| (span.line, span.start));
+    (spans, prompts)
+}
+
+impl Repository {
+    /// Intra-line spans for the lines in `options.line_ranges` of `file_path` (relative to the
+    /// repo root), with the prompts they reference
+    pub fn blame_char_spans(
+        &self,
+        file_path: &str,
+        options: &GitAiBlameOptions,
+    ) -> Result<(Vec<CharSpan>, HashMap<String, PromptRecord>), GitAiError> {
+        let mut blame_hunks = Vec::new();
+        for (start_line, end_line) in &options.line_ranges {
+            blame_hunks.extend(self.blame_hunks(file_path, *start_line, *end_line, options)?);
+        }
+        Ok(collect_char_spans(self, &blame_hunks, file_path))
+    }
+}
+
 /// Metadata about user's auth state and git identity
 #[derive(Debug, Serialize)]
 struct BlameMetadata {
@@ -941,10 +1262,24 @@ struct BlameMetadata {
     current_user: Option<String>,
 }
 
+/// Whether a line's AI attribution comes from a committed note or from the working log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum AttributionStatus {
+    Committed,
+    /// Checkpointed but not committed yet
+    Pending,
+}
+
 /// JSON output structure for blame
 #[derive(Debug, Serialize)]
 struct JsonBlameOutput {
     lines: std::collections::BTreeMap<String, String>,
+    /// Same ranges as `lines`
+    line_status: std::collections::BTreeMap<String, AttributionStatus>,
+    /// Only present with `--granularity=char`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    char_spans: Option<Vec<CharSpan>>,
     prompts: HashMap<String, PromptRecordWithOtherFiles>,
     metadata: BlameMetadata,
 }
@@ -958,6 +1293,20 @@ struct PromptRecordWithOtherFiles {
     commits: Vec<String>,
 }
 
+/// Other files touched by each prompt hash across authorship logs
+fn files_by_prompt_hash<'a>(
+    prompt_hashes: impl Iterator<Item = &'a String>,
+    authorship_logs: &[AuthorshipLog],
+    exclude_file: &str,
+) -> HashMap<String, Vec<String>> {
+    prompt_hashes
+        .map(|hash| {
+            let files = get_files_for_prompt_hash(hash, authorship_logs, exclude_file);
+            (hash.clone(), files)
+        })
+        .collect()
+}
+
 /// Helper function to get all files touched by a prompt hash across authorship logs
 fn get_files_for_prompt_hash(
     prompt_hash: &str,
@@ -990,74 +1339,103 @@ fn get_files_for_prompt_hash(
     file_vec
 }
 
+#[allow(clippy::too_many_arguments)]
 fn output_json_format(
     repo: &Repository,
     line_authors: &HashMap<u32, String>,
     prompt_records: &HashMap<String, PromptRecord>,
-    authorship_logs: &[AuthorshipLog],
+    prompt_other_files: &HashMap<String, Vec<String>>,
     prompt_commits: &HashMap<String, Vec<String>>,
-    current_file: &str,
+    pending: &PendingAttributions,
+    char_spans: Option<(Vec<CharSpan>, HashMap<String, PromptRecord>)>,
+    json_out: Option<&mut Option<serde_json::Value>>,
 ) -> Result<(), GitAiError> {
     // Filter to only AI lines (where author is a prompt_id in prompt_records)
-    let mut ai_lines: Vec<(u32, String)> = line_authors
+    let mut ai_lines: Vec<(u32, String, AttributionStatus)> = line_authors
         .iter()
         .filter(|(_, author)| prompt_records.contains_key(*author))
-        .map(|(line, author)| (*line, author.clone()))
+        .map(|(line, author)| {
+            let status = if pending.lines.contains_key(line) {
+                AttributionStatus::Pending
+            } else {
+                AttributionStatus::Committed
+            };
+            (*line, author.clone(), status)
+        })
         .collect();
 
     // Sort by line number
-    ai_lines.sort_by_key(|(line, _)| *line);
+    ai_lines.sort_by_key(|(line, _, _)| *line);
 
-    // Group consecutive lines with the same prompt_id into ranges
+    // Group consecutive lines with the same prompt_id and status into ranges
     let mut lines_map: std::collections::BTreeMap<String, String> =
         std::collections::BTreeMap::new();
+    let mut line_status: std::collections::BTreeMap<String, AttributionStatus> =
+        std::collections::BTreeMap::new();
+    let range_key = |start: u32, end: u32| {
+        if start == end {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, end)
+        }
+    };
 
     if !ai_lines.is_empty() {
         let mut range_start = ai_lines[0].0;
         let mut range_end = ai_lines[0].0;
         let mut current_prompt_id = ai_lines[0].1.clone();
+        let mut current_status = ai_lines[0].2;
 
-        for (line, prompt_id) in ai_lines.iter().skip(1) {
-            if *prompt_id == current_prompt_id && *line == range_end + 1 {
+        for (line, prompt_id, status) in ai_lines.iter().skip(1) {
+            if *prompt_id == current_prompt_id
+                && *status == current_status
+                && *line == range_end + 1
+            {
                 // Extend current range
                 range_end = *line;
             } else {
                 // Save current range and start new one
-                let range_key = if range_start == range_end {
-                    range_start.to_string()
-                } else {
-                    format!("{}-{}", range_start, range_end)
-                };
-                lines_map.insert(range_key, current_prompt_id.clone());
+                let key = range_key(range_start, range_end);
+                lines_map.insert(key.clone(), current_prompt_id.clone());
+                line_status.insert(key, current_status);
 
                 range_start = *line;
                 range_end = *line;
                 current_prompt_id = prompt_id.clone();
+                current_status = *status;
             }
         }
 
         // Don't forget the last range
-        let range_key = if range_start == range_end {
-            range_start.to_string()
-        } else {
-            format!("{}-{}", range_start, range_end)
-        };
-        lines_map.insert(range_key, current_prompt_id);
+        let key = range_key(range_start, range_end);
+        lines_map.insert(key.clone(), current_prompt_id);
+        line_status.insert(key, current_status);
     }
 
-    // Only include prompts that are actually referenced in lines
-    let referenced_prompt_ids: std::collections::HashSet<&String> = lines_map.values().collect();
+    // Only include prompts that are actually referenced in lines or spans
+    let mut enriched_prompts = prompt_records.clone();
+    let (char_spans, span_prompts) = match char_spans {
+        Some((spans, prompts)) => (Some(spans), prompts),
+        None => (None, HashMap::new()),
+    };
+    for (hash, prompt) in span_prompts {
+        enriched_prompts.entry(hash).or_insert(prompt);
+    }
+    let referenced_prompt_ids: std::collections::HashSet<&String> = lines_map
+        .values()
+        .chain(char_spans.iter().flatten().map(|span| &span.author))
+        .filter(|hash| enriched_prompts.contains_key(*hash))
+        .collect();
 
     // Enrich prompts that have empty messages by falling back through storage layers
-    let mut enriched_prompts = prompt_records.clone();
-    enrich_prompt_messages(&mut enriched_prompts, &referenced_prompt_ids);
+    enrich_prompt_messages(repo, &mut enriched_prompts, &referenced_prompt_ids);
 
     // Create read models with other_files and commits populated
     let filtered_prompts: HashMap<String, PromptRecordWithOtherFiles> = enriched_prompts
         .iter()
         .filter(|(k, _)| referenced_prompt_ids.contains(k))
         .map(|(k, v)| {
-            let other_files = get_files_for_prompt_hash(k, authorship_logs, current_file);
+            let other_files = prompt_other_files.get(k).cloned().unwrap_or_default();
             let commits = prompt_commits.get(k).cloned().unwrap_or_default();
             (
                 k.clone(),
@@ -1091,6 +1469,8 @@ fn output_json_format(
 
     let output = JsonBlameOutput {
         lines: lines_map,
+        line_status,
+        char_spans,
         prompts: filtered_prompts,
         metadata: BlameMetadata {
             is_logged_in,
@@ -1098,6 +1478,13 @@ fn output_json_format(
         },
     };
 
+    if let Some(json_out) = json_out {
+        let value = serde_json::to_value(&output)
+            .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON output: {}", e)))?;
+        *json_out = Some(value);
+        return Ok(());
+    }
+
     let json_str = serde_json::to_string_pretty(&output)
         .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON output: {}", e)))?;
 
@@ -1511,7 +1898,7 @@ fn output_default_format(
 
         if !referenced_ids.is_empty() {
             let mut enriched_prompts = prompt_records.clone();
-            enrich_prompt_messages(&mut enriched_prompts, &referenced_ids);
+            enrich_prompt_messages(repo, &mut enriched_prompts, &referenced_ids);
 
             output.push_str("---\n");
 
@@ -1603,7 +1990,10 @@ fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOpti
 }
 
 pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions), GitAiError> {
-    let mut options = GitAiBlameOptions::default();
+    let mut options = GitAiBlameOptions {
+        use_cache: true,
+        ..Default::default()
+    };
     let mut file_path = None;
     let mut i = 0;
 
@@ -1852,6 +2242,23 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 options.json = true;
                 i += 1;
             }
+            "--include-uncommitted" => {
+                options.include_uncommitted = true;
+                i += 1;
+            }
+            "--granularity" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --granularity".to_string(),
+                    ));
+                }
+                options.granularity = args[i + 1].parse()?;
+                i += 2;
+            }
+            arg if arg.starts_with("--granularity=") => {
+                options.granularity = arg["--granularity=".len()..].parse()?;
+                i += 1;
+            }
 
             // Mark unknown authorship
             "--mark-unknown" => {
@@ -1865,6 +2272,12 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 i += 1;
             }
 
+            // Bypass the persistent blame cache
+            "--no-cache" => {
+                options.use_cache = false;
+                i += 1;
+            }
+
             // File path (non-option argument)
             arg if !arg.starts_with('-') => {
                 if file_path.is_none() {
@@ -1887,6 +2300,12 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
     let file_path =
         file_path.ok_or_else(|| GitAiError::Generic("No file path specified".to_string()))?;
 
+    if options.granularity == Granularity::Char && !options.json {
+        return Err(GitAiError::Generic(
+            "--granularity=char requires --json".to_string(),
+        ));
+    }
+
     Ok((file_path, options))
 }
 
@@ -0,0 +1,201 @@
+//! Persistent cache of blame attributions
+//!
+//! Blaming a large file reruns `git blame` and reparses the authorship note of every commit it
+//! touches. Results are cached under `.git/ai/blame_cache/`, one JSON file per blamed
+//! (commit, file blob, path, options), so repeated queries from editors or `search --file` skip
+//! both steps.
+//!
+//! Only results without uncommitted lines are cached: when blaming the working tree (or
+//! `--contents`), the content has to match the file's blob at HEAD. Entries are keyed on the
+//! state of the notes refs blame reads as well, and the whole cache is pruned as soon as those
+//! refs move, so a new, rewritten or fetched note is never served stale.
+
+use crate::authorship::authorship_log::PromptRecord;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::git::refs::{notes_ref_for_namespace, read_notes_namespaces};
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::utils::debug_log;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Bumped whenever the layout of `CachedBlame` or the key changes
+const CACHE_VERSION: u32 = 1;
+
+/// Notes ref state the entries in the cache directory were computed against
+const NOTES_STATE_FILE: &str = "notes_state";
+
+/// Everything blame needs to render `--json` or return its results without rerunning
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CachedBlame {
+    pub line_authors: HashMap<u32, String>,
+    pub prompt_records: HashMap<String, PromptRecord>,
+    /// prompt hash -> commits containing it
+    pub prompt_commits: HashMap<String, Vec<String>>,
+    /// prompt hash -> other files it touched in those commits
+    pub prompt_other_files: HashMap<String, Vec<String>>,
+}
+
+/// Location of one cache entry
+#[derive(Debug, Clone)]
+pub struct BlameCacheKey {
+    dir: PathBuf,
+    notes_state: String,
+    entry: String,
+}
+
+impl BlameCacheKey {
+    /// Key for blaming `file_path` with `content` under `options`, or None when the result
+    /// can't be cached (uncommitted changes, unborn HEAD, file not in the commit)
+    pub fn for_blame(
+        repo: &Repository,
+        file_path: &str,
+        content: &str,
+        options: &GitAiBlameOptions,
+    ) -> Option<Self> {
+        let rev = options.newest_commit.as_deref().unwrap_or("HEAD");
+        let mut args = repo.global_args_for_exec();
+        args.push("rev-parse".to_string());
+        args.push(format!("{}^{{commit}}", rev));
+        args.push(format!("{}:{}", rev, file_path));
+        let output = exec_git(&args).ok()?;
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let mut oids = stdout.lines();
+        let commit = oids.next()?.to_string();
+        let blob = oids.next()?.to_string();
+
+        if options.newest_commit.is_none() && blob_oid(repo, file_path, content)? != blob {
+            return None;
+        }
+
+        let notes_state = notes_state(repo)?;
+        let mut hasher = Sha256::new();
+        for part in [
+            CACHE_VERSION.to_string(),
+            notes_state.clone(),
+            commit,
+            blob,
+            file_path.to_string(),
+            options_key(options),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+
+        Some(Self {
+            dir: repo.storage.blame_cache.clone(),
+            notes_state,
+            entry: format!("{:x}.json", hasher.finalize()),
+        })
+    }
+
+    pub fn load(&self) -> Option<CachedBlame> {
+        let data = fs::read(self.dir.join(&self.entry)).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Store `blame`, dropping every entry computed against other notes. Failures only cost
+    /// a cache miss later, so they are logged and ignored.
+    pub fn store(&self, blame: &CachedBlame) {
+        if let Err(e) = self.try_store(blame) {
+            debug_log(&format!("Failed to write blame cache entry: {}", e));
+        }
+    }
+
+    fn try_store(&self, blame: &CachedBlame) -> std::io::Result<()> {
+        let state_path = self.dir.join(NOTES_STATE_FILE);
+        let cached_state = fs::read_to_string(&state_path).ok();
+        if cached_state.as_deref() != Some(self.notes_state.as_str()) {
+            if self.dir.exists() {
+                fs::remove_dir_all(&self.dir)?;
+            }
+            fs::create_dir_all(&self.dir)?;
+            fs::write(&state_path, &self.notes_state)?;
+        }
+
+        // Write then rename, so concurrent blames never read a partial entry
+        let tmp_path = self
+            .dir
+            .join(format!("{}.{}.tmp", self.entry, uuid::Uuid::new_v4()));
+        fs::write(&tmp_path, serde_json::to_vec(blame)?)?;
+        fs::rename(&tmp_path, self.dir.join(&self.entry))
+    }
+}
+
+/// Object id `content` would get as a blob at `file_path`, after the repository's clean filters
+fn blob_oid(repo: &Repository, file_path: &str, content: &str) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.push("hash-object".to_string());
+    args.push(format!("--path={}", file_path));
+    args.push("--stdin".to_string());
+    let output = exec_git_stdin(&args, content.as_bytes()).ok()?;
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+/// Current target of every notes ref blame reads authorship from
+fn notes_state(repo: &Repository) -> Option<String> {
+    let mut args = repo.global_args_for_exec();
+    args.push("for-each-ref".to_string());
+    args.push("--format=%(refname) %(objectname)".to_string());
+    for namespace in read_notes_namespaces(repo) {
+        args.push(notes_ref_for_namespace(&namespace));
+    }
+    let output = exec_git(&args).ok()?;
+    String::from_utf8(output.stdout).ok()
+}
+
+/// The options that change which lines blame attributes to whom
+fn options_key(options: &GitAiBlameOptions) -> String {
+    let ignore_revs_file = options.ignore_revs_file.as_ref().map(|path| {
+        let contents = fs::read(path).map(|data| format!("{:x}", Sha256::digest(data)));
+        (path, contents.ok())
+    });
+    format!(
+        "{:?}",
+        (
+            &options.line_ranges,
+            &options.oldest_commit,
+            options.oldest_date.map(|date| date.to_rfc3339()),
+            &options.ignore_revs,
+            ignore_revs_file,
+            options.ignore_whitespace,
+            options.use_prompt_hashes_as_names,
+            options.return_human_authors_as_human,
+            options.mark_unknown,
+            options.split_hunks_by_ai_author,
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_key_tracks_attribution_options() {
+        let base = GitAiBlameOptions::default();
+        let key = options_key(&base);
+
+        // Output-only options share entries
+        let json = GitAiBlameOptions {
+            json: true,
+            no_output: true,
+            ..Default::default()
+        };
+        assert_eq!(options_key(&json), key);
+
+        let ranged = GitAiBlameOptions {
+            line_ranges: vec![(1, 5)],
+            ..Default::default()
+        };
+        assert_ne!(options_key(&ranged), key);
+
+        let hashes = GitAiBlameOptions {
+            use_prompt_hashes_as_names: true,
+            ..Default::default()
+        };
+        assert_ne!(options_key(&hashes), key);
+    }
+}
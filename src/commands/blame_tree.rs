@@ -0,0 +1,590 @@
+//! Directory-wide blame for git-ai
+//!
+//! Provides `git-ai blame --tree <path>`, which blames every file tracked at HEAD under a path
+//! and reports the share of lines currently attributed to each tool, model and human: an AI
+//! coverage map of the codebase. Files are blamed in parallel with a bounded pool.
+
+use crate::authorship::authorship_log::PromptRecord;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository_in_path;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Number of files blamed concurrently unless `--jobs` is given
+const DEFAULT_JOBS: usize = 8;
+
+/// Line ownership for a file or directory. Lines from commits without an authorship note count
+/// as human, under their git author.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageStats {
+    pub total_lines: u32,
+    pub ai_lines: u32,
+    pub human_lines: u32,
+    pub by_tool: BTreeMap<String, u32>,
+    pub by_model: BTreeMap<String, u32>,
+    pub by_human: BTreeMap<String, u32>,
+}
+
+impl CoverageStats {
+    /// Count the lines of one file from its blame results
+    pub fn from_blame(
+        line_authors: &HashMap<u32, String>,
+        prompt_records: &HashMap<String, PromptRecord>,
+    ) -> Self {
+        let mut stats = Self::default();
+        for author in line_authors.values() {
+            stats.total_lines += 1;
+            if let Some(prompt) = prompt_records.get(author) {
+                stats.ai_lines += 1;
+                *stats
+                    .by_tool
+                    .entry(prompt.agent_id.tool.clone())
+                    .or_insert(0) += 1;
+                *stats
+                    .by_model
+                    .entry(prompt.agent_id.model.clone())
+                    .or_insert(0) += 1;
+            } else {
+                stats.human_lines += 1;
+                *stats.by_human.entry(author.clone()).or_insert(0) += 1;
+            }
+        }
+        stats
+    }
+
+    pub fn add(&mut self, other: &CoverageStats) {
+        self.total_lines += other.total_lines;
+        self.ai_lines += other.ai_lines;
+        self.human_lines += other.human_lines;
+        for (into, from) in [
+            (&mut self.by_tool, &other.by_tool),
+            (&mut self.by_model, &other.by_model),
+            (&mut self.by_human, &other.by_human),
+        ] {
+            for (key, lines) in from {
+                *into.entry(key.clone()).or_insert(0) += lines;
+            }
+        }
+    }
+
+    pub fn ai_percentage(&self) -> f64 {
+        if self.total_lines == 0 {
+            0.0
+        } else {
+            self.ai_lines as f64 / self.total_lines as f64 * 100.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileCoverage {
+    pub path: String,
+    #[serde(flatten)]
+    pub stats: CoverageStats,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TreeCoverage {
+    pub commit: String,
+    /// Repository-relative directory that was blamed ("" for the whole repository)
+    pub path: String,
+    pub totals: CoverageStats,
+    /// Sorted by path
+    pub files: Vec<FileCoverage>,
+    /// Binary files, which have no lines to attribute
+    pub skipped: Vec<String>,
+}
+
+/// A blob tracked at the blamed commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TrackedFile {
+    path: String,
+    blob_oid: String,
+}
+
+/// Parse `git ls-tree -r -z` output, keeping regular files (not symlinks or submodules)
+fn parse_ls_tree_output(output: &[u8]) -> Vec<TrackedFile> {
+    output
+        .split(|b| *b == 0)
+        .filter_map(|record| {
+            let record = std::str::from_utf8(record).ok()?;
+            let (meta, path) = record.split_once('\t')?;
+            let mut fields = meta.split_whitespace();
+            let mode = fields.next()?;
+            let kind = fields.next()?;
+            let oid = fields.next()?;
+            (kind == "blob" && mode != "120000").then(|| TrackedFile {
+                path: path.to_string(),
+                blob_oid: oid.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn list_tracked_files(
+    repo: &Repository,
+    commit: &str,
+    path: &str,
+) -> Result<Vec<TrackedFile>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("ls-tree".to_string());
+    args.push("-r".to_string());
+    args.push("-z".to_string());
+    args.push("--full-tree".to_string());
+    args.push(commit.to_string());
+    if !path.is_empty() {
+        args.push("--".to_string());
+        args.push(path.to_string());
+    }
+    let output = exec_git(&args)?;
+    Ok(parse_ls_tree_output(&output.stdout))
+}
+
+/// Blame one file at `commit`. Returns None for binary files.
+fn blame_file_coverage(
+    repo: &Repository,
+    commit: &str,
+    file: &TrackedFile,
+) -> Result<Option<CoverageStats>, GitAiError> {
+    let content = repo.find_blob(file.blob_oid.clone())?.content()?;
+    if content.contains(&0) {
+        return Ok(None);
+    }
+    if content.is_empty() {
+        return Ok(Some(CoverageStats::default()));
+    }
+
+    let options = GitAiBlameOptions {
+        newest_commit: Some(commit.to_string()),
+        no_output: true,
+        use_prompt_hashes_as_names: true,
+        use_cache: true,
+        ..Default::default()
+    };
+    let (line_authors, prompt_records) = repo.blame(&file.path, &options)?;
+    Ok(Some(CoverageStats::from_blame(
+        &line_authors,
+        &prompt_records,
+    )))
+}
+
+/// Blame every file tracked at `commit` under `path` (repository-relative, "" for everything),
+/// running at most `jobs` blames at once
+pub fn blame_tree(
+    repo: &Repository,
+    commit: &str,
+    path: &str,
+    jobs: usize,
+) -> Result<TreeCoverage, GitAiError> {
+    let files = list_tracked_files(repo, commit, path)?;
+
+    let semaphore = Arc::new(smol::lock::Semaphore::new(jobs.max(1)));
+    let results = smol::block_on(async {
+        let mut tasks = Vec::new();
+        for file in files {
+            let repo = repo.clone();
+            let commit = commit.to_string();
+            let semaphore = Arc::clone(&semaphore);
+
+            let task = smol::spawn(async move {
+                // Acquire semaphore permit to limit concurrency
+                let _permit = semaphore.acquire().await;
+
+                // Wrap blocking git operations in smol::unblock
+                smol::unblock(move || {
+                    let coverage = blame_file_coverage(&repo, &commit, &file);
+                    (file.path, coverage)
+                })
+                .await
+            });
+            tasks.push(task);
+        }
+        futures::future::join_all(tasks).await
+    });
+
+    let mut coverage = TreeCoverage {
+        commit: commit.to_string(),
+        path: path.to_string(),
+        ..Default::default()
+    };
+    for (file_path, result) in results {
+        match result? {
+            Some(stats) => {
+                coverage.totals.add(&stats);
+                coverage.files.push(FileCoverage {
+                    path: file_path,
+                    stats,
+                });
+            }
+            None => coverage.skipped.push(file_path),
+        }
+    }
+    coverage.files.sort_by(|a, b| a.path.cmp(&b.path));
+    coverage.skipped.sort();
+    Ok(coverage)
+}
+
+/// Aggregate file stats into every directory that contains them, keyed by repository-relative
+/// directory path ("" is the repository root)
+fn directory_totals(files: &[FileCoverage]) -> BTreeMap<String, CoverageStats> {
+    let mut directories: BTreeMap<String, CoverageStats> = BTreeMap::new();
+    for file in files {
+        let mut dir = file.path.as_str();
+        while let Some((parent, _)) = dir.rsplit_once('/') {
+            directories
+                .entry(parent.to_string())
+                .or_default()
+                .add(&file.stats);
+            dir = parent;
+        }
+    }
+    directories
+}
+
+/// "claude 12, cursor 3", largest first
+fn format_breakdown(counts: &BTreeMap<String, u32>) -> String {
+    let mut entries: Vec<(&String, &u32)> = counts.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    entries
+        .iter()
+        .map(|(key, lines)| format!("{} {}", key, lines))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Render the coverage as an indented tree of directories and files
+fn format_tree(coverage: &TreeCoverage) -> String {
+    let directories = directory_totals(&coverage.files);
+    let root_depth = if coverage.path.is_empty() {
+        0
+    } else {
+        coverage.path.matches('/').count() + 1
+    };
+
+    // Rows in path order; directories sort before their contents because of the trailing '/'
+    let mut rows: Vec<(String, usize, &CoverageStats)> = Vec::new();
+    for (dir, stats) in &directories {
+        let depth = dir.matches('/').count() + 1;
+        if depth > root_depth {
+            rows.push((format!("{}/", dir), depth, stats));
+        }
+    }
+    for file in &coverage.files {
+        let depth = file.path.matches('/').count() + 1;
+        rows.push((file.path.clone(), depth, &file.stats));
+    }
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let label = |path: &str, depth: usize| {
+        let name = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(path);
+        let suffix = if path.ends_with('/') { "/" } else { "" };
+        format!(
+            "{}{}{}",
+            "  ".repeat(depth.saturating_sub(root_depth)),
+            name,
+            suffix
+        )
+    };
+    let root_label = if coverage.path.is_empty() {
+        ".".to_string()
+    } else {
+        format!("{}/", coverage.path)
+    };
+    let name_width = rows
+        .iter()
+        .map(|(path, depth, _)| label(path, *depth).chars().count())
+        .chain(std::iter::once(root_label.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max("Path".len());
+
+    let mut output = format!(
+        "{:<name_width$}  {:>7}  {:>7}  {:>5}  TOOLS\n",
+        "Path", "LINES", "AI", "AI%"
+    );
+    let mut push_row = |name: &str, stats: &CoverageStats| {
+        let row = format!(
+            "{:<name_width$}  {:>7}  {:>7}  {:>4.0}%  {}",
+            name,
+            stats.total_lines,
+            stats.ai_lines,
+            stats.ai_percentage(),
+            format_breakdown(&stats.by_tool)
+        );
+        output.push_str(row.trim_end());
+        output.push('\n');
+    };
+    push_row(&root_label, &coverage.totals);
+    for (path, depth, stats) in &rows {
+        push_row(&label(path, *depth), stats);
+    }
+
+    output.push('\n');
+    for (title, counts) in [
+        ("Tools", &coverage.totals.by_tool),
+        ("Models", &coverage.totals.by_model),
+        ("Humans", &coverage.totals.by_human),
+    ] {
+        let breakdown = format_breakdown(counts);
+        output.push_str(&format!(
+            "{}: {}\n",
+            title,
+            if breakdown.is_empty() {
+                "(none)"
+            } else {
+                &breakdown
+            }
+        ));
+    }
+    if !coverage.skipped.is_empty() {
+        output.push_str(&format!(
+            "Skipped {} binary files\n",
+            coverage.skipped.len()
+        ));
+    }
+    output.trim_end().to_string()
+}
+
+/// Resolve a path given on the command line (relative to the current directory) to a
+/// repository-relative directory path
+fn repo_relative_path(repo: &Repository, path: &str) -> Result<String, GitAiError> {
+    let workdir = repo.workdir()?.canonicalize()?;
+    let absolute = std::env::current_dir()?.join(path);
+    let absolute = absolute
+        .canonicalize()
+        .map_err(|e| GitAiError::Generic(format!("Failed to resolve path '{}': {}", path, e)))?;
+    let relative = absolute.strip_prefix(&workdir).map_err(|_| {
+        GitAiError::Generic(format!(
+            "Path '{}' is not within repository root '{}'",
+            path,
+            workdir.display()
+        ))
+    })?;
+    Ok(relative
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_string())
+}
+
+pub fn handle_blame_tree(args: &[String]) {
+    let mut path: Option<String> = None;
+    let mut json = false;
+    let mut jobs = DEFAULT_JOBS;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--tree" => {}
+            "--json" => json = true,
+            "-j" | "--jobs" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("{} requires a value", args[i]);
+                    print_usage();
+                    std::process::exit(1);
+                };
+                jobs = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => {
+                        eprintln!("Invalid --jobs value: {}", value);
+                        std::process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other if other.starts_with('-') => {
+                eprintln!("Unknown argument: {}", other);
+                print_usage();
+                std::process::exit(1);
+            }
+            other => {
+                if path.is_some() {
+                    eprintln!("Unexpected argument: {}", other);
+                    print_usage();
+                    std::process::exit(1);
+                }
+                path = Some(other.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository_in_path(".") {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = repo_relative_path(&repo, path.as_deref().unwrap_or(".")).and_then(|relative| {
+        let head = repo.head()?.target()?;
+        blame_tree(&repo, &head, &relative, jobs)
+    });
+    let coverage = match result {
+        Ok(coverage) => coverage,
+        Err(e) => {
+            eprintln!("Blame failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&coverage) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to serialize coverage: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        println!("{}", format_tree(&coverage));
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: git-ai blame --tree [<path>] [--json] [--jobs <n>]");
+    eprintln!();
+    eprintln!("Blames every file tracked at HEAD under <path> (default: the current directory)");
+    eprintln!("and shows the share of lines attributed to each tool, model and human.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --json         Output the coverage map as JSON");
+    eprintln!(
+        "  -j, --jobs <n> Number of files blamed in parallel (default: {})",
+        DEFAULT_JOBS
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::working_log::AgentId;
+
+    fn prompt(tool: &str, model: &str) -> PromptRecord {
+        PromptRecord {
+            agent_id: AgentId {
+                tool: tool.to_string(),
+                id: "session".to_string(),
+                model: model.to_string(),
+            },
+            human_author: None,
+            messages: Vec::new(),
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+        }
+    }
+
+    fn file(path: &str, ai_lines: u32, human_lines: u32) -> FileCoverage {
+        FileCoverage {
+            path: path.to_string(),
+            stats: CoverageStats {
+                total_lines: ai_lines + human_lines,
+                ai_lines,
+                human_lines,
+                by_tool: if ai_lines > 0 {
+                    BTreeMap::from([("cursor".to_string(), ai_lines)])
+                } else {
+                    BTreeMap::new()
+                },
+                by_model: BTreeMap::new(),
+                by_human: BTreeMap::from([("Alice".to_string(), human_lines)]),
+            },
+        }
+    }
+
+    #[test]
+    fn test_coverage_from_blame() {
+        let line_authors = HashMap::from([
+            (1, "a1b2c3".to_string()),
+            (2, "a1b2c3".to_string()),
+            (3, "d4e5f6".to_string()),
+            (4, "Alice".to_string()),
+        ]);
+        let prompt_records = HashMap::from([
+            ("a1b2c3".to_string(), prompt("cursor", "gpt-5")),
+            ("d4e5f6".to_string(), prompt("claude", "claude-sonnet-4")),
+        ]);
+
+        let stats = CoverageStats::from_blame(&line_authors, &prompt_records);
+        assert_eq!(stats.total_lines, 4);
+        assert_eq!(stats.ai_lines, 3);
+        assert_eq!(stats.human_lines, 1);
+        assert_eq!(
+            stats.by_tool,
+            BTreeMap::from([("claude".to_string(), 1), ("cursor".to_string(), 2)])
+        );
+        assert_eq!(stats.by_model["claude-sonnet-4"], 1);
+        assert_eq!(stats.by_human["Alice"], 1);
+        assert_eq!(stats.ai_percentage(), 75.0);
+    }
+
+    #[test]
+    fn test_parse_ls_tree_output() {
+        let output = b"100644 blob 1111111111111111111111111111111111111111\tsrc/lib.rs\0\
+120000 blob 2222222222222222222222222222222222222222\tlink\0\
+160000 commit 3333333333333333333333333333333333333333\tvendor/dep\0\
+100755 blob 4444444444444444444444444444444444444444\tscripts/run me.sh\0";
+        let files = parse_ls_tree_output(output);
+        assert_eq!(
+            files,
+            vec![
+                TrackedFile {
+                    path: "src/lib.rs".to_string(),
+                    blob_oid: "1111111111111111111111111111111111111111".to_string(),
+                },
+                TrackedFile {
+                    path: "scripts/run me.sh".to_string(),
+                    blob_oid: "4444444444444444444444444444444444444444".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_tree_rolls_up_directories() {
+        let files = vec![
+            file("README.md", 0, 4),
+            file("src/cli/args.rs", 2, 2),
+            file("src/lib.rs", 6, 2),
+        ];
+        let mut coverage = TreeCoverage {
+            commit: "abc123".to_string(),
+            files,
+            ..Default::default()
+        };
+        for file in coverage.files.clone() {
+            coverage.totals.add(&file.stats);
+        }
+
+        let directories = directory_totals(&coverage.files);
+        assert_eq!(directories["src"].total_lines, 12);
+        assert_eq!(directories["src"].ai_lines, 8);
+        assert_eq!(directories["src/cli"].total_lines, 4);
+
+        let output = format_tree(&coverage);
+        let rows: Vec<&str> = output.lines().collect();
+        assert_eq!(rows[0], "Path             LINES       AI    AI%  TOOLS");
+        assert_eq!(rows[1], ".                   16        8    50%  cursor 8");
+        assert_eq!(rows[2], "  README.md          4        0     0%");
+        assert_eq!(rows[3], "  src/              12        8    67%  cursor 8");
+        assert_eq!(rows[4], "    cli/             4        2    50%  cursor 2");
+        assert_eq!(rows[5], "      args.rs        4        2    50%  cursor 2");
+        assert_eq!(rows[6], "    lib.rs           8        6    75%  cursor 6");
+        assert!(output.contains("Humans: Alice 8"), "{}", output);
+    }
+}
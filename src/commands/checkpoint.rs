@@ -13,7 +13,7 @@ use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
 use crate::config::Config;
 use crate::error::GitAiError;
-use crate::git::repo_storage::{PersistedWorkingLog, RepoStorage};
+use crate::git::repo_storage::{CheckpointIndex, PersistedWorkingLog, RepoStorage};
 use crate::git::repository::Repository;
 use crate::git::status::{EntryKind, StatusCode};
 use crate::utils::{debug_log, normalize_to_posix};
@@ -278,20 +278,22 @@ pub fn run(
     ));
 
     let read_checkpoints_start = Instant::now();
-    let mut checkpoints = if reset {
+    let checkpoint_index = if reset {
         // If reset flag is set, start with an empty working log
         working_log.reset_working_log()?;
-        Vec::new()
+        CheckpointIndex::default()
     } else {
-        working_log.read_all_checkpoints()?
+        working_log.read_checkpoint_index()?
     };
+    let mut checkpoint_count = checkpoint_index.checkpoint_count;
     debug_log(&format!(
-        "[BENCHMARK] Reading {} checkpoints took {:?}",
-        checkpoints.len(),
+        "[BENCHMARK] Reading checkpoint index ({} checkpoints) took {:?}",
+        checkpoint_count,
         read_checkpoints_start.elapsed()
     ));
 
     if show_working_log {
+        let checkpoints = working_log.read_all_checkpoints()?;
         if checkpoints.is_empty() {
             eprintln!("No working log entries found.");
         } else {
@@ -376,7 +378,7 @@ pub fn run(
         &working_log,
         &files,
         &file_content_hashes,
-        &checkpoints,
+        &checkpoint_index,
         agent_run_result.as_ref(),
         ts,
         is_pre_commit,
@@ -443,7 +445,7 @@ pub fn run(
             "[BENCHMARK] Appending checkpoint to working log took {:?}",
             append_start.elapsed()
         ));
-        checkpoints.push(checkpoint.clone());
+        checkpoint_count += 1;
 
         // Build common attributes once (reused for all events)
         let attrs = build_checkpoint_attrs(repo, &base_commit, checkpoint.agent_id.as_ref());
@@ -530,7 +532,7 @@ pub fn run(
         "[BENCHMARK] Total checkpoint run took {:?}",
         checkpoint_start.elapsed()
     ));
-    Ok((entries.len(), files.len(), checkpoints.len()))
+    Ok((entries.len(), files.len(), checkpoint_count))
 }
 
 // Gets tracked changes AND
@@ -660,43 +662,34 @@ fn get_all_tracked_files(
     ));
 
     let checkpoints_read_start = Instant::now();
-    if let Ok(working_log_data) = working_log.read_all_checkpoints() {
-        for checkpoint in &working_log_data {
-            for entry in &checkpoint.entries {
-                // Normalize path separators to forward slashes
-                let normalized_path = normalize_to_posix(&entry.file);
-                // Filter out paths outside the repository to prevent git command failures
-                if !is_path_in_repo(&normalized_path) {
-                    debug_log(&format!(
-                        "Skipping checkpoint file outside repository: {}",
-                        normalized_path
-                    ));
-                    continue;
-                }
-                if should_ignore_file_with_matcher(&normalized_path, ignore_matcher) {
-                    continue;
-                }
-                if !files.contains(&normalized_path) {
-                    // Check if it's a text file before adding
-                    if is_text_file(working_log, &normalized_path) {
-                        files.insert(normalized_path);
-                    }
-                }
+    let checkpoint_index = working_log.read_checkpoint_index().unwrap_or_default();
+    for file in checkpoint_index.files.keys() {
+        // Normalize path separators to forward slashes
+        let normalized_path = normalize_to_posix(file);
+        // Filter out paths outside the repository to prevent git command failures
+        if !is_path_in_repo(&normalized_path) {
+            debug_log(&format!(
+                "Skipping checkpoint file outside repository: {}",
+                normalized_path
+            ));
+            continue;
+        }
+        if should_ignore_file_with_matcher(&normalized_path, ignore_matcher) {
+            continue;
+        }
+        if !files.contains(&normalized_path) {
+            // Check if it's a text file before adding
+            if is_text_file(working_log, &normalized_path) {
+                files.insert(normalized_path);
             }
         }
     }
     debug_log(&format!(
-        "[BENCHMARK]   Reading checkpoints in get_all_tracked_files took {:?}",
+        "[BENCHMARK]   Reading checkpoint index in get_all_tracked_files took {:?}",
         checkpoints_read_start.elapsed()
     ));
 
-    let has_ai_checkpoints = if let Ok(working_log_data) = working_log.read_all_checkpoints() {
-        working_log_data.iter().any(|checkpoint| {
-            checkpoint.kind == CheckpointKind::AiAgent || checkpoint.kind == CheckpointKind::AiTab
-        })
-    } else {
-        false
-    };
+    let has_ai_checkpoints = checkpoint_index.has_ai_checkpoints;
 
     let status_files_start = Instant::now();
     let mut results_for_tracked_files = if is_pre_commit && !has_ai_checkpoints {
@@ -844,40 +837,25 @@ fn get_previous_content_from_head(
     }
 }
 
-fn working_log_entry_has_non_human_attribution(entry: &WorkingLogEntry) -> bool {
-    entry
-        .line_attributions
-        .iter()
-        .any(|attr| attr.author_id != CheckpointKind::Human.to_str())
-        || entry
-            .attributions
-            .iter()
-            .any(|attr| attr.author_id != CheckpointKind::Human.to_str())
-}
-
 fn build_previous_file_state_maps(
-    previous_checkpoints: &[Checkpoint],
+    checkpoint_index: &CheckpointIndex,
     initial_attributions: &HashMap<String, Vec<LineAttribution>>,
 ) -> (HashMap<String, PreviousFileState>, HashSet<String>) {
     let mut previous_file_state_by_file: HashMap<String, PreviousFileState> = HashMap::new();
     let mut ai_touched_files: HashSet<String> = initial_attributions.keys().cloned().collect();
 
-    // Keep only the latest entry for each file.
-    for checkpoint in previous_checkpoints {
-        for entry in &checkpoint.entries {
-            previous_file_state_by_file.insert(
-                entry.file.clone(),
-                PreviousFileState {
-                    blob_sha: entry.blob_sha.clone(),
-                    attributions: entry.attributions.clone(),
-                },
-            );
+    // The index already holds only the latest entry for each file.
+    for (file, indexed) in &checkpoint_index.files {
+        previous_file_state_by_file.insert(
+            file.clone(),
+            PreviousFileState {
+                blob_sha: indexed.latest.blob_sha.clone(),
+                attributions: indexed.latest.attributions.clone(),
+            },
+        );
 
-            if checkpoint.kind != CheckpointKind::Human
-                || working_log_entry_has_non_human_attribution(entry)
-            {
-                ai_touched_files.insert(entry.file.clone());
-            }
+        if indexed.ai_attributed {
+            ai_touched_files.insert(file.clone());
         }
     }
 
@@ -1109,7 +1087,7 @@ async fn get_checkpoint_entries(
     working_log: &PersistedWorkingLog,
     files: &[String],
     file_content_hashes: &HashMap<String, String>,
-    previous_checkpoints: &[Checkpoint],
+    checkpoint_index: &CheckpointIndex,
     agent_run_result: Option<&AgentRunResult>,
     ts: u128,
     is_pre_commit: bool,
@@ -1127,7 +1105,7 @@ async fn get_checkpoint_entries(
 
     let precompute_start = Instant::now();
     let (previous_file_state_by_file, ai_touched_files) =
-        build_previous_file_state_maps(previous_checkpoints, &initial_attributions);
+        build_previous_file_state_maps(checkpoint_index, &initial_attributions);
     debug_log(&format!(
         "[BENCHMARK] Precomputing previous state maps took {:?}",
         precompute_start.elapsed()
@@ -3,6 +3,10 @@ use crate::{
         transcript::{AiTranscript, Message},
         working_log::{AgentId, CheckpointKind},
     },
+    commands::checkpoint_agent::transcript_stream::{
+        BoundedTranscript, JsonlTranscriptParser, TranscriptBudget, parse_jsonl_transcript,
+        parse_jsonl_transcript_resumable,
+    },
     error::GitAiError,
     observability::log_error,
 };
@@ -10,7 +14,7 @@ use chrono::{TimeZone, Utc};
 use dirs;
 use glob::glob;
 use rusqlite::{Connection, OpenFlags};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::path::{Component, Path, PathBuf};
@@ -82,25 +86,28 @@ impl AgentCheckpointPreset for ClaudePreset {
                 )
             })?;
 
-        // Parse into transcript and extract model
-        let (transcript, model) =
-            match ClaudePreset::transcript_and_model_from_claude_code_jsonl(transcript_path) {
-                Ok((transcript, model)) => (transcript, model),
-                Err(e) => {
-                    eprintln!("[Warning] Failed to parse Claude JSONL: {e}");
-                    log_error(
-                        &e,
-                        Some(serde_json::json!({
-                            "agent_tool": "claude",
-                            "operation": "transcript_and_model_from_claude_code_jsonl"
-                        })),
-                    );
-                    (
-                        crate::authorship::transcript::AiTranscript::new(),
-                        Some("unknown".to_string()),
-                    )
-                }
-            };
+        // Parse into transcript and extract model. Checkpoints run after every edit, so only
+        // the lines appended since the previous checkpoint are parsed.
+        let (transcript, model) = match parse_jsonl_transcript_resumable::<ClaudeTranscriptParser>(
+            path,
+            &TranscriptBudget::default(),
+        ) {
+            Ok((transcript, model)) => (transcript, model),
+            Err(e) => {
+                eprintln!("[Warning] Failed to parse Claude JSONL: {e}");
+                log_error(
+                    &e,
+                    Some(serde_json::json!({
+                        "agent_tool": "claude",
+                        "operation": "transcript_and_model_from_claude_code_jsonl"
+                    })),
+                );
+                (
+                    crate::authorship::transcript::AiTranscript::new(),
+                    Some("unknown".to_string()),
+                )
+            }
+        };
 
         // The filename should be a UUID
         let agent_id = AgentId {
@@ -165,118 +172,144 @@ impl ClaudePreset {
     pub fn transcript_and_model_from_claude_code_jsonl(
         transcript_path: &str,
     ) -> Result<(AiTranscript, Option<String>), GitAiError> {
-        let jsonl_content =
-            std::fs::read_to_string(transcript_path).map_err(GitAiError::IoError)?;
-        let mut transcript = AiTranscript::new();
-        let mut model = None;
-        let mut plan_states = std::collections::HashMap::new();
+        parse_jsonl_transcript::<ClaudeTranscriptParser>(
+            Path::new(transcript_path),
+            &TranscriptBudget::default(),
+        )
+    }
+}
 
-        for line in jsonl_content.lines() {
-            if !line.trim().is_empty() {
-                // Parse the raw JSONL entry
-                let raw_entry: serde_json::Value = serde_json::from_str(line)?;
-                let timestamp = raw_entry["timestamp"].as_str().map(|s| s.to_string());
+/// Line parser for Claude Code JSONL transcripts
+#[derive(Default, Serialize, Deserialize)]
+struct ClaudeTranscriptParser {
+    /// Plan file contents, so Edits to a plan can be replayed onto it
+    plan_states: HashMap<String, String>,
+}
 
-                // Extract model from assistant messages if we haven't found it yet
-                if model.is_none()
-                    && raw_entry["type"].as_str() == Some("assistant")
-                    && let Some(model_str) = raw_entry["message"]["model"].as_str()
-                {
-                    model = Some(model_str.to_string());
-                }
+impl JsonlTranscriptParser for ClaudeTranscriptParser {
+    const KIND: &'static str = "claude";
 
-                // Extract messages based on the type
-                match raw_entry["type"].as_str() {
-                    Some("user") => {
-                        // Handle user messages
-                        if let Some(content) = raw_entry["message"]["content"].as_str() {
-                            if !content.trim().is_empty() {
-                                transcript.add_message(Message::User {
-                                    text: content.to_string(),
-                                    timestamp: timestamp.clone(),
-                                });
-                            }
-                        } else if let Some(content_array) =
-                            raw_entry["message"]["content"].as_array()
+    fn parse_line(
+        &mut self,
+        raw_entry: &serde_json::Value,
+        transcript: &mut BoundedTranscript,
+        model: &mut Option<String>,
+        budget: &TranscriptBudget,
+    ) {
+        let timestamp = raw_entry["timestamp"].as_str().map(|s| s.to_string());
+
+        // Extract model from assistant messages if we haven't found it yet
+        if model.is_none()
+            && raw_entry["type"].as_str() == Some("assistant")
+            && let Some(model_str) = raw_entry["message"]["model"].as_str()
+        {
+            *model = Some(model_str.to_string());
+        }
+
+        // Extract messages based on the type
+        match raw_entry["type"].as_str() {
+            Some("user") => {
+                // Handle user messages
+                if let Some(content) = raw_entry["message"]["content"].as_str() {
+                    if !content.trim().is_empty() {
+                        transcript.push(
+                            Message::User {
+                                text: content.to_string(),
+                                timestamp: timestamp.clone(),
+                            },
+                            budget,
+                        );
+                    }
+                } else if let Some(content_array) = raw_entry["message"]["content"].as_array() {
+                    // Handle user messages with content array
+                    for item in content_array {
+                        // Skip tool_result items - those are system-generated responses, not human input
+                        if item["type"].as_str() == Some("tool_result") {
+                            continue;
+                        }
+                        // Handle text content blocks from actual user input
+                        if item["type"].as_str() == Some("text")
+                            && let Some(text) = item["text"].as_str()
+                            && !text.trim().is_empty()
                         {
-                            // Handle user messages with content array
-                            for item in content_array {
-                                // Skip tool_result items - those are system-generated responses, not human input
-                                if item["type"].as_str() == Some("tool_result") {
-                                    continue;
-                                }
-                                // Handle text content blocks from actual user input
-                                if item["type"].as_str() == Some("text")
-                                    && let Some(text) = item["text"].as_str()
+                            transcript.push(
+                                Message::User {
+                                    text: text.to_string(),
+                                    timestamp: timestamp.clone(),
+                                },
+                                budget,
+                            );
+                        }
+                    }
+                }
+            }
+            Some("assistant") => {
+                // Handle assistant messages
+                if let Some(content_array) = raw_entry["message"]["content"].as_array() {
+                    for item in content_array {
+                        match item["type"].as_str() {
+                            Some("text") => {
+                                if let Some(text) = item["text"].as_str()
                                     && !text.trim().is_empty()
                                 {
-                                    transcript.add_message(Message::User {
-                                        text: text.to_string(),
-                                        timestamp: timestamp.clone(),
-                                    });
+                                    transcript.push(
+                                        Message::Assistant {
+                                            text: text.to_string(),
+                                            timestamp: timestamp.clone(),
+                                        },
+                                        budget,
+                                    );
                                 }
                             }
-                        }
-                    }
-                    Some("assistant") => {
-                        // Handle assistant messages
-                        if let Some(content_array) = raw_entry["message"]["content"].as_array() {
-                            for item in content_array {
-                                match item["type"].as_str() {
-                                    Some("text") => {
-                                        if let Some(text) = item["text"].as_str()
-                                            && !text.trim().is_empty()
-                                        {
-                                            transcript.add_message(Message::Assistant {
-                                                text: text.to_string(),
+                            Some("thinking") => {
+                                if let Some(thinking) = item["thinking"].as_str()
+                                    && !thinking.trim().is_empty()
+                                {
+                                    transcript.push(
+                                        Message::Assistant {
+                                            text: thinking.to_string(),
+                                            timestamp: timestamp.clone(),
+                                        },
+                                        budget,
+                                    );
+                                }
+                            }
+                            Some("tool_use") => {
+                                if let (Some(name), Some(_input)) =
+                                    (item["name"].as_str(), item["input"].as_object())
+                                {
+                                    // Check if this is a Write/Edit to a plan file
+                                    if let Some(plan_text) = extract_plan_from_tool_use(
+                                        name,
+                                        &item["input"],
+                                        &mut self.plan_states,
+                                    ) {
+                                        transcript.push(
+                                            Message::Plan {
+                                                text: plan_text,
                                                 timestamp: timestamp.clone(),
-                                            });
-                                        }
-                                    }
-                                    Some("thinking") => {
-                                        if let Some(thinking) = item["thinking"].as_str()
-                                            && !thinking.trim().is_empty()
-                                        {
-                                            transcript.add_message(Message::Assistant {
-                                                text: thinking.to_string(),
+                                            },
+                                            budget,
+                                        );
+                                    } else {
+                                        transcript.push(
+                                            Message::ToolUse {
+                                                name: name.to_string(),
+                                                input: item["input"].clone(),
                                                 timestamp: timestamp.clone(),
-                                            });
-                                        }
-                                    }
-                                    Some("tool_use") => {
-                                        if let (Some(name), Some(_input)) =
-                                            (item["name"].as_str(), item["input"].as_object())
-                                        {
-                                            // Check if this is a Write/Edit to a plan file
-                                            if let Some(plan_text) = extract_plan_from_tool_use(
-                                                name,
-                                                &item["input"],
-                                                &mut plan_states,
-                                            ) {
-                                                transcript.add_message(Message::Plan {
-                                                    text: plan_text,
-                                                    timestamp: timestamp.clone(),
-                                                });
-                                            } else {
-                                                transcript.add_message(Message::ToolUse {
-                                                    name: name.to_string(),
-                                                    input: item["input"].clone(),
-                                                    timestamp: timestamp.clone(),
-                                                });
-                                            }
-                                        }
+                                            },
+                                            budget,
+                                        );
                                     }
-                                    _ => continue, // Skip unknown content types
                                 }
                             }
+                            _ => continue, // Skip unknown content types
                         }
                     }
-                    _ => continue, // Skip unknown message types
                 }
             }
+            _ => {} // Skip unknown message types
         }
-
-        Ok((transcript, model))
     }
 }
 
@@ -827,7 +860,10 @@ impl AgentCheckpointPreset for CodexPreset {
             );
 
         let (transcript, model) = if let Some(path) = transcript_path.as_deref() {
-            match CodexPreset::transcript_and_model_from_codex_rollout_jsonl(path) {
+            match parse_jsonl_transcript_resumable::<CodexTranscriptParser>(
+                Path::new(path),
+                &TranscriptBudget::default(),
+            ) {
                 Ok((transcript, model)) => (transcript, model),
                 Err(e) => {
                     eprintln!("[Warning] Failed to parse Codex rollout JSONL: {e}");
@@ -943,177 +979,181 @@ impl CodexPreset {
     pub fn transcript_and_model_from_codex_rollout_jsonl(
         transcript_path: &str,
     ) -> Result<(AiTranscript, Option<String>), GitAiError> {
-        let jsonl_content =
-            std::fs::read_to_string(transcript_path).map_err(GitAiError::IoError)?;
+        parse_jsonl_transcript::<CodexTranscriptParser>(
+            Path::new(transcript_path),
+            &TranscriptBudget::default(),
+        )
+    }
+}
 
-        let mut parsed_lines: Vec<serde_json::Value> = Vec::new();
-        for line in jsonl_content.lines() {
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            let value: serde_json::Value = serde_json::from_str(trimmed)?;
-            parsed_lines.push(value);
-        }
+/// Line parser for Codex rollout JSONL transcripts
+#[derive(Default, Serialize, Deserialize)]
+struct CodexTranscriptParser {
+    /// Legacy `event_msg` messages, used only when the rollout has no response items
+    fallback: BoundedTranscript,
+}
 
-        let mut transcript = AiTranscript::new();
-        let mut model = None;
+impl JsonlTranscriptParser for CodexTranscriptParser {
+    const KIND: &'static str = "codex";
 
-        for entry in &parsed_lines {
-            let timestamp = entry
-                .get("timestamp")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
+    fn parse_line(
+        &mut self,
+        entry: &serde_json::Value,
+        transcript: &mut BoundedTranscript,
+        model: &mut Option<String>,
+        budget: &TranscriptBudget,
+    ) {
+        let timestamp = entry
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
 
-            let item_type = entry
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default();
-            let payload = entry.get("payload").unwrap_or(entry);
+        let item_type = entry
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let payload = entry.get("payload").unwrap_or(entry);
 
-            match item_type {
-                "turn_context" => {
-                    if let Some(model_name) = payload.get("model").and_then(|v| v.as_str())
-                        && !model_name.trim().is_empty()
-                    {
-                        // Keep the latest model for sessions that switched models mid-thread.
-                        model = Some(model_name.to_string());
-                    }
+        match item_type {
+            "turn_context" => {
+                if let Some(model_name) = payload.get("model").and_then(|v| v.as_str())
+                    && !model_name.trim().is_empty()
+                {
+                    // Keep the latest model for sessions that switched models mid-thread.
+                    *model = Some(model_name.to_string());
                 }
-                "response_item" => {
-                    let response_type = payload
-                        .get("type")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or_default();
-                    match response_type {
-                        "message" => {
-                            let role = payload
-                                .get("role")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or_default();
-
-                            let mut text_parts: Vec<String> = Vec::new();
-                            if let Some(content_arr) =
-                                payload.get("content").and_then(|v| v.as_array())
-                            {
-                                for item in content_arr {
-                                    let content_type = item
-                                        .get("type")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or_default();
-                                    if (role == "assistant" || role == "user")
-                                        && (content_type == "output_text"
-                                            || content_type == "input_text")
-                                        && let Some(text) =
-                                            item.get("text").and_then(|v| v.as_str())
-                                    {
-                                        let trimmed = text.trim();
-                                        if !trimmed.is_empty() {
-                                            text_parts.push(trimmed.to_string());
-                                        }
+            }
+            "response_item" => {
+                let response_type = payload
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                match response_type {
+                    "message" => {
+                        let role = payload
+                            .get("role")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default();
+
+                        let mut text_parts: Vec<String> = Vec::new();
+                        if let Some(content_arr) = payload.get("content").and_then(|v| v.as_array())
+                        {
+                            for item in content_arr {
+                                let content_type = item
+                                    .get("type")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or_default();
+                                if (role == "assistant" || role == "user")
+                                    && (content_type == "output_text"
+                                        || content_type == "input_text")
+                                    && let Some(text) = item.get("text").and_then(|v| v.as_str())
+                                {
+                                    let trimmed = text.trim();
+                                    if !trimmed.is_empty() {
+                                        text_parts.push(trimmed.to_string());
                                     }
                                 }
                             }
+                        }
 
-                            if !text_parts.is_empty() {
-                                let joined = text_parts.join("\n");
-                                if role == "user" {
-                                    transcript.add_message(Message::User {
+                        if !text_parts.is_empty() {
+                            let joined = text_parts.join("\n");
+                            if role == "user" {
+                                transcript.push(
+                                    Message::User {
                                         text: joined,
-                                        timestamp: timestamp.clone(),
-                                    });
-                                } else if role == "assistant" {
-                                    transcript.add_message(Message::Assistant {
+                                        timestamp,
+                                    },
+                                    budget,
+                                );
+                            } else if role == "assistant" {
+                                transcript.push(
+                                    Message::Assistant {
                                         text: joined,
-                                        timestamp: timestamp.clone(),
-                                    });
-                                }
+                                        timestamp,
+                                    },
+                                    budget,
+                                );
                             }
                         }
-                        "function_call" | "custom_tool_call" | "local_shell_call"
-                        | "web_search_call" => {
-                            let name = payload
-                                .get("name")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or(response_type)
-                                .to_string();
-
-                            let input = if response_type == "function_call" {
-                                if let Some(arguments) =
-                                    payload.get("arguments").and_then(|v| v.as_str())
-                                {
-                                    serde_json::from_str::<serde_json::Value>(arguments)
-                                        .unwrap_or_else(|_| {
-                                            serde_json::Value::String(arguments.to_string())
-                                        })
-                                } else {
-                                    payload.get("arguments").cloned().unwrap_or_else(|| {
-                                        serde_json::Value::Object(serde_json::Map::new())
-                                    })
-                                }
-                            } else if let Some(input) =
-                                payload.get("input").and_then(|v| v.as_str())
+                    }
+                    "function_call" | "custom_tool_call" | "local_shell_call"
+                    | "web_search_call" => {
+                        let name = payload
+                            .get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or(response_type)
+                            .to_string();
+
+                        let input = if response_type == "function_call" {
+                            if let Some(arguments) =
+                                payload.get("arguments").and_then(|v| v.as_str())
                             {
-                                serde_json::Value::String(input.to_string())
+                                serde_json::from_str::<serde_json::Value>(arguments).unwrap_or_else(
+                                    |_| serde_json::Value::String(arguments.to_string()),
+                                )
                             } else {
-                                payload.clone()
-                            };
+                                payload.get("arguments").cloned().unwrap_or_else(|| {
+                                    serde_json::Value::Object(serde_json::Map::new())
+                                })
+                            }
+                        } else if let Some(input) = payload.get("input").and_then(|v| v.as_str()) {
+                            serde_json::Value::String(input.to_string())
+                        } else {
+                            payload.clone()
+                        };
 
-                            transcript.add_message(Message::ToolUse {
+                        transcript.push(
+                            Message::ToolUse {
                                 name,
                                 input,
-                                timestamp: timestamp.clone(),
-                            });
-                        }
-                        _ => {}
+                                timestamp,
+                            },
+                            budget,
+                        );
                     }
+                    _ => {}
                 }
-                _ => {}
             }
-        }
-
-        if transcript.messages().is_empty() {
             // Backward-compatible fallback for sessions that only recorded legacy event messages.
-            for entry in &parsed_lines {
-                let timestamp = entry
-                    .get("timestamp")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string());
-                if entry.get("type").and_then(|v| v.as_str()) != Some("event_msg") {
-                    continue;
-                }
-
-                let payload = entry.get("payload").unwrap_or(entry);
+            "event_msg" if transcript.is_empty() => {
                 let event_type = payload
                     .get("type")
                     .and_then(|v| v.as_str())
                     .unwrap_or_default();
+                let text = payload
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .map(|text| text.trim().to_string())
+                    .filter(|text| !text.is_empty());
 
-                if event_type == "user_message" {
-                    if let Some(text) = payload.get("message").and_then(|v| v.as_str()) {
-                        let trimmed = text.trim();
-                        if !trimmed.is_empty() {
-                            transcript.add_message(Message::User {
-                                text: trimmed.to_string(),
-                                timestamp: timestamp.clone(),
-                            });
-                        }
+                match (event_type, text) {
+                    ("user_message", Some(text)) => {
+                        self.fallback
+                            .push(Message::User { text, timestamp }, budget);
                     }
-                } else if event_type == "agent_message"
-                    && let Some(text) = payload.get("message").and_then(|v| v.as_str())
-                {
-                    let trimmed = text.trim();
-                    if !trimmed.is_empty() {
-                        transcript.add_message(Message::Assistant {
-                            text: trimmed.to_string(),
-                            timestamp: timestamp.clone(),
-                        });
+                    ("agent_message", Some(text)) => {
+                        self.fallback
+                            .push(Message::Assistant { text, timestamp }, budget);
                     }
+                    _ => {}
                 }
             }
+            _ => {}
         }
 
-        Ok((transcript, model))
+        // Response items make the fallback redundant; don't carry it in the cursor
+        if !transcript.is_empty() && !self.fallback.is_empty() {
+            self.fallback = BoundedTranscript::new();
+        }
+    }
+
+    fn finish(&self, transcript: BoundedTranscript) -> BoundedTranscript {
+        if transcript.is_empty() {
+            self.fallback.clone()
+        } else {
+            transcript
+        }
     }
 }
 
@@ -1586,6 +1626,250 @@ impl CursorPreset {
     }
 }
 
+// Windsurf (Cascade) to checkpoint preset
+pub struct WindsurfPreset;
+
+impl AgentCheckpointPreset for WindsurfPreset {
+    fn run(&self, flags: AgentCheckpointFlags) -> Result<AgentRunResult, GitAiError> {
+        let hook_input_json = flags.hook_input.ok_or_else(|| {
+            GitAiError::PresetError("hook_input is required for Windsurf preset".to_string())
+        })?;
+
+        let hook_data: serde_json::Value = serde_json::from_str(&hook_input_json)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let agent_action_name = hook_data
+            .get("agent_action_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("agent_action_name not found in hook_input".to_string())
+            })?;
+
+        if !matches!(
+            agent_action_name,
+            "pre_user_prompt" | "pre_write_code" | "post_write_code"
+        ) {
+            return Err(GitAiError::PresetError(format!(
+                "Invalid agent_action_name: {}. Expected 'pre_user_prompt', 'pre_write_code' or 'post_write_code'",
+                agent_action_name
+            )));
+        }
+
+        let trajectory_id = hook_data
+            .get("trajectory_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("trajectory_id not found in hook_input".to_string())
+            })?
+            .to_string();
+
+        let tool_info = hook_data.get("tool_info");
+        let file_path = tool_info
+            .and_then(|info| info.get("file_path"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        // Cascade runs hooks from the workspace root; an edited file pins down its repo
+        let repo_working_dir = file_path
+            .as_ref()
+            .and_then(|path| Path::new(path).parent())
+            .map(|dir| dir.to_string_lossy().to_string())
+            .or_else(|| {
+                hook_data
+                    .get("cwd")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            });
+
+        let hook_model = hook_data
+            .get("model_name")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        if agent_action_name != "post_write_code" {
+            // Human checkpoint before the prompt is sent or before Cascade writes a file
+            return Ok(AgentRunResult {
+                agent_id: AgentId {
+                    tool: "windsurf".to_string(),
+                    id: trajectory_id,
+                    model: hook_model.unwrap_or_else(|| "unknown".to_string()),
+                },
+                agent_metadata: None,
+                checkpoint_kind: CheckpointKind::Human,
+                transcript: None,
+                repo_working_dir,
+                edited_filepaths: None,
+                will_edit_filepaths: file_path.map(|path| vec![path]),
+                dirty_files: None,
+            });
+        }
+
+        let transcript_path = match tool_info
+            .and_then(|info| info.get("transcript_path"))
+            .and_then(|v| v.as_str())
+        {
+            Some(path) => PathBuf::from(path),
+            None => Self::trajectory_transcript_path(&trajectory_id)?,
+        };
+
+        // Cascade writes the trajectory asynchronously, so a missing or partial transcript
+        // is refetched at commit time
+        let (transcript, transcript_model) = match Self::fetch_trajectory_payload(&transcript_path)
+            .map(|payload| Self::transcript_and_model_from_trajectory(&payload))
+        {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!(
+                    "[Warning] Could not read Windsurf trajectory {}: {}. Retrying at commit.",
+                    trajectory_id, e
+                );
+                (AiTranscript::new(), None)
+            }
+        };
+
+        let agent_id = AgentId {
+            tool: "windsurf".to_string(),
+            id: trajectory_id,
+            model: hook_model
+                .or(transcript_model)
+                .unwrap_or_else(|| "unknown".to_string()),
+        };
+
+        let agent_metadata = HashMap::from([(
+            "transcript_path".to_string(),
+            transcript_path.to_string_lossy().to_string(),
+        )]);
+
+        Ok(AgentRunResult {
+            agent_id,
+            agent_metadata: Some(agent_metadata),
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: Some(transcript),
+            repo_working_dir,
+            edited_filepaths: file_path.map(|path| vec![path]),
+            will_edit_filepaths: None,
+            dirty_files: None,
+        })
+    }
+}
+
+impl WindsurfPreset {
+    /// Where Cascade keeps the transcript of a trajectory:
+    /// `~/.windsurf/transcripts/<trajectory_id>.jsonl`
+    pub fn trajectory_transcript_path(trajectory_id: &str) -> Result<PathBuf, GitAiError> {
+        let transcripts_dir = match env::var("GIT_AI_WINDSURF_TRANSCRIPTS_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => dirs::home_dir()
+                .ok_or_else(|| {
+                    GitAiError::Generic("Could not determine home directory".to_string())
+                })?
+                .join(".windsurf")
+                .join("transcripts"),
+        };
+        Ok(transcripts_dir.join(format!("{}.jsonl", trajectory_id)))
+    }
+
+    /// Read the steps of a Cascade trajectory, one JSON object per line
+    pub fn fetch_trajectory_payload(
+        transcript_path: &Path,
+    ) -> Result<Vec<serde_json::Value>, GitAiError> {
+        let content = std::fs::read_to_string(transcript_path).map_err(GitAiError::IoError)?;
+        let steps: Vec<serde_json::Value> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            // The last line may still be being written
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        if steps.is_empty() {
+            return Err(GitAiError::PresetError(
+                "No trajectory steps found in Windsurf transcript".to_string(),
+            ));
+        }
+        Ok(steps)
+    }
+
+    /// Convert trajectory steps into a transcript and the model Cascade used
+    pub fn transcript_and_model_from_trajectory(
+        steps: &[serde_json::Value],
+    ) -> (AiTranscript, Option<String>) {
+        let mut transcript = AiTranscript::new();
+        let mut model = None;
+
+        for step in steps {
+            let Some(step_type) = step.get("type").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let timestamp = step
+                .get("timestamp")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+            let body = step.get(step_type);
+
+            match step_type {
+                "user_input" => {
+                    if let Some(text) = body
+                        .and_then(|b| b.get("user_response"))
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|text| !text.is_empty())
+                    {
+                        transcript.add_message(Message::user(text.to_string(), timestamp));
+                    }
+                }
+                "planner_response" => {
+                    if model.is_none()
+                        && let Some(model_name) = step.get("model_name").and_then(|v| v.as_str())
+                    {
+                        model = Some(model_name.to_string());
+                    }
+                    if let Some(thinking) = body
+                        .and_then(|b| b.get("thinking"))
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|text| !text.is_empty())
+                    {
+                        transcript.add_message(Message::thinking(
+                            thinking.to_string(),
+                            timestamp.clone(),
+                        ));
+                    }
+                    if let Some(text) = body
+                        .and_then(|b| b.get("response"))
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|text| !text.is_empty())
+                    {
+                        transcript.add_message(Message::assistant(text.to_string(), timestamp));
+                    }
+                }
+                "code_action" => {
+                    let file_path = body
+                        .and_then(|b| b.get("path"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("");
+                    // Only the path: the edit contents are already in the diff
+                    transcript.add_message(Message::ToolUse {
+                        name: "code_action".to_string(),
+                        input: serde_json::json!({ "file_path": file_path }),
+                        timestamp,
+                    });
+                }
+                "run_command" | "view_file" | "grep_search" | "find" | "list_directory"
+                | "search_web" | "read_url_content" | "mcp_tool" => {
+                    transcript.add_message(Message::ToolUse {
+                        name: step_type.to_string(),
+                        input: body.cloned().unwrap_or(serde_json::Value::Null),
+                        timestamp,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        (transcript, model)
+    }
+}
+
 pub struct GithubCopilotPreset;
 
 #[derive(Default)]
@@ -2518,21 +2802,23 @@ impl AgentCheckpointPreset for DroidPreset {
         };
 
         // Parse the Droid transcript JSONL file
-        let transcript =
-            match DroidPreset::transcript_and_model_from_droid_jsonl(&resolved_transcript_path) {
-                Ok((transcript, _model)) => transcript,
-                Err(e) => {
-                    eprintln!("[Warning] Failed to parse Droid JSONL: {e}");
-                    log_error(
-                        &e,
-                        Some(serde_json::json!({
-                            "agent_tool": "droid",
-                            "operation": "transcript_and_model_from_droid_jsonl"
-                        })),
-                    );
-                    crate::authorship::transcript::AiTranscript::new()
-                }
-            };
+        let transcript = match parse_jsonl_transcript_resumable::<DroidTranscriptParser>(
+            Path::new(&resolved_transcript_path),
+            &TranscriptBudget::default(),
+        ) {
+            Ok((transcript, _model)) => transcript,
+            Err(e) => {
+                eprintln!("[Warning] Failed to parse Droid JSONL: {e}");
+                log_error(
+                    &e,
+                    Some(serde_json::json!({
+                        "agent_tool": "droid",
+                        "operation": "transcript_and_model_from_droid_jsonl"
+                    })),
+                );
+                crate::authorship::transcript::AiTranscript::new()
+            }
+        };
 
         // Extract model from settings.json
         let model = match DroidPreset::model_from_droid_settings_json(&resolved_settings_path) {
@@ -2593,141 +2879,166 @@ impl DroidPreset {
     pub fn transcript_and_model_from_droid_jsonl(
         transcript_path: &str,
     ) -> Result<(AiTranscript, Option<String>), GitAiError> {
-        let jsonl_content =
-            std::fs::read_to_string(transcript_path).map_err(GitAiError::IoError)?;
-        let mut transcript = AiTranscript::new();
-        let mut plan_states = std::collections::HashMap::new();
+        parse_jsonl_transcript::<DroidTranscriptParser>(
+            Path::new(transcript_path),
+            &TranscriptBudget::default(),
+        )
+    }
 
-        for line in jsonl_content.lines() {
-            if line.trim().is_empty() {
-                continue;
-            }
+    /// Read the model from a Droid .settings.json file
+    pub fn model_from_droid_settings_json(
+        settings_path: &str,
+    ) -> Result<Option<String>, GitAiError> {
+        let content = std::fs::read_to_string(settings_path).map_err(GitAiError::IoError)?;
+        let settings: serde_json::Value =
+            serde_json::from_str(&content).map_err(GitAiError::JsonError)?;
+        Ok(settings["model"].as_str().map(|s| s.to_string()))
+    }
+
+    /// Derive JSONL and settings.json paths from a session_id and cwd.
+    /// Droid stores sessions at ~/.factory/sessions/{encoded_cwd}/{session_id}.jsonl
+    /// where encoded_cwd replaces '/' with '-'.
+    pub fn droid_session_paths(session_id: &str, cwd: &str) -> (PathBuf, PathBuf) {
+        let encoded_cwd = cwd.replace('/', "-");
+        let base = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("~"))
+            .join(".factory")
+            .join("sessions")
+            .join(&encoded_cwd);
+        let jsonl_path = base.join(format!("{}.jsonl", session_id));
+        let settings_path = base.join(format!("{}.settings.json", session_id));
+        (jsonl_path, settings_path)
+    }
+}
 
-            let raw_entry: serde_json::Value = serde_json::from_str(line)?;
+/// Line parser for Droid JSONL transcripts
+#[derive(Default, Serialize, Deserialize)]
+struct DroidTranscriptParser {
+    plan_states: HashMap<String, String>,
+}
 
-            // Only process "message" entries; skip session_start, todo_state, etc.
-            if raw_entry["type"].as_str() != Some("message") {
-                continue;
-            }
+impl JsonlTranscriptParser for DroidTranscriptParser {
+    const KIND: &'static str = "droid";
 
-            let timestamp = raw_entry["timestamp"].as_str().map(|s| s.to_string());
+    fn parse_line(
+        &mut self,
+        raw_entry: &serde_json::Value,
+        transcript: &mut BoundedTranscript,
+        _model: &mut Option<String>,
+        budget: &TranscriptBudget,
+    ) {
+        // Only process "message" entries; skip session_start, todo_state, etc.
+        if raw_entry["type"].as_str() != Some("message") {
+            return;
+        }
 
-            let message = &raw_entry["message"];
-            let role = match message["role"].as_str() {
-                Some(r) => r,
-                None => continue,
-            };
+        let timestamp = raw_entry["timestamp"].as_str().map(|s| s.to_string());
 
-            match role {
-                "user" => {
-                    if let Some(content_array) = message["content"].as_array() {
-                        for item in content_array {
-                            // Skip tool_result items — those are system-generated responses
-                            if item["type"].as_str() == Some("tool_result") {
-                                continue;
-                            }
-                            if item["type"].as_str() == Some("text")
-                                && let Some(text) = item["text"].as_str()
-                                && !text.trim().is_empty()
-                            {
-                                transcript.add_message(Message::User {
+        let message = &raw_entry["message"];
+        let role = match message["role"].as_str() {
+            Some(r) => r,
+            None => return,
+        };
+
+        match role {
+            "user" => {
+                if let Some(content_array) = message["content"].as_array() {
+                    for item in content_array {
+                        // Skip tool_result items — those are system-generated responses
+                        if item["type"].as_str() == Some("tool_result") {
+                            continue;
+                        }
+                        if item["type"].as_str() == Some("text")
+                            && let Some(text) = item["text"].as_str()
+                            && !text.trim().is_empty()
+                        {
+                            transcript.push(
+                                Message::User {
                                     text: text.to_string(),
                                     timestamp: timestamp.clone(),
-                                });
-                            }
+                                },
+                                budget,
+                            );
                         }
-                    } else if let Some(content) = message["content"].as_str()
-                        && !content.trim().is_empty()
-                    {
-                        transcript.add_message(Message::User {
+                    }
+                } else if let Some(content) = message["content"].as_str()
+                    && !content.trim().is_empty()
+                {
+                    transcript.push(
+                        Message::User {
                             text: content.to_string(),
                             timestamp: timestamp.clone(),
-                        });
-                    }
+                        },
+                        budget,
+                    );
                 }
-                "assistant" => {
-                    if let Some(content_array) = message["content"].as_array() {
-                        for item in content_array {
-                            match item["type"].as_str() {
-                                Some("text") => {
-                                    if let Some(text) = item["text"].as_str()
-                                        && !text.trim().is_empty()
-                                    {
-                                        transcript.add_message(Message::Assistant {
+            }
+            "assistant" => {
+                if let Some(content_array) = message["content"].as_array() {
+                    for item in content_array {
+                        match item["type"].as_str() {
+                            Some("text") => {
+                                if let Some(text) = item["text"].as_str()
+                                    && !text.trim().is_empty()
+                                {
+                                    transcript.push(
+                                        Message::Assistant {
                                             text: text.to_string(),
                                             timestamp: timestamp.clone(),
-                                        });
-                                    }
+                                        },
+                                        budget,
+                                    );
                                 }
-                                Some("thinking") => {
-                                    if let Some(thinking) = item["thinking"].as_str()
-                                        && !thinking.trim().is_empty()
-                                    {
-                                        transcript.add_message(Message::Assistant {
+                            }
+                            Some("thinking") => {
+                                if let Some(thinking) = item["thinking"].as_str()
+                                    && !thinking.trim().is_empty()
+                                {
+                                    transcript.push(
+                                        Message::Assistant {
                                             text: thinking.to_string(),
                                             timestamp: timestamp.clone(),
-                                        });
-                                    }
+                                        },
+                                        budget,
+                                    );
                                 }
-                                Some("tool_use") => {
-                                    if let (Some(name), Some(_input)) =
-                                        (item["name"].as_str(), item["input"].as_object())
-                                    {
-                                        // Check if this is a Write/Edit to a plan file
-                                        if let Some(plan_text) = extract_plan_from_tool_use(
-                                            name,
-                                            &item["input"],
-                                            &mut plan_states,
-                                        ) {
-                                            transcript.add_message(Message::Plan {
+                            }
+                            Some("tool_use") => {
+                                if let (Some(name), Some(_input)) =
+                                    (item["name"].as_str(), item["input"].as_object())
+                                {
+                                    // Check if this is a Write/Edit to a plan file
+                                    if let Some(plan_text) = extract_plan_from_tool_use(
+                                        name,
+                                        &item["input"],
+                                        &mut self.plan_states,
+                                    ) {
+                                        transcript.push(
+                                            Message::Plan {
                                                 text: plan_text,
                                                 timestamp: timestamp.clone(),
-                                            });
-                                        } else {
-                                            transcript.add_message(Message::ToolUse {
+                                            },
+                                            budget,
+                                        );
+                                    } else {
+                                        transcript.push(
+                                            Message::ToolUse {
                                                 name: name.to_string(),
                                                 input: item["input"].clone(),
                                                 timestamp: timestamp.clone(),
-                                            });
-                                        }
+                                            },
+                                            budget,
+                                        );
                                     }
                                 }
-                                _ => continue,
                             }
+                            _ => continue,
                         }
                     }
                 }
-                _ => continue,
             }
+            _ => {}
         }
-
-        // Model is not in the JSONL — return None
-        Ok((transcript, None))
-    }
-
-    /// Read the model from a Droid .settings.json file
-    pub fn model_from_droid_settings_json(
-        settings_path: &str,
-    ) -> Result<Option<String>, GitAiError> {
-        let content = std::fs::read_to_string(settings_path).map_err(GitAiError::IoError)?;
-        let settings: serde_json::Value =
-            serde_json::from_str(&content).map_err(GitAiError::JsonError)?;
-        Ok(settings["model"].as_str().map(|s| s.to_string()))
-    }
-
-    /// Derive JSONL and settings.json paths from a session_id and cwd.
-    /// Droid stores sessions at ~/.factory/sessions/{encoded_cwd}/{session_id}.jsonl
-    /// where encoded_cwd replaces '/' with '-'.
-    pub fn droid_session_paths(session_id: &str, cwd: &str) -> (PathBuf, PathBuf) {
-        let encoded_cwd = cwd.replace('/', "-");
-        let base = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("~"))
-            .join(".factory")
-            .join("sessions")
-            .join(&encoded_cwd);
-        let jsonl_path = base.join(format!("{}.jsonl", session_id));
-        let settings_path = base.join(format!("{}.settings.json", session_id));
-        (jsonl_path, settings_path)
     }
 }
 
@@ -0,0 +1,514 @@
+use crate::{
+    authorship::{
+        transcript::{AiTranscript, Message},
+        working_log::{AgentId, CheckpointKind},
+    },
+    commands::checkpoint_agent::agent_presets::{
+        AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+    },
+    error::GitAiError,
+    observability::log_error,
+};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Aider's default chat transcript, written to the root of the repo it runs in
+pub const AIDER_CHAT_HISTORY_FILE: &str = ".aider.chat.history.md";
+/// Aider's default prompt history, with a timestamp for every input
+pub const AIDER_INPUT_HISTORY_FILE: &str = ".aider.input.history";
+
+const SESSION_HEADER_PREFIX: &str = "# aider chat started at ";
+
+pub struct AiderPreset;
+
+/// Hook input for the Aider preset.
+///
+/// Aider has no hook payloads of its own: `git-ai checkpoint aider <file>...` (run by Aider as
+/// its `lint-cmd` after every edit) builds this from its arguments.
+#[derive(Debug, Deserialize)]
+pub struct AiderHookInput {
+    /// `before_edit` for a human checkpoint, anything else for an AI checkpoint
+    #[serde(default)]
+    pub hook_event_name: Option<String>,
+    pub cwd: String,
+    #[serde(default)]
+    pub edited_filepaths: Vec<String>,
+    #[serde(default)]
+    pub chat_history_file: Option<String>,
+    #[serde(default)]
+    pub input_history_file: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+impl AgentCheckpointPreset for AiderPreset {
+    fn run(&self, flags: AgentCheckpointFlags) -> Result<AgentRunResult, GitAiError> {
+        let stdin_json = flags.hook_input.ok_or_else(|| {
+            GitAiError::PresetError("hook_input is required for Aider preset".to_string())
+        })?;
+
+        let hook_input: AiderHookInput = serde_json::from_str(&stdin_json)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let cwd = PathBuf::from(&hook_input.cwd);
+        let chat_history_path = hook_input
+            .chat_history_file
+            .as_ref()
+            .map(|path| cwd.join(path))
+            .unwrap_or_else(|| cwd.join(AIDER_CHAT_HISTORY_FILE));
+        let input_history_path = hook_input
+            .input_history_file
+            .as_ref()
+            .map(|path| cwd.join(path))
+            .unwrap_or_else(|| cwd.join(AIDER_INPUT_HISTORY_FILE));
+        let chat_history_path = chat_history_path.to_string_lossy().to_string();
+        let input_history_path = input_history_path.to_string_lossy().to_string();
+
+        let edited_filepaths = if hook_input.edited_filepaths.is_empty() {
+            None
+        } else {
+            Some(hook_input.edited_filepaths.clone())
+        };
+
+        let (transcript, model, session_started_at) =
+            match AiderPreset::transcript_and_model_from_aider_history(
+                &chat_history_path,
+                Some(&input_history_path),
+            ) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("[Warning] Failed to parse Aider chat history: {e}");
+                    log_error(
+                        &e,
+                        Some(serde_json::json!({
+                            "agent_tool": "aider",
+                            "operation": "transcript_and_model_from_aider_history"
+                        })),
+                    );
+                    (AiTranscript::new(), None, None)
+                }
+            };
+
+        let agent_id = AgentId {
+            tool: "aider".to_string(),
+            id: AiderPreset::session_id(&chat_history_path, session_started_at.as_deref()),
+            model: hook_input
+                .model
+                .clone()
+                .or(model)
+                .unwrap_or_else(|| "unknown".to_string()),
+        };
+
+        if hook_input.hook_event_name.as_deref() == Some("before_edit") {
+            return Ok(AgentRunResult {
+                agent_id,
+                agent_metadata: None,
+                checkpoint_kind: CheckpointKind::Human,
+                transcript: None,
+                repo_working_dir: Some(hook_input.cwd),
+                edited_filepaths: None,
+                will_edit_filepaths: edited_filepaths,
+                dirty_files: None,
+            });
+        }
+
+        let agent_metadata = HashMap::from([
+            ("transcript_path".to_string(), chat_history_path),
+            ("input_history_path".to_string(), input_history_path),
+        ]);
+
+        Ok(AgentRunResult {
+            agent_id,
+            agent_metadata: Some(agent_metadata),
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: Some(transcript),
+            repo_working_dir: Some(hook_input.cwd),
+            edited_filepaths,
+            will_edit_filepaths: None,
+            dirty_files: None,
+        })
+    }
+}
+
+impl AiderPreset {
+    /// Build the hook input for `git-ai checkpoint aider <file>...`, the form Aider invokes
+    /// as its `lint-cmd` with each edited file appended
+    pub fn hook_input_from_args(cwd: &Path, files: &[String]) -> String {
+        serde_json::json!({
+            "hook_event_name": "after_edit",
+            "cwd": cwd.to_string_lossy(),
+            "edited_filepaths": files,
+        })
+        .to_string()
+    }
+
+    /// The human checkpoint to record ahead of an AI checkpoint from Aider's `lint-cmd`.
+    ///
+    /// Aider only runs the lint command once the file is edited, so the content from just
+    /// before the edit is rebuilt by undoing the SEARCH/REPLACE blocks of its last reply.
+    /// Files whose edits can't be undone exactly (e.g. Aider's whole-file edit format) are
+    /// left out rather than checkpointing the AI's lines as the human's.
+    pub fn pre_edit_run_result(ai_run: &AgentRunResult) -> Option<AgentRunResult> {
+        let chat_history_path = ai_run.agent_metadata.as_ref()?.get("transcript_path")?;
+        let cwd = PathBuf::from(ai_run.repo_working_dir.as_ref()?);
+        let files = ai_run.edited_filepaths.as_ref()?;
+        let chat_history = std::fs::read_to_string(chat_history_path).ok()?;
+
+        let dirty_files = Self::pre_edit_contents(&chat_history, &cwd, files);
+        if dirty_files.is_empty() {
+            return None;
+        }
+        let mut will_edit_filepaths: Vec<String> = dirty_files.keys().cloned().collect();
+        will_edit_filepaths.sort();
+
+        Some(AgentRunResult {
+            agent_id: ai_run.agent_id.clone(),
+            agent_metadata: None,
+            checkpoint_kind: CheckpointKind::Human,
+            transcript: None,
+            repo_working_dir: ai_run.repo_working_dir.clone(),
+            edited_filepaths: None,
+            will_edit_filepaths: Some(will_edit_filepaths),
+            dirty_files: Some(dirty_files),
+        })
+    }
+
+    /// Content of each of `files` from before Aider's last reply edited it, keyed by the
+    /// path as given
+    pub fn pre_edit_contents(
+        chat_history: &str,
+        cwd: &Path,
+        files: &[String],
+    ) -> HashMap<String, String> {
+        let session = match chat_history.rfind(SESSION_HEADER_PREFIX) {
+            Some(idx) => &chat_history[idx..],
+            None => chat_history,
+        };
+        let edits = Self::last_reply_edits(session, cwd);
+
+        files
+            .iter()
+            .filter_map(|file| {
+                let blocks: Vec<&EditBlock> = edits
+                    .iter()
+                    .filter(|edit| cwd.join(&edit.file_path) == cwd.join(file))
+                    .collect();
+                if blocks.is_empty() {
+                    return None;
+                }
+                let current = std::fs::read_to_string(cwd.join(file)).ok()?;
+                undo_edit_blocks(&current, &blocks).map(|content| (file.clone(), content))
+            })
+            .collect()
+    }
+
+    /// The SEARCH/REPLACE blocks of the last assistant reply in a session that made any
+    fn last_reply_edits(session: &str, cwd: &Path) -> Vec<EditBlock> {
+        let mut last_edits = Vec::new();
+        let mut reply_edits: Vec<EditBlock> = Vec::new();
+        // The file a block applies to is named on the line before it (or before its fence);
+        // later blocks for the same file may leave it out, and prose isn't a path
+        let mut named_file: Option<String> = None;
+        let mut current_file: Option<String> = None;
+        let mut block: Option<(Vec<String>, Option<Vec<String>>)> = None;
+
+        for line in session.lines().skip(1) {
+            let line = line.trim_end();
+            if let Some((search, replace)) = block.as_mut() {
+                if is_replace_marker(line) {
+                    let (search, replace) = block.take().unwrap();
+                    if let Some(file_path) = current_file.clone() {
+                        reply_edits.push(EditBlock {
+                            file_path,
+                            search,
+                            replace: replace.unwrap_or_default(),
+                        });
+                    }
+                } else if replace.is_none() && is_divider_marker(line) {
+                    *replace = Some(Vec::new());
+                } else {
+                    replace.as_mut().unwrap_or(search).push(line.to_string());
+                }
+                continue;
+            }
+
+            if is_search_marker(line) {
+                if let Some(file_path) = named_file.take()
+                    && cwd.join(&file_path).exists()
+                {
+                    current_file = Some(file_path);
+                }
+                block = Some((Vec::new(), None));
+                continue;
+            }
+
+            let is_reply_line =
+                !(line.starts_with("####") || line.starts_with("> ") || line == ">");
+            if !is_reply_line {
+                if !reply_edits.is_empty() {
+                    last_edits = std::mem::take(&mut reply_edits);
+                }
+                named_file = None;
+                current_file = None;
+                continue;
+            }
+            let name = line.trim().trim_matches(|c| c == '`' || c == '*').trim();
+            if !name.is_empty() && !line.trim_start().starts_with("```") {
+                named_file = Some(name.trim_end_matches(':').to_string());
+            }
+        }
+        if !reply_edits.is_empty() {
+            last_edits = reply_edits;
+        }
+        last_edits
+    }
+
+    /// A stable id for the session: Aider has none, so hash the history file and the time
+    /// the session started
+    pub fn session_id(chat_history_path: &str, session_started_at: Option<&str>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(chat_history_path.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(session_started_at.unwrap_or("").as_bytes());
+        let digest = hasher.finalize();
+        digest
+            .iter()
+            .take(8)
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Parse the last session in Aider's `.aider.chat.history.md` into a transcript.
+    ///
+    /// Returns the transcript, the model (from Aider's startup banner) and the session start
+    /// time. User messages get timestamps from `.aider.input.history` when it's available.
+    pub fn transcript_and_model_from_aider_history(
+        chat_history_path: &str,
+        input_history_path: Option<&str>,
+    ) -> Result<(AiTranscript, Option<String>, Option<String>), GitAiError> {
+        let content = std::fs::read_to_string(chat_history_path).map_err(GitAiError::IoError)?;
+
+        // Only the current session belongs to this checkpoint
+        let session = match content.rfind(SESSION_HEADER_PREFIX) {
+            Some(idx) => &content[idx..],
+            None => content.as_str(),
+        };
+        let session_started_at = session
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix(SESSION_HEADER_PREFIX))
+            .map(|s| s.trim().to_string());
+
+        let mut input_history = match input_history_path {
+            Some(path) if Path::new(path).exists() => {
+                let content = std::fs::read_to_string(path).map_err(GitAiError::IoError)?;
+                Self::parse_input_history(&content)
+            }
+            _ => Vec::new(),
+        };
+        // Only inputs from this session can match its messages
+        if let Some(started_at) = session_started_at
+            .as_deref()
+            .and_then(parse_aider_timestamp)
+        {
+            input_history.retain(|(timestamp, _)| {
+                parse_aider_timestamp(timestamp).is_some_and(|ts| ts >= started_at)
+            });
+        }
+
+        let mut transcript = AiTranscript::new();
+        let mut model = None;
+        let mut user_lines: Vec<String> = Vec::new();
+        let mut assistant_lines: Vec<String> = Vec::new();
+        let mut next_input = 0;
+
+        let mut flush_user = |lines: &mut Vec<String>, transcript: &mut AiTranscript| {
+            if lines.is_empty() {
+                return;
+            }
+            let raw = lines.join("\n");
+            lines.clear();
+
+            let mut timestamp = None;
+            if let Some(offset) = input_history[next_input..]
+                .iter()
+                .position(|(_, text)| text.trim() == raw.trim())
+            {
+                let (ts, _) = &input_history[next_input + offset];
+                timestamp = parse_aider_timestamp(ts).map(|dt| dt.to_rfc3339());
+                next_input += offset + 1;
+            }
+
+            if let Some(text) = Self::prompt_text(&raw) {
+                transcript.add_message(Message::User { text, timestamp });
+            }
+        };
+        let flush_assistant = |lines: &mut Vec<String>, transcript: &mut AiTranscript| {
+            let text = lines.join("\n").trim().to_string();
+            lines.clear();
+            if !text.is_empty() {
+                transcript.add_message(Message::Assistant {
+                    text,
+                    timestamp: None,
+                });
+            }
+        };
+
+        for line in session.lines().skip(1) {
+            let line = line.trim_end();
+            if let Some(user_line) = line
+                .strip_prefix("#### ")
+                .or_else(|| if line == "####" { Some("") } else { None })
+            {
+                flush_assistant(&mut assistant_lines, &mut transcript);
+                user_lines.push(user_line.to_string());
+                continue;
+            }
+            flush_user(&mut user_lines, &mut transcript);
+
+            if let Some(output) = line
+                .strip_prefix("> ")
+                .or_else(|| if line == ">" { Some("") } else { None })
+            {
+                flush_assistant(&mut assistant_lines, &mut transcript);
+                if model.is_none() {
+                    model = Self::model_from_banner_line(output);
+                }
+                if let Some(file_path) = output.strip_prefix("Applied edit to ") {
+                    transcript.add_message(Message::ToolUse {
+                        name: "edit".to_string(),
+                        input: serde_json::json!({ "file_path": file_path.trim() }),
+                        timestamp: None,
+                    });
+                }
+                continue;
+            }
+
+            assistant_lines.push(line.to_string());
+        }
+        flush_user(&mut user_lines, &mut transcript);
+        flush_assistant(&mut assistant_lines, &mut transcript);
+
+        Ok((transcript, model, session_started_at))
+    }
+
+    /// Parse `.aider.input.history` into `(timestamp, input)` pairs, oldest first
+    fn parse_input_history(content: &str) -> Vec<(String, String)> {
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        for line in content.lines() {
+            if let Some(timestamp) = line.strip_prefix("# ") {
+                entries.push((timestamp.trim().to_string(), Vec::new()));
+            } else if let Some(text) = line.strip_prefix('+')
+                && let Some((_, lines)) = entries.last_mut()
+            {
+                lines.push(text.to_string());
+            }
+        }
+        entries
+            .into_iter()
+            .map(|(timestamp, lines)| (timestamp, lines.join("\n")))
+            .collect()
+    }
+
+    /// The prompt text of a user input, or `None` for Aider commands like `/add`
+    fn prompt_text(raw: &str) -> Option<String> {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        if !trimmed.starts_with('/') {
+            return Some(trimmed.to_string());
+        }
+        // Chat mode commands carry a prompt; every other command is Aider housekeeping
+        ["/ask ", "/code ", "/architect "]
+            .iter()
+            .find_map(|command| trimmed.strip_prefix(command))
+            .map(|prompt| prompt.trim().to_string())
+            .filter(|prompt| !prompt.is_empty())
+    }
+
+    /// Read the model from Aider's startup banner, e.g.
+    /// `Main model: claude-3-5-sonnet-20241022 with diff edit format, infinite output`
+    fn model_from_banner_line(line: &str) -> Option<String> {
+        let rest = line
+            .strip_prefix("Main model: ")
+            .or_else(|| line.strip_prefix("Model: "))?;
+        rest.split_whitespace()
+            .next()
+            .map(|model| model.to_string())
+    }
+}
+
+/// Aider writes local times like `2024-05-01 12:34:56` or `2024-05-01 12:35:00.123456`
+fn parse_aider_timestamp(timestamp: &str) -> Option<chrono::DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(timestamp.trim(), "%Y-%m-%d %H:%M:%S%.f").ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// One SEARCH/REPLACE edit from an Aider reply
+struct EditBlock {
+    file_path: String,
+    search: Vec<String>,
+    replace: Vec<String>,
+}
+
+fn is_search_marker(line: &str) -> bool {
+    let line = line.trim();
+    let marker = line.trim_start_matches('<');
+    (5..=9).contains(&(line.len() - marker.len()))
+        && marker.strip_suffix('>').unwrap_or(marker) == " SEARCH"
+}
+
+fn is_divider_marker(line: &str) -> bool {
+    let line = line.trim();
+    (5..=9).contains(&line.len()) && line.bytes().all(|b| b == b'=')
+}
+
+fn is_replace_marker(line: &str) -> bool {
+    let line = line.trim();
+    let marker = line.trim_start_matches('>');
+    (5..=9).contains(&(line.len() - marker.len())) && marker == " REPLACE"
+}
+
+/// Undo `blocks` (in the order Aider applied them) on `content`.
+///
+/// Lines are compared without trailing whitespace, which Aider's chat history drops.
+/// Returns `None` if any block's replacement can't be found.
+fn undo_edit_blocks(content: &str, blocks: &[&EditBlock]) -> Option<String> {
+    let same_lines = |a: &[String], b: &[String]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.trim_end() == y.trim_end())
+    };
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    for block in blocks.iter().rev() {
+        if block.replace.is_empty() {
+            // A deletion leaves nothing behind to find
+            return None;
+        }
+        let start = if block.search.is_empty() {
+            // An empty SEARCH creates the file, or appends to it
+            let start = lines.len().checked_sub(block.replace.len())?;
+            same_lines(&lines[start..], &block.replace).then_some(start)?
+        } else {
+            (0..=lines.len().checked_sub(block.replace.len())?).find(|&start| {
+                same_lines(&lines[start..start + block.replace.len()], &block.replace)
+            })?
+        };
+        lines.splice(
+            start..start + block.replace.len(),
+            block.search.iter().cloned(),
+        );
+    }
+
+    let mut restored = lines.join("\n");
+    if content.ends_with('\n') && !restored.is_empty() {
+        restored.push('\n');
+    }
+    Some(restored)
+}
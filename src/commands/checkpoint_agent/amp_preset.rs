@@ -0,0 +1,231 @@
+use crate::{
+    authorship::{
+        transcript::{AiTranscript, Message},
+        working_log::{AgentId, CheckpointKind},
+    },
+    commands::checkpoint_agent::agent_presets::{
+        AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+    },
+    error::GitAiError,
+    observability::log_error,
+};
+use chrono::DateTime;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub struct AmpPreset;
+
+impl AgentCheckpointPreset for AmpPreset {
+    fn run(&self, flags: AgentCheckpointFlags) -> Result<AgentRunResult, GitAiError> {
+        let hook_input_json = flags.hook_input.ok_or_else(|| {
+            GitAiError::PresetError("hook_input is required for Amp preset".to_string())
+        })?;
+
+        let hook_data: serde_json::Value = serde_json::from_str(&hook_input_json)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let thread_id = hook_data
+            .get("thread_id")
+            .or_else(|| hook_data.get("threadId"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("thread_id not found in hook_input".to_string())
+            })?
+            .to_string();
+
+        let cwd = hook_data
+            .get("cwd")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| GitAiError::PresetError("cwd not found in hook_input".to_string()))?
+            .to_string();
+
+        let hook_event_name = hook_data
+            .get("hook_event_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("hook_event_name not found in hook_input".to_string())
+            })?;
+
+        // Amp's file tools (edit_file, create_file, undo_edit) take a `path`
+        let file_path_as_vec = hook_data
+            .get("tool_input")
+            .and_then(|ti| ti.get("path").or_else(|| ti.get("file_path")))
+            .and_then(|v| v.as_str())
+            .map(|path| vec![path.to_string()]);
+
+        if hook_event_name == "PreToolUse" {
+            return Ok(AgentRunResult {
+                agent_id: AgentId {
+                    tool: "amp".to_string(),
+                    id: thread_id,
+                    model: "unknown".to_string(),
+                },
+                agent_metadata: None,
+                checkpoint_kind: CheckpointKind::Human,
+                transcript: None,
+                repo_working_dir: Some(cwd),
+                edited_filepaths: None,
+                will_edit_filepaths: file_path_as_vec,
+                dirty_files: None,
+            });
+        }
+
+        let transcript_path = match hook_data.get("transcript_path").and_then(|v| v.as_str()) {
+            Some(path) => PathBuf::from(path),
+            None => Self::thread_path(&thread_id)?,
+        };
+        let transcript_path = transcript_path.to_string_lossy().to_string();
+
+        let (transcript, model) = match Self::transcript_and_model_from_amp_thread(&transcript_path)
+        {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("[Warning] Failed to parse Amp thread: {e}");
+                log_error(
+                    &e,
+                    Some(serde_json::json!({
+                        "agent_tool": "amp",
+                        "operation": "transcript_and_model_from_amp_thread"
+                    })),
+                );
+                (AiTranscript::new(), None)
+            }
+        };
+
+        let agent_id = AgentId {
+            tool: "amp".to_string(),
+            id: thread_id,
+            model: model.unwrap_or_else(|| "unknown".to_string()),
+        };
+
+        let agent_metadata = HashMap::from([("transcript_path".to_string(), transcript_path)]);
+
+        Ok(AgentRunResult {
+            agent_id,
+            agent_metadata: Some(agent_metadata),
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: Some(transcript),
+            repo_working_dir: Some(cwd),
+            edited_filepaths: file_path_as_vec,
+            will_edit_filepaths: None,
+            dirty_files: None,
+        })
+    }
+}
+
+impl AmpPreset {
+    /// Where Amp keeps a thread: `~/.local/share/amp/threads/<thread_id>.json`
+    pub fn thread_path(thread_id: &str) -> Result<PathBuf, GitAiError> {
+        let threads_dir = match std::env::var("GIT_AI_AMP_THREADS_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => dirs::home_dir()
+                .ok_or_else(|| {
+                    GitAiError::PresetError("Could not determine home directory".to_string())
+                })?
+                .join(".local")
+                .join("share")
+                .join("amp")
+                .join("threads"),
+        };
+        Ok(threads_dir.join(format!("{}.json", thread_id)))
+    }
+
+    /// Parse an Amp thread file into a transcript.
+    ///
+    /// Threads hold Anthropic-style messages: user turns carry `text` and `tool_result`
+    /// blocks, assistant turns carry `thinking`, `text` and `tool_use` blocks. The model is
+    /// read from the usage of the latest assistant turn.
+    pub fn transcript_and_model_from_amp_thread(
+        thread_path: &str,
+    ) -> Result<(AiTranscript, Option<String>), GitAiError> {
+        let content = std::fs::read_to_string(thread_path).map_err(GitAiError::IoError)?;
+        let thread: serde_json::Value = serde_json::from_str(&content)?;
+
+        let messages = thread
+            .get("messages")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| {
+                GitAiError::PresetError("Amp thread has no messages array".to_string())
+            })?;
+
+        let mut transcript = AiTranscript::new();
+        let mut model = None;
+
+        for message in messages {
+            let timestamp = message
+                .get("meta")
+                .and_then(|meta| meta.get("sentAt"))
+                .and_then(|v| v.as_i64())
+                .and_then(DateTime::from_timestamp_millis)
+                .map(|dt| dt.to_rfc3339());
+
+            let Some(content) = message.get("content").and_then(|v| v.as_array()) else {
+                continue;
+            };
+
+            match message.get("role").and_then(|v| v.as_str()) {
+                Some("user") => {
+                    for block in content {
+                        // tool_result blocks are Amp's responses to tool calls, not prompts
+                        if block["type"].as_str() == Some("text")
+                            && let Some(text) = block["text"].as_str()
+                            && !text.trim().is_empty()
+                        {
+                            transcript.add_message(Message::User {
+                                text: text.to_string(),
+                                timestamp: timestamp.clone(),
+                            });
+                        }
+                    }
+                }
+                Some("assistant") => {
+                    if let Some(message_model) = message
+                        .get("usage")
+                        .and_then(|usage| usage.get("model"))
+                        .and_then(|v| v.as_str())
+                    {
+                        model = Some(message_model.to_string());
+                    }
+
+                    for block in content {
+                        match block["type"].as_str() {
+                            Some("text") => {
+                                if let Some(text) = block["text"].as_str()
+                                    && !text.trim().is_empty()
+                                {
+                                    transcript.add_message(Message::Assistant {
+                                        text: text.to_string(),
+                                        timestamp: timestamp.clone(),
+                                    });
+                                }
+                            }
+                            Some("thinking") => {
+                                if let Some(thinking) = block["thinking"].as_str()
+                                    && !thinking.trim().is_empty()
+                                {
+                                    transcript.add_message(Message::Thinking {
+                                        text: thinking.to_string(),
+                                        timestamp: timestamp.clone(),
+                                    });
+                                }
+                            }
+                            Some("tool_use") => {
+                                if let Some(name) = block["name"].as_str() {
+                                    transcript.add_message(Message::ToolUse {
+                                        name: name.to_string(),
+                                        input: block["input"].clone(),
+                                        timestamp: timestamp.clone(),
+                                    });
+                                }
+                            }
+                            _ => continue,
+                        }
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        Ok((transcript, model))
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{
+    authorship::{
+        transcript::{AiTranscript, Message},
+        working_log::{AgentId, CheckpointKind},
+    },
+    commands::checkpoint_agent::agent_presets::{
+        AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+    },
+    error::GitAiError,
+    observability::log_error,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub struct JuniePreset;
+
+impl AgentCheckpointPreset for JuniePreset {
+    fn run(&self, flags: AgentCheckpointFlags) -> Result<AgentRunResult, GitAiError> {
+        let hook_input_json = flags.hook_input.ok_or_else(|| {
+            GitAiError::PresetError("hook_input is required for Junie preset".to_string())
+        })?;
+
+        let hook_data: serde_json::Value = serde_json::from_str(&hook_input_json)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let session_id = hook_data
+            .get("session_id")
+            .or_else(|| hook_data.get("sessionId"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("session_id not found in hook_input".to_string())
+            })?
+            .to_string();
+
+        let cwd = hook_data
+            .get("cwd")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| GitAiError::PresetError("cwd not found in hook_input".to_string()))?
+            .to_string();
+
+        let hook_event_name = hook_data
+            .get("hook_event_name")
+            .or_else(|| hook_data.get("hookEventName"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("hook_event_name not found in hook_input".to_string())
+            })?;
+
+        let file_path_as_vec = hook_data
+            .get("tool_input")
+            .and_then(|ti| ti.get("file_path").or_else(|| ti.get("path")))
+            .and_then(|v| v.as_str())
+            .map(|path| vec![path.to_string()]);
+
+        if hook_event_name == "PreToolUse" {
+            return Ok(AgentRunResult {
+                agent_id: AgentId {
+                    tool: "junie".to_string(),
+                    id: session_id,
+                    model: "unknown".to_string(),
+                },
+                agent_metadata: None,
+                checkpoint_kind: CheckpointKind::Human,
+                transcript: None,
+                repo_working_dir: Some(cwd),
+                edited_filepaths: None,
+                will_edit_filepaths: file_path_as_vec,
+                dirty_files: None,
+            });
+        }
+
+        let transcript_path = match hook_data.get("transcript_path").and_then(|v| v.as_str()) {
+            Some(path) => PathBuf::from(path),
+            None => Self::session_path(&session_id)?,
+        };
+        let transcript_path = transcript_path.to_string_lossy().to_string();
+
+        let (transcript, model) =
+            match Self::transcript_and_model_from_junie_session(&transcript_path) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("[Warning] Failed to parse Junie session: {e}");
+                    log_error(
+                        &e,
+                        Some(serde_json::json!({
+                            "agent_tool": "junie",
+                            "operation": "transcript_and_model_from_junie_session"
+                        })),
+                    );
+                    (AiTranscript::new(), None)
+                }
+            };
+
+        let agent_id = AgentId {
+            tool: "junie".to_string(),
+            id: session_id,
+            model: model.unwrap_or_else(|| "unknown".to_string()),
+        };
+
+        let agent_metadata = HashMap::from([("transcript_path".to_string(), transcript_path)]);
+
+        Ok(AgentRunResult {
+            agent_id,
+            agent_metadata: Some(agent_metadata),
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: Some(transcript),
+            repo_working_dir: Some(cwd),
+            edited_filepaths: file_path_as_vec,
+            will_edit_filepaths: None,
+            dirty_files: None,
+        })
+    }
+}
+
+impl JuniePreset {
+    /// Where Junie keeps a session's event log: `~/.junie/sessions/<session_id>.jsonl`
+    pub fn session_path(session_id: &str) -> Result<PathBuf, GitAiError> {
+        let sessions_dir = match std::env::var("GIT_AI_JUNIE_SESSIONS_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => dirs::home_dir()
+                .ok_or_else(|| {
+                    GitAiError::PresetError("Could not determine home directory".to_string())
+                })?
+                .join(".junie")
+                .join("sessions"),
+        };
+        Ok(sessions_dir.join(format!("{}.jsonl", session_id)))
+    }
+
+    /// Parse a Junie session event log into a transcript.
+    ///
+    /// Every line is one event: `session_start` (with the model), `user_message`,
+    /// `agent_thought`, `agent_message`, `plan_update` and `tool_call`. Tool results and
+    /// unknown events are skipped. A `model` on a later event (after `/model`) wins.
+    pub fn transcript_and_model_from_junie_session(
+        session_path: &str,
+    ) -> Result<(AiTranscript, Option<String>), GitAiError> {
+        let content = std::fs::read_to_string(session_path).map_err(GitAiError::IoError)?;
+        let mut transcript = AiTranscript::new();
+        let mut model = None;
+
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let event: serde_json::Value = serde_json::from_str(line)?;
+            let timestamp = event["timestamp"].as_str().map(|s| s.to_string());
+
+            if let Some(event_model) = event["model"].as_str() {
+                model = Some(event_model.to_string());
+            }
+
+            let text = event["text"]
+                .as_str()
+                .filter(|text| !text.trim().is_empty())
+                .map(|text| text.to_string());
+
+            match (event["type"].as_str(), text) {
+                (Some("user_message"), Some(text)) => {
+                    transcript.add_message(Message::User { text, timestamp });
+                }
+                (Some("agent_message"), Some(text)) => {
+                    transcript.add_message(Message::Assistant { text, timestamp });
+                }
+                (Some("agent_thought"), Some(text)) => {
+                    transcript.add_message(Message::Thinking { text, timestamp });
+                }
+                (Some("plan_update"), Some(text)) => {
+                    transcript.add_message(Message::Plan { text, timestamp });
+                }
+                (Some("tool_call"), _) => {
+                    if let Some(name) = event["tool"].as_str() {
+                        transcript.add_message(Message::ToolUse {
+                            name: name.to_string(),
+                            input: event["arguments"].clone(),
+                            timestamp,
+                        });
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        Ok((transcript, model))
+    }
+}
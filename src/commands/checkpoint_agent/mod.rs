@@ -1,3 +1,8 @@
 pub mod agent_presets;
 pub mod agent_v1_preset;
+pub mod aider_preset;
+pub mod amp_preset;
+pub mod junie_preset;
 pub mod opencode_preset;
+pub mod rovodev_preset;
+pub mod transcript_stream;
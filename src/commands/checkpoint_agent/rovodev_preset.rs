@@ -0,0 +1,237 @@
+use crate::{
+    authorship::{
+        transcript::{AiTranscript, Message},
+        working_log::{AgentId, CheckpointKind},
+    },
+    commands::checkpoint_agent::agent_presets::{
+        AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+    },
+    error::GitAiError,
+    observability::log_error,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub struct RovoDevPreset;
+
+impl AgentCheckpointPreset for RovoDevPreset {
+    fn run(&self, flags: AgentCheckpointFlags) -> Result<AgentRunResult, GitAiError> {
+        let hook_input_json = flags.hook_input.ok_or_else(|| {
+            GitAiError::PresetError("hook_input is required for Rovo Dev preset".to_string())
+        })?;
+
+        let hook_data: serde_json::Value = serde_json::from_str(&hook_input_json)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid JSON in hook_input: {}", e)))?;
+
+        let session_id = hook_data
+            .get("session_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("session_id not found in hook_input".to_string())
+            })?
+            .to_string();
+
+        let cwd = hook_data
+            .get("cwd")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| GitAiError::PresetError("cwd not found in hook_input".to_string()))?
+            .to_string();
+
+        let hook_event_name = hook_data
+            .get("hook_event_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GitAiError::PresetError("hook_event_name not found in hook_input".to_string())
+            })?;
+
+        // Rovo Dev's file tools (create_file, find_and_replace_code, delete_file) take a
+        // `file_path`
+        let file_path_as_vec = hook_data
+            .get("tool_input")
+            .and_then(|ti| ti.get("file_path"))
+            .and_then(|v| v.as_str())
+            .map(|path| vec![path.to_string()]);
+
+        if hook_event_name == "PreToolUse" {
+            return Ok(AgentRunResult {
+                agent_id: AgentId {
+                    tool: "rovodev".to_string(),
+                    id: session_id,
+                    model: "unknown".to_string(),
+                },
+                agent_metadata: None,
+                checkpoint_kind: CheckpointKind::Human,
+                transcript: None,
+                repo_working_dir: Some(cwd),
+                edited_filepaths: None,
+                will_edit_filepaths: file_path_as_vec,
+                dirty_files: None,
+            });
+        }
+
+        let transcript_path = match hook_data.get("transcript_path").and_then(|v| v.as_str()) {
+            Some(path) => PathBuf::from(path),
+            None => Self::session_context_path(&session_id)?,
+        };
+        let transcript_path = transcript_path.to_string_lossy().to_string();
+
+        let (transcript, model) =
+            match Self::transcript_and_model_from_rovodev_session(&transcript_path) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    eprintln!("[Warning] Failed to parse Rovo Dev session: {e}");
+                    log_error(
+                        &e,
+                        Some(serde_json::json!({
+                            "agent_tool": "rovodev",
+                            "operation": "transcript_and_model_from_rovodev_session"
+                        })),
+                    );
+                    (AiTranscript::new(), None)
+                }
+            };
+
+        let agent_id = AgentId {
+            tool: "rovodev".to_string(),
+            id: session_id,
+            model: model.unwrap_or_else(|| "unknown".to_string()),
+        };
+
+        let agent_metadata = HashMap::from([("transcript_path".to_string(), transcript_path)]);
+
+        Ok(AgentRunResult {
+            agent_id,
+            agent_metadata: Some(agent_metadata),
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: Some(transcript),
+            repo_working_dir: Some(cwd),
+            edited_filepaths: file_path_as_vec,
+            will_edit_filepaths: None,
+            dirty_files: None,
+        })
+    }
+}
+
+impl RovoDevPreset {
+    /// Where Rovo Dev keeps a session:
+    /// `~/.rovodev/sessions/<session_id>/session_context.json`
+    pub fn session_context_path(session_id: &str) -> Result<PathBuf, GitAiError> {
+        let sessions_dir = match std::env::var("GIT_AI_ROVODEV_SESSIONS_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => dirs::home_dir()
+                .ok_or_else(|| {
+                    GitAiError::PresetError("Could not determine home directory".to_string())
+                })?
+                .join(".rovodev")
+                .join("sessions"),
+        };
+        Ok(sessions_dir.join(session_id).join("session_context.json"))
+    }
+
+    /// Parse a Rovo Dev `session_context.json` into a transcript.
+    ///
+    /// `message_history` is a list of pydantic-ai messages: `request`s hold `user-prompt`
+    /// parts (plus system prompts and tool returns, which are skipped) and `response`s hold
+    /// `text`, `thinking` and `tool-call` parts along with the `model_name` that produced them.
+    pub fn transcript_and_model_from_rovodev_session(
+        session_context_path: &str,
+    ) -> Result<(AiTranscript, Option<String>), GitAiError> {
+        let content = std::fs::read_to_string(session_context_path).map_err(GitAiError::IoError)?;
+        let session: serde_json::Value = serde_json::from_str(&content)?;
+
+        let history = session
+            .get("message_history")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| {
+                GitAiError::PresetError("Rovo Dev session has no message_history array".to_string())
+            })?;
+
+        let mut transcript = AiTranscript::new();
+        let mut model = None;
+
+        for message in history {
+            let Some(parts) = message.get("parts").and_then(|v| v.as_array()) else {
+                continue;
+            };
+            let message_timestamp = message["timestamp"].as_str().map(|s| s.to_string());
+
+            match message["kind"].as_str() {
+                Some("request") => {
+                    for part in parts {
+                        if part["part_kind"].as_str() != Some("user-prompt") {
+                            continue;
+                        }
+                        let timestamp = part["timestamp"].as_str().map(|s| s.to_string());
+                        // A prompt is either a string or a list of strings and attachments
+                        let text = match &part["content"] {
+                            serde_json::Value::String(text) => text.clone(),
+                            serde_json::Value::Array(items) => items
+                                .iter()
+                                .filter_map(|item| item.as_str())
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                            _ => continue,
+                        };
+                        if !text.trim().is_empty() {
+                            transcript.add_message(Message::User { text, timestamp });
+                        }
+                    }
+                }
+                Some("response") => {
+                    if let Some(message_model) = message["model_name"].as_str() {
+                        model = Some(message_model.to_string());
+                    }
+
+                    for part in parts {
+                        let timestamp = message_timestamp.clone();
+                        match part["part_kind"].as_str() {
+                            Some("text") => {
+                                if let Some(text) = part["content"].as_str()
+                                    && !text.trim().is_empty()
+                                {
+                                    transcript.add_message(Message::Assistant {
+                                        text: text.to_string(),
+                                        timestamp,
+                                    });
+                                }
+                            }
+                            Some("thinking") => {
+                                if let Some(text) = part["content"].as_str()
+                                    && !text.trim().is_empty()
+                                {
+                                    transcript.add_message(Message::Thinking {
+                                        text: text.to_string(),
+                                        timestamp,
+                                    });
+                                }
+                            }
+                            Some("tool-call") => {
+                                if let Some(name) = part["tool_name"].as_str() {
+                                    transcript.add_message(Message::ToolUse {
+                                        name: name.to_string(),
+                                        input: Self::tool_call_args(&part["args"]),
+                                        timestamp,
+                                    });
+                                }
+                            }
+                            _ => continue,
+                        }
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        Ok((transcript, model))
+    }
+
+    /// pydantic-ai stores tool-call args either as an object or as a JSON string
+    fn tool_call_args(args: &serde_json::Value) -> serde_json::Value {
+        match args {
+            serde_json::Value::String(raw) => {
+                serde_json::from_str(raw).unwrap_or_else(|_| args.clone())
+            }
+            _ => args.clone(),
+        }
+    }
+}
@@ -0,0 +1,650 @@
+//! Bounded-memory parsing of agent JSONL transcripts.
+//!
+//! Agent sessions can grow to hundreds of megabytes (see `specs/runaway-memory-plan.md`), so
+//! transcripts are never loaded whole. [`JsonlStream`] reads one line at a time, skipping
+//! lines over [`TranscriptBudget::max_line_bytes`]. [`BoundedTranscript`] keeps only the
+//! newest messages that fit the budget and truncates giant tool inputs.
+//!
+//! Checkpoints run after every edit, so [`parse_jsonl_transcript_resumable`] also saves a
+//! [`TranscriptCursor`] under `~/.git-ai/internal/transcript-cursors`. The next checkpoint
+//! parses only the lines appended since then.
+
+use crate::authorship::transcript::{AiTranscript, Message};
+use crate::config;
+use crate::error::GitAiError;
+use crate::utils::debug_log;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Newest messages kept per transcript
+pub const DEFAULT_MAX_MESSAGES: usize = 2_000;
+/// Approximate size of the messages kept per transcript
+pub const DEFAULT_MAX_TRANSCRIPT_BYTES: usize = 4 * 1024 * 1024;
+/// Tool inputs over this size are truncated
+pub const DEFAULT_MAX_TOOL_INPUT_BYTES: usize = 64 * 1024;
+/// JSONL lines over this size are skipped without being parsed
+pub const DEFAULT_MAX_LINE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Bytes hashed at the start of the file and before the cursor to detect rewrites
+const FINGERPRINT_WINDOW: u64 = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptBudget {
+    pub max_messages: usize,
+    pub max_bytes: usize,
+    pub max_tool_input_bytes: usize,
+    pub max_line_bytes: usize,
+}
+
+impl Default for TranscriptBudget {
+    fn default() -> Self {
+        Self {
+            max_messages: DEFAULT_MAX_MESSAGES,
+            max_bytes: DEFAULT_MAX_TRANSCRIPT_BYTES,
+            max_tool_input_bytes: DEFAULT_MAX_TOOL_INPUT_BYTES,
+            max_line_bytes: DEFAULT_MAX_LINE_BYTES,
+        }
+    }
+}
+
+/// The newest messages of a transcript that fit a [`TranscriptBudget`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoundedTranscript {
+    messages: VecDeque<Message>,
+    bytes: usize,
+    /// Older messages evicted to stay within the budget
+    dropped: usize,
+}
+
+impl BoundedTranscript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a message, evicting the oldest ones once the budget is exceeded.
+    /// The newest message is always kept.
+    pub fn push(&mut self, message: Message, budget: &TranscriptBudget) {
+        let message = match message {
+            Message::ToolUse {
+                name,
+                input,
+                timestamp,
+            } => Message::ToolUse {
+                name,
+                input: truncate_tool_input(input, budget.max_tool_input_bytes),
+                timestamp,
+            },
+            other => other,
+        };
+
+        self.bytes += message_size(&message);
+        self.messages.push_back(message);
+
+        while self.messages.len() > 1
+            && (self.messages.len() > budget.max_messages || self.bytes > budget.max_bytes)
+        {
+            if let Some(evicted) = self.messages.pop_front() {
+                self.bytes = self.bytes.saturating_sub(message_size(&evicted));
+                self.dropped += 1;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn into_transcript(self) -> AiTranscript {
+        AiTranscript {
+            messages: self.messages.into(),
+        }
+    }
+}
+
+fn message_size(message: &Message) -> usize {
+    match message {
+        Message::User { text, .. }
+        | Message::Assistant { text, .. }
+        | Message::Thinking { text, .. }
+        | Message::Plan { text, .. } => text.len(),
+        Message::ToolUse { name, input, .. } => name.len() + value_size(input),
+    }
+}
+
+/// Approximate serialized size of a JSON value, without serializing it
+fn value_size(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => 8,
+        Value::String(s) => s.len() + 2,
+        Value::Array(items) => 2 + items.iter().map(|v| value_size(v) + 1).sum::<usize>(),
+        Value::Object(map) => {
+            2 + map
+                .iter()
+                .map(|(k, v)| k.len() + 4 + value_size(v))
+                .sum::<usize>()
+        }
+    }
+}
+
+/// Shrink a tool input to roughly `max_bytes`: long strings (file contents, patches,
+/// command output) are cut first, and anything still too large is replaced with a marker.
+pub fn truncate_tool_input(input: Value, max_bytes: usize) -> Value {
+    let original_bytes = value_size(&input);
+    if original_bytes <= max_bytes {
+        return input;
+    }
+
+    let string_limit = (max_bytes / 8).max(256);
+    let truncated = truncate_strings(input, string_limit);
+    if value_size(&truncated) <= max_bytes {
+        return truncated;
+    }
+
+    serde_json::json!({
+        "truncated": true,
+        "original_bytes": original_bytes,
+    })
+}
+
+fn truncate_strings(value: Value, limit: usize) -> Value {
+    match value {
+        Value::String(s) if s.len() > limit => {
+            let mut end = limit;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            Value::String(format!(
+                "{}… [truncated {} bytes]",
+                &s[..end],
+                s.len() - end
+            ))
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|v| truncate_strings(v, limit))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, truncate_strings(v, limit)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Reads a JSONL file one line at a time from a byte offset
+pub struct JsonlStream {
+    reader: BufReader<File>,
+    line: Vec<u8>,
+    /// Offset just past the last complete (newline-terminated) line
+    offset: u64,
+    max_line_bytes: usize,
+    skipped_lines: usize,
+}
+
+impl JsonlStream {
+    pub fn open(path: &Path, start_offset: u64, max_line_bytes: usize) -> Result<Self, GitAiError> {
+        let mut file = File::open(path)?;
+        if start_offset > 0 {
+            file.seek(SeekFrom::Start(start_offset))?;
+        }
+        Ok(Self {
+            reader: BufReader::with_capacity(64 * 1024, file),
+            line: Vec::new(),
+            offset: start_offset,
+            max_line_bytes,
+            skipped_lines: 0,
+        })
+    }
+
+    /// The next non-empty line as JSON, or `None` at end of file.
+    ///
+    /// A trailing line without a newline may still be being written by the agent. If it
+    /// doesn't parse yet it is ignored and [`JsonlStream::offset`] stays before it, so a
+    /// resumed parse reads it again. Complete lines that aren't valid JSON are an error.
+    pub fn next_value(&mut self) -> Option<Result<Value, GitAiError>> {
+        loop {
+            self.line.clear();
+            let mut consumed = 0u64;
+            let mut too_long = false;
+            let mut complete = false;
+
+            loop {
+                let available = match self.reader.fill_buf() {
+                    Ok(available) => available,
+                    Err(e) => return Some(Err(GitAiError::IoError(e))),
+                };
+                if available.is_empty() {
+                    break;
+                }
+                let (take, found_newline) = match available.iter().position(|b| *b == b'\n') {
+                    Some(i) => (i + 1, true),
+                    None => (available.len(), false),
+                };
+                if !too_long {
+                    if self.line.len() + take > self.max_line_bytes {
+                        too_long = true;
+                        self.line = Vec::new();
+                    } else {
+                        self.line.extend_from_slice(&available[..take]);
+                    }
+                }
+                self.reader.consume(take);
+                consumed += take as u64;
+                if found_newline {
+                    complete = true;
+                    break;
+                }
+            }
+
+            if consumed == 0 {
+                return None;
+            }
+            if complete {
+                self.offset += consumed;
+            }
+
+            if too_long {
+                self.skipped_lines += 1;
+                debug_log(&format!(
+                    "Skipping transcript line of more than {} bytes",
+                    self.max_line_bytes
+                ));
+                continue;
+            }
+
+            let text = String::from_utf8_lossy(&self.line);
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            return match serde_json::from_str::<Value>(trimmed) {
+                Ok(value) => {
+                    if !complete {
+                        // A whole value, just missing its newline: the agent's next write
+                        // starts with it, and the resulting blank line is skipped
+                        self.offset += consumed;
+                    }
+                    Some(Ok(value))
+                }
+                Err(_) if !complete => None,
+                Err(e) => Some(Err(GitAiError::JsonError(e))),
+            };
+        }
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped_lines
+    }
+}
+
+/// Per-agent parsing of transcript lines; the parser's own state is saved with the cursor
+pub trait JsonlTranscriptParser: Default + Serialize + DeserializeOwned {
+    /// Distinguishes cursors of different parsers over the same file
+    const KIND: &'static str;
+
+    fn parse_line(
+        &mut self,
+        entry: &Value,
+        transcript: &mut BoundedTranscript,
+        model: &mut Option<String>,
+        budget: &TranscriptBudget,
+    );
+
+    /// Final adjustments once every line has been parsed
+    fn finish(&self, transcript: BoundedTranscript) -> BoundedTranscript {
+        transcript
+    }
+}
+
+/// Where a resumable parse of a transcript stopped
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "P: JsonlTranscriptParser")]
+pub struct TranscriptCursor<P> {
+    pub path: String,
+    pub offset: u64,
+    pub fingerprint: String,
+    pub budget_max_messages: usize,
+    pub budget_max_bytes: usize,
+    pub transcript: BoundedTranscript,
+    pub model: Option<String>,
+    pub parser: P,
+}
+
+/// Parse a whole JSONL transcript within `budget`
+pub fn parse_jsonl_transcript<P: JsonlTranscriptParser>(
+    path: &Path,
+    budget: &TranscriptBudget,
+) -> Result<(AiTranscript, Option<String>), GitAiError> {
+    let cursor = parse_from::<P>(path, budget, None)?;
+    Ok((
+        cursor.parser.finish(cursor.transcript).into_transcript(),
+        cursor.model,
+    ))
+}
+
+/// Like [`parse_jsonl_transcript`], but picks up where the previous parse of `path` stopped
+/// and saves a cursor for the next one. Falls back to a full parse when the file was
+/// truncated or rewritten, or when the cursor can't be read.
+pub fn parse_jsonl_transcript_resumable<P: JsonlTranscriptParser>(
+    path: &Path,
+    budget: &TranscriptBudget,
+) -> Result<(AiTranscript, Option<String>), GitAiError> {
+    let cursor_path = cursor_path::<P>(path);
+    let previous = cursor_path
+        .as_deref()
+        .and_then(load_cursor::<P>)
+        .filter(|cursor| {
+            cursor.path == path.to_string_lossy()
+                && cursor.budget_max_messages == budget.max_messages
+                && cursor.budget_max_bytes == budget.max_bytes
+                && file_fingerprint(path, cursor.offset).ok().as_deref()
+                    == Some(cursor.fingerprint.as_str())
+        });
+
+    let cursor = parse_from(path, budget, previous)?;
+    if let Some(cursor_path) = cursor_path
+        && let Err(e) = save_cursor(&cursor_path, &cursor)
+    {
+        debug_log(&format!(
+            "Failed to save transcript cursor {}: {}",
+            cursor_path.display(),
+            e
+        ));
+    }
+
+    Ok((
+        cursor.parser.finish(cursor.transcript).into_transcript(),
+        cursor.model,
+    ))
+}
+
+fn parse_from<P: JsonlTranscriptParser>(
+    path: &Path,
+    budget: &TranscriptBudget,
+    previous: Option<TranscriptCursor<P>>,
+) -> Result<TranscriptCursor<P>, GitAiError> {
+    let (start_offset, mut transcript, mut model, mut parser) = match previous {
+        Some(cursor) => (
+            cursor.offset,
+            cursor.transcript,
+            cursor.model,
+            cursor.parser,
+        ),
+        None => (0, BoundedTranscript::new(), None, P::default()),
+    };
+
+    let mut stream = JsonlStream::open(path, start_offset, budget.max_line_bytes)?;
+    while let Some(entry) = stream.next_value() {
+        parser.parse_line(&entry?, &mut transcript, &mut model, budget);
+    }
+
+    if stream.skipped_lines() > 0 || transcript.dropped() > 0 {
+        debug_log(&format!(
+            "Transcript {} exceeded its budget: skipped {} oversized lines, kept the newest {} messages",
+            path.display(),
+            stream.skipped_lines(),
+            transcript.len()
+        ));
+    }
+
+    let offset = stream.offset();
+    Ok(TranscriptCursor {
+        path: path.to_string_lossy().to_string(),
+        offset,
+        fingerprint: file_fingerprint(path, offset)?,
+        budget_max_messages: budget.max_messages,
+        budget_max_bytes: budget.max_bytes,
+        transcript,
+        model,
+        parser,
+    })
+}
+
+/// Hash of the start of the file and of the bytes just before `offset`: if either changed,
+/// the file was rewritten rather than appended to
+fn file_fingerprint(path: &Path, offset: u64) -> Result<String, GitAiError> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() < offset {
+        return Err(GitAiError::Generic(
+            "transcript is shorter than the cursor".to_string(),
+        ));
+    }
+
+    let mut hasher = Sha256::new();
+    let mut window = Vec::new();
+    (&mut file)
+        .take(FINGERPRINT_WINDOW.min(offset))
+        .read_to_end(&mut window)?;
+    hasher.update(&window);
+
+    let tail_start = offset.saturating_sub(FINGERPRINT_WINDOW);
+    file.seek(SeekFrom::Start(tail_start))?;
+    window.clear();
+    (&mut file)
+        .take(offset - tail_start)
+        .read_to_end(&mut window)?;
+    hasher.update(&window);
+    hasher.update(offset.to_le_bytes());
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn cursor_path<P: JsonlTranscriptParser>(transcript_path: &Path) -> Option<PathBuf> {
+    let mut hasher = Sha256::new();
+    hasher.update(P::KIND.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(transcript_path.to_string_lossy().as_bytes());
+    let key = format!("{:x}", hasher.finalize());
+    config::internal_dir_path().map(|dir| {
+        dir.join("transcript-cursors")
+            .join(format!("{}.json", &key[..16]))
+    })
+}
+
+fn load_cursor<P: JsonlTranscriptParser>(cursor_path: &Path) -> Option<TranscriptCursor<P>> {
+    let data = fs::read(cursor_path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+fn save_cursor<P: JsonlTranscriptParser>(
+    cursor_path: &Path,
+    cursor: &TranscriptCursor<P>,
+) -> Result<(), GitAiError> {
+    if let Some(dir) = cursor_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Concurrent checkpoints may save the same cursor; each writes its own temp file
+    let tmp_path = cursor_path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, serde_json::to_vec(cursor)?)?;
+    fs::rename(&tmp_path, cursor_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct EchoParser {
+        lines_seen: usize,
+    }
+
+    impl JsonlTranscriptParser for EchoParser {
+        const KIND: &'static str = "echo";
+
+        fn parse_line(
+            &mut self,
+            entry: &Value,
+            transcript: &mut BoundedTranscript,
+            model: &mut Option<String>,
+            budget: &TranscriptBudget,
+        ) {
+            self.lines_seen += 1;
+            if let Some(m) = entry["model"].as_str() {
+                *model = Some(m.to_string());
+            }
+            if let Some(text) = entry["text"].as_str() {
+                transcript.push(Message::user(text.to_string(), None), budget);
+            }
+        }
+    }
+
+    fn user_texts(transcript: &AiTranscript) -> Vec<&str> {
+        transcript
+            .messages()
+            .iter()
+            .filter_map(|m| match m {
+                Message::User { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bounded_transcript_keeps_newest_messages() {
+        let budget = TranscriptBudget {
+            max_messages: 2,
+            ..TranscriptBudget::default()
+        };
+        let mut transcript = BoundedTranscript::new();
+        for text in ["one", "two", "three"] {
+            transcript.push(Message::user(text.to_string(), None), &budget);
+        }
+
+        assert_eq!(transcript.dropped(), 1);
+        assert_eq!(
+            user_texts(&transcript.into_transcript()),
+            vec!["two", "three"]
+        );
+    }
+
+    #[test]
+    fn test_bounded_transcript_respects_byte_budget_but_keeps_newest() {
+        let budget = TranscriptBudget {
+            max_bytes: 10,
+            ..TranscriptBudget::default()
+        };
+        let mut transcript = BoundedTranscript::new();
+        transcript.push(Message::user("12345678".to_string(), None), &budget);
+        transcript.push(
+            Message::user("a much longer message".to_string(), None),
+            &budget,
+        );
+
+        assert_eq!(
+            user_texts(&transcript.into_transcript()),
+            vec!["a much longer message"]
+        );
+    }
+
+    #[test]
+    fn test_truncate_tool_input_cuts_long_strings() {
+        let input = json!({ "file_path": "src/main.rs", "content": "x".repeat(10_000) });
+        let truncated = truncate_tool_input(input, 2_048);
+
+        assert_eq!(truncated["file_path"], "src/main.rs");
+        let content = truncated["content"].as_str().unwrap();
+        assert!(content.len() < 1_000);
+        assert!(content.ends_with("[truncated 9744 bytes]"));
+
+        let many_strings = Value::Array((0..1_000).map(|i| json!(format!("{i:0>300}"))).collect());
+        assert_eq!(truncate_tool_input(many_strings, 2_048)["truncated"], true);
+
+        let small = json!({ "command": "ls" });
+        assert_eq!(truncate_tool_input(small.clone(), 2_048), small);
+    }
+
+    #[test]
+    fn test_stream_skips_oversized_lines_and_holds_partial_line() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "{{\"text\":\"a\"}}\n\n{{\"text\":\"{}\"}}\n{{\"text\":\"b\"}}\n{{\"text\":",
+            "x".repeat(200)
+        )
+        .unwrap();
+
+        let mut stream = JsonlStream::open(file.path(), 0, 100).unwrap();
+        let mut texts = Vec::new();
+        while let Some(value) = stream.next_value() {
+            texts.push(value.unwrap()["text"].as_str().unwrap().to_string());
+        }
+
+        assert_eq!(texts, vec!["a", "b"]);
+        assert_eq!(stream.skipped_lines(), 1);
+        let len = file.as_file().metadata().unwrap().len();
+        assert_eq!(stream.offset(), len - "{\"text\":".len() as u64);
+    }
+
+    #[test]
+    fn test_stream_errors_on_invalid_complete_line() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "not json").unwrap();
+
+        let mut stream = JsonlStream::open(file.path(), 0, 1024).unwrap();
+        assert!(matches!(stream.next_value(), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_parse_from_resumes_and_restarts_after_rewrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let budget = TranscriptBudget::default();
+        // The agent hasn't written the newline yet
+        fs::write(&path, "{\"text\":\"one\",\"model\":\"m1\"}").unwrap();
+
+        let first = parse_from::<EchoParser>(&path, &budget, None).unwrap();
+        assert_eq!(first.parser.lines_seen, 1);
+        assert_eq!(
+            file_fingerprint(&path, first.offset).unwrap(),
+            first.fingerprint
+        );
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "\n{{\"text\":\"two\"}}\n").unwrap();
+        assert_eq!(
+            file_fingerprint(&path, first.offset).unwrap(),
+            first.fingerprint,
+            "appending keeps the fingerprint"
+        );
+
+        // Only the appended line is parsed, on top of the saved state
+        let resumed = parse_from::<EchoParser>(&path, &budget, Some(first)).unwrap();
+        assert_eq!(resumed.parser.lines_seen, 2);
+        assert_eq!(resumed.model.as_deref(), Some("m1"));
+        assert_eq!(
+            user_texts(&resumed.transcript.clone().into_transcript()),
+            vec!["one", "two"]
+        );
+
+        fs::write(&path, "{\"text\":\"new\"}\n{\"text\":\"file\"}\n").unwrap();
+        assert_ne!(
+            file_fingerprint(&path, resumed.offset).ok(),
+            Some(resumed.fingerprint)
+        );
+    }
+}
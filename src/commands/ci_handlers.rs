@@ -1,6 +1,12 @@
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::ci::azure::{get_azure_ci_context, install_azure_ci_pipeline};
+use crate::ci::bitbucket::{get_bitbucket_ci_context, print_bitbucket_ci_yaml};
 use crate::ci::ci_context::{CiContext, CiEvent, CiRunResult};
 use crate::ci::github::{get_github_ci_context, install_github_ci_workflow};
 use crate::ci::gitlab::{get_gitlab_ci_context, print_gitlab_ci_yaml};
+use crate::ci::policy::{
+    DEFAULT_POLICY_FILE, Policy, collect_range_file_authorship, evaluate_policy, format_violations,
+};
 use crate::git::repository::find_repository_in_path;
 use crate::utils::debug_log;
 
@@ -28,6 +34,31 @@ fn print_ci_result(result: &CiRunResult, prefix: &str) {
     }
 }
 
+/// Run a CI context to completion, tear it down unless `no_cleanup`, and exit
+fn run_ci_context(ci_context: CiContext, label: &str, no_cleanup: bool) -> ! {
+    debug_log(&format!("{} context: {:?}", label, ci_context));
+    match ci_context.run() {
+        Ok(result) => {
+            debug_log(&format!("{} result: {:?}", label, result));
+            print_ci_result(&result, label);
+        }
+        Err(e) => {
+            eprintln!("Error running {} context: {}", label, e);
+            std::process::exit(1);
+        }
+    }
+    if !no_cleanup {
+        if let Err(e) = ci_context.teardown() {
+            eprintln!("Error tearing down {} context: {}", label, e);
+            std::process::exit(1);
+        }
+        debug_log(&format!("{} context teared down", label));
+    } else {
+        debug_log("Skipping teardown (--no-cleanup)");
+    }
+    std::process::exit(0);
+}
+
 pub fn handle_ci(args: &[String]) {
     if args.is_empty() {
         print_ci_help_and_exit();
@@ -40,9 +71,18 @@ pub fn handle_ci(args: &[String]) {
         "gitlab" => {
             handle_ci_gitlab(&args[1..]);
         }
+        "bitbucket" => {
+            handle_ci_bitbucket(&args[1..]);
+        }
+        "azure" => {
+            handle_ci_azure(&args[1..]);
+        }
         "local" => {
             handle_ci_local(&args[1..]);
         }
+        "check" => {
+            handle_ci_check(&args[1..]);
+        }
         _ => {
             eprintln!("Unknown ci subcommand: {}", args[0]);
             print_ci_help_and_exit();
@@ -60,29 +100,7 @@ fn handle_ci_github(args: &[String]) {
             let no_cleanup = args[1..].iter().any(|a| a == "--no-cleanup");
             let ci_context = get_github_ci_context();
             match ci_context {
-                Ok(Some(ci_context)) => {
-                    debug_log(&format!("GitHub CI context: {:?}", ci_context));
-                    match ci_context.run() {
-                        Ok(result) => {
-                            debug_log(&format!("GitHub CI result: {:?}", result));
-                            print_ci_result(&result, "GitHub CI");
-                        }
-                        Err(e) => {
-                            eprintln!("Error running GitHub CI context: {}", e);
-                            std::process::exit(1);
-                        }
-                    }
-                    if !no_cleanup {
-                        if let Err(e) = ci_context.teardown() {
-                            eprintln!("Error tearing down GitHub CI context: {}", e);
-                            std::process::exit(1);
-                        }
-                        debug_log("GitHub CI context teared down");
-                    } else {
-                        debug_log("Skipping teardown (--no-cleanup)");
-                    }
-                    std::process::exit(0);
-                }
+                Ok(Some(ci_context)) => run_ci_context(ci_context, "GitHub CI", no_cleanup),
                 Err(e) => {
                     eprintln!("Failed to get GitHub CI context: {}", e);
                     std::process::exit(1);
@@ -120,29 +138,7 @@ fn handle_ci_gitlab(args: &[String]) {
             let no_cleanup = args[1..].iter().any(|a| a == "--no-cleanup");
             let ci_context = get_gitlab_ci_context();
             match ci_context {
-                Ok(Some(ci_context)) => {
-                    debug_log(&format!("GitLab CI context: {:?}", ci_context));
-                    match ci_context.run() {
-                        Ok(result) => {
-                            debug_log(&format!("GitLab CI result: {:?}", result));
-                            print_ci_result(&result, "GitLab CI");
-                        }
-                        Err(e) => {
-                            eprintln!("Error running GitLab CI context: {}", e);
-                            std::process::exit(1);
-                        }
-                    }
-                    if !no_cleanup {
-                        if let Err(e) = ci_context.teardown() {
-                            eprintln!("Error tearing down GitLab CI context: {}", e);
-                            std::process::exit(1);
-                        }
-                        debug_log("GitLab CI context teared down");
-                    } else {
-                        debug_log("Skipping teardown (--no-cleanup)");
-                    }
-                    std::process::exit(0);
-                }
+                Ok(Some(ci_context)) => run_ci_context(ci_context, "GitLab CI", no_cleanup),
                 Err(e) => {
                     eprintln!("Failed to get GitLab CI context: {}", e);
                     std::process::exit(1);
@@ -164,6 +160,77 @@ fn handle_ci_gitlab(args: &[String]) {
     }
 }
 
+fn handle_ci_bitbucket(args: &[String]) {
+    if args.is_empty() {
+        print_ci_bitbucket_help_and_exit();
+    }
+    // Subcommands: install | run
+    match args[0].as_str() {
+        "run" => {
+            let no_cleanup = args[1..].iter().any(|a| a == "--no-cleanup");
+            match get_bitbucket_ci_context() {
+                Ok(Some(ci_context)) => {
+                    run_ci_context(ci_context, "Bitbucket Pipelines", no_cleanup)
+                }
+                Err(e) => {
+                    eprintln!("Failed to get Bitbucket Pipelines context: {}", e);
+                    std::process::exit(1);
+                }
+                Ok(None) => {
+                    // No matching PR found - this is not an error, just nothing to do
+                    std::process::exit(0);
+                }
+            }
+        }
+        "install" => {
+            print_bitbucket_ci_yaml();
+            std::process::exit(0);
+        }
+        other => {
+            eprintln!("Unknown ci bitbucket subcommand: {}", other);
+            print_ci_help_and_exit();
+        }
+    }
+}
+
+fn handle_ci_azure(args: &[String]) {
+    if args.is_empty() {
+        print_ci_azure_help_and_exit();
+    }
+    // Subcommands: install | run
+    match args[0].as_str() {
+        "run" => {
+            let no_cleanup = args[1..].iter().any(|a| a == "--no-cleanup");
+            match get_azure_ci_context() {
+                Ok(Some(ci_context)) => run_ci_context(ci_context, "Azure Pipelines", no_cleanup),
+                Err(e) => {
+                    eprintln!("Failed to get Azure Pipelines context: {}", e);
+                    std::process::exit(1);
+                }
+                Ok(None) => {
+                    // No matching PR found - this is not an error, just nothing to do
+                    std::process::exit(0);
+                }
+            }
+        }
+        "install" => match install_azure_ci_pipeline() {
+            Ok(path) => {
+                println!("Installed Azure Pipelines definition to {}", path.display());
+                println!("Create a pipeline from this file in Azure DevOps to enable it.");
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("Failed to install Azure Pipelines definition: {}", e);
+                std::process::exit(1);
+            }
+        },
+        other => {
+            eprintln!("Unknown ci azure subcommand: {}", other);
+            print_ci_help_and_exit();
+        }
+    }
+}
+
 fn handle_ci_local(args: &[String]) {
     if args.is_empty() {
         print_ci_local_help_and_exit();
@@ -275,6 +342,126 @@ fn handle_ci_local(args: &[String]) {
     }
 }
 
+fn handle_ci_check(args: &[String]) {
+    let mut base: Option<String> = None;
+    let mut head = "HEAD".to_string();
+    let mut policy_path: Option<String> = None;
+    let mut json = false;
+
+    let mut i = 0usize;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
+            "--base" | "--head" | "--policy" => {
+                let Some(value) = args.get(i + 1).cloned() else {
+                    eprintln!("Missing value for flag {}", arg);
+                    std::process::exit(1);
+                };
+                match arg {
+                    "--base" => base = Some(value),
+                    "--head" => head = value,
+                    _ => policy_path = Some(value),
+                }
+                i += 2;
+            }
+            "--json" => {
+                json = true;
+                i += 1;
+            }
+            "--help" | "-h" => print_ci_check_help_and_exit(),
+            other => {
+                eprintln!("Unknown ci check argument: {}", other);
+                print_ci_check_help_and_exit();
+            }
+        }
+    }
+
+    // Fall back to the PR base exposed by GitHub Actions / GitLab CI
+    let base = base
+        .or_else(|| {
+            std::env::var("GITHUB_BASE_REF")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| format!("origin/{}", v))
+        })
+        .or_else(|| {
+            std::env::var("CI_MERGE_REQUEST_DIFF_BASE_SHA")
+                .ok()
+                .filter(|v| !v.is_empty())
+        });
+    let Some(base) = base else {
+        eprintln!("--base is required outside of a GitHub or GitLab pull request pipeline");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository_in_path(".") {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Failed to open repository in current directory: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let policy_path = match policy_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => match repo.workdir() {
+            Ok(workdir) => workdir.join(DEFAULT_POLICY_FILE),
+            Err(e) => {
+                eprintln!("Failed to locate {}: {}", DEFAULT_POLICY_FILE, e);
+                std::process::exit(1);
+            }
+        },
+    };
+    let policy = match Policy::load(&policy_path) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    let authorship = match collect_range_file_authorship(&repo, &base, &head, &ignore_patterns) {
+        Ok(authorship) => authorship,
+        Err(e) => {
+            eprintln!("Failed to collect authorship for {}..{}: {}", base, head, e);
+            std::process::exit(1);
+        }
+    };
+    let violations = evaluate_policy(&policy, &authorship);
+
+    if json {
+        match serde_json::to_string(&serde_json::json!({
+            "base": base,
+            "head": head,
+            "passed": violations.is_empty(),
+            "violations": violations,
+        })) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to serialize policy result: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else if violations.is_empty() {
+        println!(
+            "AI authorship policy passed ({} rules, {} files checked)",
+            policy.rules.len(),
+            authorship.files.len()
+        );
+    } else {
+        eprint!("{}", format_violations(&violations, &authorship));
+        eprintln!(
+            "AI authorship policy failed: {} violation(s) in {}..{}",
+            violations.len(),
+            base,
+            head
+        );
+    }
+
+    std::process::exit(if violations.is_empty() { 0 } else { 1 });
+}
+
 fn print_ci_help_and_exit() -> ! {
     eprintln!("git-ai ci - Continuous integration utilities");
     eprintln!();
@@ -287,6 +474,16 @@ fn print_ci_help_and_exit() -> ! {
     eprintln!("  gitlab           GitLab CI");
     eprintln!("    run [--no-cleanup]  Run GitLab CI in current repo");
     eprintln!("    install        Print YAML snippet to add to .gitlab-ci.yml");
+    eprintln!("  bitbucket        Bitbucket Pipelines");
+    eprintln!("    run [--no-cleanup]  Run Bitbucket Pipelines CI in current repo");
+    eprintln!("    install        Print YAML snippet to add to bitbucket-pipelines.yml");
+    eprintln!("  azure            Azure Pipelines");
+    eprintln!("    run [--no-cleanup]  Run Azure Pipelines CI in current repo");
+    eprintln!("    install        Write azure-pipelines-git-ai.yml in current repo");
+    eprintln!("  check            Fail when a commit range breaks the AI authorship policy");
+    eprintln!(
+        "                   Usage: git-ai ci check [--base <ref>] [--head <ref>] [--policy <file>] [--json]"
+    );
     eprintln!("  local            Run CI locally by event name and flags");
     eprintln!("                   Usage: git-ai ci local <event> [flags]");
     eprintln!("                   Events:");
@@ -308,6 +505,25 @@ fn print_ci_local_help_and_exit() -> ! {
     std::process::exit(1);
 }
 
+fn print_ci_check_help_and_exit() -> ! {
+    eprintln!("git-ai ci check - Enforce an AI authorship policy on a commit range");
+    eprintln!();
+    eprintln!("Usage: git-ai ci check [--base <ref>] [--head <ref>] [--policy <file>] [--json]");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --base <ref>     Base of the range (default: origin/$GITHUB_BASE_REF or");
+    eprintln!("                   $CI_MERGE_REQUEST_DIFF_BASE_SHA)");
+    eprintln!("  --head <ref>     Head of the range (default: HEAD)");
+    eprintln!(
+        "  --policy <file>  Policy file (default: {} at the repo root)",
+        DEFAULT_POLICY_FILE
+    );
+    eprintln!("  --json           Print the result as JSON");
+    eprintln!();
+    eprintln!("Exits with status 1 when any policy rule is violated.");
+    std::process::exit(1);
+}
+
 fn print_ci_github_help_and_exit() -> ! {
     eprintln!("git-ai ci github - GitHub CI utilities");
     eprintln!();
@@ -331,3 +547,27 @@ fn print_ci_gitlab_help_and_exit() -> ! {
     eprintln!("  install              Print YAML snippet to add to .gitlab-ci.yml");
     std::process::exit(1);
 }
+
+fn print_ci_bitbucket_help_and_exit() -> ! {
+    eprintln!("git-ai ci bitbucket - Bitbucket Pipelines utilities");
+    eprintln!();
+    eprintln!("Usage: git-ai ci bitbucket <subcommand> [args...]");
+    eprintln!();
+    eprintln!("Subcommands:");
+    eprintln!("  run [--no-cleanup]   Run Bitbucket Pipelines CI in current repo");
+    eprintln!("                       --no-cleanup  Skip teardown after run");
+    eprintln!("  install              Print YAML snippet to add to bitbucket-pipelines.yml");
+    std::process::exit(1);
+}
+
+fn print_ci_azure_help_and_exit() -> ! {
+    eprintln!("git-ai ci azure - Azure Pipelines utilities");
+    eprintln!();
+    eprintln!("Usage: git-ai ci azure <subcommand> [args...]");
+    eprintln!();
+    eprintln!("Subcommands:");
+    eprintln!("  run [--no-cleanup]   Run Azure Pipelines CI in current repo");
+    eprintln!("                       --no-cleanup  Skip teardown after run");
+    eprintln!("  install              Write azure-pipelines-git-ai.yml in current repo");
+    std::process::exit(1);
+}
@@ -1,6 +1,7 @@
 use dirs;
 use serde_json::Value;
 
+use crate::config::SecretRuleConfig;
 use crate::git::repository::find_repository_in_path;
 
 /// Determines the type of pattern value provided
@@ -106,10 +107,16 @@ fn print_config_help() {
     eprintln!("  update_channel               Update channel (latest/next)");
     eprintln!("  feature_flags                Feature flags (object)");
     eprintln!("  api_key                      API key for X-API-Key header");
-    eprintln!("  prompt_storage               Prompt storage mode (default/notes/local)");
+    eprintln!("  prompt_storage               Prompt storage mode (default/notes/local/git)");
     eprintln!("  include_prompts_in_repositories  Repos to include for prompt storage (array)");
     eprintln!("  default_prompt_storage       Fallback storage mode for non-included repos");
     eprintln!("  quiet                        Suppress chart output after commits (bool)");
+    eprintln!(
+        "  secret_rules                 Custom secret detectors: [{{\"id\", \"regex\"}}] (array)"
+    );
+    eprintln!(
+        "  secret_allowlist             Regexes for values never redacted as secrets (array)"
+    );
     eprintln!();
     eprintln!("Repository Patterns:");
     eprintln!("  For exclude/allow/exclude_prompts_in_repositories, you can provide:");
@@ -125,6 +132,9 @@ fn print_config_help() {
     eprintln!("  git-ai config --add exclude_repositories \"temp/*\"");
     eprintln!("  git-ai config --add allow_repositories ~/projects/my-repo");
     eprintln!("  git-ai config --add feature_flags.my_flag true");
+    eprintln!(
+        "  git-ai config --add secret_rules '{{\"id\":\"acme-token\",\"regex\":\"acme_[a-z0-9]{{32}}\"}}'"
+    );
     eprintln!("  git-ai config unset exclude_repositories");
     eprintln!();
     std::process::exit(0);
@@ -304,6 +314,15 @@ fn show_all_config() -> Result<(), String> {
 
     effective_config.insert("quiet".to_string(), Value::Bool(runtime_config.is_quiet()));
 
+    effective_config.insert(
+        "secret_rules".to_string(),
+        serde_json::to_value(runtime_config.secret_rules()).unwrap_or(Value::Array(vec![])),
+    );
+    effective_config.insert(
+        "secret_allowlist".to_string(),
+        serde_json::to_value(runtime_config.secret_allowlist()).unwrap_or(Value::Array(vec![])),
+    );
+
     // Feature flags - show effective flags with defaults applied
     let flags_value = serde_json::to_value(runtime_config.get_feature_flags())
         .unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
@@ -392,6 +411,11 @@ fn get_config_value(key: &str) -> Result<(), String> {
                 }
             }
             "quiet" => Value::Bool(runtime_config.is_quiet()),
+            "secret_rules" => {
+                serde_json::to_value(runtime_config.secret_rules()).unwrap_or(Value::Array(vec![]))
+            }
+            "secret_allowlist" => serde_json::to_value(runtime_config.secret_allowlist())
+                .unwrap_or(Value::Array(vec![])),
             _ => return Err(format!("Unknown config key: {}", key)),
         };
 
@@ -553,6 +577,39 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 eprintln!("[quiet]: {}", bool_value);
             }
+            "secret_rules" => {
+                let rules = parse_secret_rules(value)?;
+                let mut list = if add_mode {
+                    file_config.secret_rules.take().unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                for rule in &rules {
+                    // A rule with the same id is replaced
+                    list.retain(|existing| existing.id != rule.id);
+                    list.push(rule.clone());
+                }
+                file_config.secret_rules = Some(list);
+                crate::config::save_file_config(&file_config)?;
+                for rule in rules {
+                    eprintln!("+ [secret_rules]: {} = {}", rule.id, rule.regex);
+                }
+            }
+            "secret_allowlist" => {
+                regex::Regex::new(value)
+                    .map_err(|e| format!("Invalid regex for secret_allowlist: {}", e))?;
+                let mut list = if add_mode {
+                    file_config.secret_allowlist.take().unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                if !list.iter().any(|pattern| pattern == value) {
+                    list.push(value.to_string());
+                }
+                file_config.secret_allowlist = Some(list);
+                crate::config::save_file_config(&file_config)?;
+                log_array_changes(&[value.to_string()], add_mode);
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -724,6 +781,20 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     eprintln!("- [quiet]: {}", v);
                 }
             }
+            "secret_rules" => {
+                let old_values = file_config.secret_rules.take();
+                crate::config::save_file_config(&file_config)?;
+                for rule in old_values.unwrap_or_default() {
+                    eprintln!("- [secret_rules]: {}", rule.id);
+                }
+            }
+            "secret_allowlist" => {
+                let old_values = file_config.secret_allowlist.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(items) = old_values {
+                    log_array_removals(&items);
+                }
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -913,22 +984,47 @@ fn mask_api_key(key: &str) -> String {
 
 /// Validate prompt_storage value
 fn validate_prompt_storage_value(value: &str) -> Result<(), String> {
-    if value != "default" && value != "notes" && value != "local" {
+    if !matches!(value, "default" | "notes" | "local" | "git") {
         return Err(format!(
-            "Invalid prompt_storage value '{}'. Expected 'default', 'notes', or 'local'",
+            "Invalid prompt_storage value '{}'. Expected 'default', 'notes', 'local', or 'git'",
             value
         ));
     }
     Ok(())
 }
 
+/// Parse a `secret_rules` value: one `{"id", "regex"}` object or an array of them
+fn parse_secret_rules(value: &str) -> Result<Vec<SecretRuleConfig>, String> {
+    let json_value: Value =
+        serde_json::from_str(value).map_err(|e| format!("Invalid JSON for secret_rules: {}", e))?;
+    let rules: Vec<SecretRuleConfig> = if json_value.is_array() {
+        serde_json::from_value(json_value)
+    } else {
+        serde_json::from_value(json_value).map(|rule| vec![rule])
+    }
+    .map_err(|e| {
+        format!(
+            "Invalid secret_rules value: {}. Expected {{\"id\": ..., \"regex\": ...}}",
+            e
+        )
+    })?;
+    for rule in &rules {
+        if rule.id.is_empty() {
+            return Err("secret_rules entries need a non-empty id".to_string());
+        }
+        regex::Regex::new(&rule.regex)
+            .map_err(|e| format!("Invalid regex for secret rule '{}': {}", rule.id, e))?;
+    }
+    Ok(rules)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_prompt_storage_valid_values() {
-        for value in ["default", "notes", "local"] {
+        for value in ["default", "notes", "local", "git"] {
             let result = validate_prompt_storage_value(value);
             assert!(result.is_ok(), "Expected '{}' to be valid", value);
         }
@@ -1020,6 +1116,29 @@ mod tests {
         assert_eq!(result, Value::String("plain text".to_string()));
     }
 
+    #[test]
+    fn test_parse_secret_rules_single_and_array() {
+        let rules = parse_secret_rules(r#"{"id":"acme","regex":"acme_[a-z0-9]{32}"}"#).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].id, "acme");
+
+        let rules = parse_secret_rules(
+            r#"[{"id":"acme","regex":"acme_[a-z0-9]{32}"},{"id":"internal","regex":"int-\\d+"}]"#,
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[1].regex, r"int-\d+");
+    }
+
+    #[test]
+    fn test_parse_secret_rules_rejects_invalid_values() {
+        assert!(parse_secret_rules("acme_[a-z]+").is_err());
+        assert!(parse_secret_rules(r#"{"id":"acme"}"#).is_err());
+        assert!(parse_secret_rules(r#"{"id":"","regex":"x"}"#).is_err());
+        let err = parse_secret_rules(r#"{"id":"acme","regex":"acme_("}"#).unwrap_err();
+        assert!(err.contains("Invalid regex for secret rule 'acme'"));
+    }
+
     #[test]
     fn test_mask_api_key_long() {
         let key = "abcdefghijklmnop";
@@ -262,11 +262,11 @@ fn gather_session_context(
     let mut prompts: BTreeMap<String, PromptRecord> = result.prompts.into_iter().collect();
 
     // 3. Redact secrets
-    let redaction_count = redact_secrets_from_prompts(&mut prompts);
-    if redaction_count > 0 {
+    let redactions = redact_secrets_from_prompts(&mut prompts);
+    if redactions.total > 0 {
         eprintln!(
-            "Redacted {} potential secret(s) from output",
-            redaction_count
+            "Redacted {} potential secret(s) from output ({})",
+            redactions.total, redactions
         );
     }
 
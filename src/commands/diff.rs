@@ -1,5 +1,5 @@
-use crate::authorship::authorship_log::{LineRange, PromptRecord};
-use crate::commands::blame::GitAiBlameOptions;
+use crate::authorship::authorship_log::{CharSpan, LineRange, PromptRecord};
+use crate::commands::blame::{GitAiBlameOptions, Granularity};
 use crate::error::GitAiError;
 use crate::git::repository::{Repository, exec_git};
 use serde::{Deserialize, Serialize, Serializer};
@@ -17,8 +17,12 @@ pub enum DiffSpec {
 }
 
 pub enum DiffFormat {
-    Json,
+    Json(Granularity),
     GitCompatibleTerminal,
+    /// SARIF 2.1.0 log with one result per AI-authored line range
+    Sarif,
+    /// GitHub Actions `::notice` workflow commands for AI-authored line ranges
+    GithubAnnotations,
 }
 
 #[derive(Debug)]
@@ -60,6 +64,9 @@ pub struct FileDiffJson {
     pub diff: String,
     /// The base content of the file (before changes)
     pub base_content: String,
+    /// Intra-line spans for added lines written by more than one author (`--granularity=char`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub char_spans: Vec<CharSpan>,
 }
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
@@ -84,6 +91,7 @@ pub fn handle_diff(repo: &Repository, args: &[String]) -> Result<(), GitAiError>
         eprintln!("Error: diff requires a commit or commit range argument");
         eprintln!("Usage: git-ai diff <commit>");
         eprintln!("       git-ai diff <commit1>..<commit2>");
+        eprintln!("Options: --json [--granularity=char] | --sarif | --github-annotations");
         std::process::exit(1);
     }
 
@@ -101,10 +109,40 @@ pub fn handle_diff(repo: &Repository, args: &[String]) -> Result<(), GitAiError>
 pub fn parse_diff_args(args: &[String]) -> Result<(DiffSpec, DiffFormat), GitAiError> {
     let arg = &args[0];
 
-    let format = if args.iter().any(|arg| arg == "--json") {
-        DiffFormat::Json
-    } else {
-        DiffFormat::GitCompatibleTerminal
+    let format_flags: Vec<&String> = args
+        .iter()
+        .filter(|arg| matches!(arg.as_str(), "--json" | "--sarif" | "--github-annotations"))
+        .collect();
+    if format_flags.len() > 1 {
+        return Err(GitAiError::Generic(
+            "--json, --sarif and --github-annotations are mutually exclusive".to_string(),
+        ));
+    }
+    let mut granularity = Granularity::Line;
+    let mut i = 1;
+    while i < args.len() {
+        if args[i] == "--granularity" {
+            let value = args.get(i + 1).ok_or_else(|| {
+                GitAiError::Generic("Missing argument for --granularity".to_string())
+            })?;
+            granularity = value.parse()?;
+            i += 1;
+        } else if let Some(value) = args[i].strip_prefix("--granularity=") {
+            granularity = value.parse()?;
+        }
+        i += 1;
+    }
+    if granularity == Granularity::Char && !format_flags.iter().any(|flag| *flag == "--json") {
+        return Err(GitAiError::Generic(
+            "--granularity=char requires --json".to_string(),
+        ));
+    }
+
+    let format = match format_flags.first().map(|flag| flag.as_str()) {
+        Some("--json") => DiffFormat::Json(granularity),
+        Some("--sarif") => DiffFormat::Sarif,
+        Some("--github-annotations") => DiffFormat::GithubAnnotations,
+        _ => DiffFormat::GitCompatibleTerminal,
     };
 
     // Check for commit range (start..end)
@@ -159,14 +197,33 @@ pub fn execute_diff(
 
     // Step 3: Format and output annotated diff
     let output = match format {
-        DiffFormat::Json => {
-            let diff_json = build_diff_json(repo, &from_commit, &to_commit, &hunks, &attributions)?;
+        DiffFormat::Json(granularity) => {
+            let diff_json = build_diff_json(
+                repo,
+                &from_commit,
+                &to_commit,
+                &hunks,
+                &attributions,
+                granularity,
+            )?;
             serde_json::to_string(&diff_json)
                 .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON: {}", e)))?
         }
         DiffFormat::GitCompatibleTerminal => {
             format_annotated_diff(repo, &from_commit, &to_commit, &attributions)?
         }
+        DiffFormat::Sarif => {
+            let annotations = collect_diff_annotations(repo, &from_commit, &to_commit, &hunks)?;
+            let sarif = build_sarif_log(&annotations);
+            let mut output = serde_json::to_string_pretty(&sarif)
+                .map_err(|e| GitAiError::Generic(format!("Failed to serialize SARIF: {}", e)))?;
+            output.push('\n');
+            output
+        }
+        DiffFormat::GithubAnnotations => {
+            let annotations = collect_diff_annotations(repo, &from_commit, &to_commit, &hunks)?;
+            format_github_annotations(&annotations)
+        }
     };
 
     Ok(output)
@@ -486,6 +543,7 @@ fn build_diff_json(
     to_commit: &str,
     hunks: &[DiffHunk],
     _attributions: &HashMap<DiffLineKey, Attribution>,
+    granularity: Granularity,
 ) -> Result<DiffJson, GitAiError> {
     let mut files: BTreeMap<String, FileDiffJson> = BTreeMap::new();
     let mut all_prompts: BTreeMap<String, PromptRecord> = BTreeMap::new();
@@ -518,12 +576,22 @@ fn build_diff_json(
             Err(_) => String::new(), // File didn't exist in from_commit (new file)
         };
 
+        let char_spans = if granularity == Granularity::Char {
+            let (spans, span_prompts) =
+                collect_file_char_spans(repo, from_commit, to_commit, file_path, hunks);
+            all_prompts.extend(span_prompts);
+            spans
+        } else {
+            Vec::new()
+        };
+
         files.insert(
             file_path.clone(),
             FileDiffJson {
                 annotations: file_annotations.0,
                 diff,
                 base_content,
+                char_spans,
             },
         );
     }
@@ -684,6 +752,45 @@ fn collect_file_annotations(
     Ok((annotations, prompt_records))
 }
 
+/// Collect intra-line spans for the added lines of a specific file, with the prompts they
+/// reference
+fn collect_file_char_spans(
+    repo: &Repository,
+    from_commit: &str,
+    to_commit: &str,
+    file_path: &str,
+    hunks: &[DiffHunk],
+) -> (Vec<CharSpan>, HashMap<String, PromptRecord>) {
+    let mut added_lines: Vec<u32> = hunks
+        .iter()
+        .filter(|hunk| hunk.file_path == file_path)
+        .flat_map(|hunk| hunk.added_lines.iter().copied())
+        .collect();
+    added_lines.sort_unstable();
+    added_lines.dedup();
+    if added_lines.is_empty() {
+        return (Vec::new(), HashMap::new());
+    }
+
+    let options = GitAiBlameOptions {
+        oldest_commit: Some(from_commit.to_string()),
+        newest_commit: Some(to_commit.to_string()),
+        line_ranges: lines_to_ranges(&added_lines),
+        no_output: true,
+        ..Default::default()
+    };
+
+    // Blame failed, no spans for this file
+    let (spans, prompts) = repo
+        .blame_char_spans(file_path, &options)
+        .unwrap_or_default();
+    let spans = spans
+        .into_iter()
+        .filter(|span| added_lines.binary_search(&span.line).is_ok())
+        .collect();
+    (spans, prompts)
+}
+
 // ============================================================================
 // Output Formatting
 // ============================================================================
@@ -924,6 +1031,174 @@ where
     map.end()
 }
 
+// ============================================================================
+// SARIF and GitHub Annotation Output
+// ============================================================================
+
+const SARIF_RULE_ID: &str = "git-ai/ai-authored";
+
+/// AI-authored line ranges of a diff, keyed by file then prompt hash
+#[derive(Debug, Default)]
+pub struct DiffAnnotations {
+    pub files: BTreeMap<String, BTreeMap<String, Vec<LineRange>>>,
+    pub prompts: BTreeMap<String, PromptRecord>,
+}
+
+/// Collect the AI annotations of every file in the diff without the content
+/// needed for `--json` output
+pub fn collect_diff_annotations(
+    repo: &Repository,
+    from_commit: &str,
+    to_commit: &str,
+    hunks: &[DiffHunk],
+) -> Result<DiffAnnotations, GitAiError> {
+    let mut unique_files: Vec<String> = hunks.iter().map(|h| h.file_path.clone()).collect();
+    unique_files.sort();
+    unique_files.dedup();
+
+    let mut result = DiffAnnotations::default();
+    for file_path in &unique_files {
+        let (annotations, prompt_records) =
+            collect_file_annotations(repo, from_commit, to_commit, file_path, hunks)?;
+        if annotations.is_empty() {
+            continue;
+        }
+        for hash in annotations.keys() {
+            if let Some(prompt_record) = prompt_records.get(hash) {
+                result.prompts.insert(hash.clone(), prompt_record.clone());
+            }
+        }
+        result.files.insert(file_path.clone(), annotations);
+    }
+
+    Ok(result)
+}
+
+fn line_range_bounds(range: &LineRange) -> (u32, u32) {
+    match range {
+        LineRange::Single(line) => (*line, *line),
+        LineRange::Range(start, end) => (*start, *end),
+    }
+}
+
+/// Human readable provenance of a prompt, e.g. "cursor (gpt-4), prompt 1a2b3c4d"
+fn describe_prompt(hash: &str, prompt: Option<&PromptRecord>) -> String {
+    match prompt {
+        Some(prompt) if !prompt.agent_id.model.is_empty() => format!(
+            "{} ({}), prompt {}",
+            prompt.agent_id.tool, prompt.agent_id.model, hash
+        ),
+        Some(prompt) => format!("{}, prompt {}", prompt.agent_id.tool, hash),
+        None => format!("prompt {}", hash),
+    }
+}
+
+/// Build a SARIF 2.1.0 log with one `note` result per AI-authored line range
+pub fn build_sarif_log(annotations: &DiffAnnotations) -> serde_json::Value {
+    let mut results = Vec::new();
+
+    for (file_path, by_prompt) in &annotations.files {
+        for (hash, ranges) in by_prompt {
+            let prompt = annotations.prompts.get(hash);
+            for range in ranges {
+                let (start, end) = line_range_bounds(range);
+                results.push(serde_json::json!({
+                    "ruleId": SARIF_RULE_ID,
+                    "level": "note",
+                    "message": {
+                        "text": format!("AI-authored by {}", describe_prompt(hash, prompt)),
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": file_path },
+                            "region": { "startLine": start, "endLine": end },
+                        },
+                    }],
+                    "partialFingerprints": {
+                        "gitAiPromptHash/v1": hash,
+                    },
+                    "properties": {
+                        "tool": prompt.map(|p| p.agent_id.tool.as_str()),
+                        "model": prompt.map(|p| p.agent_id.model.as_str()),
+                        "promptHash": hash,
+                        "humanAuthor": prompt.and_then(|p| p.human_author.as_deref()),
+                    },
+                }));
+            }
+        }
+    }
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "git-ai",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/git-ai-project/git-ai",
+                    "rules": [{
+                        "id": SARIF_RULE_ID,
+                        "name": "AiAuthoredCode",
+                        "shortDescription": { "text": "AI-authored code" },
+                        "fullDescription": {
+                            "text": "Lines attributed to an AI agent by git-ai authorship notes",
+                        },
+                        "defaultConfiguration": { "level": "note" },
+                    }],
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Format GitHub Actions `::notice` workflow commands, one per AI-authored line range
+pub fn format_github_annotations(annotations: &DiffAnnotations) -> String {
+    let mut output = String::new();
+
+    for (file_path, by_prompt) in &annotations.files {
+        for (hash, ranges) in by_prompt {
+            let prompt = annotations.prompts.get(hash);
+            let title = match prompt {
+                Some(prompt) => format!("AI-authored ({})", prompt.agent_id.tool),
+                None => "AI-authored".to_string(),
+            };
+            for range in ranges {
+                let (start, end) = line_range_bounds(range);
+                output.push_str(&format!(
+                    "::notice file={},line={},endLine={},title={}::{}\n",
+                    escape_workflow_property(file_path),
+                    start,
+                    end,
+                    escape_workflow_property(&title),
+                    escape_workflow_data(&format!(
+                        "AI-authored by {}",
+                        describe_prompt(hash, prompt)
+                    ))
+                ));
+            }
+        }
+    }
+
+    output
+}
+
+/// Escape the message of a workflow command
+fn escape_workflow_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property of a workflow command
+fn escape_workflow_property(value: &str) -> String {
+    escape_workflow_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
 // ============================================================================
 // Filtered Diff for Bundle Sharing
 // ============================================================================
@@ -959,7 +1234,14 @@ pub fn get_diff_json_filtered(
     let attributions = overlay_diff_attributions(repo, &from_commit, &to_commit, &hunks)?;
 
     // Build the full DiffJson structure
-    let mut diff_json = build_diff_json(repo, &from_commit, &to_commit, &hunks, &attributions)?;
+    let mut diff_json = build_diff_json(
+        repo,
+        &from_commit,
+        &to_commit,
+        &hunks,
+        &attributions,
+        Granularity::Line,
+    )?;
 
     // Apply filtering if requested
     if options.filter_to_attributed_files
@@ -1207,4 +1489,130 @@ index 111222..333444 100644
         let result = parse_diff_hunks(diff_text).unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    fn sample_annotations() -> DiffAnnotations {
+        let prompt = PromptRecord {
+            agent_id: crate::authorship::working_log::AgentId {
+                tool: "cursor".to_string(),
+                id: "session".to_string(),
+                model: "gpt-4".to_string(),
+            },
+            human_author: Some("alice".to_string()),
+            messages: vec![],
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+        };
+        let mut file_annotations = BTreeMap::new();
+        file_annotations.insert(
+            "abc123".to_string(),
+            vec![LineRange::Range(3, 5), LineRange::Single(9)],
+        );
+        let mut annotations = DiffAnnotations::default();
+        annotations
+            .files
+            .insert("src/a,b.rs".to_string(), file_annotations);
+        annotations.prompts.insert("abc123".to_string(), prompt);
+        annotations
+    }
+
+    #[test]
+    fn test_parse_diff_args_output_formats() {
+        let args = vec!["HEAD".to_string(), "--sarif".to_string()];
+        let (_spec, format) = parse_diff_args(&args).unwrap();
+        assert!(matches!(format, DiffFormat::Sarif));
+
+        let args = vec!["HEAD".to_string(), "--github-annotations".to_string()];
+        let (_spec, format) = parse_diff_args(&args).unwrap();
+        assert!(matches!(format, DiffFormat::GithubAnnotations));
+
+        let args = vec![
+            "HEAD".to_string(),
+            "--json".to_string(),
+            "--sarif".to_string(),
+        ];
+        assert!(parse_diff_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_diff_args_granularity() {
+        let args = vec!["HEAD".to_string(), "--json".to_string()];
+        let (_spec, format) = parse_diff_args(&args).unwrap();
+        assert!(matches!(format, DiffFormat::Json(Granularity::Line)));
+
+        let args = vec![
+            "HEAD".to_string(),
+            "--json".to_string(),
+            "--granularity=char".to_string(),
+        ];
+        let (_spec, format) = parse_diff_args(&args).unwrap();
+        assert!(matches!(format, DiffFormat::Json(Granularity::Char)));
+
+        let args = vec![
+            "HEAD".to_string(),
+            "--granularity".to_string(),
+            "char".to_string(),
+            "--json".to_string(),
+        ];
+        let (_spec, format) = parse_diff_args(&args).unwrap();
+        assert!(matches!(format, DiffFormat::Json(Granularity::Char)));
+
+        let args = vec!["HEAD".to_string(), "--granularity=char".to_string()];
+        assert!(parse_diff_args(&args).is_err());
+
+        let args = vec![
+            "HEAD".to_string(),
+            "--json".to_string(),
+            "--granularity=word".to_string(),
+        ];
+        assert!(parse_diff_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_build_sarif_log() {
+        let sarif = build_sarif_log(&sample_annotations());
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "git-ai");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], SARIF_RULE_ID);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], SARIF_RULE_ID);
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/a,b.rs"
+        );
+        let region = &results[0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 3);
+        assert_eq!(region["endLine"], 5);
+        assert_eq!(
+            results[0]["message"]["text"],
+            "AI-authored by cursor (gpt-4), prompt abc123"
+        );
+        assert_eq!(results[1]["properties"]["model"], "gpt-4");
+        assert_eq!(results[1]["properties"]["promptHash"], "abc123");
+    }
+
+    #[test]
+    fn test_format_github_annotations() {
+        let output = format_github_annotations(&sample_annotations());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "::notice file=src/a%2Cb.rs,line=3,endLine=5,title=AI-authored (cursor)::AI-authored by cursor (gpt-4), prompt abc123",
+                "::notice file=src/a%2Cb.rs,line=9,endLine=9,title=AI-authored (cursor)::AI-authored by cursor (gpt-4), prompt abc123",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape_workflow_values() {
+        assert_eq!(escape_workflow_data("50%\nnext"), "50%25%0Anext");
+        assert_eq!(escape_workflow_property("a:b,c"), "a%3Ab%2Cc");
+    }
 }
@@ -0,0 +1,755 @@
+//! Fsck command for git-ai
+//!
+//! Provides `git-ai fsck`, which validates every note under `refs/notes/ai` against the
+//! Git AI Standard v3.1.0 (`specs/git_ai_standard_v3.1.0.md`) and optionally repairs them.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{
+    AUTHORSHIP_LOG_VERSION, AuthorshipLog, is_supported_schema_version,
+};
+use crate::error::GitAiError;
+use crate::git::authorship_traversal::{batch_read_blobs_with_oids, get_notes_list};
+use crate::git::find_repository_in_path;
+use crate::git::refs::notes_add_batch;
+use crate::git::repository::{Repository, exec_git_stdin};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// The note is not a valid authorship log
+    Unparseable,
+    /// `schema_version` is not the supported version
+    UnsupportedSchemaVersion,
+    /// A session hash is not 16 (or legacy 7) hexadecimal characters
+    InvalidHash,
+    /// An attestation references a hash missing from the `prompts` map
+    MissingPrompt,
+    /// A line range is zero, reversed or otherwise malformed
+    InvalidLineRange,
+    /// A line range extends past the end of the file at that commit
+    LineOutOfRange,
+    /// An attested file does not exist at that commit
+    MissingFile,
+    /// A prompt record is not referenced by any attestation or line state
+    OrphanedPrompt,
+}
+
+impl IssueKind {
+    fn severity(self) -> Severity {
+        match self {
+            IssueKind::OrphanedPrompt => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Whether `--repair` fixes this kind of issue
+    fn repairable(self) -> bool {
+        matches!(
+            self,
+            IssueKind::InvalidLineRange
+                | IssueKind::LineOutOfRange
+                | IssueKind::MissingFile
+                | IssueKind::OrphanedPrompt
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FsckIssue {
+    pub commit: String,
+    pub kind: IssueKind,
+    pub severity: Severity,
+    pub repairable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    pub message: String,
+}
+
+impl FsckIssue {
+    fn new(
+        commit: &str,
+        kind: IssueKind,
+        file: Option<&str>,
+        hash: Option<&str>,
+        message: String,
+    ) -> Self {
+        Self {
+            commit: commit.to_string(),
+            kind,
+            severity: kind.severity(),
+            repairable: kind.repairable(),
+            file: file.map(str::to_string),
+            hash: hash.map(str::to_string),
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FsckReport {
+    pub notes_checked: usize,
+    pub issues: Vec<FsckIssue>,
+    pub notes_repaired: usize,
+}
+
+impl FsckReport {
+    /// Errors that are still present after any repair
+    fn remaining_errors(&self, repaired: bool) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .filter(|issue| !(repaired && issue.repairable))
+            .count()
+    }
+}
+
+fn is_valid_hash(hash: &str) -> bool {
+    (hash.len() == 16 || hash.len() == 7) && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn format_range(range: &LineRange) -> String {
+    match range {
+        LineRange::Single(line) => line.to_string(),
+        LineRange::Range(start, end) => format!("{}-{}", start, end),
+    }
+}
+
+/// Check the hash and line ranges of one attestation or line state entry, returning the
+/// ranges that remain after dropping invalid lines and lines past the end of the file
+fn check_entry(
+    commit: &str,
+    log: &AuthorshipLog,
+    file_path: &str,
+    hash: &str,
+    line_ranges: &[LineRange],
+    line_count: usize,
+    issues: &mut Vec<FsckIssue>,
+) -> Vec<LineRange> {
+    if !is_valid_hash(hash) {
+        issues.push(FsckIssue::new(
+            commit,
+            IssueKind::InvalidHash,
+            Some(file_path),
+            Some(hash),
+            "session hash must be 16 hexadecimal characters".to_string(),
+        ));
+    }
+    if !log.metadata.prompts.contains_key(hash) {
+        issues.push(FsckIssue::new(
+            commit,
+            IssueKind::MissingPrompt,
+            Some(file_path),
+            Some(hash),
+            "hash has no record in metadata prompts".to_string(),
+        ));
+    }
+
+    let mut lines = Vec::new();
+    for range in line_ranges {
+        let (start, end) = match *range {
+            LineRange::Single(line) => (line, line),
+            LineRange::Range(start, end) => (start, end),
+        };
+        if start == 0 || start > end {
+            issues.push(FsckIssue::new(
+                commit,
+                IssueKind::InvalidLineRange,
+                Some(file_path),
+                Some(hash),
+                format!("invalid line range {}", format_range(range)),
+            ));
+            continue;
+        }
+        if end as usize > line_count {
+            issues.push(FsckIssue::new(
+                commit,
+                IssueKind::LineOutOfRange,
+                Some(file_path),
+                Some(hash),
+                format!(
+                    "line range {} is beyond the end of the file ({} lines)",
+                    format_range(range),
+                    line_count
+                ),
+            ));
+        }
+        lines.extend((start..=end).take_while(|line| *line as usize <= line_count));
+    }
+    lines.sort_unstable();
+    lines.dedup();
+    LineRange::compress_lines(&lines)
+}
+
+/// Validate one authorship log. `line_counts` holds the number of lines of every attested
+/// file, or file with line states, that exists at `commit`; files absent from it are treated
+/// as missing.
+///
+/// Returns the issues found and, when any of them is repairable, the repaired log.
+pub fn check_authorship_log(
+    commit: &str,
+    log: &AuthorshipLog,
+    line_counts: &HashMap<String, usize>,
+) -> (Vec<FsckIssue>, Option<AuthorshipLog>) {
+    let mut issues = Vec::new();
+
+    if !is_supported_schema_version(&log.metadata.schema_version) {
+        issues.push(FsckIssue::new(
+            commit,
+            IssueKind::UnsupportedSchemaVersion,
+            None,
+            None,
+            format!(
+                "schema_version is '{}' (expected '{}' or another 3.x version)",
+                log.metadata.schema_version, AUTHORSHIP_LOG_VERSION
+            ),
+        ));
+    }
+
+    let mut repaired = log.clone();
+    let mut referenced = HashSet::new();
+    for file in repaired.attestations.iter_mut() {
+        let Some(&line_count) = line_counts.get(&file.file_path) else {
+            issues.push(FsckIssue::new(
+                commit,
+                IssueKind::MissingFile,
+                Some(&file.file_path),
+                None,
+                "file does not exist at this commit".to_string(),
+            ));
+            file.entries.clear();
+            continue;
+        };
+
+        for entry in file.entries.iter_mut() {
+            entry.line_ranges = check_entry(
+                commit,
+                log,
+                &file.file_path,
+                &entry.hash,
+                &entry.line_ranges,
+                line_count,
+                &mut issues,
+            );
+        }
+        file.entries.retain(|entry| !entry.line_ranges.is_empty());
+        referenced.extend(file.entries.iter().map(|entry| entry.hash.clone()));
+    }
+    repaired
+        .attestations
+        .retain(|file| !file.entries.is_empty());
+
+    // Human-edited AI lines are only recorded in line states, which keep their prompt alive
+    for (file_path, states) in repaired.metadata.line_states.iter_mut() {
+        let Some(&line_count) = line_counts.get(file_path) else {
+            issues.push(FsckIssue::new(
+                commit,
+                IssueKind::MissingFile,
+                Some(file_path),
+                None,
+                "file with line states does not exist at this commit".to_string(),
+            ));
+            states.clear();
+            continue;
+        };
+
+        for state in states.iter_mut() {
+            state.lines = check_entry(
+                commit,
+                log,
+                file_path,
+                &state.hash,
+                &state.lines,
+                line_count,
+                &mut issues,
+            );
+        }
+        states.retain(|state| !state.lines.is_empty());
+        referenced.extend(states.iter().map(|state| state.hash.clone()));
+    }
+    repaired
+        .metadata
+        .line_states
+        .retain(|_, states| !states.is_empty());
+
+    let all_referenced: HashSet<&String> = log
+        .attestations
+        .iter()
+        .flat_map(|file| file.entries.iter().map(|entry| &entry.hash))
+        .chain(
+            log.metadata
+                .line_states
+                .values()
+                .flatten()
+                .map(|state| &state.hash),
+        )
+        .collect();
+    for hash in log.metadata.prompts.keys() {
+        if !all_referenced.contains(hash) {
+            issues.push(FsckIssue::new(
+                commit,
+                IssueKind::OrphanedPrompt,
+                None,
+                Some(hash),
+                "prompt record is not referenced by any attestation or line state".to_string(),
+            ));
+        }
+    }
+    repaired
+        .metadata
+        .prompts
+        .retain(|hash, _| referenced.contains(hash));
+
+    let needs_repair = issues.iter().any(|issue| issue.repairable);
+    (issues, needs_repair.then_some(repaired))
+}
+
+/// Number of lines of each `<commit>:<path>` that exists, via one `cat-file --batch-check`
+/// and one `cat-file --batch` over the distinct blobs
+fn file_line_counts(
+    repo: &Repository,
+    entries: &[(String, String)],
+) -> Result<HashMap<(String, String), usize>, GitAiError> {
+    if entries.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("cat-file".to_string());
+    args.push("--batch-check".to_string());
+
+    let mut stdin_data = String::new();
+    for (commit_sha, path) in entries {
+        stdin_data.push_str(&format!("{}:{}\n", commit_sha, path));
+    }
+    let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    let mut blob_for_entry = Vec::new();
+    for (entry, line) in entries.iter().zip(stdout.lines()) {
+        let mut parts = line.split_whitespace();
+        if let (Some(oid), Some("blob")) = (parts.next(), parts.next()) {
+            blob_for_entry.push((entry.clone(), oid.to_string()));
+        }
+    }
+
+    let mut blob_oids: Vec<String> = blob_for_entry
+        .iter()
+        .map(|(_, oid)| oid.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    blob_oids.sort();
+    let contents = batch_read_blobs_with_oids(&repo.global_args_for_exec(), &blob_oids)?;
+
+    Ok(blob_for_entry
+        .into_iter()
+        .filter_map(|(entry, oid)| {
+            contents
+                .get(&oid)
+                .map(|content| (entry, content.lines().count()))
+        })
+        .collect())
+}
+
+/// Files a log makes claims about, in attestations or line states
+fn note_paths(log: &AuthorshipLog) -> Vec<String> {
+    let mut paths: Vec<String> = log
+        .attestations
+        .iter()
+        .map(|file| file.file_path.clone())
+        .chain(log.metadata.line_states.keys().cloned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Check every authorship note, writing repaired notes back when `repair` is set
+pub fn run_fsck(repo: &Repository, repair: bool) -> Result<FsckReport, GitAiError> {
+    let mut report = FsckReport::default();
+
+    let notes = get_notes_list(repo)?;
+    let mut blob_oids: Vec<String> = notes
+        .iter()
+        .map(|(blob_oid, _)| blob_oid.clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    blob_oids.sort();
+    let contents = batch_read_blobs_with_oids(&repo.global_args_for_exec(), &blob_oids)?;
+
+    let mut logs = Vec::new();
+    for (blob_oid, commit_sha) in &notes {
+        report.notes_checked += 1;
+        let content = contents.get(blob_oid).map(String::as_str).unwrap_or("");
+        match AuthorshipLog::deserialize_from_string(content) {
+            Ok(log) => logs.push((commit_sha.clone(), log)),
+            Err(e) => report.issues.push(FsckIssue::new(
+                commit_sha,
+                IssueKind::Unparseable,
+                None,
+                None,
+                e.to_string(),
+            )),
+        }
+    }
+
+    let path_checks: Vec<(String, String)> = logs
+        .iter()
+        .flat_map(|(commit_sha, log)| {
+            note_paths(log)
+                .into_iter()
+                .map(move |path| (commit_sha.clone(), path))
+        })
+        .collect();
+    let line_counts = file_line_counts(repo, &path_checks)?;
+
+    let mut repaired_notes = Vec::new();
+    for (commit_sha, log) in &logs {
+        let counts: HashMap<String, usize> = note_paths(log)
+            .into_iter()
+            .filter_map(|path| {
+                line_counts
+                    .get(&(commit_sha.clone(), path.clone()))
+                    .map(|count| (path, *count))
+            })
+            .collect();
+
+        let (issues, repaired) = check_authorship_log(commit_sha, log, &counts);
+        report.issues.extend(issues);
+        if let Some(repaired) = repaired {
+            let content = repaired.serialize_to_string().map_err(|_| {
+                GitAiError::Generic("Failed to serialize authorship log".to_string())
+            })?;
+            repaired_notes.push((commit_sha.clone(), content));
+        }
+    }
+
+    if repair {
+        notes_add_batch(repo, &repaired_notes)?;
+        report.notes_repaired = repaired_notes.len();
+    }
+
+    Ok(report)
+}
+
+pub fn handle_fsck(args: &[String]) {
+    let mut repair = false;
+    let mut json = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--repair" => repair = true,
+            "--json" => json = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                print_usage();
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let repo = match find_repository_in_path(".") {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let report = match run_fsck(&repo, repair) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("fsck failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        for issue in &report.issues {
+            let severity = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            let location = match (&issue.file, &issue.hash) {
+                (Some(file), Some(hash)) => format!(" {} [{}]", file, hash),
+                (Some(file), None) => format!(" {}", file),
+                (None, Some(hash)) => format!(" [{}]", hash),
+                (None, None) => String::new(),
+            };
+            println!(
+                "{} {}{}: {}",
+                severity, issue.commit, location, issue.message
+            );
+        }
+        println!(
+            "Checked {} notes: {} issues found",
+            report.notes_checked,
+            report.issues.len()
+        );
+        if repair {
+            println!("✓ Repaired {} notes", report.notes_repaired);
+        } else if report.issues.iter().any(|issue| issue.repairable) {
+            println!("Run `git-ai fsck --repair` to fix repairable issues");
+        }
+    }
+
+    if report.remaining_errors(repair) > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: git-ai fsck [--repair] [--json]");
+    eprintln!();
+    eprintln!("Validates every note in refs/notes/ai against the authorship/3.x format.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --repair   Drop invalid line ranges, missing files and orphaned prompt records,");
+    eprintln!("             in attestations and line states alike");
+    eprintln!("  --json     Output the report as JSON");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::PromptRecord;
+    use crate::authorship::authorship_log_serialization::{
+        AttestationEntry, LineState, LineStateEntry,
+    };
+    use crate::authorship::working_log::AgentId;
+
+    const COMMIT: &str = "1111111111111111111111111111111111111111";
+    const HASH: &str = "d9978a8723e02b52";
+
+    fn prompt() -> PromptRecord {
+        PromptRecord {
+            agent_id: AgentId {
+                tool: "cursor".to_string(),
+                id: "session".to_string(),
+                model: "model".to_string(),
+            },
+            human_author: None,
+            messages: vec![],
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+        }
+    }
+
+    fn kinds(issues: &[FsckIssue]) -> Vec<IssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn test_valid_log_has_no_issues() {
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                HASH.to_string(),
+                vec![LineRange::Range(1, 3)],
+            ));
+        log.metadata.prompts.insert(HASH.to_string(), prompt());
+
+        let counts = HashMap::from([("src/lib.rs".to_string(), 3)]);
+        let (issues, repaired) = check_authorship_log(COMMIT, &log, &counts);
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(repaired.is_none());
+    }
+
+    #[test]
+    fn test_repairs_ranges_missing_files_and_orphaned_prompts() {
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                HASH.to_string(),
+                vec![
+                    LineRange::Range(2, 1),
+                    LineRange::Single(2),
+                    LineRange::Range(4, 8),
+                ],
+            ));
+        log.get_or_create_file("deleted.rs")
+            .add_entry(AttestationEntry::new(
+                "aaaaaaaaaaaaaaaa".to_string(),
+                vec![LineRange::Single(1)],
+            ));
+        log.metadata.prompts.insert(HASH.to_string(), prompt());
+        log.metadata
+            .prompts
+            .insert("aaaaaaaaaaaaaaaa".to_string(), prompt());
+        log.metadata
+            .prompts
+            .insert("bbbbbbbbbbbbbbbb".to_string(), prompt());
+
+        let counts = HashMap::from([("src/lib.rs".to_string(), 5)]);
+        let (issues, repaired) = check_authorship_log(COMMIT, &log, &counts);
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                IssueKind::InvalidLineRange,
+                IssueKind::LineOutOfRange,
+                IssueKind::MissingFile,
+                IssueKind::OrphanedPrompt,
+            ]
+        );
+
+        let repaired = repaired.expect("log should be repaired");
+        assert_eq!(repaired.attestations.len(), 1);
+        assert_eq!(
+            repaired.attestations[0].entries[0].line_ranges,
+            vec![LineRange::Single(2), LineRange::Range(4, 5)]
+        );
+        assert_eq!(
+            repaired.metadata.prompts.keys().collect::<Vec<_>>(),
+            vec![HASH]
+        );
+    }
+
+    #[test]
+    fn test_reports_unrepairable_hash_problems() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.schema_version = "authorship/2.0.0".to_string();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "not-a-hash".to_string(),
+                vec![LineRange::Single(1)],
+            ));
+
+        let counts = HashMap::from([("src/lib.rs".to_string(), 1)]);
+        let (issues, repaired) = check_authorship_log(COMMIT, &log, &counts);
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                IssueKind::UnsupportedSchemaVersion,
+                IssueKind::InvalidHash,
+                IssueKind::MissingPrompt,
+            ]
+        );
+        assert!(issues.iter().all(|issue| !issue.repairable));
+        assert!(repaired.is_none());
+    }
+
+    #[test]
+    fn test_accepts_3_0_logs_and_prompts_kept_alive_by_line_states() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.schema_version = "authorship/3.0.0".to_string();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                HASH.to_string(),
+                vec![LineRange::Single(1)],
+            ));
+        log.metadata.prompts.insert(HASH.to_string(), prompt());
+
+        // A session whose only lines were edited by a human is not orphaned
+        let edited_hash = "aaaaaaaaaaaaaaaa";
+        log.metadata
+            .prompts
+            .insert(edited_hash.to_string(), prompt());
+        log.metadata.line_states.insert(
+            "src/lib.rs".to_string(),
+            vec![LineStateEntry {
+                hash: edited_hash.to_string(),
+                lines: vec![LineRange::Single(2)],
+                state: LineState::AiHumanEdited,
+                ai_timestamp: None,
+                human_author: None,
+                human_edit_timestamp: None,
+            }],
+        );
+
+        let counts = HashMap::from([("src/lib.rs".to_string(), 2)]);
+        let (issues, repaired) = check_authorship_log(COMMIT, &log, &counts);
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(repaired.is_none());
+    }
+
+    fn edited_state(hash: &str, lines: Vec<LineRange>) -> LineStateEntry {
+        LineStateEntry {
+            hash: hash.to_string(),
+            lines,
+            state: LineState::AiHumanEdited,
+            ai_timestamp: None,
+            human_author: None,
+            human_edit_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_keeps_line_states_of_files_without_attestations() {
+        // Every AI line of src/edited.rs was edited, so it only appears in line states
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(HASH.to_string(), prompt());
+        log.metadata.line_states.insert(
+            "src/edited.rs".to_string(),
+            vec![edited_state(HASH, vec![LineRange::Range(1, 2)])],
+        );
+
+        let counts = HashMap::from([("src/edited.rs".to_string(), 2)]);
+        let (issues, repaired) = check_authorship_log(COMMIT, &log, &counts);
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(repaired.is_none());
+    }
+
+    #[test]
+    fn test_checks_line_state_ranges_and_hashes() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(HASH.to_string(), prompt());
+        log.metadata.line_states.insert(
+            "src/edited.rs".to_string(),
+            vec![
+                edited_state(HASH, vec![LineRange::Range(2, 6)]),
+                edited_state("aaaaaaaaaaaaaaaa", vec![LineRange::Single(1)]),
+            ],
+        );
+        log.metadata.line_states.insert(
+            "deleted.rs".to_string(),
+            vec![edited_state(HASH, vec![LineRange::Single(1)])],
+        );
+
+        let counts = HashMap::from([("src/edited.rs".to_string(), 3)]);
+        let (issues, repaired) = check_authorship_log(COMMIT, &log, &counts);
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                IssueKind::MissingFile,
+                IssueKind::LineOutOfRange,
+                IssueKind::MissingPrompt,
+            ]
+        );
+
+        let repaired = repaired.expect("log should be repaired");
+        let states = &repaired.metadata.line_states;
+        assert_eq!(states.keys().collect::<Vec<_>>(), vec!["src/edited.rs"]);
+        assert_eq!(
+            states["src/edited.rs"][0].lines,
+            vec![LineRange::Range(2, 3)]
+        );
+        assert_eq!(states["src/edited.rs"].len(), 2);
+    }
+}
@@ -8,16 +8,22 @@ use crate::commands;
 use crate::commands::checkpoint_agent::agent_presets::{
     AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult, AiTabPreset, ClaudePreset,
     CodexPreset, ContinueCliPreset, CursorPreset, DroidPreset, GeminiPreset, GithubCopilotPreset,
+    WindsurfPreset,
 };
 use crate::commands::checkpoint_agent::agent_v1_preset::AgentV1Preset;
+use crate::commands::checkpoint_agent::aider_preset::AiderPreset;
+use crate::commands::checkpoint_agent::amp_preset::AmpPreset;
+use crate::commands::checkpoint_agent::junie_preset::JuniePreset;
 use crate::commands::checkpoint_agent::opencode_preset::OpenCodePreset;
+use crate::commands::checkpoint_agent::rovodev_preset::RovoDevPreset;
 use crate::config;
 use crate::git::find_repository;
 use crate::git::find_repository_in_path;
 use crate::git::repository::{CommitRange, group_files_by_repository};
+use crate::mdm::agents::AiderInstaller;
 use crate::observability::wrapper_performance_targets::log_performance_for_checkpoint;
 use crate::observability::{self, log_message};
-use crate::utils::is_interactive_terminal;
+use crate::utils::{is_interactive_terminal, run_internal_git_ai_subcommand};
 use std::env;
 use std::io::IsTerminal;
 use std::io::Read;
@@ -32,7 +38,7 @@ pub fn handle_git_ai(args: &[String]) {
     // Start DB warmup early for commands that need database access
     match args[0].as_str() {
         "checkpoint" | "show-prompt" | "share" | "sync-prompts" | "flush-cas" | "search"
-        | "continue" => {
+        | "continue" | "lsp" | "mcp" => {
             InternalDatabase::warmup();
         }
         _ => {}
@@ -62,6 +68,12 @@ pub fn handle_git_ai(args: &[String]) {
             }
             handle_stats(&args[1..]);
         }
+        "report" => {
+            commands::report::handle_report(&args[1..]);
+            if is_interactive_terminal() {
+                log_message("report", "info", None)
+            }
+        }
         "status" => {
             commands::status::handle_status(&args[1..]);
         }
@@ -125,6 +137,15 @@ pub fn handle_git_ai(args: &[String]) {
         "amend-authorship" => {
             commands::amend_authorship_cmd::handle_amend_authorship(&args[1..]);
         }
+        "fsck" => {
+            commands::fsck::handle_fsck(&args[1..]);
+        }
+        "remap-notes" => {
+            commands::remap_notes::handle_remap_notes(&args[1..]);
+        }
+        "verify" => {
+            commands::verify::handle_verify(&args[1..]);
+        }
         "ci" => {
             commands::ci_handlers::handle_ci(&args[1..]);
         }
@@ -170,6 +191,18 @@ pub fn handle_git_ai(args: &[String]) {
         "continue" => {
             commands::continue_session::handle_continue(&args[1..]);
         }
+        "serve" => {
+            commands::serve::handle_serve(&args[1..]);
+        }
+        "lsp" => {
+            commands::lsp::handle_lsp(&args[1..]);
+        }
+        "mcp" => {
+            commands::mcp::handle_mcp(&args[1..]);
+        }
+        "secrets" => {
+            commands::secrets::handle_secrets(&args[1..]);
+        }
         #[cfg(debug_assertions)]
         "show-transcript" => {
             handle_show_transcript(&args[1..]);
@@ -189,8 +222,9 @@ fn print_help() {
     eprintln!("Commands:");
     eprintln!("  checkpoint         Checkpoint working changes and attribute author");
     eprintln!(
-        "    Presets: claude, codex, continue-cli, cursor, gemini, github-copilot, ai_tab, mock_ai"
+        "    Presets: claude, codex, continue-cli, cursor, gemini, github-copilot, droid, opencode,"
     );
+    eprintln!("             aider, windsurf, amp, junie, rovodev, ai_tab, mock_ai");
     eprintln!(
         "    --hook-input <json|stdin>   JSON payload required by presets, or 'stdin' to read from stdin"
     );
@@ -198,11 +232,32 @@ fn print_help() {
     eprintln!("    --reset                     Reset working log");
     eprintln!("    mock_ai [pathspecs...]      Test preset accepting optional file pathspecs");
     eprintln!("  blame <file>       Git blame with AI authorship overlay");
+    eprintln!("    --tree [path]         AI coverage map of every file under a directory at HEAD");
+    eprintln!("    --no-cache            Don't read or write the blame cache in .git/ai");
+    eprintln!(
+        "    --include-uncommitted With --json, blame the working tree instead of HEAD"
+    );
     eprintln!("  diff <commit|range>  Show diff with AI authorship annotations");
     eprintln!("    <commit>              Diff from commit's parent to commit");
     eprintln!("    <commit1>..<commit2>  Diff between two commits");
+    eprintln!("    --json                Output in JSON format");
+    eprintln!("    --sarif               Output AI-authored ranges as SARIF 2.1.0");
+    eprintln!("    --github-annotations  Output GitHub Actions ::notice annotations");
     eprintln!("  stats [commit]     Show AI authorship statistics for a commit");
     eprintln!("    --json                 Output in JSON format");
+    eprintln!(
+        "  report [rev]       AI authorship report by author, directory, tool/model and week"
+    );
+    eprintln!("    --since <date>         Only commits after this date");
+    eprintln!("    --until <date>         Only commits before this date");
+    eprintln!("    --author <pattern>     Only commits by matching authors");
+    eprintln!("    --path <pathspec>      Only count matching files (repeatable)");
+    eprintln!("    --ignore <pattern>     Exclude files matching the glob (repeatable)");
+    eprintln!(
+        "    --depth <n>            Directory depth for the directory breakdown (default: 1)"
+    );
+    eprintln!("    --json                 Output in JSON format");
+    eprintln!("    --csv                  Output in CSV format");
     eprintln!("  status             Show uncommitted AI authorship status (debug)");
     eprintln!("    --json                 Output in JSON format");
     eprintln!("  show <rev|range>   Display authorship logs for a revision or range");
@@ -213,6 +268,13 @@ fn print_help() {
     );
     eprintln!("  share <id>         Share a prompt by creating a bundle");
     eprintln!("    --title <title>       Custom title for the bundle (default: auto-generated)");
+    eprintln!("  serve              Serve the CAS and bundle API from a local SQLite store");
+    eprintln!("    --listen <addr>       Address to listen on (default: 127.0.0.1:8787)");
+    eprintln!("    --db <path>           SQLite store (default: ~/.git-ai/serve/db)");
+    eprintln!("    --api-key <key>       Require this X-API-Key on every request");
+    eprintln!("    --public-url <url>    Base URL used in bundle links");
+    eprintln!("  lsp                Language server showing AI authorship in any editor (stdio)");
+    eprintln!("  mcp                MCP server for agents to query prompts and blame (stdio)");
     eprintln!("  sync-prompts       Update prompts in database to latest versions");
     eprintln!("    --since <time>        Only sync prompts updated after this time");
     eprintln!(
@@ -245,6 +307,18 @@ fn print_help() {
     eprintln!("  amend-authorship   Rewrite authorship after commit amend");
     eprintln!("    <original_commit> <amended_commit>");
     eprintln!("    --dry-run             Show what would be done without making changes");
+    eprintln!("  fsck               Validate authorship notes against the authorship/3.x format");
+    eprintln!("    --repair              Drop invalid ranges and orphaned prompt records");
+    eprintln!("    --json                Output the report as JSON");
+    eprintln!("  secrets scan       Audit prompt messages in authorship notes for secrets");
+    eprintln!("    --json                Output the report as JSON");
+    eprintln!("  remap-notes        Move notes onto commits rewritten by filter-repo/BFG");
+    eprintln!("    --commit-map <file>   Old-to-new commit map (required)");
+    eprintln!("    --path-rename <old>:<new>  Rename attested paths (repeatable)");
+    eprintln!("    --dry-run             Show what would be done without making changes");
+    eprintln!("  verify             Check authorship note signatures");
+    eprintln!("    [<rev|range>]         Commit or range to check (default: HEAD)");
+    eprintln!("    --json                Output the report as JSON");
     eprintln!("  git-path           Print the path to the underlying git executable");
     eprintln!("  upgrade            Check for updates and install if available");
     eprintln!("    --force               Reinstall latest version even if already up to date");
@@ -289,6 +363,14 @@ fn print_help() {
 }
 
 fn handle_checkpoint(args: &[String]) {
+    if args.first().map(String::as_str) == Some("aider")
+        && args
+            .iter()
+            .any(|arg| arg == AiderInstaller::CHAIN_LINT_CMD_FLAG)
+    {
+        handle_aider_lint_cmd(args);
+    }
+
     let mut repository_working_dir = std::env::current_dir()
         .unwrap()
         .to_string_lossy()
@@ -344,6 +426,9 @@ fn handle_checkpoint(args: &[String]) {
     }
 
     let mut agent_run_result = None;
+    // A human checkpoint to record before `agent_run_result`, for agents whose hook only
+    // runs after the edit
+    let mut pre_edit_run_result = None;
     // Handle preset arguments after parsing all flags
     if !args.is_empty() {
         match args[0].as_str() {
@@ -501,6 +586,101 @@ fn handle_checkpoint(args: &[String]) {
                     }
                 }
             }
+            "windsurf" => {
+                match WindsurfPreset.run(AgentCheckpointFlags {
+                    hook_input: hook_input.clone(),
+                }) {
+                    Ok(agent_run) => {
+                        if agent_run.repo_working_dir.is_some() {
+                            repository_working_dir = agent_run.repo_working_dir.clone().unwrap();
+                        }
+                        agent_run_result = Some(agent_run);
+                    }
+                    Err(e) => {
+                        eprintln!("Windsurf preset error: {}", e);
+                        std::process::exit(0);
+                    }
+                }
+            }
+            "amp" => {
+                match AmpPreset.run(AgentCheckpointFlags {
+                    hook_input: hook_input.clone(),
+                }) {
+                    Ok(agent_run) => {
+                        if agent_run.repo_working_dir.is_some() {
+                            repository_working_dir = agent_run.repo_working_dir.clone().unwrap();
+                        }
+                        agent_run_result = Some(agent_run);
+                    }
+                    Err(e) => {
+                        eprintln!("Amp preset error: {}", e);
+                        std::process::exit(0);
+                    }
+                }
+            }
+            "junie" => {
+                match JuniePreset.run(AgentCheckpointFlags {
+                    hook_input: hook_input.clone(),
+                }) {
+                    Ok(agent_run) => {
+                        if agent_run.repo_working_dir.is_some() {
+                            repository_working_dir = agent_run.repo_working_dir.clone().unwrap();
+                        }
+                        agent_run_result = Some(agent_run);
+                    }
+                    Err(e) => {
+                        eprintln!("Junie preset error: {}", e);
+                        std::process::exit(0);
+                    }
+                }
+            }
+            "rovodev" => {
+                match RovoDevPreset.run(AgentCheckpointFlags {
+                    hook_input: hook_input.clone(),
+                }) {
+                    Ok(agent_run) => {
+                        if agent_run.repo_working_dir.is_some() {
+                            repository_working_dir = agent_run.repo_working_dir.clone().unwrap();
+                        }
+                        agent_run_result = Some(agent_run);
+                    }
+                    Err(e) => {
+                        eprintln!("Rovo Dev preset error: {}", e);
+                        std::process::exit(0);
+                    }
+                }
+            }
+            "aider" => {
+                // Aider runs `git-ai checkpoint aider <file>` as its lint command
+                let aider_hook_input = hook_input.clone().unwrap_or_else(|| {
+                    let files: Vec<String> = args[1..]
+                        .iter()
+                        .filter(|arg| !arg.starts_with("--"))
+                        .cloned()
+                        .collect();
+                    AiderPreset::hook_input_from_args(
+                        std::path::Path::new(&repository_working_dir),
+                        &files,
+                    )
+                });
+                match AiderPreset.run(AgentCheckpointFlags {
+                    hook_input: Some(aider_hook_input),
+                }) {
+                    Ok(agent_run) => {
+                        if agent_run.repo_working_dir.is_some() {
+                            repository_working_dir = agent_run.repo_working_dir.clone().unwrap();
+                        }
+                        if agent_run.checkpoint_kind == CheckpointKind::AiAgent {
+                            pre_edit_run_result = AiderPreset::pre_edit_run_result(&agent_run);
+                        }
+                        agent_run_result = Some(agent_run);
+                    }
+                    Err(e) => {
+                        eprintln!("Aider preset error: {}", e);
+                        std::process::exit(0);
+                    }
+                }
+            }
             "mock_ai" => {
                 let mock_agent_id = format!(
                     "ai-thread-{}",
@@ -846,6 +1026,26 @@ fn handle_checkpoint(args: &[String]) {
     };
 
     commands::git_hook_handlers::ensure_repo_level_hooks_for_checkpoint(&repo);
+    if let Some(pre_edit) = pre_edit_run_result
+        && let Err(e) = commands::checkpoint::run(
+            &repo,
+            &default_user_name,
+            CheckpointKind::Human,
+            false,
+            false,
+            false,
+            Some(pre_edit),
+            false,
+        )
+    {
+        eprintln!("Pre-edit checkpoint failed: {}", e);
+        let context = serde_json::json!({
+            "function": "checkpoint",
+            "agent": agent_tool.clone().unwrap_or_default(),
+            "checkpoint_kind": "pre_edit"
+        });
+        observability::log_error(&e, Some(context));
+    }
     let checkpoint_result = commands::checkpoint::run(
         &repo,
         &default_user_name,
@@ -952,12 +1152,61 @@ fn handle_checkpoint(args: &[String]) {
     }
 }
 
+/// Aider's `lint-cmd` (see `AiderInstaller`): checkpoint the edited files, then run the
+/// user's own lint command on them so installing git-ai doesn't switch their linter off.
+///
+/// The checkpoint runs in a child process because it exits early on errors, and the lint
+/// command has to run regardless: its exit code is what Aider acts on.
+fn handle_aider_lint_cmd(args: &[String]) -> ! {
+    let checkpoint_args: Vec<String> = args
+        .iter()
+        .filter(|arg| *arg != AiderInstaller::CHAIN_LINT_CMD_FLAG)
+        .cloned()
+        .collect();
+    if let Err(e) = run_internal_git_ai_subcommand("checkpoint", &checkpoint_args) {
+        eprintln!("Failed to run Aider checkpoint: {}", e);
+    }
+
+    let lint_cmds = AiderInstaller::user_lint_cmds();
+    let mut exit_code = 0;
+    for file in args[1..].iter().filter(|arg| !arg.starts_with("--")) {
+        let Some(lint_cmd) = AiderInstaller::lint_cmd_for_file(&lint_cmds, file) else {
+            continue;
+        };
+        // Aider appends the file to its lint command, so do the same
+        #[cfg(windows)]
+        let status = std::process::Command::new("cmd")
+            .arg("/C")
+            .arg(format!("{} \"{}\"", lint_cmd, file))
+            .status();
+        #[cfg(not(windows))]
+        let status = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} '{}'", lint_cmd, file.replace('\'', "'\\''")))
+            .status();
+        match status {
+            Ok(status) if !status.success() => exit_code = status.code().unwrap_or(1),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to run lint command `{}`: {}", lint_cmd, e);
+                exit_code = 1;
+            }
+        }
+    }
+    std::process::exit(exit_code);
+}
+
 fn handle_ai_blame(args: &[String]) {
     if args.is_empty() {
         eprintln!("Error: blame requires a file argument");
         std::process::exit(1);
     }
 
+    if args.iter().any(|arg| arg == "--tree") {
+        commands::blame_tree::handle_blame_tree(args);
+        return;
+    }
+
     // Find the git repository from current directory
     let current_dir = env::current_dir()
         .unwrap_or_else(|_| std::path::PathBuf::from("."))
@@ -1295,7 +1544,10 @@ fn handle_show_transcript(args: &[String]) {
     if args.len() < 2 {
         eprintln!("Error: show-transcript requires agent name and path/id");
         eprintln!("Usage: git-ai show-transcript <agent> <path|id>");
-        eprintln!("  Agents: claude, codex, gemini, continue-cli, github-copilot, cursor");
+        eprintln!(
+            "  Agents: claude, codex, gemini, continue-cli, github-copilot, cursor, aider, windsurf,"
+        );
+        eprintln!("          amp, junie, rovodev");
         eprintln!("  For cursor, provide conversation_id instead of path");
         std::process::exit(1);
     }
@@ -1328,6 +1580,43 @@ fn handle_show_transcript(args: &[String]) {
                 std::process::exit(1);
             }
         },
+        "windsurf" => {
+            match WindsurfPreset::fetch_trajectory_payload(std::path::Path::new(path_or_id)) {
+                Ok(steps) => Ok(WindsurfPreset::transcript_and_model_from_trajectory(&steps)),
+                Err(e) => {
+                    eprintln!("Error loading Windsurf transcript: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        "aider" => match AiderPreset::transcript_and_model_from_aider_history(path_or_id, None) {
+            Ok((transcript, model, _session_started_at)) => Ok((transcript, model)),
+            Err(e) => {
+                eprintln!("Error loading Aider transcript: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "amp" => match AmpPreset::transcript_and_model_from_amp_thread(path_or_id) {
+            Ok((transcript, model)) => Ok((transcript, model)),
+            Err(e) => {
+                eprintln!("Error loading Amp transcript: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "junie" => match JuniePreset::transcript_and_model_from_junie_session(path_or_id) {
+            Ok((transcript, model)) => Ok((transcript, model)),
+            Err(e) => {
+                eprintln!("Error loading Junie transcript: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "rovodev" => match RovoDevPreset::transcript_and_model_from_rovodev_session(path_or_id) {
+            Ok((transcript, model)) => Ok((transcript, model)),
+            Err(e) => {
+                eprintln!("Error loading Rovo Dev transcript: {}", e);
+                std::process::exit(1);
+            }
+        },
         "continue-cli" => match ContinueCliPreset::transcript_from_continue_json(path_or_id) {
             Ok(transcript) => Ok((transcript, None)),
             Err(e) => {
@@ -4,15 +4,18 @@ use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::commands::git_hook_handlers::{
     ENV_SKIP_MANAGED_HOOKS, has_repo_hook_state, resolve_previous_non_managed_hooks_path,
 };
+use crate::commands::hooks::am_hooks;
 use crate::commands::hooks::checkout_hooks;
 use crate::commands::hooks::cherry_pick_hooks;
 use crate::commands::hooks::clone_hooks;
 use crate::commands::hooks::commit_hooks;
 use crate::commands::hooks::fetch_hooks;
+use crate::commands::hooks::format_patch_hooks;
 use crate::commands::hooks::merge_hooks;
 use crate::commands::hooks::push_hooks;
 use crate::commands::hooks::rebase_hooks;
 use crate::commands::hooks::reset_hooks;
+use crate::commands::hooks::revert_hooks;
 use crate::commands::hooks::stash_hooks;
 use crate::commands::hooks::switch_hooks;
 use crate::config;
@@ -27,13 +30,14 @@ use crate::utils::CREATE_NO_WINDOW;
 use crate::utils::debug_log;
 #[cfg(windows)]
 use crate::utils::is_interactive_terminal;
+use std::io::{Read, Write};
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
-use std::process::Command;
+use std::process::{Command, Stdio};
 #[cfg(unix)]
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::time::Instant;
@@ -106,7 +110,7 @@ pub fn handle_git(args: &[String]) {
     // and delegate directly to the real git so existing completion scripts work.
     if in_shell_completion_context() {
         let orig_args: Vec<String> = std::env::args().skip(1).collect();
-        proxy_to_git(&orig_args, true, None);
+        proxy_to_git(&orig_args, true, None, None);
         return;
     }
 
@@ -130,7 +134,7 @@ pub fn handle_git(args: &[String]) {
     // Note: clone aliases (e.g., alias.cl = clone) won't trigger clone hooks because
     // alias resolution requires a Repository object, which doesn't exist yet for clone.
     if parsed_args.command.as_deref() == Some("clone") && !parsed_args.is_help && !skip_hooks {
-        let exit_status = proxy_to_git(&parsed_args.to_invocation_vec(), false, None);
+        let exit_status = proxy_to_git(&parsed_args.to_invocation_vec(), false, None, None);
         if exit_status_was_interrupted(&exit_status) {
             exit_with_status(exit_status);
         }
@@ -163,10 +167,13 @@ pub fn handle_git(args: &[String]) {
         let child_hooks_path_override =
             resolve_child_git_hooks_path_override(&parsed_args, Some(repository));
         let git_start = Instant::now();
+        let mut git_stdout = Vec::new();
+        let capture_stdout = format_patch_hooks::needs_git_stdout(&parsed_args);
         let exit_status = proxy_to_git(
             &parsed_args.to_invocation_vec(),
             false,
             child_hooks_path_override.as_deref(),
+            capture_stdout.then_some(&mut git_stdout),
         );
         if exit_status_was_interrupted(&exit_status) {
             exit_with_status(exit_status);
@@ -174,11 +181,13 @@ pub fn handle_git(args: &[String]) {
         let git_duration = git_start.elapsed();
 
         let post_command_start = Instant::now();
+        let git_stdout = capture_stdout.then(|| String::from_utf8_lossy(&git_stdout).to_string());
         run_post_command_hooks(
             &mut command_hooks_context,
             &parsed_args,
             exit_status,
             repository,
+            git_stdout.as_deref(),
         );
         let post_command_duration = post_command_start.elapsed();
 
@@ -198,6 +207,7 @@ pub fn handle_git(args: &[String]) {
             &parsed_args.to_invocation_vec(),
             false,
             child_hooks_path_override.as_deref(),
+            None,
         )
     };
     exit_with_status(exit_status);
@@ -355,6 +365,12 @@ fn run_pre_command_hooks(
                     command_hooks_context,
                 );
             }
+            Some("revert") => {
+                revert_hooks::pre_revert_hook(parsed_args, repository, command_hooks_context);
+            }
+            Some("am") => {
+                am_hooks::pre_am_hook(parsed_args, repository);
+            }
             Some("push") => {
                 command_hooks_context.push_authorship_handle =
                     push_hooks::push_pre_command_hook(parsed_args, repository);
@@ -404,11 +420,13 @@ fn run_pre_command_hooks(
     }
 }
 
+/// `git_stdout` is what git printed, for the commands `proxy_to_git` captures it for
 fn run_post_command_hooks(
     command_hooks_context: &mut CommandHooksContext,
     parsed_args: &ParsedGitInvocation,
     exit_status: std::process::ExitStatus,
     repository: &mut Repository,
+    git_stdout: Option<&str>,
 ) {
     let _disable_hooks_guard = disable_internal_git_hooks();
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -452,6 +470,19 @@ fn run_post_command_hooks(
                 exit_status,
                 repository,
             ),
+            Some("revert") => revert_hooks::post_revert_hook(
+                command_hooks_context,
+                parsed_args,
+                exit_status,
+                repository,
+            ),
+            Some("am") => am_hooks::post_am_hook(parsed_args, exit_status, repository),
+            Some("format-patch") => format_patch_hooks::post_format_patch_hook(
+                parsed_args,
+                exit_status,
+                repository,
+                git_stdout,
+            ),
             Some("stash") => {
                 let config = config::Config::get();
 
@@ -524,6 +555,7 @@ fn command_uses_managed_hooks(command: Option<&str>) -> bool {
             "commit"
                 | "rebase"
                 | "cherry-pick"
+                | "revert"
                 | "reset"
                 | "stash"
                 | "merge"
@@ -562,10 +594,13 @@ fn resolve_child_git_hooks_path_override(
     Some(hooks_path)
 }
 
+/// Run the real git with `args`. When `stdout_sink` is given, git's stdout is still passed
+/// through to ours but a copy is kept in the sink.
 fn proxy_to_git(
     args: &[String],
     exit_on_completion: bool,
     child_hooks_path_override: Option<&str>,
+    stdout_sink: Option<&mut Vec<u8>>,
 ) -> std::process::ExitStatus {
     // debug_log(&format!("proxying to git with args: {:?}", args));
     // debug_log(&format!("prepended global args: {:?}", prepend_global(args)));
@@ -587,6 +622,9 @@ fn proxy_to_git(
             }
             cmd.args(args);
             cmd.env(ENV_SKIP_MANAGED_HOOKS, "1");
+            if stdout_sink.is_some() {
+                cmd.stdout(Stdio::piped());
+            }
             unsafe {
                 let setpgid_flag = should_setpgid;
                 cmd.pre_exec(move || {
@@ -613,6 +651,9 @@ fn proxy_to_git(
             }
             cmd.args(args);
             cmd.env(ENV_SKIP_MANAGED_HOOKS, "1");
+            if stdout_sink.is_some() {
+                cmd.stdout(Stdio::piped());
+            }
 
             #[cfg(windows)]
             {
@@ -637,6 +678,9 @@ fn proxy_to_git(
                     install_forwarding_handlers();
                 }
             }
+            if let Some(sink) = stdout_sink {
+                tee_child_stdout(&mut child, sink);
+            }
             let status = child.wait();
             match status {
                 Ok(status) => {
@@ -674,6 +718,9 @@ fn proxy_to_git(
     #[cfg(not(unix))]
     match child {
         Ok(mut child) => {
+            if let Some(sink) = stdout_sink {
+                tee_child_stdout(&mut child, sink);
+            }
             let status = child.wait();
             match status {
                 Ok(status) => {
@@ -695,6 +742,27 @@ fn proxy_to_git(
     }
 }
 
+/// Copy the child's piped stdout to ours as it arrives, keeping what was written in `sink`
+fn tee_child_stdout(child: &mut std::process::Child, sink: &mut Vec<u8>) {
+    let Some(mut child_stdout) = child.stdout.take() else {
+        return;
+    };
+    let mut stdout = std::io::stdout();
+    let mut buffer = [0u8; 8192];
+    loop {
+        match child_stdout.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                let _ = stdout.write_all(&buffer[..n]);
+                let _ = stdout.flush();
+                sink.extend_from_slice(&buffer[..n]);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+}
+
 // Exit mirroring the child's termination: same signal if signaled, else exit code
 fn exit_with_status(status: std::process::ExitStatus) -> ! {
     #[cfg(unix)]
@@ -2112,6 +2112,49 @@ fn maybe_record_cherry_pick_post_commit(repo: &mut Repository) {
     maybe_finalize_cherry_pick_batch_state(repo, true);
 }
 
+/// Rewrite authorship for a commit created by `git revert`. Git does not set REVERT_HEAD
+/// or GIT_REFLOG_ACTION for non-conflicting reverts, so detect them from the HEAD reflog
+/// and the default "This reverts commit <sha>." message. Returns false to fall back to
+/// regular post-commit handling.
+fn maybe_record_revert_post_commit(repo: &mut Repository) -> bool {
+    if !latest_head_reflog_subject(repo).is_some_and(|subject| subject.starts_with("revert: ")) {
+        return false;
+    }
+    let Ok(new_head) = repo.head().and_then(|head| head.target()) else {
+        return false;
+    };
+    let Ok(commit) = repo.find_commit(new_head.clone()) else {
+        return false;
+    };
+    let Ok(original_head) = commit.parent(0).map(|parent| parent.id()) else {
+        return false;
+    };
+    let reverted_commit = commit
+        .body()
+        .ok()
+        .and_then(|body| crate::authorship::rebase_authorship::reverted_commit_from_message(&body))
+        .and_then(|sha| repo.revparse_single(&sha).ok().map(|object| object.id()));
+    let Some(reverted_commit) = reverted_commit else {
+        return false;
+    };
+
+    let commit_author = commit_hooks::get_commit_default_author(repo, &[]);
+    repo.handle_rewrite_log_event(
+        crate::git::rewrite_log::RewriteLogEvent::revert_complete(
+            crate::git::rewrite_log::RevertCompleteEvent::new(
+                original_head,
+                new_head.clone(),
+                vec![reverted_commit],
+                vec![new_head],
+            ),
+        ),
+        commit_author,
+        false,
+        true,
+    );
+    true
+}
+
 fn is_post_commit_for_cherry_pick(repo: &Repository) -> bool {
     if repo.path().join("CHERRY_PICK_HEAD").is_file() {
         return true;
@@ -2221,6 +2264,9 @@ fn run_managed_hook(
                 maybe_record_cherry_pick_post_commit(&mut repo);
                 return 0;
             }
+            if maybe_record_revert_post_commit(&mut repo) {
+                return 0;
+            }
             if is_post_commit_amend(&repo) {
                 // For --amend, post-rewrite (amend) owns rewrite mapping.
                 // This avoids duplicate rewrite-log events while still preserving
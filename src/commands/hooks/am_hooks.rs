@@ -0,0 +1,186 @@
+use crate::authorship::patch_authorship::parse_mbox;
+use crate::authorship::rebase_authorship::walk_commits_to_base;
+use crate::commands::hooks::commit_hooks::get_commit_default_author;
+use crate::git::cli_parser::ParsedGitInvocation;
+use crate::git::repository::{Repository, resolve_command_base_dir};
+use crate::git::rewrite_log::{
+    AmAbortEvent, AmCompleteEvent, AmPatch, AmStartEvent, RewriteLogEvent,
+};
+use crate::utils::debug_log;
+use std::path::Path;
+
+pub fn pre_am_hook(parsed_args: &ParsedGitInvocation, repository: &mut Repository) {
+    debug_log("=== AM PRE-COMMAND HOOK ===");
+
+    // --continue, --skip, --abort etc. operate on the session started earlier
+    if is_am_in_progress(repository) {
+        debug_log("Continuing existing am (will read original head from log in post-hook)");
+        return;
+    }
+
+    let Ok(original_head) = repository.head().and_then(|head| head.target()) else {
+        debug_log("Could not read HEAD for new am");
+        return;
+    };
+
+    let base_dir = resolve_command_base_dir(&parsed_args.global_args).unwrap_or_default();
+    let patches = read_am_patches(&base_dir, &parsed_args.command_args);
+    debug_log(&format!(
+        "Starting new am from HEAD {}: {} patches, {} with authorship",
+        original_head,
+        patches.len(),
+        patches.iter().filter(|p| p.authorship.is_some()).count()
+    ));
+
+    let start_event = RewriteLogEvent::am_start(AmStartEvent::new(original_head, patches));
+    match repository.storage.append_rewrite_event(start_event) {
+        Ok(_) => debug_log("✓ Logged AmStart event"),
+        Err(e) => debug_log(&format!("✗ Failed to log AmStart event: {}", e)),
+    }
+}
+
+pub fn post_am_hook(
+    parsed_args: &ParsedGitInvocation,
+    exit_status: std::process::ExitStatus,
+    repository: &mut Repository,
+) {
+    debug_log("=== AM POST-COMMAND HOOK ===");
+    debug_log(&format!("Exit status: {}", exit_status));
+
+    if is_am_in_progress(repository) {
+        debug_log("⏸ am still in progress, waiting for completion (conflict)");
+        return;
+    }
+
+    let Some(start) = find_active_am_start_event(repository) else {
+        debug_log("⚠ am finished but couldn't find an active AmStart event");
+        return;
+    };
+
+    let args = &parsed_args.command_args;
+    let aborted = args.iter().any(|arg| arg == "--abort" || arg == "--quit");
+    if aborted || !exit_status.success() {
+        debug_log(&format!("✗ am aborted/failed from {}", start.original_head));
+        let abort_event = RewriteLogEvent::am_abort(AmAbortEvent::new(start.original_head));
+        match repository.storage.append_rewrite_event(abort_event) {
+            Ok(_) => debug_log("✓ Logged AmAbort event"),
+            Err(e) => debug_log(&format!("✗ Failed to log AmAbort event: {}", e)),
+        }
+        return;
+    }
+
+    let Ok(new_head) = repository.head().and_then(|head| head.target()) else {
+        debug_log("✗ Failed to read HEAD after am");
+        return;
+    };
+    if new_head == start.original_head {
+        debug_log("am resulted in no new commits");
+        return;
+    }
+
+    let new_commits = match walk_commits_to_base(repository, &new_head, &start.original_head) {
+        Ok(mut commits) => {
+            commits.reverse();
+            commits
+        }
+        Err(e) => {
+            debug_log(&format!("✗ Failed to find applied commits: {}", e));
+            return;
+        }
+    };
+    debug_log(&format!("Applied commits: {:?}", new_commits));
+
+    let commit_author = get_commit_default_author(repository, args);
+    let complete_event = RewriteLogEvent::am_complete(AmCompleteEvent::new(
+        start.original_head,
+        new_head,
+        start.patches,
+        new_commits,
+    ));
+    repository.handle_rewrite_log_event(complete_event, commit_author, false, true);
+
+    debug_log("✓ am authorship rewrite complete");
+}
+
+fn is_am_in_progress(repository: &Repository) -> bool {
+    repository
+        .path()
+        .join("rebase-apply")
+        .join("applying")
+        .exists()
+}
+
+/// Find the most recent am Start event, unless a Complete or Abort came after it
+fn find_active_am_start_event(repository: &Repository) -> Option<AmStartEvent> {
+    let events = repository.storage.read_rewrite_events().ok()?;
+
+    // Events are newest-first
+    for event in events {
+        match event {
+            RewriteLogEvent::AmComplete { .. } | RewriteLogEvent::AmAbort { .. } => return None,
+            RewriteLogEvent::AmStart { am_start } => return Some(am_start),
+            _ => continue,
+        }
+    }
+
+    None
+}
+
+/// Read the mailboxes passed to `git am`. Patches piped through stdin can't be read
+/// without consuming them, so they carry no authorship.
+fn read_am_patches(base_dir: &Path, args: &[String]) -> Vec<AmPatch> {
+    let mut patches = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg.starts_with('-') {
+            // Skip option values for flags that take a separate argument
+            if matches!(
+                arg.as_str(),
+                "--directory"
+                    | "--exclude"
+                    | "--include"
+                    | "--patch-format"
+                    | "--resolvemsg"
+                    | "--whitespace"
+                    | "--quoted-cr"
+                    | "--empty"
+            ) {
+                i += 2;
+                continue;
+            }
+            i += 1;
+            continue;
+        }
+
+        patches.extend(read_mailbox(&base_dir.join(arg)));
+        i += 1;
+    }
+
+    patches
+}
+
+/// Read an mbox file, or every message file of a Maildir / directory of patches
+fn read_mailbox(path: &Path) -> Vec<AmPatch> {
+    if !path.is_dir() {
+        return std::fs::read_to_string(path)
+            .map(|content| parse_mbox(&content))
+            .unwrap_or_default();
+    }
+
+    let mut files = Vec::new();
+    for dir in [path.join("cur"), path.join("new"), path.to_path_buf()] {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            files.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|p| p.is_file()),
+            );
+        }
+    }
+    files.sort();
+
+    files.iter().flat_map(|file| read_mailbox(file)).collect()
+}
@@ -255,7 +255,7 @@ fn parse_cherry_pick_commits(repository: &Repository, args: &[String]) -> Vec<St
 }
 
 /// Expand a commit range like A..B or A^..B into a list of commits
-fn expand_commit_range(
+pub(crate) fn expand_commit_range(
     repository: &Repository,
     range: &str,
 ) -> Result<Vec<String>, crate::error::GitAiError> {
@@ -276,7 +276,7 @@ fn expand_commit_range(
 }
 
 /// Resolve a commit reference to its full SHA
-fn resolve_commit_sha(
+pub(crate) fn resolve_commit_sha(
     repository: &Repository,
     commit_ref: &str,
 ) -> Result<String, crate::error::GitAiError> {
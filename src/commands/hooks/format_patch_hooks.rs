@@ -0,0 +1,102 @@
+use crate::authorship::patch_authorship::{build_patch_payload, embed_payload, mbox_source_commit};
+use crate::git::cli_parser::ParsedGitInvocation;
+use crate::git::repository::{Repository, resolve_command_base_dir};
+use crate::utils::debug_log;
+use std::path::Path;
+
+/// Whether the format-patch hook needs git's stdout: without `--stdout`, format-patch
+/// prints the path of every file it writes
+pub fn needs_git_stdout(parsed_args: &ParsedGitInvocation) -> bool {
+    parsed_args.command.as_deref() == Some("format-patch")
+        && !parsed_args.is_help
+        && !writes_to_stdout(&parsed_args.command_args)
+}
+
+pub fn post_format_patch_hook(
+    parsed_args: &ParsedGitInvocation,
+    exit_status: std::process::ExitStatus,
+    repository: &Repository,
+    git_stdout: Option<&str>,
+) {
+    if !exit_status.success() {
+        debug_log("format-patch failed, skipping authorship payloads");
+        return;
+    }
+
+    if writes_to_stdout(&parsed_args.command_args) {
+        eprintln!(
+            "Warning: git-ai does not embed AI authorship in `format-patch --stdout` output. Write patch files instead to carry it to `git am`."
+        );
+        return;
+    }
+
+    if parsed_args
+        .command_args
+        .iter()
+        .any(|arg| arg == "-q" || arg == "--quiet")
+    {
+        eprintln!(
+            "Warning: git-ai does not embed AI authorship with `format-patch --quiet`, which hides the patch files it wrote."
+        );
+        return;
+    }
+
+    let Ok(base_dir) = resolve_command_base_dir(&parsed_args.global_args) else {
+        return;
+    };
+
+    // Only the files this invocation wrote, which may sit next to older patches
+    let mut embedded = 0;
+    for line in git_stdout.unwrap_or_default().lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let path = base_dir.join(line);
+        let is_cover_letter = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("0000-cover-letter"));
+        if is_cover_letter {
+            continue;
+        }
+        match embed_authorship_in_patch_file(repository, &path) {
+            Ok(true) => embedded += 1,
+            Ok(false) => {}
+            Err(e) => debug_log(&format!(
+                "Failed to embed authorship in {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    debug_log(&format!(
+        "✓ Embedded authorship payloads in {} patch files",
+        embedded
+    ));
+}
+
+fn writes_to_stdout(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--stdout")
+}
+
+/// Embed the authorship payload of the patch's source commit. Returns false when the file
+/// is not a format-patch message, its commit has no authorship, or it already has a payload.
+fn embed_authorship_in_patch_file(
+    repository: &Repository,
+    path: &Path,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let Some(source_commit) = content.lines().next().and_then(mbox_source_commit) else {
+        return Ok(false);
+    };
+    let Some(payload) = build_patch_payload(repository, source_commit)? else {
+        return Ok(false);
+    };
+    let Some(patched) = embed_payload(&content, &payload) else {
+        return Ok(false);
+    };
+
+    std::fs::write(path, patched)?;
+    Ok(true)
+}
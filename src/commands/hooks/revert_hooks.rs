@@ -0,0 +1,222 @@
+use crate::authorship::rebase_authorship::walk_commits_to_base;
+use crate::commands::git_handlers::CommandHooksContext;
+use crate::commands::hooks::cherry_pick_hooks::{expand_commit_range, resolve_commit_sha};
+use crate::commands::hooks::commit_hooks::get_commit_default_author;
+use crate::git::cli_parser::ParsedGitInvocation;
+use crate::git::repository::Repository;
+use crate::git::rewrite_log::{
+    RevertAbortEvent, RevertCompleteEvent, RevertMixedEvent, RevertStartEvent, RewriteLogEvent,
+};
+use crate::utils::debug_log;
+use std::collections::BTreeSet;
+
+pub fn pre_revert_hook(
+    parsed_args: &ParsedGitInvocation,
+    repository: &mut Repository,
+    _command_hooks_context: &mut CommandHooksContext,
+) {
+    debug_log("=== REVERT PRE-COMMAND HOOK ===");
+
+    // Check if we're continuing an existing revert or starting a new one
+    let revert_in_progress = is_revert_in_progress(repository);
+    let has_active_start = has_active_revert_start_event(repository);
+    let is_continuing = revert_in_progress && has_active_start;
+
+    debug_log(&format!(
+        "Revert state: in_progress={}, has_active_start={}, is_continuing={}",
+        revert_in_progress, has_active_start, is_continuing
+    ));
+
+    if is_continuing {
+        debug_log("Continuing existing revert (will read original head from log in post-hook)");
+        return;
+    }
+
+    let Ok(original_head) = repository.head().and_then(|head| head.target()) else {
+        debug_log("Could not read HEAD for new revert");
+        return;
+    };
+
+    let reverted_commits = parse_revert_commits(repository, &parsed_args.command_args);
+    debug_log(&format!(
+        "Starting new revert from HEAD {}: {:?}",
+        original_head, reverted_commits
+    ));
+
+    let start_event =
+        RewriteLogEvent::revert_start(RevertStartEvent::new(original_head, reverted_commits));
+    match repository.storage.append_rewrite_event(start_event) {
+        Ok(_) => debug_log("✓ Logged RevertStart event"),
+        Err(e) => debug_log(&format!("✗ Failed to log RevertStart event: {}", e)),
+    }
+}
+
+pub fn post_revert_hook(
+    _context: &CommandHooksContext,
+    parsed_args: &ParsedGitInvocation,
+    exit_status: std::process::ExitStatus,
+    repository: &mut Repository,
+) {
+    debug_log("=== REVERT POST-COMMAND HOOK ===");
+    debug_log(&format!("Exit status: {}", exit_status));
+
+    let args = &parsed_args.command_args;
+    let no_commit = args.iter().any(|arg| arg == "-n" || arg == "--no-commit");
+
+    // --no-commit leaves REVERT_HEAD behind even when it succeeds
+    if is_revert_in_progress(repository) && !(no_commit && exit_status.success()) {
+        debug_log("⏸ Revert still in progress, waiting for completion (conflict or multi-step)");
+        return;
+    }
+
+    let Some(start) = find_revert_start_event(repository) else {
+        debug_log("⚠ Revert finished but couldn't find the RevertStart event");
+        return;
+    };
+
+    let aborted = args.iter().any(|arg| arg == "--abort" || arg == "--quit");
+    if aborted || !exit_status.success() {
+        debug_log(&format!(
+            "✗ Revert aborted/failed from {}",
+            start.original_head
+        ));
+        let abort_event =
+            RewriteLogEvent::revert_abort(RevertAbortEvent::new(start.original_head.clone()));
+        match repository.storage.append_rewrite_event(abort_event) {
+            Ok(_) => debug_log("✓ Logged RevertAbort event"),
+            Err(e) => debug_log(&format!("✗ Failed to log RevertAbort event: {}", e)),
+        }
+        return;
+    }
+
+    let commit_author = get_commit_default_author(repository, args);
+
+    if no_commit {
+        // The inverse changes are staged on top of HEAD; attribute them before the commit
+        let affected_files: BTreeSet<String> = start
+            .reverted_commits
+            .iter()
+            .filter_map(|commit| repository.list_commit_files(commit, None).ok())
+            .flatten()
+            .collect();
+        let mixed_event = RewriteLogEvent::revert_mixed(RevertMixedEvent::new(
+            start.original_head.clone(),
+            start.reverted_commits.clone(),
+            true,
+            affected_files.into_iter().collect(),
+        ));
+        repository.handle_rewrite_log_event(mixed_event, commit_author, false, true);
+        debug_log("✓ Revert --no-commit attributions prepared");
+        return;
+    }
+
+    let Ok(new_head) = repository.head().and_then(|head| head.target()) else {
+        debug_log("✗ Failed to read HEAD after revert");
+        return;
+    };
+    if new_head == start.original_head {
+        debug_log("Revert resulted in no changes");
+        return;
+    }
+
+    let new_commits = match walk_commits_to_base(repository, &new_head, &start.original_head) {
+        Ok(mut commits) => {
+            commits.reverse();
+            commits
+        }
+        Err(e) => {
+            debug_log(&format!("✗ Failed to find revert commits: {}", e));
+            return;
+        }
+    };
+    debug_log(&format!(
+        "Reverted commits: {:?}, new commits: {:?}",
+        start.reverted_commits, new_commits
+    ));
+
+    let complete_event = RewriteLogEvent::revert_complete(RevertCompleteEvent::new(
+        start.original_head,
+        new_head,
+        start.reverted_commits,
+        new_commits,
+    ));
+    repository.handle_rewrite_log_event(complete_event, commit_author, false, true);
+
+    debug_log("✓ Revert authorship rewrite complete");
+}
+
+fn is_revert_in_progress(repository: &Repository) -> bool {
+    let revert_head = repository.path().join("REVERT_HEAD");
+    let sequencer_dir = repository.path().join("sequencer");
+    revert_head.exists() || sequencer_dir.exists()
+}
+
+/// Check if there's an active revert Start event (not followed by Complete, Mixed or Abort)
+fn has_active_revert_start_event(repository: &Repository) -> bool {
+    let Ok(events) = repository.storage.read_rewrite_events() else {
+        return false;
+    };
+
+    // Events are newest-first
+    for event in events {
+        match event {
+            RewriteLogEvent::RevertComplete { .. }
+            | RewriteLogEvent::RevertMixed { .. }
+            | RewriteLogEvent::RevertAbort { .. } => return false,
+            RewriteLogEvent::RevertStart { .. } => return true,
+            _ => continue,
+        }
+    }
+
+    false
+}
+
+/// Find the most recent revert Start event in the log
+fn find_revert_start_event(repository: &Repository) -> Option<RevertStartEvent> {
+    let events = repository.storage.read_rewrite_events().ok()?;
+
+    // Events are newest-first
+    events.into_iter().find_map(|event| match event {
+        RewriteLogEvent::RevertStart { revert_start } => Some(revert_start),
+        _ => None,
+    })
+}
+
+/// Parse revert commit arguments
+/// Handles:
+/// - Single commit: `git revert A`
+/// - Multiple commits: `git revert A B C`
+/// - Ranges: `git revert A..C`
+fn parse_revert_commits(repository: &Repository, args: &[String]) -> Vec<String> {
+    let mut commits = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+
+        if arg.starts_with('-') {
+            // Skip option values for flags that take a separate argument
+            if matches!(
+                arg.as_str(),
+                "-m" | "--mainline" | "--strategy" | "-X" | "--strategy-option" | "--cleanup"
+            ) {
+                i += 2;
+                continue;
+            }
+            i += 1;
+            continue;
+        }
+
+        if arg.contains("..") {
+            if let Ok(expanded) = expand_commit_range(repository, arg) {
+                commits.extend(expanded);
+            }
+        } else if let Ok(resolved) = resolve_commit_sha(repository, arg) {
+            commits.push(resolved);
+        }
+
+        i += 1;
+    }
+
+    commits
+}
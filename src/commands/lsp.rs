@@ -0,0 +1,614 @@
+//! Language server for git-ai
+//!
+//! Provides `git-ai lsp`, a Language Server Protocol server over stdio so any editor with an LSP
+//! client gets the AI provenance the VS Code and IntelliJ plugins show:
+//!
+//! - `textDocument/hover`: prompt, model and human author of the AI line under the cursor
+//! - `textDocument/codeLens`: share of AI lines in each function
+//! - `git-ai/blame`: the `git-ai blame --json` document for a file
+//!
+//! Open documents are synced in full, so unsaved buffers are blamed as `--contents`.
+
+use crate::authorship::authorship_log::PromptRecord;
+use crate::authorship::prompt_utils::find_prompt_with_db_fallback;
+use crate::authorship::transcript::Message;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::find_repository_for_file;
+use crate::git::repository::Repository;
+use crate::utils::debug_log;
+use regex::Regex;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Custom request returning the blame JSON of a document
+pub const BLAME_REQUEST: &str = "git-ai/blame";
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Characters of the first user message shown in a hover
+const MAX_HOVER_PROMPT_CHARS: usize = 400;
+
+/// Lines between a function header and the `{` or `:` opening its body
+const MAX_SIGNATURE_LINES: usize = 10;
+
+/// Lines that start a function or method in common languages
+static FUNCTION_HEADERS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        // Rust, Python, Go, JavaScript/TypeScript, Kotlin, Swift
+        r#"^\s*(?:(?:pub(?:\([^)]*\))?|export|default|async|static|public|private|protected|internal|override|final|abstract|unsafe|const|extern(?:\s+"[^"]*")?|suspend|inline|open)\s+)*(?:fn|def|func|function\*?|fun)\s*[\w$<(]"#,
+        // JavaScript/TypeScript arrow functions bound to a name
+        r"^\s*(?:export\s+)?(?:const|let|var)\s+[\w$]+\s*(?::[^=]+)?=\s*(?:async\s+)?(?:\([^)]*\)|[\w$]+)\s*(?::[^=]+)?=>",
+        // Java, C#, Kotlin and TypeScript methods with modifiers
+        r"^\s*(?:(?:public|private|protected|static|final|abstract|synchronized|override|virtual|async|internal)\s+)+[\w<>\[\],.?\s]*?[\w$]+\s*\([^;]*$",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+fn is_function_header(line: &str) -> bool {
+    FUNCTION_HEADERS.iter().any(|regex| regex.is_match(line))
+}
+
+fn indentation(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+/// Last line of the indented block (Python) opened by the line `colon_line`
+fn indented_block_end(lines: &[&str], colon_line: usize, indent: usize) -> usize {
+    let mut end = colon_line;
+    for (i, line) in lines.iter().enumerate().skip(colon_line + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indentation(line) <= indent {
+            break;
+        }
+        end = i;
+    }
+    end
+}
+
+/// Last line of the function whose header is at `header`, or None for declarations without a
+/// body (trait methods, interface members, one-line arrow functions)
+fn function_end(lines: &[&str], header: usize) -> Option<usize> {
+    let indent = indentation(lines[header]);
+    let mut depth: i32 = 0;
+    let mut opened = false;
+
+    for (i, line) in lines.iter().enumerate().skip(header) {
+        if !opened {
+            if i > header + MAX_SIGNATURE_LINES || (i > header && is_function_header(line)) {
+                return None;
+            }
+            if !line.contains('{') {
+                let code = line.trim_end();
+                if code.ends_with(':') {
+                    return Some(indented_block_end(lines, i, indent));
+                }
+                if code.ends_with(';') {
+                    return None;
+                }
+                continue;
+            }
+        }
+        for c in line.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if opened && depth <= 0 {
+            return Some(i);
+        }
+    }
+
+    // Unbalanced braces: the function runs to the end of the file
+    opened.then(|| lines.len() - 1)
+}
+
+/// Line ranges [start, end] (1-indexed, inclusive) of the functions in `text`, found with
+/// per-language header patterns and brace or indentation matching
+pub fn function_ranges(text: &str) -> Vec<(u32, u32)> {
+    let lines: Vec<&str> = text.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_function_header(line))
+        .filter_map(|(header, _)| {
+            let end = function_end(&lines, header)?;
+            Some((header as u32 + 1, end as u32 + 1))
+        })
+        .collect()
+}
+
+/// Read the body of one message framed with a `Content-Length` header. Returns None at end of
+/// input.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Vec<u8>>, GitAiError> {
+    let mut content_length: Option<usize> = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = value.trim().parse().ok();
+        }
+    }
+
+    let length = content_length
+        .ok_or_else(|| GitAiError::Generic("Message without Content-Length".to_string()))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), GitAiError> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Error returned to the client for a request
+#[derive(Debug)]
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+impl From<GitAiError> for ResponseError {
+    fn from(e: GitAiError) -> Self {
+        ResponseError {
+            code: INTERNAL_ERROR,
+            message: e.to_string(),
+        }
+    }
+}
+
+fn invalid_params(message: &str) -> ResponseError {
+    ResponseError {
+        code: INVALID_PARAMS,
+        message: message.to_string(),
+    }
+}
+
+fn document_uri(params: &Value) -> Result<&str, ResponseError> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .ok_or_else(|| invalid_params("Missing textDocument.uri"))
+}
+
+fn uri_to_path(uri: &str) -> Result<PathBuf, ResponseError> {
+    url::Url::parse(uri)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .ok_or_else(|| invalid_params(&format!("Not a file URI: {}", uri)))
+}
+
+/// Markdown shown when hovering a line written with `prompt`
+fn hover_markdown(prompt_hash: &str, prompt: &PromptRecord) -> String {
+    let mut markdown = format!(
+        "**{}** · {}\n\nPrompt `{}`",
+        prompt.agent_id.tool, prompt.agent_id.model, prompt_hash
+    );
+    if let Some(human) = &prompt.human_author {
+        markdown.push_str(&format!("\n\nHuman: {}", human));
+    }
+
+    let first_user_message = prompt.messages.iter().find_map(|message| match message {
+        Message::User { text, .. } => Some(text.trim()),
+        _ => None,
+    });
+    if let Some(text) = first_user_message.filter(|text| !text.is_empty()) {
+        let mut excerpt: String = text.chars().take(MAX_HOVER_PROMPT_CHARS).collect();
+        if excerpt.len() < text.len() {
+            excerpt.push('…');
+        }
+        markdown.push_str("\n\n");
+        for line in excerpt.lines() {
+            markdown.push_str(&format!("> {}\n", line));
+        }
+    }
+    markdown
+}
+
+/// Code lens title for a function with `ai_lines` of `total_lines` written by `tools`
+fn code_lens_title(ai_lines: u32, total_lines: u32, tools: &BTreeSet<String>) -> String {
+    let percentage = ai_lines as f64 / total_lines as f64 * 100.0;
+    let mut title = format!("AI {:.0}% ({}/{} lines)", percentage, ai_lines, total_lines);
+    if !tools.is_empty() {
+        let tools: Vec<&str> = tools.iter().map(String::as_str).collect();
+        title.push_str(&format!(" · {}", tools.join(", ")));
+    }
+    title
+}
+
+/// State of one `git-ai lsp` session
+#[derive(Default)]
+pub struct LspServer {
+    /// Text of the documents open in the editor, by URI
+    documents: HashMap<String, String>,
+    /// Repositories found so far, by the directory of the file they were looked up for
+    repositories: HashMap<PathBuf, Repository>,
+}
+
+impl LspServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve messages from `reader` until the client sends `exit` or closes the stream
+    pub fn run(
+        &mut self,
+        reader: &mut impl BufRead,
+        writer: &mut impl Write,
+    ) -> Result<(), GitAiError> {
+        while let Some(body) = read_message(reader)? {
+            let response = match serde_json::from_slice::<Value>(&body) {
+                Ok(message) if message["method"] == "exit" => break,
+                Ok(message) => self.handle_message(&message),
+                Err(e) => Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": PARSE_ERROR, "message": e.to_string() },
+                })),
+            };
+            if let Some(response) = response {
+                write_message(writer, &response)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle one request or notification, returning the response for requests
+    pub fn handle_message(&mut self, message: &Value) -> Option<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let Some(id) = message.get("id") else {
+            self.handle_notification(method, params);
+            return None;
+        };
+
+        let result = match method {
+            "initialize" => Ok(Self::capabilities()),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/hover" => self.hover(params),
+            "textDocument/codeLens" => self.code_lenses(params),
+            BLAME_REQUEST => self.blame_document(params),
+            _ => Err(ResponseError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unknown method: {}", method),
+            }),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": error.code, "message": error.message },
+            }),
+        })
+    }
+
+    fn capabilities() -> Value {
+        json!({
+            "capabilities": {
+                // Full document sync
+                "textDocumentSync": { "openClose": true, "change": 1 },
+                "hoverProvider": true,
+                "codeLensProvider": { "resolveProvider": false },
+            },
+            "serverInfo": { "name": "git-ai", "version": env!("CARGO_PKG_VERSION") },
+        })
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return;
+        };
+        match method {
+            "textDocument/didOpen" => {
+                if let Some(text) = params["textDocument"]["text"].as_str() {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+            }
+            "textDocument/didChange" => {
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                if let Some(text) = text {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+            }
+            _ => {}
+        }
+    }
+
+    fn repository_for(&mut self, path: &Path) -> Result<Repository, GitAiError> {
+        let dir = path.parent().unwrap_or(path).to_path_buf();
+        if let Some(repo) = self.repositories.get(&dir) {
+            return Ok(repo.clone());
+        }
+        let repo = find_repository_for_file(&path.to_string_lossy(), None)?;
+        self.repositories.insert(dir, repo.clone());
+        Ok(repo)
+    }
+
+    /// Repository, path and blame options for the document at `uri`
+    fn blame_target(
+        &mut self,
+        uri: &str,
+        line_ranges: Vec<(u32, u32)>,
+    ) -> Result<(Repository, PathBuf, GitAiBlameOptions), ResponseError> {
+        let path = uri_to_path(uri)?;
+        let repo = self.repository_for(&path)?;
+        let options = GitAiBlameOptions {
+            line_ranges,
+            contents_data: self.documents.get(uri).map(|text| text.as_bytes().to_vec()),
+            no_output: true,
+            use_prompt_hashes_as_names: true,
+            use_cache: true,
+            // Editors show the working copy, pending AI lines included
+            include_uncommitted: true,
+            ..Default::default()
+        };
+        Ok((repo, path, options))
+    }
+
+    fn hover(&mut self, params: &Value) -> Result<Value, ResponseError> {
+        let uri = document_uri(params)?;
+        let line = params["position"]["line"]
+            .as_u64()
+            .ok_or_else(|| invalid_params("Missing position.line"))? as u32
+            + 1;
+        let (repo, path, options) = self.blame_target(uri, vec![(line, line)])?;
+
+        // Lines blame can't attribute (new files, past the end) just have no hover
+        let (line_authors, prompt_records) = match repo.blame(&path.to_string_lossy(), &options) {
+            Ok(result) => result,
+            Err(e) => {
+                debug_log(&format!("lsp: no blame for {}:{}: {}", uri, line, e));
+                return Ok(Value::Null);
+            }
+        };
+        let Some((prompt_hash, prompt)) = line_authors
+            .get(&line)
+            .and_then(|author| prompt_records.get_key_value(author))
+        else {
+            return Ok(Value::Null);
+        };
+
+        // Notes may carry prompts without their transcript
+        let prompt = match find_prompt_with_db_fallback(prompt_hash, Some(&repo)) {
+            Ok((_, stored)) if !stored.messages.is_empty() => stored,
+            _ => prompt.clone(),
+        };
+        Ok(json!({
+            "contents": { "kind": "markdown", "value": hover_markdown(prompt_hash, &prompt) },
+        }))
+    }
+
+    fn code_lenses(&mut self, params: &Value) -> Result<Value, ResponseError> {
+        let uri = document_uri(params)?;
+        let (repo, path, options) = self.blame_target(uri, Vec::new())?;
+        let text = match self.documents.get(uri) {
+            Some(text) => text.clone(),
+            None => std::fs::read_to_string(&path).map_err(GitAiError::from)?,
+        };
+
+        let (line_authors, prompt_records) = match repo.blame(&path.to_string_lossy(), &options) {
+            Ok(result) => result,
+            Err(e) => {
+                debug_log(&format!("lsp: no blame for {}: {}", uri, e));
+                return Ok(json!([]));
+            }
+        };
+
+        let mut lenses = Vec::new();
+        for (start, end) in function_ranges(&text) {
+            let mut total_lines = 0;
+            let mut ai_lines = 0;
+            let mut tools = BTreeSet::new();
+            for line in start..=end {
+                let Some(author) = line_authors.get(&line) else {
+                    continue;
+                };
+                total_lines += 1;
+                if let Some(prompt) = prompt_records.get(author) {
+                    ai_lines += 1;
+                    tools.insert(prompt.agent_id.tool.clone());
+                }
+            }
+            if total_lines == 0 {
+                continue;
+            }
+
+            let position = json!({ "line": start - 1, "character": 0 });
+            lenses.push(json!({
+                "range": { "start": position, "end": position },
+                "command": {
+                    "title": code_lens_title(ai_lines, total_lines, &tools),
+                    "command": "",
+                },
+            }));
+        }
+        Ok(Value::Array(lenses))
+    }
+
+    fn blame_document(&mut self, params: &Value) -> Result<Value, ResponseError> {
+        let uri = document_uri(params)?;
+        let (repo, path, options) = self.blame_target(uri, Vec::new())?;
+        Ok(repo.blame_json(&path.to_string_lossy(), &options)?)
+    }
+}
+
+pub fn handle_lsp(args: &[String]) {
+    for arg in args {
+        match arg.as_str() {
+            // Passed by some clients when launching the server
+            "--stdio" => {}
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                print_usage();
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    if let Err(e) = LspServer::new().run(&mut stdin.lock(), &mut stdout.lock()) {
+        eprintln!("lsp failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: git-ai lsp [--stdio]");
+    eprintln!();
+    eprintln!("Run a Language Server Protocol server on stdin/stdout that shows AI authorship:");
+    eprintln!("hovers with the prompt behind a line, code lenses with the AI share of each");
+    eprintln!(
+        "function and a '{}' request returning the blame JSON of a document.",
+        BLAME_REQUEST
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::working_log::AgentId;
+
+    #[test]
+    fn test_function_ranges_brace_languages() {
+        let text = "use std::fmt;\n\
+                    \n\
+                    pub fn add(a: u32, b: u32) -> u32 {\n\
+                    \x20   a + b\n\
+                    }\n\
+                    \n\
+                    trait Shape {\n\
+                    \x20   fn area(&self) -> f64;\n\
+                    }\n\
+                    \n\
+                    async fn fetch(\n\
+                    \x20   url: &str,\n\
+                    ) -> String {\n\
+                    \x20   if url.is_empty() {\n\
+                    \x20       return String::new();\n\
+                    \x20   }\n\
+                    \x20   url.to_string()\n\
+                    }\n";
+        assert_eq!(function_ranges(text), vec![(3, 5), (11, 18)]);
+    }
+
+    #[test]
+    fn test_function_ranges_indented_languages() {
+        let text = "class Greeter:\n\
+                    \x20   def greet(self, name):\n\
+                    \x20       message = f\"Hi {name}\"\n\
+                    \n\
+                    \x20       return message\n\
+                    \n\
+                    \x20   def bye(self):\n\
+                    \x20       pass\n\
+                    \n\
+                    value = 1\n";
+        assert_eq!(function_ranges(text), vec![(2, 5), (7, 8)]);
+    }
+
+    #[test]
+    fn test_message_framing_round_trip() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" });
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message).unwrap();
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut reader = std::io::BufReader::new(buffer.as_slice());
+        for _ in 0..2 {
+            let body = read_message(&mut reader).unwrap().unwrap();
+            assert_eq!(serde_json::from_slice::<Value>(&body).unwrap(), message);
+        }
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_malformed_message_gets_parse_error_and_server_keeps_serving() {
+        let mut input = b"Content-Length: 9\r\n\r\n{\"jsonrpc".to_vec();
+        write_message(
+            &mut input,
+            &json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" }),
+        )
+        .unwrap();
+
+        let mut output = Vec::new();
+        LspServer::new()
+            .run(&mut std::io::BufReader::new(input.as_slice()), &mut output)
+            .unwrap();
+
+        let mut reader = std::io::BufReader::new(output.as_slice());
+        let parse_error: Value =
+            serde_json::from_slice(&read_message(&mut reader).unwrap().unwrap()).unwrap();
+        assert_eq!(parse_error["error"]["code"], PARSE_ERROR);
+        assert_eq!(parse_error["id"], Value::Null);
+        let shutdown: Value =
+            serde_json::from_slice(&read_message(&mut reader).unwrap().unwrap()).unwrap();
+        assert_eq!(shutdown["id"], 1);
+        assert_eq!(shutdown["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_hover_markdown_and_lens_title() {
+        let prompt = PromptRecord {
+            agent_id: AgentId {
+                tool: "claude".to_string(),
+                id: "session".to_string(),
+                model: "sonnet".to_string(),
+            },
+            human_author: Some("Ada <ada@example.com>".to_string()),
+            messages: vec![Message::user("Add a parser\nwith tests".to_string(), None)],
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+        };
+        assert_eq!(
+            hover_markdown("abc123", &prompt),
+            "**claude** · sonnet\n\nPrompt `abc123`\n\nHuman: Ada <ada@example.com>\n\n\
+             > Add a parser\n> with tests\n"
+        );
+
+        let tools = BTreeSet::from(["claude".to_string()]);
+        assert_eq!(code_lens_title(2, 3, &tools), "AI 67% (2/3 lines) · claude");
+        assert_eq!(code_lens_title(0, 4, &BTreeSet::new()), "AI 0% (0/4 lines)");
+    }
+}
@@ -0,0 +1,481 @@
+//! Model Context Protocol server for git-ai
+//!
+//! Provides `git-ai mcp`, an MCP server over stdio (newline-delimited JSON-RPC) that lets an
+//! agent session query the prompts and authorship earlier sessions recorded in the repository
+//! of the current directory, instead of the user copying context in with `git-ai continue`.
+//!
+//! Tools:
+//! - `search_prompts`: `git-ai search` by commit, file, pattern or prompt ID
+//! - `blame_lines`: `git-ai blame --json` for a file or line range
+//! - `show_prompt`: `git-ai show-prompt`, including the transcript
+//! - `commit_ai_stats`: `git-ai stats --json` for a commit
+
+use crate::authorship::stats::stats_for_commit_stats;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::commands::search::{
+    SearchFilters, SearchMode, apply_filters, format_json, search_by_commit,
+    search_by_commit_range, search_by_file, search_by_pattern, search_by_prompt_id,
+};
+use crate::commands::show_prompt::show_prompt;
+use crate::error::GitAiError;
+use crate::git::find_repository_in_path;
+use crate::git::repository::Repository;
+use serde_json::{Value, json};
+use std::io::{BufRead, Write};
+
+/// Protocol versions this server speaks, newest last
+const PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+const INSTRUCTIONS: &str = "Prompts and authorship recorded by git-ai for this repository. \
+Use blame_lines to find which prompt wrote some code, show_prompt to read that prompt's \
+transcript, and search_prompts or commit_ai_stats to explore a commit's AI history.";
+
+/// Schema and description of every tool, as returned by `tools/list`
+fn tool_definitions() -> Value {
+    let line_range = json!({
+        "start_line": {
+            "type": "integer",
+            "minimum": 1,
+            "description": "First line (1-indexed) to look at",
+        },
+        "end_line": {
+            "type": "integer",
+            "minimum": 1,
+            "description": "Last line (inclusive), defaults to start_line",
+        },
+    });
+    let mut blame_properties = json!({
+        "file": {
+            "type": "string",
+            "description": "File path relative to the repository root",
+        },
+    });
+    let mut search_properties = json!({
+        "commit": {
+            "type": "string",
+            "description": "Commit, branch or tag to search, or a range like main..HEAD",
+        },
+        "file": {
+            "type": "string",
+            "description": "File path relative to the repository root",
+        },
+        "pattern": {
+            "type": "string",
+            "description": "Text to look for in prompt transcripts",
+        },
+        "prompt_id": { "type": "string", "description": "Prompt hash to look up" },
+        "tool": { "type": "string", "description": "Only prompts from this agent, e.g. claude" },
+        "author": {
+            "type": "string",
+            "description": "Only prompts from humans whose name contains this text",
+        },
+    });
+    for properties in [&mut blame_properties, &mut search_properties] {
+        properties
+            .as_object_mut()
+            .unwrap()
+            .extend(line_range.as_object().unwrap().clone());
+    }
+
+    json!([
+        {
+            "name": "search_prompts",
+            "description": "Find the AI prompt sessions behind a commit, a commit range, a file \
+                (optionally a line range), a transcript text pattern or a prompt ID. Give exactly \
+                one of commit, file, pattern or prompt_id. Returns the prompts with their \
+                transcripts, files, line ranges and commits as JSON.",
+            "inputSchema": { "type": "object", "properties": search_properties },
+        },
+        {
+            "name": "blame_lines",
+            "description": "Which AI prompt wrote each line of a file in the working tree. Returns \
+                line ranges mapped to prompt hashes, and the prompts (agent, model, human author, \
+                transcript, commits, other files touched) as JSON. Lines missing from the result \
+                were written by humans.",
+            "inputSchema": {
+                "type": "object",
+                "properties": blame_properties,
+                "required": ["file"],
+            },
+        },
+        {
+            "name": "show_prompt",
+            "description": "The full record of one prompt session, including its transcript, by \
+                prompt hash. Use this to learn why code was written the way it was.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "prompt_id": { "type": "string", "description": "Prompt hash" },
+                    "commit": {
+                        "type": "string",
+                        "description": "Only look in this commit",
+                    },
+                    "offset": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Skip this many more recent occurrences (default 0)",
+                    },
+                },
+                "required": ["prompt_id"],
+            },
+        },
+        {
+            "name": "commit_ai_stats",
+            "description": "How many lines of a commit were written by AI, by humans, or by AI \
+                and then edited, broken down by agent and model.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "commit": { "type": "string", "description": "Commit to measure (default HEAD)" },
+                    "ignore": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Glob patterns of files to leave out, e.g. *.lock",
+                    },
+                },
+            },
+        },
+    ])
+}
+
+fn string_arg(args: &Value, name: &str) -> Option<String> {
+    args[name].as_str().map(str::to_string)
+}
+
+fn u32_arg(args: &Value, name: &str) -> Result<Option<u32>, GitAiError> {
+    match &args[name] {
+        Value::Null => Ok(None),
+        value => value
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| GitAiError::Generic(format!("{} must be a non-negative integer", name))),
+    }
+}
+
+/// The line range given with `start_line` and `end_line`, if any
+fn line_ranges_arg(args: &Value) -> Result<Vec<(u32, u32)>, GitAiError> {
+    match (u32_arg(args, "start_line")?, u32_arg(args, "end_line")?) {
+        (Some(start), end) => Ok(vec![(start, end.unwrap_or(start))]),
+        (None, Some(_)) => Err(GitAiError::Generic(
+            "end_line requires start_line".to_string(),
+        )),
+        (None, None) => Ok(Vec::new()),
+    }
+}
+
+fn pretty(value: &Value) -> Result<String, GitAiError> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+/// State of one `git-ai mcp` session
+#[derive(Default)]
+pub struct McpServer {
+    repository: Option<Repository>,
+}
+
+impl McpServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve newline-delimited messages from `reader` until it is closed
+    pub fn run(
+        &mut self,
+        reader: &mut impl BufRead,
+        writer: &mut impl Write,
+    ) -> Result<(), GitAiError> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle_message(&message),
+                Err(e) => Some(json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": PARSE_ERROR, "message": e.to_string() },
+                })),
+            };
+            if let Some(response) = response {
+                writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle one request or notification, returning the response for requests
+    pub fn handle_message(&mut self, message: &Value) -> Option<Value> {
+        // Notifications (initialized, cancelled) need no answer
+        let id = message.get("id")?;
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+
+        let result = match method {
+            "initialize" => Ok(Self::initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(params),
+            _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        })
+    }
+
+    fn initialize(params: &Value) -> Value {
+        let requested = params["protocolVersion"].as_str().unwrap_or_default();
+        let version = PROTOCOL_VERSIONS
+            .iter()
+            .find(|version| **version == requested)
+            .or(PROTOCOL_VERSIONS.last())
+            .unwrap();
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": "git-ai", "version": env!("CARGO_PKG_VERSION") },
+            "instructions": INSTRUCTIONS,
+        })
+    }
+
+    /// Run a tool. Failures of the tool itself are results with `isError` so the agent sees
+    /// them; only unknown tools are protocol errors.
+    fn call_tool(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params["name"].as_str().unwrap_or_default();
+        let args = &params["arguments"];
+        let output = match name {
+            "search_prompts" => self.search_prompts(args),
+            "blame_lines" => self.blame_lines(args),
+            "show_prompt" => self.show_prompt(args),
+            "commit_ai_stats" => self.commit_ai_stats(args),
+            _ => return Err((INVALID_PARAMS, format!("Unknown tool: {}", name))),
+        };
+
+        let (text, is_error) = match output {
+            Ok(text) => (text, false),
+            Err(e) => (e.to_string(), true),
+        };
+        Ok(json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        }))
+    }
+
+    fn repository(&mut self) -> Result<Repository, GitAiError> {
+        if let Some(repo) = &self.repository {
+            return Ok(repo.clone());
+        }
+        let current_dir = std::env::current_dir()?.to_string_lossy().to_string();
+        let repo = find_repository_in_path(&current_dir)?;
+        self.repository = Some(repo.clone());
+        Ok(repo)
+    }
+
+    fn search_prompts(&mut self, args: &Value) -> Result<String, GitAiError> {
+        let repo = self.repository()?;
+        let mode = if let Some(commit) = string_arg(args, "commit") {
+            match commit.split_once("..") {
+                Some((start, end)) => SearchMode::CommitRange {
+                    start: start.to_string(),
+                    end: end.to_string(),
+                },
+                None => SearchMode::Commit { commit_rev: commit },
+            }
+        } else if let Some(file_path) = string_arg(args, "file") {
+            SearchMode::File {
+                file_path,
+                line_ranges: line_ranges_arg(args)?,
+            }
+        } else if let Some(query) = string_arg(args, "pattern") {
+            SearchMode::Pattern { query }
+        } else if let Some(prompt_id) = string_arg(args, "prompt_id") {
+            SearchMode::PromptId { prompt_id }
+        } else {
+            return Err(GitAiError::Generic(
+                "Give one of commit, file, pattern or prompt_id".to_string(),
+            ));
+        };
+
+        let result = match &mode {
+            SearchMode::Commit { commit_rev } => search_by_commit(&repo, commit_rev)?,
+            SearchMode::CommitRange { start, end } => search_by_commit_range(&repo, start, end)?,
+            SearchMode::File {
+                file_path,
+                line_ranges,
+            } => search_by_file(&repo, file_path, line_ranges)?,
+            SearchMode::Pattern { query } => search_by_pattern(query)?,
+            SearchMode::PromptId { prompt_id } => search_by_prompt_id(&repo, prompt_id)?,
+        };
+        let filters = SearchFilters {
+            tool: string_arg(args, "tool"),
+            author: string_arg(args, "author"),
+            ..Default::default()
+        };
+        Ok(format_json(&apply_filters(result, &filters), &mode))
+    }
+
+    fn blame_lines(&mut self, args: &Value) -> Result<String, GitAiError> {
+        let repo = self.repository()?;
+        let file = string_arg(args, "file")
+            .ok_or_else(|| GitAiError::Generic("file is required".to_string()))?;
+        let options = GitAiBlameOptions {
+            line_ranges: line_ranges_arg(args)?,
+            use_cache: true,
+            ..Default::default()
+        };
+        pretty(&repo.blame_json(&file, &options)?)
+    }
+
+    fn show_prompt(&mut self, args: &Value) -> Result<String, GitAiError> {
+        let repo = self.repository()?;
+        let prompt_id = string_arg(args, "prompt_id")
+            .ok_or_else(|| GitAiError::Generic("prompt_id is required".to_string()))?;
+        let commit = string_arg(args, "commit");
+        let offset = u32_arg(args, "offset")?.unwrap_or(0) as usize;
+        pretty(&show_prompt(&repo, &prompt_id, commit.as_deref(), offset)?)
+    }
+
+    fn commit_ai_stats(&mut self, args: &Value) -> Result<String, GitAiError> {
+        let repo = self.repository()?;
+        let rev = string_arg(args, "commit").unwrap_or_else(|| "HEAD".to_string());
+        let commit_sha = repo.revparse_single(&rev)?.id();
+        let ignore_patterns: Vec<String> = args["ignore"]
+            .as_array()
+            .map(|patterns| {
+                patterns
+                    .iter()
+                    .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let stats = stats_for_commit_stats(&repo, &commit_sha, &ignore_patterns)?;
+        pretty(&json!({ "commit": commit_sha, "stats": stats }))
+    }
+}
+
+pub fn handle_mcp(args: &[String]) {
+    if let Some(arg) = args.first() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                print_usage();
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    if let Err(e) = McpServer::new().run(&mut stdin.lock(), &mut stdout.lock()) {
+        eprintln!("mcp failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: git-ai mcp");
+    eprintln!();
+    eprintln!("Run a Model Context Protocol server on stdin/stdout for the repository in the");
+    eprintln!("current directory, with the tools search_prompts, blame_lines, show_prompt and");
+    eprintln!("commit_ai_stats.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_negotiates_protocol_version() {
+        let mut server = McpServer::new();
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05" },
+            }))
+            .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "initialize",
+                "params": { "protocolVersion": "1999-01-01" },
+            }))
+            .unwrap();
+        assert_eq!(
+            response["result"]["protocolVersion"],
+            *PROTOCOL_VERSIONS.last().unwrap()
+        );
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert_eq!(server.handle_message(&notification), None);
+    }
+
+    #[test]
+    fn test_tools_list_and_unknown_tool() {
+        let mut server = McpServer::new();
+        let response = server
+            .handle_message(&json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }))
+            .unwrap();
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "search_prompts",
+                "blame_lines",
+                "show_prompt",
+                "commit_ai_stats"
+            ]
+        );
+
+        let response = server
+            .handle_message(&json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": { "name": "missing", "arguments": {} },
+            }))
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_line_ranges_arg() {
+        assert_eq!(line_ranges_arg(&json!({})).unwrap(), vec![]);
+        assert_eq!(
+            line_ranges_arg(&json!({ "start_line": 3 })).unwrap(),
+            vec![(3, 3)]
+        );
+        assert_eq!(
+            line_ranges_arg(&json!({ "start_line": 3, "end_line": 7 })).unwrap(),
+            vec![(3, 7)]
+        );
+        assert!(line_ranges_arg(&json!({ "end_line": 7 })).is_err());
+        assert!(line_ranges_arg(&json!({ "start_line": -1 })).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 pub mod blame;
+pub mod blame_cache;
+pub mod blame_tree;
 pub mod checkpoint;
 pub mod checkpoint_agent;
 pub mod ci_handlers;
@@ -7,6 +9,7 @@ pub mod continue_session;
 pub mod diff;
 pub mod exchange_nonce;
 pub mod flush_cas;
+pub mod fsck;
 pub mod flush_logs;
 pub mod flush_metrics_db;
 pub mod git_ai_handlers;
@@ -16,10 +19,16 @@ pub mod hooks;
 pub mod install_hooks;
 pub mod login;
 pub mod logout;
+pub mod lsp;
+pub mod mcp;
 pub mod personal_dashboard;
 pub mod prompt_picker;
 pub mod prompts_db;
+pub mod remap_notes;
+pub mod report;
 pub mod search;
+pub mod secrets;
+pub mod serve;
 pub mod share;
 pub mod share_tui;
 pub mod show;
@@ -31,3 +40,4 @@ pub mod cherry_pick_authorship_cmd;
 pub mod amend_authorship_cmd;
 pub mod sync_prompts;
 pub mod upgrade;
+pub mod verify;
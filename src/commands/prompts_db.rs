@@ -920,7 +920,7 @@ fn get_commits_with_notes_since(repo: &Repository, since_timestamp: i64) -> Vec<
     let commit_set: HashSet<String> = commits_since.into_iter().collect();
 
     // Step 2: Get all notes mappings (note_blob_sha, commit_sha)
-    let note_mappings = get_notes_list(&global_args);
+    let note_mappings = get_notes_list(&global_args, repo.notes_namespace());
 
     // Step 3: Filter to notes for commits in our time range
     let filtered: Vec<(String, String)> = note_mappings
@@ -966,11 +966,11 @@ fn get_commits_since(global_args: &[String], since_timestamp: i64) -> Vec<String
         .collect()
 }
 
-/// Get all notes as (note_blob_sha, commit_sha) pairs
-fn get_notes_list(global_args: &[String]) -> Vec<(String, String)> {
+/// Get all notes in `namespace` as (note_blob_sha, commit_sha) pairs
+fn get_notes_list(global_args: &[String], namespace: &str) -> Vec<(String, String)> {
     let mut args = global_args.to_vec();
     args.push("notes".to_string());
-    args.push("--ref=ai".to_string());
+    args.push(format!("--ref={}", namespace));
     args.push("list".to_string());
 
     let output = match exec_git(&args) {
@@ -0,0 +1,479 @@
+//! Remap command for git-ai
+//!
+//! Provides `git-ai remap-notes`, which moves authorship notes onto the rewritten
+//! commits after a history rewrite such as `git filter-repo` or BFG.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::authorship_traversal::load_authorship_logs_for_commits;
+use crate::git::find_repository_in_path;
+use crate::git::refs::{note_blob_oids_for_commits, notes_add_batch, notes_add_blob_batch};
+use crate::git::repository::{Repository, exec_git_stdin};
+use std::collections::HashSet;
+
+/// A `--path-rename OLD:NEW` rule, matching filter-repo's option of the same name
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathRename {
+    pub old: String,
+    pub new: String,
+}
+
+impl PathRename {
+    pub fn parse(spec: &str) -> Result<Self, GitAiError> {
+        match spec.split_once(':') {
+            Some((old, new)) if !old.is_empty() => Ok(Self {
+                old: old.to_string(),
+                new: new.to_string(),
+            }),
+            _ => Err(GitAiError::Generic(format!(
+                "Invalid --path-rename '{}', expected OLD:NEW",
+                spec
+            ))),
+        }
+    }
+
+    /// Rename `path` if it is `old` or lies under the `old` directory
+    fn apply(&self, path: &str) -> Option<String> {
+        if path == self.old {
+            return Some(self.new.clone());
+        }
+        let rest = if self.old.ends_with('/') {
+            path.strip_prefix(&self.old)?
+        } else {
+            path.strip_prefix(&self.old)?.strip_prefix('/')?
+        };
+        let separator = if self.new.is_empty() || self.new.ends_with('/') || self.old.ends_with('/')
+        {
+            ""
+        } else {
+            "/"
+        };
+        Some(format!("{}{}{}", self.new, separator, rest))
+    }
+}
+
+/// Apply the first matching rename rule to `path`
+fn rename_path(renames: &[PathRename], path: &str) -> String {
+    renames
+        .iter()
+        .find_map(|rename| rename.apply(path))
+        .unwrap_or_else(|| path.to_string())
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RemapSummary {
+    /// Notes attached to rewritten commits unchanged
+    pub copied: usize,
+    /// Notes rewritten because of path renames or removed files
+    pub rewritten: usize,
+    /// File attestations dropped because the file no longer exists
+    pub dropped_files: usize,
+    /// Mapped commits whose original had no note
+    pub without_notes: usize,
+    /// Commits filtered out of history entirely
+    pub pruned: usize,
+}
+
+/// Parse a filter-repo `commit-map` or a generic "old new" mapping (one pair per line).
+///
+/// Header lines, comments and commits that were pruned from history (mapped to the
+/// null sha) are skipped; the count of pruned commits is returned alongside the pairs.
+pub fn parse_commit_map(content: &str) -> Result<(Vec<(String, String)>, usize), GitAiError> {
+    let is_sha =
+        |s: &str| (s.len() == 40 || s.len() == 64) && s.chars().all(|c| c.is_ascii_hexdigit());
+
+    let mut pairs = Vec::new();
+    let mut pruned = 0;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields == ["old", "new"] {
+            continue;
+        }
+        let [old, new] = fields.as_slice() else {
+            return Err(GitAiError::Generic(format!(
+                "Invalid commit map line {}: '{}'",
+                index + 1,
+                line
+            )));
+        };
+        if !is_sha(old) || !is_sha(new) {
+            return Err(GitAiError::Generic(format!(
+                "Invalid commit map line {}: '{}'",
+                index + 1,
+                line
+            )));
+        }
+        if new.chars().all(|c| c == '0') {
+            pruned += 1;
+            continue;
+        }
+        if old != new {
+            pairs.push((old.to_lowercase(), new.to_lowercase()));
+        }
+    }
+
+    Ok((pairs, pruned))
+}
+
+/// Check which `<commit>:<path>` entries exist with one batched `cat-file --batch-check`
+fn existing_paths(
+    repo: &Repository,
+    entries: &[(String, String)],
+) -> Result<HashSet<(String, String)>, GitAiError> {
+    if entries.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("cat-file".to_string());
+    args.push("--batch-check".to_string());
+
+    let mut stdin_data = String::new();
+    for (commit_sha, path) in entries {
+        stdin_data.push_str(&format!("{}:{}\n", commit_sha, path));
+    }
+
+    let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    Ok(entries
+        .iter()
+        .zip(stdout.lines())
+        .filter(|(_, line)| !line.ends_with(" missing"))
+        .map(|(entry, _)| entry.clone())
+        .collect())
+}
+
+/// Rename attested paths (including `line_states` keys) and drop files missing from
+/// `new_commit`. Prompts left without any attestation are dropped too.
+fn remap_authorship_log(
+    authorship_log: &AuthorshipLog,
+    new_commit: &str,
+    renames: &[PathRename],
+    existing: &HashSet<(String, String)>,
+) -> (AuthorshipLog, usize) {
+    let mut remapped = authorship_log.clone();
+    remapped.metadata.base_commit_sha = new_commit.to_string();
+
+    let before = remapped.attestations.len();
+    for file in remapped.attestations.iter_mut() {
+        file.file_path = rename_path(renames, &file.file_path);
+    }
+    remapped
+        .attestations
+        .retain(|file| existing.contains(&(new_commit.to_string(), file.file_path.clone())));
+    let dropped = before - remapped.attestations.len();
+    remapped.metadata.line_states = std::mem::take(&mut remapped.metadata.line_states)
+        .into_iter()
+        .map(|(path, states)| (rename_path(renames, &path), states))
+        .filter(|(path, _)| existing.contains(&(new_commit.to_string(), path.clone())))
+        .collect();
+
+    if dropped > 0 {
+        let referenced_before = referenced_hashes(authorship_log);
+        let attested = referenced_hashes(&remapped);
+        remapped
+            .metadata
+            .prompts
+            .retain(|hash, _| attested.contains(hash) || !referenced_before.contains(hash));
+    }
+
+    (remapped, dropped)
+}
+
+/// Prompt hashes referenced by attestations or line states
+fn referenced_hashes(log: &AuthorshipLog) -> HashSet<String> {
+    log.attestations
+        .iter()
+        .flat_map(|file| file.entries.iter().map(|entry| entry.hash.clone()))
+        .chain(
+            log.metadata
+                .line_states
+                .values()
+                .flat_map(|states| states.iter().map(|state| state.hash.clone())),
+        )
+        .collect()
+}
+
+/// Move notes from the old commits onto their rewritten counterparts.
+///
+/// Notes that need no change keep their blob and are attached with
+/// `notes_add_blob_batch`; the rest are re-serialized with renamed paths.
+pub fn remap_notes(
+    repo: &Repository,
+    commit_map: &[(String, String)],
+    renames: &[PathRename],
+    dry_run: bool,
+) -> Result<RemapSummary, GitAiError> {
+    let mut summary = RemapSummary::default();
+
+    let old_commits: Vec<String> = commit_map.iter().map(|(old, _)| old.clone()).collect();
+    let note_blobs = note_blob_oids_for_commits(repo, &old_commits)?;
+    let logs = load_authorship_logs_for_commits(repo, &old_commits)?;
+
+    let mut path_checks = Vec::new();
+    for (old, new) in commit_map {
+        if let Some(log) = logs.get(old) {
+            for file in &log.attestations {
+                path_checks.push((new.clone(), rename_path(renames, &file.file_path)));
+            }
+            for path in log.metadata.line_states.keys() {
+                path_checks.push((new.clone(), rename_path(renames, path)));
+            }
+        }
+    }
+    let existing = existing_paths(repo, &path_checks)?;
+
+    let mut blob_entries = Vec::new();
+    let mut content_entries = Vec::new();
+    for (old, new) in commit_map {
+        let Some(blob_oid) = note_blobs.get(old) else {
+            summary.without_notes += 1;
+            continue;
+        };
+        let Some(log) = logs.get(old) else {
+            // Unparseable note: carry it over untouched
+            blob_entries.push((new.clone(), blob_oid.clone()));
+            summary.copied += 1;
+            continue;
+        };
+
+        let (remapped, dropped) = remap_authorship_log(log, new, renames, &existing);
+        summary.dropped_files += dropped;
+        if remapped.attestations == log.attestations
+            && remapped.metadata.line_states == log.metadata.line_states
+        {
+            blob_entries.push((new.clone(), blob_oid.clone()));
+            summary.copied += 1;
+        } else if remapped.attestations.is_empty() && remapped.metadata.prompts.is_empty() {
+            continue;
+        } else {
+            let content = remapped.serialize_to_string().map_err(|_| {
+                GitAiError::Generic("Failed to serialize authorship log".to_string())
+            })?;
+            content_entries.push((new.clone(), content));
+            summary.rewritten += 1;
+        }
+    }
+
+    if !dry_run {
+        notes_add_blob_batch(repo, &blob_entries)?;
+        notes_add_batch(repo, &content_entries)?;
+    }
+
+    Ok(summary)
+}
+
+pub fn handle_remap_notes(args: &[String]) {
+    let mut commit_map_path = None;
+    let mut renames = Vec::new();
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--commit-map" => {
+                commit_map_path = args.get(i + 1).cloned();
+                i += 2;
+            }
+            "--path-rename" => {
+                let Some(spec) = args.get(i + 1) else {
+                    eprintln!("Error: --path-rename requires OLD:NEW");
+                    std::process::exit(1);
+                };
+                match PathRename::parse(spec) {
+                    Ok(rename) => renames.push(rename),
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                i += 2;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            arg => {
+                eprintln!("Unknown argument: {}", arg);
+                print_usage();
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(commit_map_path) = commit_map_path else {
+        eprintln!("Error: --commit-map <file> is required");
+        print_usage();
+        std::process::exit(1);
+    };
+
+    let content = match std::fs::read_to_string(&commit_map_path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", commit_map_path, e);
+            std::process::exit(1);
+        }
+    };
+    let (commit_map, pruned) = match parse_commit_map(&content) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository_in_path(".") {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut summary = match remap_notes(&repo, &commit_map, &renames, dry_run) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("Remapping notes failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+    summary.pruned = pruned;
+
+    let prefix = if dry_run {
+        "DRY RUN: would remap"
+    } else {
+        "✓ Remapped"
+    };
+    println!(
+        "{} {} notes ({} copied, {} rewritten)",
+        prefix,
+        summary.copied + summary.rewritten,
+        summary.copied,
+        summary.rewritten
+    );
+    println!(
+        "  {} file attestations dropped for removed files",
+        summary.dropped_files
+    );
+    println!("  {} rewritten commits had no note", summary.without_notes);
+    println!("  {} commits pruned from history", summary.pruned);
+}
+
+fn print_usage() {
+    eprintln!(
+        "Usage: git-ai remap-notes --commit-map <file> [--path-rename <old>:<new>]... [--dry-run]"
+    );
+    eprintln!();
+    eprintln!("Arguments:");
+    eprintln!(
+        "  --commit-map <file>       filter-repo commit-map, or any file of \"<old> <new>\" lines"
+    );
+    eprintln!(
+        "  --path-rename <old>:<new> Rename attested paths, as with filter-repo (repeatable)"
+    );
+    eprintln!("  --dry-run                 Show what would be done without making changes");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::LineRange;
+    use crate::authorship::authorship_log_serialization::{
+        AttestationEntry, LineState, LineStateEntry,
+    };
+
+    const OLD_A: &str = "1111111111111111111111111111111111111111";
+    const NEW_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const OLD_B: &str = "2222222222222222222222222222222222222222";
+
+    #[test]
+    fn test_parse_filter_repo_commit_map() {
+        let content = format!(
+            "old                                      new\n{} {}\n{} {}\n\n",
+            OLD_A,
+            NEW_A,
+            OLD_B,
+            "0".repeat(40)
+        );
+        let (pairs, pruned) = parse_commit_map(&content).unwrap();
+        assert_eq!(pairs, vec![(OLD_A.to_string(), NEW_A.to_string())]);
+        assert_eq!(pruned, 1);
+    }
+
+    #[test]
+    fn test_parse_commit_map_rejects_malformed_lines() {
+        let err = parse_commit_map(&format!("{}\n", OLD_A)).unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{}", err);
+        assert!(parse_commit_map("abc def\n").is_err());
+    }
+
+    #[test]
+    fn test_path_rename() {
+        let dir = PathRename::parse("src/:lib/").unwrap();
+        assert_eq!(dir.apply("src/main.rs"), Some("lib/main.rs".to_string()));
+        assert_eq!(dir.apply("srcs/main.rs"), None);
+
+        let bare_dir = PathRename::parse("src:core").unwrap();
+        assert_eq!(
+            bare_dir.apply("src/a/b.rs"),
+            Some("core/a/b.rs".to_string())
+        );
+        assert_eq!(bare_dir.apply("src"), Some("core".to_string()));
+        assert_eq!(bare_dir.apply("srcs/a.rs"), None);
+
+        let to_root = PathRename::parse("sub/:").unwrap();
+        assert_eq!(to_root.apply("sub/a.rs"), Some("a.rs".to_string()));
+
+        assert!(PathRename::parse("no-colon").is_err());
+    }
+
+    #[test]
+    fn test_remap_authorship_log_renames_and_drops_files() {
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "keep123".to_string(),
+                vec![LineRange::Range(1, 2)],
+            ));
+        log.get_or_create_file("secret.txt")
+            .add_entry(AttestationEntry::new(
+                "drop123".to_string(),
+                vec![LineRange::Single(1)],
+            ));
+        for (path, hash) in [("src/lib.rs", "keep123"), ("secret.txt", "drop123")] {
+            log.metadata.line_states.insert(
+                path.to_string(),
+                vec![LineStateEntry {
+                    hash: hash.to_string(),
+                    lines: vec![LineRange::Single(1)],
+                    state: LineState::Ai,
+                    ai_timestamp: None,
+                    human_author: None,
+                    human_edit_timestamp: None,
+                }],
+            );
+        }
+
+        let renames = vec![PathRename::parse("src/:core/").unwrap()];
+        let existing = HashSet::from([(NEW_A.to_string(), "core/lib.rs".to_string())]);
+        let (remapped, dropped) = remap_authorship_log(&log, NEW_A, &renames, &existing);
+
+        assert_eq!(dropped, 1);
+        assert_eq!(remapped.attestations.len(), 1);
+        assert_eq!(remapped.attestations[0].file_path, "core/lib.rs");
+        assert_eq!(
+            remapped.metadata.line_states.keys().collect::<Vec<_>>(),
+            vec!["core/lib.rs"]
+        );
+        assert_eq!(remapped.metadata.base_commit_sha, NEW_A);
+    }
+}
@@ -0,0 +1,951 @@
+//! Report command for git-ai
+//!
+//! Provides `git-ai report`, which walks a branch or time window and breaks AI
+//! authorship down per author, per directory, per tool/model and per week.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::ignore::{
+    IgnoreMatcher, build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
+};
+use crate::authorship::stats::{
+    CommitStats, ToolModelHeadlineStats, accepted_lines_from_attestations,
+    stats_from_authorship_log,
+};
+use crate::error::GitAiError;
+use crate::git::authorship_traversal::load_authorship_logs_for_commits;
+use crate::git::find_repository;
+use crate::git::repository::{Repository, exec_git, parse_diff_added_lines};
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Filters controlling which commits and files are included in a report
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Revision (or `a..b` range) to walk, defaults to HEAD
+    pub rev: String,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub author: Option<String>,
+    /// Pathspecs limiting the files counted in the report
+    pub paths: Vec<String>,
+    /// Number of leading path components used to group files into directories
+    pub directory_depth: usize,
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            rev: "HEAD".to_string(),
+            since: None,
+            until: None,
+            author: None,
+            paths: Vec::new(),
+            directory_depth: 1,
+            ignore_patterns: Vec::new(),
+        }
+    }
+}
+
+/// Stats for one row of a report breakdown
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ReportBucket<T> {
+    pub commits: u32,
+    #[serde(flatten)]
+    pub stats: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuthorshipReport {
+    pub total_commits: u32,
+    pub commits_with_authorship: u32,
+    pub totals: CommitStats,
+    pub by_author: BTreeMap<String, ReportBucket<CommitStats>>,
+    pub by_directory: BTreeMap<String, ReportBucket<DirectoryStats>>,
+    pub by_tool_model: BTreeMap<String, ReportBucket<ToolModelHeadlineStats>>,
+    /// Keyed by ISO week of the author date, e.g. "2025-W07"
+    pub by_week: BTreeMap<String, ReportBucket<CommitStats>>,
+}
+
+/// Line-level stats for a directory.
+///
+/// Mixed and generated counts come from prompt records, which span files, so they
+/// cannot be attributed to a single directory and are only reported per author,
+/// per week and per tool/model.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct DirectoryStats {
+    pub git_diff_added_lines: u32,
+    pub git_diff_deleted_lines: u32,
+    pub ai_accepted: u32,
+    pub human_additions: u32,
+}
+
+/// Tool/model key for accepted lines whose prompt is missing from the log metadata
+pub const UNKNOWN_TOOL_MODEL: &str = "unknown::unknown";
+
+#[derive(Debug, Clone, PartialEq)]
+struct FileNumstat {
+    path: String,
+    added: u32,
+    deleted: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ReportCommit {
+    sha: String,
+    author: String,
+    author_timestamp: i64,
+    files: Vec<FileNumstat>,
+    /// New-file line numbers added by this commit, keyed by path
+    added_lines: HashMap<String, Vec<u32>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReportFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+pub fn handle_report(args: &[String]) {
+    let (options, format) = match parse_report_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!();
+            print_report_help();
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut options = options;
+    options.ignore_patterns = effective_ignore_patterns(&repo, &options.ignore_patterns, &[]);
+
+    let report = match build_report(&repo, &options) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Report failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let output = match format {
+        ReportFormat::Table => format_table(&report),
+        ReportFormat::Json => match serde_json::to_string(&report) {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                std::process::exit(1);
+            }
+        },
+        ReportFormat::Csv => format_csv(&report),
+    };
+    println!("{}", output);
+}
+
+/// Walk the commits selected by `options` and aggregate their authorship stats
+pub fn build_report(
+    repo: &Repository,
+    options: &ReportOptions,
+) -> Result<AuthorshipReport, GitAiError> {
+    let mut commits = list_report_commits(repo, options)?;
+    let mut added_lines = list_report_added_lines(repo, options)?;
+    for commit in &mut commits {
+        commit.added_lines = added_lines.remove(&commit.sha).unwrap_or_default();
+    }
+
+    let commit_shas: Vec<String> = commits.iter().map(|c| c.sha.clone()).collect();
+    let logs = load_authorship_logs_for_commits(repo, &commit_shas)?;
+    let ignore_matcher = build_ignore_matcher(&options.ignore_patterns);
+
+    let mut report = AuthorshipReport::default();
+    for commit in &commits {
+        add_commit_to_report(
+            &mut report,
+            commit,
+            logs.get(&commit.sha),
+            &ignore_matcher,
+            options.directory_depth,
+        );
+    }
+
+    Ok(report)
+}
+
+fn list_report_commits(
+    repo: &Repository,
+    options: &ReportOptions,
+) -> Result<Vec<ReportCommit>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("-c".to_string());
+    args.push("core.quotePath=false".to_string());
+    args.push("log".to_string());
+    // Merge commits carry no numstat of their own, their changes are counted on the merged commits
+    args.push("--no-merges".to_string());
+    args.push("--no-renames".to_string());
+    args.push("--numstat".to_string());
+    args.push("--format=%x1e%H%x1f%an <%ae>%x1f%at".to_string());
+    push_report_filter_args(&mut args, options);
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_report_log_output(&stdout))
+}
+
+/// Added line numbers per file for every selected commit, from a single `git log -p -U0`
+fn list_report_added_lines(
+    repo: &Repository,
+    options: &ReportOptions,
+) -> Result<HashMap<String, HashMap<String, Vec<u32>>>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--no-merges".to_string());
+    args.push("--no-renames".to_string());
+    args.push("--no-color".to_string());
+    args.push("-p".to_string());
+    args.push("-U0".to_string());
+    args.push("--format=%x1e%H".to_string());
+    push_report_filter_args(&mut args, options);
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_report_patch_output(&stdout)
+}
+
+fn push_report_filter_args(args: &mut Vec<String>, options: &ReportOptions) {
+    if let Some(since) = &options.since {
+        args.push(format!("--since={}", since));
+    }
+    if let Some(until) = &options.until {
+        args.push(format!("--until={}", until));
+    }
+    if let Some(author) = &options.author {
+        args.push(format!("--author={}", author));
+    }
+    args.push(options.rev.clone());
+    args.push("--".to_string());
+    args.extend(options.paths.iter().cloned());
+}
+
+/// Parse `git log -p -U0 --format=%x1e%H` output into added line numbers per commit
+fn parse_report_patch_output(
+    output: &str,
+) -> Result<HashMap<String, HashMap<String, Vec<u32>>>, GitAiError> {
+    let mut added_lines = HashMap::new();
+
+    for record in output.split('\x1e') {
+        let (sha, patch) = record.split_once('\n').unwrap_or((record, ""));
+        let sha = sha.trim();
+        if sha.is_empty() {
+            continue;
+        }
+        added_lines.insert(sha.to_string(), parse_diff_added_lines(patch)?);
+    }
+
+    Ok(added_lines)
+}
+
+/// Parse `git log --numstat --format=%x1e%H%x1f<author>%x1f%at` output
+fn parse_report_log_output(output: &str) -> Vec<ReportCommit> {
+    let mut commits = Vec::new();
+
+    for record in output.split('\x1e') {
+        let mut lines = record.lines();
+        let Some(header) = lines.next() else {
+            continue;
+        };
+        let parts: Vec<&str> = header.split('\x1f').collect();
+        if parts.len() < 3 || parts[0].is_empty() {
+            continue;
+        }
+
+        let mut files = Vec::new();
+        for line in lines {
+            let fields: Vec<&str> = line.splitn(3, '\t').collect();
+            if fields.len() < 3 {
+                continue;
+            }
+            // Binary files report "-" for both counts
+            let (Ok(added), Ok(deleted)) = (fields[0].parse::<u32>(), fields[1].parse::<u32>())
+            else {
+                continue;
+            };
+            files.push(FileNumstat {
+                path: fields[2].to_string(),
+                added,
+                deleted,
+            });
+        }
+
+        commits.push(ReportCommit {
+            sha: parts[0].to_string(),
+            author: parts[1].to_string(),
+            author_timestamp: parts[2].trim().parse().unwrap_or(0),
+            files,
+            added_lines: HashMap::new(),
+        });
+    }
+
+    commits
+}
+
+fn add_commit_to_report(
+    report: &mut AuthorshipReport,
+    commit: &ReportCommit,
+    authorship_log: Option<&AuthorshipLog>,
+    ignore_matcher: &IgnoreMatcher,
+    directory_depth: usize,
+) {
+    let files: Vec<&FileNumstat> = commit
+        .files
+        .iter()
+        .filter(|file| !should_ignore_file_with_matcher(&file.path, ignore_matcher))
+        .collect();
+
+    let mut added_lines = 0u32;
+    let mut deleted_lines = 0u32;
+    let mut ai_accepted = 0u32;
+    let mut ai_accepted_by_tool: BTreeMap<String, u32> = BTreeMap::new();
+    let mut directories: BTreeMap<String, DirectoryStats> = BTreeMap::new();
+
+    for file in &files {
+        added_lines += file.added;
+        deleted_lines += file.deleted;
+
+        // Intersect attestations with the lines this commit added, one file at a time so
+        // the accepted count can also be attributed to the file's directory.
+        let mut file_added_lines = HashMap::new();
+        if let Some(lines) = commit.added_lines.get(&file.path) {
+            file_added_lines.insert(file.path.clone(), lines.clone());
+        }
+        let (file_ai_accepted, file_ai_by_tool) =
+            accepted_lines_from_attestations(authorship_log, &file_added_lines, false);
+        ai_accepted += file_ai_accepted;
+
+        // Attestations whose prompt is missing from the metadata still count as accepted
+        // AI lines, so keep them in an explicit bucket for the tool breakdown to add up.
+        let attributed: u32 = file_ai_by_tool.values().sum();
+        if file_ai_accepted > attributed {
+            *ai_accepted_by_tool
+                .entry(UNKNOWN_TOOL_MODEL.to_string())
+                .or_insert(0) += file_ai_accepted - attributed;
+        }
+        for (tool_model, accepted) in file_ai_by_tool {
+            *ai_accepted_by_tool.entry(tool_model).or_insert(0) += accepted;
+        }
+
+        let dir_stats = directories
+            .entry(directory_key(&file.path, directory_depth))
+            .or_default();
+        dir_stats.git_diff_added_lines += file.added;
+        dir_stats.git_diff_deleted_lines += file.deleted;
+        dir_stats.ai_accepted += file_ai_accepted;
+        dir_stats.human_additions += file.added.saturating_sub(file_ai_accepted);
+    }
+
+    let stats = stats_from_authorship_log(
+        authorship_log,
+        added_lines,
+        deleted_lines,
+        ai_accepted,
+        &ai_accepted_by_tool,
+    );
+
+    report.total_commits += 1;
+    if authorship_log.is_some() {
+        report.commits_with_authorship += 1;
+    }
+    add_commit_stats(&mut report.totals, &stats);
+
+    let author_bucket = report.by_author.entry(commit.author.clone()).or_default();
+    author_bucket.commits += 1;
+    add_commit_stats(&mut author_bucket.stats, &stats);
+
+    let week_bucket = report
+        .by_week
+        .entry(iso_week_key(commit.author_timestamp))
+        .or_default();
+    week_bucket.commits += 1;
+    add_commit_stats(&mut week_bucket.stats, &stats);
+
+    for (directory, dir_stats) in directories {
+        let dir_bucket = report.by_directory.entry(directory).or_default();
+        dir_bucket.commits += 1;
+        dir_bucket.stats.git_diff_added_lines += dir_stats.git_diff_added_lines;
+        dir_bucket.stats.git_diff_deleted_lines += dir_stats.git_diff_deleted_lines;
+        dir_bucket.stats.ai_accepted += dir_stats.ai_accepted;
+        dir_bucket.stats.human_additions += dir_stats.human_additions;
+    }
+
+    for (tool_model, tool_stats) in &stats.tool_model_breakdown {
+        let tool_bucket = report.by_tool_model.entry(tool_model.clone()).or_default();
+        tool_bucket.commits += 1;
+        add_tool_model_stats(&mut tool_bucket.stats, tool_stats);
+    }
+}
+
+/// Group a file path into its leading `depth` directory components ("." for the repo root)
+fn directory_key(path: &str, depth: usize) -> String {
+    let components: Vec<&str> = path.split('/').collect();
+    let dir_components = &components[..components.len() - 1];
+    if dir_components.is_empty() || depth == 0 {
+        return ".".to_string();
+    }
+    dir_components[..dir_components.len().min(depth)].join("/")
+}
+
+fn iso_week_key(timestamp: i64) -> String {
+    match chrono::DateTime::from_timestamp(timestamp, 0) {
+        Some(date) => {
+            let week = date.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+        None => "unknown".to_string(),
+    }
+}
+
+fn add_commit_stats(into: &mut CommitStats, from: &CommitStats) {
+    into.human_additions += from.human_additions;
+    into.mixed_additions += from.mixed_additions;
+    into.ai_additions += from.ai_additions;
+    into.ai_accepted += from.ai_accepted;
+    into.total_ai_additions += from.total_ai_additions;
+    into.total_ai_deletions += from.total_ai_deletions;
+    into.time_waiting_for_ai += from.time_waiting_for_ai;
+    into.git_diff_deleted_lines += from.git_diff_deleted_lines;
+    into.git_diff_added_lines += from.git_diff_added_lines;
+    for (tool_model, tool_stats) in &from.tool_model_breakdown {
+        add_tool_model_stats(
+            into.tool_model_breakdown
+                .entry(tool_model.clone())
+                .or_default(),
+            tool_stats,
+        );
+    }
+}
+
+fn add_tool_model_stats(into: &mut ToolModelHeadlineStats, from: &ToolModelHeadlineStats) {
+    into.ai_additions += from.ai_additions;
+    into.mixed_additions += from.mixed_additions;
+    into.ai_accepted += from.ai_accepted;
+    into.total_ai_additions += from.total_ai_additions;
+    into.total_ai_deletions += from.total_ai_deletions;
+    into.time_waiting_for_ai += from.time_waiting_for_ai;
+}
+
+fn ai_share(stats: &CommitStats) -> f64 {
+    percentage(stats.ai_additions, stats.git_diff_added_lines)
+}
+
+fn percentage(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (part as f64 / total as f64 * 100.0).min(100.0)
+    }
+}
+
+fn format_table(report: &AuthorshipReport) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "{} commits ({} with authorship logs), {} lines added, {:.0}% AI\n",
+        report.total_commits,
+        report.commits_with_authorship,
+        report.totals.git_diff_added_lines,
+        ai_share(&report.totals)
+    ));
+
+    output.push('\n');
+    output.push_str(&format_commit_stats_table("Author", &report.by_author));
+    output.push('\n');
+    output.push_str(&format_directory_table(&report.by_directory));
+    output.push('\n');
+    output.push_str(&format_commit_stats_table("Week", &report.by_week));
+
+    output.push('\n');
+    output.push_str(&format_tool_model_table(&report.by_tool_model));
+
+    output.trim_end().to_string()
+}
+
+fn format_commit_stats_table(
+    title: &str,
+    rows: &BTreeMap<String, ReportBucket<CommitStats>>,
+) -> String {
+    let name_width = rows
+        .keys()
+        .map(|k| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(title.len());
+
+    let mut output = format!(
+        "{:<name_width$}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>5}\n",
+        title, "COMMITS", "ADDED", "AI", "HUMAN", "MIXED", "AI%"
+    );
+    for (key, bucket) in rows {
+        output.push_str(&format!(
+            "{:<name_width$}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>4.0}%\n",
+            key,
+            bucket.commits,
+            bucket.stats.git_diff_added_lines,
+            bucket.stats.ai_additions,
+            bucket.stats.human_additions,
+            bucket.stats.mixed_additions,
+            ai_share(&bucket.stats)
+        ));
+    }
+    if rows.is_empty() {
+        output.push_str("(none)\n");
+    }
+    output
+}
+
+fn format_directory_table(rows: &BTreeMap<String, ReportBucket<DirectoryStats>>) -> String {
+    let title = "Directory";
+    let name_width = rows
+        .keys()
+        .map(|k| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(title.len());
+
+    let mut output = format!(
+        "{:<name_width$}  {:>7}  {:>7}  {:>8}  {:>7}  {:>5}\n",
+        title, "COMMITS", "ADDED", "ACCEPTED", "HUMAN", "AI%"
+    );
+    for (key, bucket) in rows {
+        output.push_str(&format!(
+            "{:<name_width$}  {:>7}  {:>7}  {:>8}  {:>7}  {:>4.0}%\n",
+            key,
+            bucket.commits,
+            bucket.stats.git_diff_added_lines,
+            bucket.stats.ai_accepted,
+            bucket.stats.human_additions,
+            percentage(bucket.stats.ai_accepted, bucket.stats.git_diff_added_lines)
+        ));
+    }
+    if rows.is_empty() {
+        output.push_str("(none)\n");
+    }
+    output
+}
+
+fn format_tool_model_table(
+    rows: &BTreeMap<String, ReportBucket<ToolModelHeadlineStats>>,
+) -> String {
+    let title = "Tool/Model";
+    let name_width = rows
+        .keys()
+        .map(|k| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(title.len());
+
+    let mut output = format!(
+        "{:<name_width$}  {:>7}  {:>7}  {:>8}  {:>7}  {:>9}\n",
+        title, "COMMITS", "AI", "ACCEPTED", "MIXED", "GENERATED"
+    );
+    for (key, bucket) in rows {
+        output.push_str(&format!(
+            "{:<name_width$}  {:>7}  {:>7}  {:>8}  {:>7}  {:>9}\n",
+            key,
+            bucket.commits,
+            bucket.stats.ai_additions,
+            bucket.stats.ai_accepted,
+            bucket.stats.mixed_additions,
+            bucket.stats.total_ai_additions
+        ));
+    }
+    if rows.is_empty() {
+        output.push_str("(none)\n");
+    }
+    output
+}
+
+const CSV_HEADER: &str = "dimension,key,commits,added_lines,deleted_lines,ai_additions,human_additions,mixed_additions,ai_accepted,total_ai_additions,ai_share";
+
+fn format_csv(report: &AuthorshipReport) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+
+    let total = ReportBucket {
+        commits: report.total_commits,
+        stats: report.totals.clone(),
+    };
+    lines.push(csv_commit_stats_row("total", "", &total));
+
+    for (key, bucket) in &report.by_author {
+        lines.push(csv_commit_stats_row("author", key, bucket));
+    }
+
+    // Directory rows only carry line-level columns, see `DirectoryStats`
+    for (key, bucket) in &report.by_directory {
+        lines.push(format!(
+            "directory,{},{},{},{},,{},,{},,{:.1}",
+            csv_field(key),
+            bucket.commits,
+            bucket.stats.git_diff_added_lines,
+            bucket.stats.git_diff_deleted_lines,
+            bucket.stats.human_additions,
+            bucket.stats.ai_accepted,
+            percentage(bucket.stats.ai_accepted, bucket.stats.git_diff_added_lines)
+        ));
+    }
+
+    for (key, bucket) in &report.by_week {
+        lines.push(csv_commit_stats_row("week", key, bucket));
+    }
+
+    for (key, bucket) in &report.by_tool_model {
+        lines.push(format!(
+            "tool_model,{},{},,,{},,{},{},{},",
+            csv_field(key),
+            bucket.commits,
+            bucket.stats.ai_additions,
+            bucket.stats.mixed_additions,
+            bucket.stats.ai_accepted,
+            bucket.stats.total_ai_additions
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn csv_commit_stats_row(dimension: &str, key: &str, bucket: &ReportBucket<CommitStats>) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{:.1}",
+        dimension,
+        csv_field(key),
+        bucket.commits,
+        bucket.stats.git_diff_added_lines,
+        bucket.stats.git_diff_deleted_lines,
+        bucket.stats.ai_additions,
+        bucket.stats.human_additions,
+        bucket.stats.mixed_additions,
+        bucket.stats.ai_accepted,
+        bucket.stats.total_ai_additions,
+        ai_share(&bucket.stats)
+    )
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_report_args(args: &[String]) -> Result<(ReportOptions, ReportFormat), String> {
+    let mut options = ReportOptions::default();
+    let mut format = ReportFormat::Table;
+    let mut rev_set = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        let needs_value = matches!(
+            arg,
+            "--since" | "--until" | "--author" | "--path" | "--depth" | "--ignore"
+        );
+        let value = if needs_value {
+            let value = args
+                .get(i + 1)
+                .ok_or_else(|| format!("{} requires a value", arg))?
+                .clone();
+            i += 2;
+            Some(value)
+        } else {
+            i += 1;
+            None
+        };
+
+        match (arg, value) {
+            ("--since", Some(v)) => options.since = Some(v),
+            ("--until", Some(v)) => options.until = Some(v),
+            ("--author", Some(v)) => options.author = Some(v),
+            ("--path", Some(v)) => options.paths.push(v),
+            ("--ignore", Some(v)) => options.ignore_patterns.push(v),
+            ("--depth", Some(v)) => {
+                options.directory_depth = v
+                    .parse()
+                    .map_err(|_| format!("Invalid --depth value: {}", v))?;
+            }
+            ("--json", _) => {
+                if format != ReportFormat::Table {
+                    return Err("--json and --csv are mutually exclusive".to_string());
+                }
+                format = ReportFormat::Json;
+            }
+            ("--csv", _) => {
+                if format != ReportFormat::Table {
+                    return Err("--json and --csv are mutually exclusive".to_string());
+                }
+                format = ReportFormat::Csv;
+            }
+            ("--help" | "-h", _) => {
+                print_report_help();
+                std::process::exit(0);
+            }
+            (other, _) if other.starts_with('-') => {
+                return Err(format!("Unknown report argument: {}", other));
+            }
+            (other, _) => {
+                if rev_set {
+                    return Err(format!("Unexpected argument: {}", other));
+                }
+                options.rev = other.to_string();
+                rev_set = true;
+            }
+        }
+    }
+
+    Ok((options, format))
+}
+
+fn print_report_help() {
+    eprintln!("git-ai report - AI authorship report over a branch or time window");
+    eprintln!();
+    eprintln!("USAGE:");
+    eprintln!("    git-ai report [<rev|range>] [OPTIONS]");
+    eprintln!();
+    eprintln!("FILTERS:");
+    eprintln!(
+        "    <rev|range>             Branch, commit or <a>..<b> range to walk (default: HEAD)"
+    );
+    eprintln!("    --since <date>          Only commits after this date (any git date format)");
+    eprintln!("    --until <date>          Only commits before this date");
+    eprintln!("    --author <pattern>      Only commits whose git author matches the pattern");
+    eprintln!("    --path <pathspec>       Only count files matching the pathspec (repeatable)");
+    eprintln!("    --ignore <pattern>      Exclude files matching the glob (repeatable)");
+    eprintln!();
+    eprintln!("GROUPING:");
+    eprintln!(
+        "    --depth <n>             Directory depth used for the directory breakdown (default: 1)"
+    );
+    eprintln!();
+    eprintln!("OUTPUT FORMAT (mutually exclusive):");
+    eprintln!("    (default)               Terminal tables");
+    eprintln!("    --json                  JSON output");
+    eprintln!("    --csv                   CSV output, one row per breakdown entry");
+    eprintln!();
+    eprintln!("EXAMPLES:");
+    eprintln!("    git-ai report main --since \"4 weeks ago\"");
+    eprintln!("    git-ai report origin/main..HEAD --json");
+    eprintln!("    git-ai report --author alice --path src/ --csv");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::{LineRange, PromptRecord};
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+    use crate::authorship::working_log::AgentId;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn make_log(file: &str, ranges: Vec<LineRange>) -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.metadata.prompts.insert(
+            "abc".to_string(),
+            PromptRecord {
+                agent_id: AgentId {
+                    tool: "claude".to_string(),
+                    id: "session".to_string(),
+                    model: "sonnet".to_string(),
+                },
+                human_author: None,
+                messages: vec![],
+                total_additions: 0,
+                total_deletions: 0,
+                accepted_lines: 0,
+                overriden_lines: 0,
+                messages_url: None,
+            },
+        );
+        let mut attestation = FileAttestation::new(file.to_string());
+        attestation.add_entry(AttestationEntry::new("abc".to_string(), ranges));
+        log.attestations.push(attestation);
+        log
+    }
+
+    #[test]
+    fn test_parse_report_log_output() {
+        let output = "\x1eaaa\x1fAlice <a@x.com>\x1f1700000000\n\n3\t1\tsrc/lib.rs\n-\t-\tlogo.png\n\x1ebbb\x1fBob <b@x.com>\x1f1700000100\n\n2\t0\tREADME.md\n";
+        let commits = parse_report_log_output(output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "aaa");
+        assert_eq!(commits[0].author, "Alice <a@x.com>");
+        assert_eq!(commits[0].author_timestamp, 1700000000);
+        assert_eq!(
+            commits[0].files,
+            vec![FileNumstat {
+                path: "src/lib.rs".to_string(),
+                added: 3,
+                deleted: 1
+            }]
+        );
+        assert_eq!(commits[1].files.len(), 1);
+    }
+
+    #[test]
+    fn test_directory_key() {
+        assert_eq!(directory_key("README.md", 1), ".");
+        assert_eq!(directory_key("src/lib.rs", 1), "src");
+        assert_eq!(directory_key("src/commands/report.rs", 1), "src");
+        assert_eq!(directory_key("src/commands/report.rs", 2), "src/commands");
+        assert_eq!(directory_key("src/commands/report.rs", 5), "src/commands");
+        assert_eq!(directory_key("src/lib.rs", 0), ".");
+    }
+
+    #[test]
+    fn test_iso_week_key() {
+        // 2023-11-14 is in ISO week 46
+        assert_eq!(iso_week_key(1700000000), "2023-W46");
+        // 2021-01-01 belongs to the last ISO week of 2020
+        assert_eq!(iso_week_key(1609459200), "2020-W53");
+    }
+
+    #[test]
+    fn test_parse_report_patch_output() {
+        let output = "\x1eaaa\n\ndiff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,0 +2,2 @@\n+a\n+b\n@@ -9 +11 @@\n-x\n+y\n\x1ebbb\n\ndiff --git a/old.rs b/old.rs\ndeleted file mode 100644\n--- a/old.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-gone\n";
+        let added = parse_report_patch_output(output).unwrap();
+        assert_eq!(added["aaa"]["src/lib.rs"], vec![2, 3, 11]);
+        assert!(added["bbb"].is_empty());
+    }
+
+    #[test]
+    fn test_unattested_prompt_lines_go_to_unknown_bucket() {
+        let mut log = make_log("src/lib.rs", vec![LineRange::Range(1, 2)]);
+        log.attestations[0].add_entry(AttestationEntry::new(
+            "missing".to_string(),
+            vec![LineRange::Single(3)],
+        ));
+        let commit = ReportCommit {
+            sha: "aaa".to_string(),
+            author: "Alice <a@x.com>".to_string(),
+            author_timestamp: 1700000000,
+            files: vec![FileNumstat {
+                path: "src/lib.rs".to_string(),
+                added: 3,
+                deleted: 0,
+            }],
+            added_lines: HashMap::from([("src/lib.rs".to_string(), vec![1, 2, 3])]),
+        };
+
+        let mut report = AuthorshipReport::default();
+        add_commit_to_report(
+            &mut report,
+            &commit,
+            Some(&log),
+            &build_ignore_matcher(&[]),
+            1,
+        );
+
+        assert_eq!(report.totals.ai_accepted, 3);
+        assert_eq!(report.by_tool_model["claude::sonnet"].stats.ai_accepted, 2);
+        assert_eq!(
+            report.by_tool_model[UNKNOWN_TOOL_MODEL].stats.ai_accepted,
+            1
+        );
+    }
+
+    #[test]
+    fn test_add_commit_to_report_breakdowns() {
+        let log = make_log(
+            "src/lib.rs",
+            vec![LineRange::Range(1, 1), LineRange::Range(3, 5)],
+        );
+        let commit = ReportCommit {
+            sha: "aaa".to_string(),
+            author: "Alice <a@x.com>".to_string(),
+            author_timestamp: 1700000000,
+            files: vec![
+                FileNumstat {
+                    path: "src/lib.rs".to_string(),
+                    added: 3,
+                    deleted: 0,
+                },
+                FileNumstat {
+                    path: "Cargo.lock".to_string(),
+                    added: 100,
+                    deleted: 0,
+                },
+            ],
+            // Lines 4-5 are attested but not part of this commit's diff
+            added_lines: HashMap::from([
+                ("src/lib.rs".to_string(), vec![1, 2, 3]),
+                ("Cargo.lock".to_string(), (1..=100).collect()),
+            ]),
+        };
+        let matcher = build_ignore_matcher(&["Cargo.lock".to_string()]);
+
+        let mut report = AuthorshipReport::default();
+        add_commit_to_report(&mut report, &commit, Some(&log), &matcher, 1);
+
+        assert_eq!(report.total_commits, 1);
+        assert_eq!(report.commits_with_authorship, 1);
+        assert_eq!(report.totals.git_diff_added_lines, 3);
+        assert_eq!(report.totals.ai_additions, 2);
+        assert_eq!(report.totals.human_additions, 1);
+
+        let author = &report.by_author["Alice <a@x.com>"];
+        assert_eq!(author.commits, 1);
+        assert_eq!(author.stats.ai_additions, 2);
+
+        let dir = &report.by_directory["src"];
+        assert_eq!(dir.stats.git_diff_added_lines, 3);
+        assert_eq!(dir.stats.ai_accepted, 2);
+        assert_eq!(dir.stats.human_additions, 1);
+        assert!(!report.by_directory.contains_key("."));
+
+        assert_eq!(report.by_tool_model["claude::sonnet"].stats.ai_accepted, 2);
+        assert_eq!(report.by_week["2023-W46"].commits, 1);
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_parse_report_args() {
+        let (options, format) = parse_report_args(&args(&[
+            "main",
+            "--since",
+            "2 weeks ago",
+            "--author",
+            "alice",
+            "--path",
+            "src/",
+            "--depth",
+            "2",
+            "--csv",
+        ]))
+        .unwrap();
+        assert_eq!(options.rev, "main");
+        assert_eq!(options.since.as_deref(), Some("2 weeks ago"));
+        assert_eq!(options.author.as_deref(), Some("alice"));
+        assert_eq!(options.paths, vec!["src/".to_string()]);
+        assert_eq!(options.directory_depth, 2);
+        assert_eq!(format, ReportFormat::Csv);
+
+        assert!(parse_report_args(&args(&["--json", "--csv"])).is_err());
+        assert!(parse_report_args(&args(&["--since"])).is_err());
+        assert!(parse_report_args(&args(&["--bogus"])).is_err());
+    }
+}
@@ -9,7 +9,7 @@ use crate::authorship::prompt_utils::find_prompt_with_db_fallback;
 use crate::commands::blame::GitAiBlameOptions;
 use crate::error::GitAiError;
 use crate::git::find_repository_in_path;
-use crate::git::refs::get_authorship;
+use crate::git::refs::get_authorship_merged;
 use crate::git::repository::{Repository, exec_git};
 use std::collections::HashMap;
 use std::env;
@@ -74,7 +74,7 @@ pub fn search_by_commit(repo: &Repository, commit_rev: &str) -> Result<SearchRes
     let mut result = SearchResult::new();
 
     // Try git notes first
-    if let Some(authorship_log) = get_authorship(repo, &commit_sha) {
+    if let Some(authorship_log) = get_authorship_merged(repo, &commit_sha) {
         // Extract prompts from metadata
         for (hash, prompt) in authorship_log.metadata.prompts {
             result.prompts.insert(hash.clone(), prompt);
@@ -189,6 +189,7 @@ pub fn search_by_file(
         use_prompt_hashes_as_names: true, // Get prompt hashes instead of tool names
         newest_commit: Some("HEAD".to_string()),
         line_ranges: line_ranges.to_vec(),
+        use_cache: true,
         ..Default::default()
     };
 
@@ -492,7 +493,7 @@ pub fn handle_search(args: &[String]) {
 }
 
 /// Apply filters to search results (intersection/AND semantics)
-fn apply_filters(mut result: SearchResult, filters: &SearchFilters) -> SearchResult {
+pub fn apply_filters(mut result: SearchResult, filters: &SearchFilters) -> SearchResult {
     if filters.is_empty() {
         return result;
     }
@@ -600,7 +601,7 @@ fn format_default(result: &SearchResult, mode: &SearchMode) -> String {
 }
 
 /// Format search results as JSON
-fn format_json(result: &SearchResult, mode: &SearchMode) -> String {
+pub fn format_json(result: &SearchResult, mode: &SearchMode) -> String {
     use serde_json::json;
 
     let query = match mode {
@@ -0,0 +1,576 @@
+//! Serve command for git-ai
+//!
+//! Provides `git-ai serve`, a self-hosted implementation of the CAS and bundle endpoints used by
+//! `api/cas.rs` and `api/bundle.rs`. Objects and bundles are kept in a local SQLite database, so
+//! a team can point `api_base_url` at an internal host and use `prompt_storage=default` without
+//! sending prompts to an external service.
+
+use crate::api::types::{
+    ApiErrorResponse, CAPromptStoreReadResponse, CAPromptStoreReadResult, CasObject,
+    CasUploadRequest, CasUploadResponse, CasUploadResult, CreateBundleRequest,
+    CreateBundleResponse,
+};
+use crate::error::GitAiError;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:8787";
+
+/// Largest request body accepted, in bytes
+const MAX_BODY_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Largest number of hashes accepted by a single CAS read
+const MAX_READ_HASHES: usize = 100;
+
+/// SQLite-backed storage for CAS objects and bundles
+pub struct ServeStore {
+    conn: Connection,
+}
+
+impl ServeStore {
+    /// Open (or create) the store at `path`
+    pub fn open(path: &Path) -> Result<Self, GitAiError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            r#"
+            PRAGMA journal_mode=WAL;
+            PRAGMA synchronous=NORMAL;
+            CREATE TABLE IF NOT EXISTS cas_objects (
+                hash TEXT PRIMARY KEY NOT NULL,
+                content TEXT NOT NULL,
+                metadata TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS bundles (
+                id TEXT PRIMARY KEY NOT NULL,
+                title TEXT NOT NULL,
+                data TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            "#,
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Store a CAS object after checking that its hash matches its content.
+    /// Objects are immutable, so re-uploading an existing hash is a no-op.
+    pub fn put_cas_object(&mut self, object: &CasObject) -> Result<(), GitAiError> {
+        let canonical = serde_json_canonicalizer::to_string(&object.content)
+            .map_err(|e| GitAiError::Generic(format!("Failed to canonicalize JSON: {}", e)))?;
+        let mut hasher = Sha256::new();
+        hasher.update(canonical.as_bytes());
+        let hash = format!("{:x}", hasher.finalize());
+        if hash != object.hash {
+            return Err(GitAiError::Generic(format!(
+                "Hash mismatch: content hashes to {}",
+                hash
+            )));
+        }
+
+        self.conn.execute(
+            "INSERT OR IGNORE INTO cas_objects (hash, content, metadata, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                hash,
+                canonical,
+                serde_json::to_string(&object.metadata)?,
+                unix_now()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_cas_object(&self, hash: &str) -> Result<Option<serde_json::Value>, GitAiError> {
+        let content: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT content FROM cas_objects WHERE hash = ?1",
+                params![hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        content
+            .map(|content| serde_json::from_str(&content).map_err(GitAiError::from))
+            .transpose()
+    }
+
+    /// Store a bundle and return its new ID
+    pub fn create_bundle(&mut self, request: &CreateBundleRequest) -> Result<String, GitAiError> {
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        self.conn.execute(
+            "INSERT INTO bundles (id, title, data, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                id,
+                request.title,
+                serde_json::to_string(&request.data)?,
+                unix_now()
+            ],
+        )?;
+        Ok(id)
+    }
+
+    pub fn get_bundle(&self, id: &str) -> Result<Option<CreateBundleRequest>, GitAiError> {
+        let row: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT title, data FROM bundles WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        row.map(|(title, data)| {
+            Ok(CreateBundleRequest {
+                title,
+                data: serde_json::from_str(&data)?,
+            })
+        })
+        .transpose()
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// A routed response: HTTP status plus JSON body
+type Reply = (u16, serde_json::Value);
+
+fn json_reply<T: Serialize>(status: u16, body: &T) -> Reply {
+    (
+        status,
+        serde_json::to_value(body).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+fn error_reply(status: u16, error: &str, details: Option<String>) -> Reply {
+    json_reply(
+        status,
+        &ApiErrorResponse {
+            error: error.to_string(),
+            details: details.map(serde_json::Value::String),
+        },
+    )
+}
+
+/// HTTP server exposing a [`ServeStore`]
+pub struct Server {
+    http: tiny_http::Server,
+    store: ServeStore,
+    api_key: Option<String>,
+    public_url: String,
+}
+
+impl Server {
+    /// Bind to `addr` (e.g. `127.0.0.1:8787`, or port 0 for any free port)
+    pub fn bind(addr: &str, store: ServeStore) -> Result<Self, GitAiError> {
+        let http = tiny_http::Server::http(addr)
+            .map_err(|e| GitAiError::Generic(format!("Failed to listen on {}: {}", addr, e)))?;
+        let public_url = match http.server_addr().to_ip() {
+            Some(socket) => format!("http://{}", socket),
+            None => format!("http://{}", addr),
+        };
+        Ok(Self {
+            http,
+            store,
+            api_key: None,
+            public_url,
+        })
+    }
+
+    /// Require clients to send this key in the `X-API-Key` header
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+
+    /// Base URL used in returned bundle URLs (defaults to the bound address)
+    pub fn with_public_url(mut self, public_url: Option<String>) -> Self {
+        if let Some(url) = public_url {
+            self.public_url = url.trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    pub fn public_url(&self) -> &str {
+        &self.public_url
+    }
+
+    /// Serve requests until the process exits
+    pub fn run(mut self) {
+        for mut request in self.http.incoming_requests() {
+            let (status, body) = match read_body(&mut request) {
+                Ok(raw_body) => {
+                    let api_key = request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv("X-API-Key"))
+                        .map(|h| h.value.as_str().to_string());
+                    if self.api_key.is_some() && api_key != self.api_key {
+                        error_reply(401, "Unauthorized", None)
+                    } else {
+                        route(
+                            &mut self.store,
+                            &self.public_url,
+                            request.method(),
+                            request.url(),
+                            &raw_body,
+                        )
+                    }
+                }
+                Err(reply) => reply,
+            };
+
+            eprintln!("{} {} -> {}", request.method(), request.url(), status);
+            let response = tiny_http::Response::from_string(body.to_string())
+                .with_status_code(status)
+                .with_header(
+                    tiny_http::Header::from_bytes("Content-Type", "application/json")
+                        .expect("static header is valid"),
+                );
+            let _ = request.respond(response);
+        }
+    }
+}
+
+fn read_body(request: &mut tiny_http::Request) -> Result<Vec<u8>, Reply> {
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| error_reply(400, "Failed to read request body", Some(e.to_string())))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(error_reply(413, "Request body too large", None));
+    }
+    Ok(body)
+}
+
+/// Dispatch a request to its endpoint
+fn route(
+    store: &mut ServeStore,
+    public_url: &str,
+    method: &tiny_http::Method,
+    raw_url: &str,
+    body: &[u8],
+) -> Reply {
+    let Ok(url) = url::Url::parse("http://localhost").and_then(|base| base.join(raw_url)) else {
+        return error_reply(400, "Invalid request URL", None);
+    };
+
+    let result = match (method, url.path()) {
+        (tiny_http::Method::Post, "/worker/cas/upload") => handle_cas_upload(store, body),
+        (tiny_http::Method::Get, "/worker/cas/" | "/worker/cas") => {
+            let hashes = url
+                .query_pairs()
+                .find(|(key, _)| key == "hashes")
+                .map(|(_, value)| value.into_owned())
+                .unwrap_or_default();
+            handle_cas_read(store, &hashes)
+        }
+        (tiny_http::Method::Get, path) if path.starts_with("/cas/") => {
+            match store.get_cas_object(&path["/cas/".len()..]) {
+                Ok(Some(content)) => Ok((200, content)),
+                Ok(None) => Ok(error_reply(404, "Not found", None)),
+                Err(e) => Err(e),
+            }
+        }
+        (tiny_http::Method::Post, "/api/bundles") => handle_create_bundle(store, public_url, body),
+        (tiny_http::Method::Get, path) if path.starts_with("/api/bundles/") => {
+            match store.get_bundle(&path["/api/bundles/".len()..]) {
+                Ok(Some(bundle)) => Ok(json_reply(200, &bundle)),
+                Ok(None) => Ok(error_reply(404, "Not found", None)),
+                Err(e) => Err(e),
+            }
+        }
+        _ => Ok(error_reply(404, "Not found", None)),
+    };
+
+    result.unwrap_or_else(|e| error_reply(500, "Internal server error", Some(e.to_string())))
+}
+
+fn handle_cas_upload(store: &mut ServeStore, body: &[u8]) -> Result<Reply, GitAiError> {
+    let request: CasUploadRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => {
+            return Ok(error_reply(
+                400,
+                "Invalid request body",
+                Some(e.to_string()),
+            ));
+        }
+    };
+
+    let results: Vec<CasUploadResult> = request
+        .objects
+        .iter()
+        .map(|object| match store.put_cas_object(object) {
+            Ok(()) => CasUploadResult {
+                hash: object.hash.clone(),
+                status: "ok".to_string(),
+                error: None,
+            },
+            Err(e) => CasUploadResult {
+                hash: object.hash.clone(),
+                status: "error".to_string(),
+                error: Some(e.to_string()),
+            },
+        })
+        .collect();
+    let success_count = results.iter().filter(|r| r.status == "ok").count();
+
+    Ok(json_reply(
+        200,
+        &CasUploadResponse {
+            failure_count: results.len() - success_count,
+            success_count,
+            results,
+        },
+    ))
+}
+
+fn handle_cas_read(store: &ServeStore, hashes: &str) -> Result<Reply, GitAiError> {
+    let hashes: Vec<&str> = hashes.split(',').filter(|h| !h.is_empty()).collect();
+    if hashes.is_empty() || hashes.len() > MAX_READ_HASHES {
+        return Ok(error_reply(
+            400,
+            &format!("Expected between 1 and {} hashes", MAX_READ_HASHES),
+            None,
+        ));
+    }
+
+    let mut results = Vec::new();
+    for hash in hashes {
+        results.push(match store.get_cas_object(hash)? {
+            Some(content) => CAPromptStoreReadResult {
+                hash: hash.to_string(),
+                status: "ok".to_string(),
+                content: Some(content),
+                error: None,
+            },
+            None => CAPromptStoreReadResult {
+                hash: hash.to_string(),
+                status: "error".to_string(),
+                content: None,
+                error: Some("Not found".to_string()),
+            },
+        });
+    }
+    let success_count = results.iter().filter(|r| r.status == "ok").count();
+    let status = if success_count == 0 { 404 } else { 200 };
+
+    Ok(json_reply(
+        status,
+        &CAPromptStoreReadResponse {
+            failure_count: results.len() - success_count,
+            success_count,
+            results,
+        },
+    ))
+}
+
+fn handle_create_bundle(
+    store: &mut ServeStore,
+    public_url: &str,
+    body: &[u8],
+) -> Result<Reply, GitAiError> {
+    let request: CreateBundleRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => {
+            return Ok(error_reply(
+                400,
+                "Invalid request body",
+                Some(e.to_string()),
+            ));
+        }
+    };
+    if request.title.is_empty() {
+        return Ok(error_reply(400, "Bundle title must not be empty", None));
+    }
+    if request.data.prompts.is_empty() {
+        return Ok(error_reply(
+            400,
+            "Bundle must contain at least one prompt",
+            None,
+        ));
+    }
+
+    let id = store.create_bundle(&request)?;
+    Ok(json_reply(
+        200,
+        &CreateBundleResponse {
+            success: true,
+            url: format!("{}/api/bundles/{}", public_url, id),
+            id,
+        },
+    ))
+}
+
+fn default_db_path() -> Result<PathBuf, GitAiError> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| GitAiError::Generic("Could not determine home directory".to_string()))?;
+    Ok(home.join(".git-ai").join("serve").join("db"))
+}
+
+pub fn handle_serve(args: &[String]) {
+    let mut listen = DEFAULT_LISTEN_ADDR.to_string();
+    let mut db_path: Option<PathBuf> = None;
+    let mut api_key: Option<String> = None;
+    let mut public_url: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        match flag {
+            "--listen" | "--db" | "--api-key" | "--public-url" => {
+                let Some(value) = args.get(i + 1).cloned() else {
+                    eprintln!("{} requires a value", flag);
+                    print_usage();
+                    std::process::exit(1);
+                };
+                match flag {
+                    "--listen" => listen = value,
+                    "--db" => db_path = Some(PathBuf::from(value)),
+                    "--api-key" => api_key = Some(value),
+                    _ => public_url = Some(value),
+                }
+                i += 1;
+            }
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other => {
+                eprintln!("Unknown argument: {}", other);
+                print_usage();
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let db_path = match db_path.map_or_else(default_db_path, Ok) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("serve failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let server = match ServeStore::open(&db_path).and_then(|store| Server::bind(&listen, store)) {
+        Ok(server) => server.with_api_key(api_key).with_public_url(public_url),
+        Err(e) => {
+            eprintln!("serve failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    eprintln!(
+        "git-ai serve listening on {} (store: {})",
+        server.public_url(),
+        db_path.display()
+    );
+    eprintln!(
+        "Point clients at it with: git-ai config set api_base_url {}",
+        server.public_url()
+    );
+    server.run();
+}
+
+fn print_usage() {
+    eprintln!("Usage: git-ai serve [--listen <addr>] [--db <path>] [--api-key <key>]");
+    eprintln!("                    [--public-url <url>]");
+    eprintln!();
+    eprintln!("Serve the CAS and bundle API from a local SQLite store.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!(
+        "  --listen <addr>      Address to listen on (default: {})",
+        DEFAULT_LISTEN_ADDR
+    );
+    eprintln!("  --db <path>          SQLite store (default: ~/.git-ai/serve/db)");
+    eprintln!("  --api-key <key>      Require this X-API-Key on every request");
+    eprintln!("  --public-url <url>   Base URL used in bundle links (default: listen address)");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn temp_store() -> (tempfile::TempDir, ServeStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ServeStore::open(&dir.path().join("db")).unwrap();
+        (dir, store)
+    }
+
+    fn cas_object(content: serde_json::Value) -> CasObject {
+        let canonical = serde_json_canonicalizer::to_string(&content).unwrap();
+        CasObject {
+            hash: format!("{:x}", Sha256::digest(canonical.as_bytes())),
+            content,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_put_cas_object_rejects_hash_mismatch() {
+        let (_dir, mut store) = temp_store();
+        let mut object = cas_object(json!({ "messages": [] }));
+        store.put_cas_object(&object).unwrap();
+        store.put_cas_object(&object).unwrap();
+        assert_eq!(
+            store.get_cas_object(&object.hash).unwrap(),
+            Some(json!({ "messages": [] }))
+        );
+
+        object.hash = "0".repeat(64);
+        assert!(store.put_cas_object(&object).is_err());
+        assert_eq!(store.get_cas_object(&object.hash).unwrap(), None);
+    }
+
+    #[test]
+    fn test_route_errors() {
+        let (_dir, mut store) = temp_store();
+        let base = "http://localhost:1";
+
+        let (status, _) = route(&mut store, base, &tiny_http::Method::Get, "/nope", b"");
+        assert_eq!(status, 404);
+
+        let (status, body) = route(
+            &mut store,
+            base,
+            &tiny_http::Method::Post,
+            "/worker/cas/upload",
+            b"not json",
+        );
+        assert_eq!(status, 400);
+        assert_eq!(body["error"], "Invalid request body");
+
+        let (status, _) = route(
+            &mut store,
+            base,
+            &tiny_http::Method::Get,
+            "/worker/cas/?hashes=",
+            b"",
+        );
+        assert_eq!(status, 400);
+
+        let empty_bundle = json!({ "title": "t", "data": { "prompts": {} } }).to_string();
+        let (status, _) = route(
+            &mut store,
+            base,
+            &tiny_http::Method::Post,
+            "/api/bundles",
+            empty_bundle.as_bytes(),
+        );
+        assert_eq!(status, 400);
+    }
+}
@@ -2,7 +2,10 @@ use crate::api::client::{ApiClient, ApiContext};
 use crate::api::types::CasMessagesObject;
 use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::prompt_utils::find_prompt;
+use crate::error::GitAiError;
 use crate::git::find_repository;
+use crate::git::prompt_refs::prompt_blob_oid;
+use crate::git::repository::Repository;
 use crate::utils::debug_log;
 
 /// Handle the `show-prompt` command
@@ -28,107 +31,120 @@ pub fn handle_show_prompt(args: &[String]) {
         }
     };
 
-    match find_prompt(
+    match show_prompt(
         &repo,
         &parsed.prompt_id,
         parsed.commit.as_deref(),
         parsed.offset,
     ) {
-        Ok((commit_sha, mut prompt_record)) => {
-            // If messages are empty, resolve from the best available source.
-            // Priority: CAS cache → CAS API (if messages_url) → local SQLite
-            if prompt_record.messages.is_empty() {
-                if let Some(url) = &prompt_record.messages_url
-                    && let Some(hash) = url.rsplit('/').next().filter(|h| !h.is_empty())
-                {
-                    // 1. Check cas_cache (instant, local)
-                    if let Ok(db_mutex) = InternalDatabase::global()
-                        && let Ok(db_guard) = db_mutex.lock()
-                        && let Ok(Some(cached_json)) = db_guard.get_cas_cache(hash)
-                        && let Ok(cas_obj) = serde_json::from_str::<CasMessagesObject>(&cached_json)
-                    {
-                        prompt_record.messages = cas_obj.messages;
-                        debug_log("show-prompt: resolved from cas_cache");
-                    }
+        Ok(output) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
+            );
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Find a prompt like `find_prompt`, resolving its messages from the CAS cache, the CAS API or
+/// the local database when the note doesn't carry them. Returns the `show-prompt` JSON document.
+pub fn show_prompt(
+    repo: &Repository,
+    prompt_id: &str,
+    commit: Option<&str>,
+    offset: usize,
+) -> Result<serde_json::Value, GitAiError> {
+    let (commit_sha, mut prompt_record) = find_prompt(repo, prompt_id, commit, offset)?;
 
-                    // 2. If cache miss, fetch from CAS API (network)
-                    if prompt_record.messages.is_empty() {
-                        let context = ApiContext::new(None);
-                        if context.auth_token.is_some() {
-                            debug_log(&format!(
-                                "show-prompt: trying CAS API for hash {}",
-                                &hash[..8.min(hash.len())]
-                            ));
-                            let client = ApiClient::new(context);
-                            match client.read_ca_prompt_store(&[hash]) {
-                                Ok(response) => {
-                                    for result in &response.results {
-                                        if result.status == "ok"
-                                            && let Some(content) = &result.content
+    // If messages are empty, resolve from the best available source.
+    // Priority: CAS cache → CAS API (if messages_url) → local SQLite
+    if prompt_record.messages.is_empty() {
+        if let Some(url) = &prompt_record.messages_url
+            && prompt_blob_oid(url).is_none()
+            && let Some(hash) = url.rsplit('/').next().filter(|h| !h.is_empty())
+        {
+            // 1. Check cas_cache (instant, local)
+            if let Ok(db_mutex) = InternalDatabase::global()
+                && let Ok(db_guard) = db_mutex.lock()
+                && let Ok(Some(cached_json)) = db_guard.get_cas_cache(hash)
+                && let Ok(cas_obj) = serde_json::from_str::<CasMessagesObject>(&cached_json)
+            {
+                prompt_record.messages = cas_obj.messages;
+                debug_log("show-prompt: resolved from cas_cache");
+            }
+
+            // 2. If cache miss, fetch from CAS API (network)
+            if prompt_record.messages.is_empty() {
+                // Self-hosted servers (`git-ai serve`) don't require a login
+                let context = ApiContext::new(None);
+                let using_default_api = context.base_url == crate::config::DEFAULT_API_BASE_URL;
+                if context.auth_token.is_some() || !using_default_api {
+                    debug_log(&format!(
+                        "show-prompt: trying CAS API for hash {}",
+                        &hash[..8.min(hash.len())]
+                    ));
+                    let client = ApiClient::new(context);
+                    match client.read_ca_prompt_store(&[hash]) {
+                        Ok(response) => {
+                            for result in &response.results {
+                                if result.status == "ok"
+                                    && let Some(content) = &result.content
+                                {
+                                    let json_str =
+                                        serde_json::to_string(content).unwrap_or_default();
+                                    if let Ok(cas_obj) =
+                                        serde_json::from_value::<CasMessagesObject>(content.clone())
+                                    {
+                                        prompt_record.messages = cas_obj.messages;
+                                        debug_log(&format!(
+                                            "show-prompt: resolved {} messages from CAS API",
+                                            prompt_record.messages.len()
+                                        ));
+                                        // Cache for next time
+                                        if let Ok(db_mutex) = InternalDatabase::global()
+                                            && let Ok(mut db_guard) = db_mutex.lock()
                                         {
-                                            let json_str =
-                                                serde_json::to_string(content).unwrap_or_default();
-                                            if let Ok(cas_obj) =
-                                                serde_json::from_value::<CasMessagesObject>(
-                                                    content.clone(),
-                                                )
-                                            {
-                                                prompt_record.messages = cas_obj.messages;
-                                                debug_log(&format!(
-                                                    "show-prompt: resolved {} messages from CAS API",
-                                                    prompt_record.messages.len()
-                                                ));
-                                                // Cache for next time
-                                                if let Ok(db_mutex) = InternalDatabase::global()
-                                                    && let Ok(mut db_guard) = db_mutex.lock()
-                                                {
-                                                    let _ = db_guard.set_cas_cache(hash, &json_str);
-                                                }
-                                            }
+                                            let _ = db_guard.set_cas_cache(hash, &json_str);
                                         }
                                     }
                                 }
-                                Err(e) => {
-                                    debug_log(&format!("show-prompt: CAS API error: {}", e));
-                                }
                             }
-                        } else {
-                            debug_log("show-prompt: no auth token, skipping CAS API");
+                        }
+                        Err(e) => {
+                            debug_log(&format!("show-prompt: CAS API error: {}", e));
                         }
                     }
-                }
-
-                // 3. Last resort: local SQLite (for prompts without a CAS URL)
-                if prompt_record.messages.is_empty()
-                    && let Ok(db_mutex) = InternalDatabase::global()
-                    && let Ok(db_guard) = db_mutex.lock()
-                    && let Ok(Some(db_record)) = db_guard.get_prompt(&parsed.prompt_id)
-                    && !db_record.messages.messages.is_empty()
-                {
-                    prompt_record.messages = db_record.messages.messages;
-                    debug_log(&format!(
-                        "show-prompt: resolved {} messages from local SQLite",
-                        prompt_record.messages.len()
-                    ));
+                } else {
+                    debug_log("show-prompt: not logged in to default API, skipping CAS API");
                 }
             }
-
-            // Output the prompt as JSON, including the commit SHA for context
-            let output = serde_json::json!({
-                "commit": commit_sha,
-                "prompt_id": parsed.prompt_id,
-                "prompt": prompt_record,
-            });
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
-            );
         }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+
+        // 3. Last resort: local SQLite (for prompts without a CAS URL)
+        if prompt_record.messages.is_empty()
+            && let Ok(db_mutex) = InternalDatabase::global()
+            && let Ok(db_guard) = db_mutex.lock()
+            && let Ok(Some(db_record)) = db_guard.get_prompt(prompt_id)
+            && !db_record.messages.messages.is_empty()
+        {
+            prompt_record.messages = db_record.messages.messages;
+            debug_log(&format!(
+                "show-prompt: resolved {} messages from local SQLite",
+                prompt_record.messages.len()
+            ));
         }
     }
+
+    // Output the prompt as JSON, including the commit SHA for context
+    Ok(serde_json::json!({
+        "commit": commit_sha,
+        "prompt_id": prompt_id,
+        "prompt": prompt_record,
+    }))
 }
 
 #[derive(Debug)]
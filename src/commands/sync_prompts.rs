@@ -1,6 +1,9 @@
 use crate::authorship::internal_db::{InternalDatabase, PromptDbRecord};
-use crate::authorship::prompt_utils::{PromptUpdateResult, update_prompt_from_tool};
+use crate::authorship::prompt_utils::{
+    PromptUpdateResult, find_prompt_in_commit, update_prompt_from_tool,
+};
 use crate::error::GitAiError;
+use crate::git::find_repository_in_path;
 use crate::observability::log_error;
 use chrono::{DateTime, NaiveDate};
 use std::cmp::min;
@@ -226,11 +229,29 @@ fn update_prompt_record(record: &PromptDbRecord) -> Result<Option<PromptDbRecord
 
             Ok(Some(updated_record))
         }
-        PromptUpdateResult::Unchanged => Ok(None),
-        PromptUpdateResult::Failed(e) => Err(e),
+        PromptUpdateResult::Unchanged => Ok(prompt_blob_fallback(record)),
+        PromptUpdateResult::Failed(e) => prompt_blob_fallback(record).map(Some).ok_or(e),
     }
 }
 
+/// When the agent's transcript is no longer available and the record has no messages, load
+/// them from the prompt blob referenced by the record's commit (`prompt_storage = "git"`)
+fn prompt_blob_fallback(record: &PromptDbRecord) -> Option<PromptDbRecord> {
+    if !record.messages.messages.is_empty() {
+        return None;
+    }
+    let repo = find_repository_in_path(record.workdir.as_deref()?).ok()?;
+    let (_, prompt) =
+        find_prompt_in_commit(&repo, &record.id, record.commit_sha.as_deref()?).ok()?;
+    if prompt.messages.is_empty() {
+        return None;
+    }
+
+    let mut updated_record = record.clone();
+    updated_record.messages.messages = prompt.messages;
+    Some(updated_record)
+}
+
 /// Sync recent prompts silently (for share command pre-refresh).
 /// This refreshes the database with the latest transcript data before showing/uploading.
 ///
@@ -0,0 +1,167 @@
+//! Verify command for git-ai
+//!
+//! Provides `git-ai verify <rev|range>`, which checks the signatures attached to authorship
+//! notes (see `git-ai.signNotes`) and reports commits with valid, invalid, untrusted or missing
+//! ones. Only valid signatures come from a trusted signer and match both the note and the
+//! commit it is attached to.
+
+use crate::authorship::note_signing::{SignatureStatus, verify_note};
+use crate::error::GitAiError;
+use crate::git::authorship_traversal::batch_read_blobs_with_oids;
+use crate::git::find_repository_in_path;
+use crate::git::refs::note_blob_oids_for_commits;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitVerification {
+    pub commit: String,
+    /// `None` when the commit has no authorship note
+    pub signature: Option<SignatureStatus>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VerifyReport {
+    pub commits: Vec<CommitVerification>,
+    pub valid: usize,
+    pub invalid: usize,
+    pub untrusted: usize,
+    pub missing: usize,
+    pub without_notes: usize,
+}
+
+/// Resolve a single revision or a `A..B` / `A...B` range to commit SHAs, newest first
+fn resolve_commits(repo: &Repository, spec: &str) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    if spec.contains("..") {
+        args.push("rev-list".to_string());
+        args.push(spec.to_string());
+    } else {
+        args.push("rev-parse".to_string());
+        args.push("--verify".to_string());
+        args.push(format!("{}^{{commit}}", spec));
+    }
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+pub fn run_verify(repo: &Repository, spec: &str) -> Result<VerifyReport, GitAiError> {
+    let commits = resolve_commits(repo, spec)?;
+    let note_blobs = note_blob_oids_for_commits(repo, &commits)?;
+    let blob_oids: Vec<String> = note_blobs.values().cloned().collect();
+    let contents = batch_read_blobs_with_oids(&repo.global_args_for_exec(), &blob_oids)?;
+
+    let mut report = VerifyReport::default();
+    for commit in commits {
+        let signature = note_blobs
+            .get(&commit)
+            .and_then(|blob| contents.get(blob))
+            .map(|content| verify_note(repo, &commit, content));
+        match &signature {
+            Some(SignatureStatus::Valid { .. }) => report.valid += 1,
+            Some(SignatureStatus::Invalid { .. }) => report.invalid += 1,
+            Some(SignatureStatus::Untrusted { .. }) => report.untrusted += 1,
+            Some(SignatureStatus::Missing) => report.missing += 1,
+            None => report.without_notes += 1,
+        }
+        report
+            .commits
+            .push(CommitVerification { commit, signature });
+    }
+    Ok(report)
+}
+
+pub fn handle_verify(args: &[String]) {
+    let mut json = false;
+    let mut spec: Option<String> = None;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other if other.starts_with('-') => {
+                eprintln!("Unknown argument: {}", other);
+                print_usage();
+                std::process::exit(1);
+            }
+            other => {
+                if spec.is_some() {
+                    eprintln!("Only one revision or range may be given");
+                    print_usage();
+                    std::process::exit(1);
+                }
+                spec = Some(other.to_string());
+            }
+        }
+    }
+
+    let repo = match find_repository_in_path(".") {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let spec = spec.unwrap_or_else(|| "HEAD".to_string());
+    let report = match run_verify(&repo, &spec) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("verify failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        for entry in &report.commits {
+            match &entry.signature {
+                Some(SignatureStatus::Valid { signer }) => {
+                    println!("valid   {} signed by {}", entry.commit, signer)
+                }
+                Some(SignatureStatus::Invalid { reason }) => {
+                    println!("invalid {}: {}", entry.commit, reason)
+                }
+                Some(SignatureStatus::Untrusted { reason }) => {
+                    println!("untrusted {}: {}", entry.commit, reason)
+                }
+                Some(SignatureStatus::Missing) => println!("missing {}", entry.commit),
+                None => {}
+            }
+        }
+        println!(
+            "{} valid, {} invalid, {} missing signatures, {} untrusted ({} commits without notes)",
+            report.valid, report.invalid, report.missing, report.untrusted, report.without_notes
+        );
+    }
+
+    if report.invalid > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn print_usage() {
+    eprintln!("Usage: git-ai verify [<rev|range>] [--json]");
+    eprintln!();
+    eprintln!("Checks the signatures on authorship notes for a commit (default: HEAD) or range.");
+    eprintln!("Notes are signed when git-ai.signNotes is enabled, using gpg.format and");
+    eprintln!("user.signingkey. SSH signatures are only valid when the signer is listed in");
+    eprintln!("gpg.ssh.allowedSignersFile, and are reported as untrusted otherwise.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --json     Output the report as JSON");
+}
@@ -26,6 +26,9 @@ pub enum PromptStorageMode {
     Notes,
     /// Local mode: prompts only stored in local SQLite, never shared
     Local,
+    /// Git mode: prompts stored as blobs in `refs/ai-prompts/blobs` (after secret
+    /// redaction), referenced from git notes
+    Git,
 }
 
 impl PromptStorageMode {
@@ -34,6 +37,7 @@ impl PromptStorageMode {
             PromptStorageMode::Default => "default",
             PromptStorageMode::Notes => "notes",
             PromptStorageMode::Local => "local",
+            PromptStorageMode::Git => "git",
         }
     }
 }
@@ -46,6 +50,7 @@ impl std::str::FromStr for PromptStorageMode {
             "default" => Ok(PromptStorageMode::Default),
             "notes" => Ok(PromptStorageMode::Notes),
             "local" => Ok(PromptStorageMode::Local),
+            "git" => Ok(PromptStorageMode::Git),
             other => Err(format!("invalid prompt storage mode: '{}'", other)),
         }
     }
@@ -74,6 +79,17 @@ pub struct Config {
     default_prompt_storage: Option<String>,
     api_key: Option<String>,
     quiet: bool,
+    secret_rules: Vec<SecretRuleConfig>,
+    secret_allowlist: Vec<String>,
+}
+
+/// A user-defined secret detector from the `secret_rules` config key
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SecretRuleConfig {
+    /// Rule name reported in redaction counts and by `git-ai secrets scan`
+    pub id: String,
+    /// Regex matching the secret. If it has a capture group, only the first group is redacted.
+    pub regex: String,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -140,6 +156,10 @@ pub struct FileConfig {
     pub api_key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quiet: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_rules: Option<Vec<SecretRuleConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_allowlist: Option<Vec<String>>,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -162,6 +182,10 @@ pub struct ConfigPatch {
     pub disable_auto_updates: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt_storage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_rules: Option<Vec<SecretRuleConfig>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_allowlist: Option<Vec<String>>,
 }
 
 impl Config {
@@ -376,6 +400,16 @@ impl Config {
     }
 
     /// Returns true if quiet mode is enabled (suppresses chart output after commits)
+    /// User-defined secret detectors, applied alongside the built-in ones
+    pub fn secret_rules(&self) -> &[SecretRuleConfig] {
+        &self.secret_rules
+    }
+
+    /// Regexes for values that must never be treated as secrets
+    pub fn secret_allowlist(&self) -> &[String] {
+        &self.secret_allowlist
+    }
+
     pub fn is_quiet(&self) -> bool {
         self.quiet
     }
@@ -530,13 +564,13 @@ fn build_config() -> Config {
         .unwrap_or_else(|| DEFAULT_API_BASE_URL.to_string());
 
     // Get prompt_storage setting (defaults to "default")
-    // Valid values: "default", "notes", "local"
+    // Valid values: "default", "notes", "local", "git"
     let prompt_storage = file_cfg
         .as_ref()
         .and_then(|c| c.prompt_storage.clone())
         .unwrap_or_else(|| "default".to_string());
     let prompt_storage = match prompt_storage.as_str() {
-        "default" | "notes" | "local" => prompt_storage,
+        "default" | "notes" | "local" | "git" => prompt_storage,
         other => {
             eprintln!(
                 "Warning: Invalid prompt_storage value '{}', using 'default'",
@@ -547,12 +581,12 @@ fn build_config() -> Config {
     };
 
     // Get default_prompt_storage setting (fallback for repos not in include list)
-    // Valid values: "default", "notes", "local", or None (defaults to "local")
+    // Valid values: "default", "notes", "local", "git", or None (defaults to "local")
     let default_prompt_storage = file_cfg
         .as_ref()
         .and_then(|c| c.default_prompt_storage.clone())
         .and_then(|s| {
-            if matches!(s.as_str(), "default" | "notes" | "local") {
+            if matches!(s.as_str(), "default" | "notes" | "local" | "git") {
                 Some(s)
             } else {
                 eprintln!(
@@ -577,6 +611,16 @@ fn build_config() -> Config {
     // Get quiet setting (defaults to false)
     let quiet = file_cfg.as_ref().and_then(|c| c.quiet).unwrap_or(false);
 
+    // Custom secret detectors and allowlist (regexes are compiled by the secret scanner)
+    let secret_rules = file_cfg
+        .as_ref()
+        .and_then(|c| c.secret_rules.clone())
+        .unwrap_or_default();
+    let secret_allowlist = file_cfg
+        .as_ref()
+        .and_then(|c| c.secret_allowlist.clone())
+        .unwrap_or_default();
+
     #[cfg(any(test, feature = "test-support"))]
     {
         let mut config = Config {
@@ -596,6 +640,8 @@ fn build_config() -> Config {
             default_prompt_storage,
             api_key,
             quiet,
+            secret_rules,
+            secret_allowlist,
         };
         apply_test_config_patch(&mut config);
         config
@@ -619,6 +665,8 @@ fn build_config() -> Config {
         default_prompt_storage,
         api_key,
         quiet,
+        secret_rules,
+        secret_allowlist,
     }
 }
 
@@ -848,7 +896,10 @@ fn apply_test_config_patch(config: &mut Config) {
         }
         if let Some(prompt_storage) = patch.prompt_storage {
             // Validate the value
-            if matches!(prompt_storage.as_str(), "default" | "notes" | "local") {
+            if matches!(
+                prompt_storage.as_str(),
+                "default" | "notes" | "local" | "git"
+            ) {
                 config.prompt_storage = prompt_storage;
             } else {
                 eprintln!(
@@ -857,6 +908,12 @@ fn apply_test_config_patch(config: &mut Config) {
                 );
             }
         }
+        if let Some(secret_rules) = patch.secret_rules {
+            config.secret_rules = secret_rules;
+        }
+        if let Some(secret_allowlist) = patch.secret_allowlist {
+            config.secret_allowlist = secret_allowlist;
+        }
     }
 }
 
@@ -891,6 +948,8 @@ mod tests {
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            secret_rules: vec![],
+            secret_allowlist: vec![],
         }
     }
 
@@ -998,6 +1057,8 @@ mod tests {
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            secret_rules: vec![],
+            secret_allowlist: vec![],
         }
     }
 
@@ -1114,6 +1175,8 @@ mod tests {
             default_prompt_storage: default_prompt_storage.map(|s| s.to_string()),
             api_key: None,
             quiet: false,
+            secret_rules: vec![],
+            secret_allowlist: vec![],
         }
     }
 
@@ -1252,6 +1315,10 @@ mod tests {
             "LOCAL".parse::<PromptStorageMode>().ok(),
             Some(PromptStorageMode::Local)
         );
+        assert_eq!(
+            "git".parse::<PromptStorageMode>().ok(),
+            Some(PromptStorageMode::Git)
+        );
         assert_eq!("invalid".parse::<PromptStorageMode>().ok(), None);
         assert_eq!("".parse::<PromptStorageMode>().ok(), None);
     }
@@ -1261,6 +1328,7 @@ mod tests {
         assert_eq!(PromptStorageMode::Default.as_str(), "default");
         assert_eq!(PromptStorageMode::Notes.as_str(), "notes");
         assert_eq!(PromptStorageMode::Local.as_str(), "local");
+        assert_eq!(PromptStorageMode::Git.as_str(), "git");
     }
 
     #[test]
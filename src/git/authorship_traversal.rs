@@ -1,11 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::error::GitAiError;
 use crate::git::refs::{commits_with_authorship_notes, note_blob_oids_for_commits};
-#[cfg(test)]
-use crate::git::repository::exec_git;
-use crate::git::repository::{Repository, exec_git_stdin};
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
 
 pub async fn load_ai_touched_files_for_commits(
     repo: &Repository,
@@ -44,6 +42,49 @@ pub async fn load_ai_touched_files_for_commits(
     .await
 }
 
+/// Load and parse authorship notes for many commits at once.
+///
+/// Resolves note blobs with one batched `cat-file --batch-check` and reads them with one
+/// `cat-file --batch`, so this stays fast for thousands of commits. Commits without a note
+/// (or with a note that fails to parse) are omitted from the result.
+pub fn load_authorship_logs_for_commits(
+    repo: &Repository,
+    commit_shas: &[String],
+) -> Result<HashMap<String, AuthorshipLog>, GitAiError> {
+    if commit_shas.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let note_blob_map = note_blob_oids_for_commits(repo, commit_shas)?;
+    if note_blob_map.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut blob_oids: Vec<String> = note_blob_map
+        .values()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    blob_oids.sort();
+
+    let blob_contents = batch_read_blobs_with_oids(&repo.global_args_for_exec(), &blob_oids)?;
+
+    let mut logs = HashMap::new();
+    for (commit_sha, blob_oid) in note_blob_map {
+        let Some(content) = blob_contents.get(&blob_oid) else {
+            continue;
+        };
+        if let Ok(mut log) = AuthorshipLog::deserialize_from_string(content) {
+            // Keep metadata aligned with the commit where this note is attached.
+            log.metadata.base_commit_sha = commit_sha.clone();
+            logs.insert(commit_sha, log);
+        }
+    }
+
+    Ok(logs)
+}
+
 /// Return true if any of the provided commits has an authorship note attached.
 pub fn commits_have_authorship_notes(
     repo: &Repository,
@@ -56,12 +97,11 @@ pub fn commits_have_authorship_notes(
     Ok(!commits_with_authorship_notes(repo, commit_shas)?.is_empty())
 }
 
-/// Get all notes as (note_blob_sha, commit_sha) pairs
-#[cfg(test)]
-fn get_notes_list(global_args: &[String]) -> Result<Vec<(String, String)>, GitAiError> {
-    let mut args = global_args.to_vec();
+/// Get all notes in the repository's notes namespace as (note_blob_sha, commit_sha) pairs
+pub fn get_notes_list(repo: &Repository) -> Result<Vec<(String, String)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
-    args.push("--ref=ai".to_string());
+    args.push(format!("--ref={}", repo.notes_namespace()));
     args.push("list".to_string());
 
     let output = match exec_git(&args) {
@@ -90,7 +130,8 @@ fn get_notes_list(global_args: &[String]) -> Result<Vec<(String, String)>, GitAi
     Ok(mappings)
 }
 
-fn batch_read_blobs_with_oids(
+/// Read many blobs with one `cat-file --batch`, keyed by blob oid
+pub fn batch_read_blobs_with_oids(
     global_args: &[String],
     blob_oids: &[String],
 ) -> Result<std::collections::HashMap<String, String>, GitAiError> {
@@ -195,8 +236,7 @@ mod tests {
             fetch_authorship_notes(&repo, "origin").unwrap();
 
             // Get all notes to find commits that have notes attached
-            let global_args = repo.global_args_for_exec();
-            let all_notes = get_notes_list(&global_args).unwrap();
+            let all_notes = get_notes_list(&repo).unwrap();
 
             if all_notes.len() < 3 {
                 println!(
@@ -0,0 +1,384 @@
+//! Prompt transcripts stored as git blobs (`prompt_storage = "git"`)
+//!
+//! Each transcript is written as a content-addressed blob holding the same JSON as a CAS
+//! object (`{"messages": [...]}`). A single ref, `refs/ai-prompts/blobs`, points at a commit
+//! whose tree holds every blob under its OID (with the same two-character fanout as notes). The
+//! prompt record in the authorship note points at the blob through
+//! `messages_url = "git-blob:<oid>"`, so notes stay small while transcripts travel with
+//! `git fetch`/`git push`. New blobs are appended with a commit on top of the current tip, and
+//! a fetched tip is merged in like notes: paths are derived from content, so the union of two
+//! trees never conflicts and the merged ref pushes as a fast-forward.
+
+use crate::api::types::CasMessagesObject;
+use crate::authorship::authorship_log::PromptRecord;
+use crate::authorship::transcript::Message;
+use crate::error::GitAiError;
+use crate::git::refs::{copy_ref, sanitize_remote_name};
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::utils::debug_log;
+use std::collections::BTreeMap;
+
+/// Ref pointing at the commit whose tree holds every prompt blob
+pub const PROMPTS_REF: &str = "refs/ai-prompts/blobs";
+
+/// `messages_url` scheme for prompts stored as blobs
+pub const PROMPT_BLOB_URL_SCHEME: &str = "git-blob:";
+
+/// Refspec pushing the prompts ref. It is pushed without force, so it must fast-forward.
+pub fn prompts_push_refspec() -> String {
+    format!("{0}:{0}", PROMPTS_REF)
+}
+
+/// Tracking ref the prompts ref of `remote_name` is fetched into before merging
+pub fn prompts_tracking_ref(remote_name: &str) -> String {
+    format!(
+        "refs/ai-prompts-remote/{}",
+        sanitize_remote_name(remote_name)
+    )
+}
+
+/// Blob OID referenced by a `git-blob:<oid>` messages URL
+pub fn prompt_blob_oid(messages_url: &str) -> Option<&str> {
+    messages_url
+        .strip_prefix(PROMPT_BLOB_URL_SCHEME)
+        .filter(|oid| !oid.is_empty() && oid.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Path of a prompt blob in the prompts tree
+fn prompt_blob_path(oid: &str) -> String {
+    format!("{}/{}", &oid[..2], &oid[2..])
+}
+
+/// Write each prompt's messages as a blob, add the blobs to `refs/ai-prompts/blobs`, then
+/// replace the messages with a `git-blob:` reference. Prompts without messages are left untouched.
+pub fn store_prompt_messages_as_blobs(
+    repo: &Repository,
+    prompts: &mut BTreeMap<String, PromptRecord>,
+) -> Result<(), GitAiError> {
+    let mut oids = BTreeMap::new();
+    for (prompt_id, prompt) in prompts.iter() {
+        if prompt.messages.is_empty() {
+            continue;
+        }
+        let messages_obj = serde_json::to_value(CasMessagesObject {
+            messages: prompt.messages.clone(),
+        })?;
+        let canonical = serde_json_canonicalizer::to_string(&messages_obj)
+            .map_err(|e| GitAiError::Generic(format!("Failed to canonicalize JSON: {}", e)))?;
+        let oid = repo.blob(canonical.as_bytes())?;
+        oids.insert(prompt_id.clone(), oid);
+    }
+
+    if oids.is_empty() {
+        return Ok(());
+    }
+
+    let blob_oids: Vec<String> = oids.values().cloned().collect();
+    append_prompt_blobs(repo, &blob_oids)?;
+
+    for (prompt_id, oid) in oids {
+        if let Some(prompt) = prompts.get_mut(&prompt_id) {
+            prompt.messages_url = Some(format!("{}{}", PROMPT_BLOB_URL_SCHEME, oid));
+            prompt.messages.clear();
+        }
+    }
+    Ok(())
+}
+
+/// Commit `blob_oids` on top of `refs/ai-prompts/blobs`, skipping blobs it already holds
+fn append_prompt_blobs(repo: &Repository, blob_oids: &[String]) -> Result<(), GitAiError> {
+    let tip = resolve_commit(repo, PROMPTS_REF)?;
+
+    let mut new_oids: Vec<&String> = blob_oids.iter().collect();
+    new_oids.sort();
+    new_oids.dedup();
+    if let Some(tip) = &tip {
+        let mut args = repo.global_args_for_exec();
+        args.push("ls-tree".to_string());
+        args.push("-r".to_string());
+        args.push("--name-only".to_string());
+        args.push(tip.clone());
+        args.push("--".to_string());
+        args.extend(new_oids.iter().map(|oid| prompt_blob_path(oid)));
+        let output = exec_git(&args)?;
+        let existing = String::from_utf8(output.stdout)?;
+        let existing: Vec<&str> = existing.lines().collect();
+        new_oids.retain(|oid| !existing.contains(&prompt_blob_path(oid).as_str()));
+    }
+    if new_oids.is_empty() {
+        return Ok(());
+    }
+
+    let entries: Vec<String> = new_oids.into_iter().cloned().collect();
+    commit_prompt_blobs(repo, tip.as_deref(), None, &entries)
+}
+
+/// Merge the prompts ref at `source_ref` (usually a remote tracking ref) into
+/// `refs/ai-prompts/blobs`. Fast-forwards when one side contains the other, otherwise commits
+/// the union of both trees with both tips as parents.
+pub fn merge_prompt_blobs_from_ref(repo: &Repository, source_ref: &str) -> Result<(), GitAiError> {
+    let Some(theirs) = resolve_commit(repo, source_ref)? else {
+        return Ok(());
+    };
+    let Some(ours) = resolve_commit(repo, PROMPTS_REF)? else {
+        debug_log(&format!("initializing {} from {}", PROMPTS_REF, source_ref));
+        return copy_ref(repo, &theirs, PROMPTS_REF);
+    };
+
+    if ours == theirs || is_ancestor(repo, &theirs, &ours)? {
+        return Ok(());
+    }
+    if is_ancestor(repo, &ours, &theirs)? {
+        debug_log(&format!(
+            "fast-forwarding {} to {}",
+            PROMPTS_REF, source_ref
+        ));
+        return copy_ref(repo, &theirs, PROMPTS_REF);
+    }
+
+    // Blobs only the source has: additions when diffing our tree against theirs
+    let mut args = repo.global_args_for_exec();
+    args.push("diff-tree".to_string());
+    args.push("-r".to_string());
+    args.push("--no-renames".to_string());
+    args.push("--diff-filter=A".to_string());
+    args.push(ours.clone());
+    args.push(theirs.clone());
+    let output = exec_git(&args)?;
+    let missing: Vec<String> = String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(3).map(str::to_string))
+        .collect();
+
+    debug_log(&format!(
+        "merging {} prompt blobs from {} into {}",
+        missing.len(),
+        source_ref,
+        PROMPTS_REF
+    ));
+    commit_prompt_blobs(repo, Some(&ours), Some(&theirs), &missing)
+}
+
+/// Write a commit to `refs/ai-prompts/blobs` adding `blob_oids` to the tree of `parent`
+fn commit_prompt_blobs(
+    repo: &Repository,
+    parent: Option<&str>,
+    merge_parent: Option<&str>,
+    blob_oids: &[String],
+) -> Result<(), GitAiError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| GitAiError::Generic(format!("System clock before epoch: {}", e)))?
+        .as_secs();
+
+    let mut script = Vec::<u8>::new();
+    script.extend_from_slice(format!("commit {}\n", PROMPTS_REF).as_bytes());
+    script.extend_from_slice(format!("committer git-ai <git-ai@local> {} +0000\n", now).as_bytes());
+    script.extend_from_slice(b"data 0\n");
+    if let Some(parent) = parent {
+        script.extend_from_slice(format!("from {}\n", parent).as_bytes());
+    }
+    if let Some(merge_parent) = merge_parent {
+        script.extend_from_slice(format!("merge {}\n", merge_parent).as_bytes());
+    }
+    for oid in blob_oids {
+        script
+            .extend_from_slice(format!("M 100644 {} {}\n", oid, prompt_blob_path(oid)).as_bytes());
+    }
+    script.extend_from_slice(b"\n");
+
+    let mut fast_import_args = repo.global_args_for_exec();
+    fast_import_args.push("fast-import".to_string());
+    fast_import_args.push("--quiet".to_string());
+    exec_git_stdin(&fast_import_args, &script)?;
+    Ok(())
+}
+
+/// Commit a ref points at, or None when the ref does not exist
+fn resolve_commit(repo: &Repository, ref_name: &str) -> Result<Option<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-parse".to_string());
+    args.push("--verify".to_string());
+    args.push("--quiet".to_string());
+    args.push(format!("{}^{{commit}}", ref_name));
+    match exec_git(&args) {
+        Ok(output) => Ok(Some(String::from_utf8(output.stdout)?.trim().to_string())),
+        Err(GitAiError::GitCliError {
+            code: Some(128), ..
+        })
+        | Err(GitAiError::GitCliError { code: Some(1), .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn is_ancestor(repo: &Repository, ancestor: &str, descendant: &str) -> Result<bool, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("merge-base".to_string());
+    args.push("--is-ancestor".to_string());
+    args.push(ancestor.to_string());
+    args.push(descendant.to_string());
+    match exec_git(&args) {
+        Ok(_) => Ok(true),
+        Err(GitAiError::GitCliError { code: Some(1), .. }) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read the messages stored in a prompt blob
+pub fn read_prompt_blob(repo: &Repository, oid: &str) -> Result<Vec<Message>, GitAiError> {
+    let content = repo.find_blob(oid.to_string())?.content()?;
+    let messages_obj: CasMessagesObject = serde_json::from_slice(&content)?;
+    Ok(messages_obj.messages)
+}
+
+/// Fill in `prompt.messages` from its blob when the note only holds a `git-blob:` reference.
+/// Returns true if messages were loaded.
+pub fn resolve_prompt_messages(repo: &Repository, prompt: &mut PromptRecord) -> bool {
+    if !prompt.messages.is_empty() {
+        return false;
+    }
+    let Some(oid) = prompt.messages_url.as_deref().and_then(prompt_blob_oid) else {
+        return false;
+    };
+    match read_prompt_blob(repo, oid) {
+        Ok(messages) => {
+            prompt.messages = messages;
+            true
+        }
+        Err(e) => {
+            debug_log(&format!("failed to read prompt blob {}: {}", oid, e));
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::working_log::AgentId;
+    use crate::git::test_utils::TmpRepo;
+
+    fn prompt(messages: Vec<Message>) -> PromptRecord {
+        PromptRecord {
+            agent_id: AgentId {
+                tool: "claude".to_string(),
+                id: "session".to_string(),
+                model: "claude-sonnet-4".to_string(),
+            },
+            human_author: None,
+            messages,
+            total_additions: 1,
+            total_deletions: 0,
+            accepted_lines: 1,
+            overriden_lines: 0,
+            messages_url: None,
+        }
+    }
+
+    #[test]
+    fn test_prompt_blob_oid() {
+        assert_eq!(prompt_blob_oid("git-blob:abc123"), Some("abc123"));
+        assert_eq!(prompt_blob_oid("git-blob:"), None);
+        assert_eq!(prompt_blob_oid("git-blob:../../etc"), None);
+        assert_eq!(prompt_blob_oid("https://usegitai.com/cas/abc123"), None);
+    }
+
+    fn prompt_blob_paths(repo: &Repository, rev: &str) -> Vec<String> {
+        let mut args = repo.global_args_for_exec();
+        args.extend(["ls-tree", "-r", "--name-only", rev].map(String::from));
+        let output = exec_git(&args).unwrap();
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn store_prompt(repo: &Repository, text: &str) -> String {
+        let mut prompts = BTreeMap::from([(
+            "prompt".to_string(),
+            prompt(vec![Message::user(text.to_string(), None)]),
+        )]);
+        store_prompt_messages_as_blobs(repo, &mut prompts).unwrap();
+        prompt_blob_oid(prompts["prompt"].messages_url.as_deref().unwrap())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_store_and_resolve_prompt_blobs() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        let repo = tmp_repo.gitai_repo();
+        assert_eq!(resolve_commit(repo, PROMPTS_REF).unwrap(), None);
+
+        let messages = vec![Message::user("Add a helper".to_string(), None)];
+        let mut prompts = BTreeMap::from([
+            ("with_messages".to_string(), prompt(messages.clone())),
+            ("empty".to_string(), prompt(Vec::new())),
+        ]);
+        store_prompt_messages_as_blobs(repo, &mut prompts).unwrap();
+
+        let stored = &prompts["with_messages"];
+        assert!(stored.messages.is_empty());
+        let oid = prompt_blob_oid(stored.messages_url.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            prompt_blob_paths(repo, PROMPTS_REF),
+            vec![prompt_blob_path(oid)]
+        );
+        assert_eq!(prompts["empty"].messages_url, None);
+
+        let mut resolved = stored.clone();
+        assert!(resolve_prompt_messages(repo, &mut resolved));
+        assert_eq!(resolved.messages, messages);
+    }
+
+    #[test]
+    fn test_store_appends_to_the_prompts_ref() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        let repo = tmp_repo.gitai_repo();
+
+        let first = store_prompt(repo, "first");
+        let first_tip = resolve_commit(repo, PROMPTS_REF).unwrap().unwrap();
+        let second = store_prompt(repo, "second");
+        let second_tip = resolve_commit(repo, PROMPTS_REF).unwrap().unwrap();
+        assert!(is_ancestor(repo, &first_tip, &second_tip).unwrap());
+
+        let mut expected = vec![prompt_blob_path(&first), prompt_blob_path(&second)];
+        expected.sort();
+        assert_eq!(prompt_blob_paths(repo, PROMPTS_REF), expected);
+
+        // Storing a blob the ref already holds doesn't add a commit
+        store_prompt(repo, "second");
+        assert_eq!(resolve_commit(repo, PROMPTS_REF).unwrap(), Some(second_tip));
+    }
+
+    #[test]
+    fn test_merge_prompt_blobs_from_diverged_ref() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        let repo = tmp_repo.gitai_repo();
+
+        let shared = store_prompt(repo, "shared");
+        let base = resolve_commit(repo, PROMPTS_REF).unwrap().unwrap();
+        let theirs_oid = store_prompt(repo, "theirs");
+        let theirs = resolve_commit(repo, PROMPTS_REF).unwrap().unwrap();
+        copy_ref(repo, &theirs, "refs/ai-prompts-remote/origin").unwrap();
+        copy_ref(repo, &base, PROMPTS_REF).unwrap();
+        let ours_oid = store_prompt(repo, "ours");
+        let ours = resolve_commit(repo, PROMPTS_REF).unwrap().unwrap();
+
+        merge_prompt_blobs_from_ref(repo, "refs/ai-prompts-remote/origin").unwrap();
+        let merged = resolve_commit(repo, PROMPTS_REF).unwrap().unwrap();
+        assert!(is_ancestor(repo, &ours, &merged).unwrap());
+        assert!(is_ancestor(repo, &theirs, &merged).unwrap());
+        let mut expected: Vec<String> = [&shared, &theirs_oid, &ours_oid]
+            .into_iter()
+            .map(|oid| prompt_blob_path(oid))
+            .collect();
+        expected.sort();
+        assert_eq!(prompt_blob_paths(repo, PROMPTS_REF), expected);
+
+        // Merging again is a no-op, and a ref behind ours is left alone
+        merge_prompt_blobs_from_ref(repo, "refs/ai-prompts-remote/origin").unwrap();
+        assert_eq!(resolve_commit(repo, PROMPTS_REF).unwrap(), Some(merged));
+    }
+}
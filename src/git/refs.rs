@@ -1,4 +1,7 @@
-use crate::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, AuthorshipLog};
+use crate::authorship::authorship_log_serialization::{
+    AUTHORSHIP_LOG_VERSION, AuthorshipLog, is_supported_schema_version,
+};
+use crate::authorship::note_signing::maybe_sign_note;
 use crate::authorship::working_log::Checkpoint;
 use crate::error::GitAiError;
 use crate::git::repository::{Repository, exec_git, exec_git_stdin};
@@ -6,9 +9,75 @@ use crate::utils::debug_log;
 use serde_json;
 use std::collections::{HashMap, HashSet};
 
-// Modern refspecs without force to enable proper merging
+/// Default notes namespace: authorship logs live in `refs/notes/ai`
 pub const AI_AUTHORSHIP_REFNAME: &str = "ai";
-pub const AI_AUTHORSHIP_PUSH_REFSPEC: &str = "refs/notes/ai:refs/notes/ai";
+
+/// Git config key for the namespace new authorship notes are written to
+pub const NOTES_NAMESPACE_CONFIG_KEY: &str = "git-ai.notesRef";
+/// Multi-valued git config key for extra namespaces that blame, stats and search merge in
+pub const READ_NOTES_NAMESPACES_CONFIG_KEY: &str = "git-ai.readNotesRef";
+
+/// Accept either `ai-internal` or `refs/notes/ai-internal`, returning the short namespace
+pub fn normalize_notes_namespace(value: &str) -> Option<String> {
+    let namespace = value.trim();
+    let namespace = namespace.strip_prefix("refs/notes/").unwrap_or(namespace);
+    let valid = !namespace.is_empty()
+        && !namespace.contains("..")
+        && !namespace.starts_with('/')
+        && !namespace.ends_with('/')
+        && !namespace
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, ':' | '~' | '^' | '?' | '*' | '[' | '\\'));
+    valid.then(|| namespace.to_string())
+}
+
+pub fn notes_ref_for_namespace(namespace: &str) -> String {
+    format!("refs/notes/{}", namespace)
+}
+
+// Modern refspecs without force to enable proper merging
+pub fn push_refspec_for_namespace(namespace: &str) -> String {
+    let notes_ref = notes_ref_for_namespace(namespace);
+    format!("{}:{}", notes_ref, notes_ref)
+}
+
+/// Namespaces read by blame, stats and search: the write namespace first, then every
+/// `git-ai.readNotesRef` value
+pub fn read_notes_namespaces(repo: &Repository) -> Vec<String> {
+    let mut namespaces = vec![repo.notes_namespace().to_string()];
+    for value in repo
+        .config_get_all_str(READ_NOTES_NAMESPACES_CONFIG_KEY)
+        .unwrap_or_default()
+    {
+        if let Some(namespace) = normalize_notes_namespace(&value)
+            && !namespaces.contains(&namespace)
+        {
+            namespaces.push(namespace);
+        }
+    }
+    namespaces
+}
+
+/// Namespaces synced with `remote_name`: every `remote.<name>.gitAiNotesRef` value, or just
+/// `ai` when none is configured. Namespaces not listed are never fetched from or pushed to
+/// that remote.
+pub fn remote_notes_namespaces(repo: &Repository, remote_name: &str) -> Vec<String> {
+    let mut namespaces = Vec::new();
+    for value in repo
+        .config_get_all_str(&format!("remote.{}.gitAiNotesRef", remote_name))
+        .unwrap_or_default()
+    {
+        if let Some(namespace) = normalize_notes_namespace(&value)
+            && !namespaces.contains(&namespace)
+        {
+            namespaces.push(namespace);
+        }
+    }
+    if namespaces.is_empty() {
+        namespaces.push(AI_AUTHORSHIP_REFNAME.to_string());
+    }
+    namespaces
+}
 
 pub fn notes_add(
     repo: &Repository,
@@ -17,13 +86,15 @@ pub fn notes_add(
 ) -> Result<(), GitAiError> {
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
-    args.push("--ref=ai".to_string());
+    args.push(format!("--ref={}", repo.notes_namespace()));
     args.push("add".to_string());
     args.push("-f".to_string()); // Always force overwrite
     args.push("-F".to_string());
     args.push("-".to_string()); // Read note content from stdin
     args.push(commit_sha.to_string());
 
+    let note_content = maybe_sign_note(repo, note_content);
+
     // Use stdin to provide the note content to avoid command line length limits
     exec_git_stdin(&args, note_content.as_bytes())?;
     Ok(())
@@ -37,12 +108,16 @@ fn notes_path_for_object(oid: &str) -> String {
     }
 }
 
-fn flat_note_pathspec_for_commit(commit_sha: &str) -> String {
-    format!("refs/notes/ai:{}", commit_sha)
+fn flat_note_pathspec_for_commit(namespace: &str, commit_sha: &str) -> String {
+    format!("{}:{}", notes_ref_for_namespace(namespace), commit_sha)
 }
 
-fn fanout_note_pathspec_for_commit(commit_sha: &str) -> String {
-    format!("refs/notes/ai:{}", notes_path_for_object(commit_sha))
+fn fanout_note_pathspec_for_commit(namespace: &str, commit_sha: &str) -> String {
+    format!(
+        "{}:{}",
+        notes_ref_for_namespace(namespace),
+        notes_path_for_object(commit_sha)
+    )
 }
 
 fn parse_batch_check_blob_oid(line: &str) -> Option<String> {
@@ -75,13 +150,14 @@ pub fn note_blob_oids_for_commits(
     args.push("cat-file".to_string());
     args.push("--batch-check".to_string());
 
+    let namespace = repo.notes_namespace();
     let mut stdin_data = String::new();
     for commit_sha in commit_shas {
         // Notes can be stored with either flat paths (<sha>) or fanout paths (<aa>/<bb...>).
         // Query both forms so this works regardless of repository note fanout state.
-        stdin_data.push_str(&flat_note_pathspec_for_commit(commit_sha));
+        stdin_data.push_str(&flat_note_pathspec_for_commit(namespace, commit_sha));
         stdin_data.push('\n');
-        stdin_data.push_str(&fanout_note_pathspec_for_commit(commit_sha));
+        stdin_data.push_str(&fanout_note_pathspec_for_commit(namespace, commit_sha));
         stdin_data.push('\n');
     }
 
@@ -114,7 +190,7 @@ pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Resul
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
     args.push("--verify".to_string());
-    args.push("refs/notes/ai".to_string());
+    args.push(notes_ref_for_namespace(repo.notes_namespace()));
     let existing_notes_tip = match exec_git(&args) {
         Ok(output) => Some(String::from_utf8(output.stdout)?.trim().to_string()),
         Err(GitAiError::GitCliError {
@@ -128,7 +204,10 @@ pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Resul
     let mut seen = HashSet::new();
     for (commit_sha, note_content) in entries.iter().rev() {
         if seen.insert(commit_sha.as_str()) {
-            deduped_entries.push((commit_sha.clone(), note_content.clone()));
+            deduped_entries.push((
+                commit_sha.clone(),
+                maybe_sign_note(repo, note_content).into_owned(),
+            ));
         }
     }
     deduped_entries.reverse();
@@ -148,7 +227,13 @@ pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Resul
         script.extend_from_slice(b"\n");
     }
 
-    script.extend_from_slice(b"commit refs/notes/ai\n");
+    script.extend_from_slice(
+        format!(
+            "commit {}\n",
+            notes_ref_for_namespace(repo.notes_namespace())
+        )
+        .as_bytes(),
+    );
     script.extend_from_slice(format!("committer git-ai <git-ai@local> {} +0000\n", now).as_bytes());
     script.extend_from_slice(b"data 0\n");
     if let Some(existing_tip) = existing_notes_tip {
@@ -177,7 +262,6 @@ pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Resul
 /// Batch-attach existing note blobs to commits without rewriting blob contents.
 ///
 /// Each entry is (commit_sha, existing_note_blob_oid).
-#[allow(dead_code)]
 pub fn notes_add_blob_batch(
     repo: &Repository,
     entries: &[(String, String)],
@@ -189,7 +273,7 @@ pub fn notes_add_blob_batch(
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
     args.push("--verify".to_string());
-    args.push("refs/notes/ai".to_string());
+    args.push(notes_ref_for_namespace(repo.notes_namespace()));
     let existing_notes_tip = match exec_git(&args) {
         Ok(output) => Some(String::from_utf8(output.stdout)?.trim().to_string()),
         Err(GitAiError::GitCliError {
@@ -214,7 +298,13 @@ pub fn notes_add_blob_batch(
         .as_secs();
 
     let mut script = Vec::<u8>::new();
-    script.extend_from_slice(b"commit refs/notes/ai\n");
+    script.extend_from_slice(
+        format!(
+            "commit {}\n",
+            notes_ref_for_namespace(repo.notes_namespace())
+        )
+        .as_bytes(),
+    );
     script.extend_from_slice(format!("committer git-ai <git-ai@local> {} +0000\n", now).as_bytes());
     script.extend_from_slice(b"data 0\n");
     if let Some(existing_tip) = existing_notes_tip {
@@ -329,9 +419,17 @@ pub fn get_commits_with_notes_from_list(
 
 // Show an authorship note and return its JSON content if found, or None if it doesn't exist.
 pub fn show_authorship_note(repo: &Repository, commit_sha: &str) -> Option<String> {
+    show_authorship_note_in_namespace(repo, repo.notes_namespace(), commit_sha)
+}
+
+pub fn show_authorship_note_in_namespace(
+    repo: &Repository,
+    namespace: &str,
+    commit_sha: &str,
+) -> Option<String> {
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
-    args.push("--ref=ai".to_string());
+    args.push(format!("--ref={}", namespace));
     args.push("show".to_string());
     args.push(commit_sha.to_string());
 
@@ -367,6 +465,61 @@ pub fn get_authorship(repo: &Repository, commit_sha: &str) -> Option<AuthorshipL
     Some(authorship_log)
 }
 
+/// Like `get_authorship`, but reads every namespace from `read_notes_namespaces` and merges
+/// the logs found for the commit.
+pub fn get_authorship_merged(repo: &Repository, commit_sha: &str) -> Option<AuthorshipLog> {
+    let namespaces = read_notes_namespaces(repo);
+    let logs = namespaces
+        .iter()
+        .filter_map(|namespace| show_authorship_note_in_namespace(repo, namespace, commit_sha))
+        .filter_map(|content| AuthorshipLog::deserialize_from_string(&content).ok());
+    let mut authorship_log = merge_authorship_logs(logs)?;
+    authorship_log.metadata.base_commit_sha = commit_sha.to_string();
+    Some(authorship_log)
+}
+
+/// Like `get_reference_as_authorship_log_v3`, but merged across `read_notes_namespaces`.
+/// Logs with an unsupported schema version are skipped.
+pub fn get_reference_as_authorship_log_v3_merged(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<AuthorshipLog, GitAiError> {
+    let namespaces = read_notes_namespaces(repo);
+    let logs = namespaces
+        .iter()
+        .filter_map(|namespace| show_authorship_note_in_namespace(repo, namespace, commit_sha))
+        .filter_map(|content| AuthorshipLog::deserialize_from_string(&content).ok())
+        .filter(|log| is_supported_schema_version(&log.metadata.schema_version));
+    let mut authorship_log = merge_authorship_logs(logs)
+        .ok_or_else(|| GitAiError::Generic("No authorship note found".to_string()))?;
+    authorship_log.metadata.base_commit_sha = commit_sha.to_string();
+    Ok(authorship_log)
+}
+
+/// Merge authorship logs attached to the same commit in different namespaces.
+///
+/// The first log takes precedence: its attestation entries are kept last so they win
+/// line lookups, and its prompt records win when a hash appears more than once.
+fn merge_authorship_logs(mut logs: impl Iterator<Item = AuthorshipLog>) -> Option<AuthorshipLog> {
+    let mut merged = logs.next()?;
+    for log in logs {
+        for file in log.attestations {
+            let merged_file = merged.get_or_create_file(&file.file_path);
+            merged_file.entries.splice(0..0, file.entries);
+        }
+        for (hash, prompt) in log.metadata.prompts {
+            merged.metadata.prompts.entry(hash).or_insert(prompt);
+        }
+        for (file, spans) in log.metadata.char_spans {
+            merged.metadata.char_spans.entry(file).or_insert(spans);
+        }
+        for (file, states) in log.metadata.line_states {
+            merged.metadata.line_states.entry(file).or_insert(states);
+        }
+    }
+    Some(merged)
+}
+
 #[allow(dead_code)]
 pub fn get_reference_as_working_log(
     repo: &Repository,
@@ -396,7 +549,7 @@ pub fn get_reference_as_authorship_log_v3(
     };
 
     // Check version compatibility
-    if authorship_log.metadata.schema_version != AUTHORSHIP_LOG_VERSION {
+    if !is_supported_schema_version(&authorship_log.metadata.schema_version) {
         return Err(GitAiError::Generic(format!(
             "Unsupported authorship log version: {} (expected: {})",
             authorship_log.metadata.schema_version, AUTHORSHIP_LOG_VERSION
@@ -411,7 +564,7 @@ pub fn get_reference_as_authorship_log_v3(
 
 /// Sanitize a remote name to create a safe ref name
 /// Replaces special characters with underscores to ensure valid ref names
-fn sanitize_remote_name(remote: &str) -> String {
+pub(crate) fn sanitize_remote_name(remote: &str) -> String {
     remote
         .chars()
         .map(|c| {
@@ -424,17 +577,21 @@ fn sanitize_remote_name(remote: &str) -> String {
         .collect()
 }
 
-/// Generate a tracking ref name for notes from a specific remote
-/// Returns a ref like "refs/notes/ai-remote/origin"
+/// Generate a tracking ref name for a notes namespace fetched from a specific remote
+/// Returns a ref like "refs/notes/ai-remote/origin" (or "refs/notes/<namespace>-remote/origin")
 ///
-/// SAFETY: These tracking refs are stored under refs/notes/ai-remote/* which:
+/// SAFETY: These tracking refs are stored under refs/notes/<namespace>-remote/* which:
 /// - Won't be pushed by `git push` (only pushes refs/heads/* by default)
 /// - Won't be pushed by `git push --all` (only pushes refs/heads/*)
 /// - Won't be pushed by `git push --tags` (only pushes refs/tags/*)
 /// - **WILL** be pushed by `git push --mirror` (usually only used for backups, etc.)
 /// - **WILL** be pushed if user explicitly specifies refs/notes/ai-remote/* (extremely rare)
-pub fn tracking_ref_for_remote(remote_name: &str) -> String {
-    format!("refs/notes/ai-remote/{}", sanitize_remote_name(remote_name))
+pub fn tracking_ref_for_namespace(namespace: &str, remote_name: &str) -> String {
+    format!(
+        "refs/notes/{}-remote/{}",
+        namespace,
+        sanitize_remote_name(remote_name)
+    )
 }
 
 /// Check if a ref exists in the repository
@@ -448,12 +605,16 @@ pub fn ref_exists(repo: &Repository, ref_name: &str) -> bool {
     exec_git(&args).is_ok()
 }
 
-/// Merge notes from a source ref into refs/notes/ai
+/// Merge notes from a source ref into refs/notes/<namespace>
 /// Uses the 'ours' strategy to combine notes without data loss
-pub fn merge_notes_from_ref(repo: &Repository, source_ref: &str) -> Result<(), GitAiError> {
+pub fn merge_notes_from_ref(
+    repo: &Repository,
+    source_ref: &str,
+    namespace: &str,
+) -> Result<(), GitAiError> {
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
-    args.push(format!("--ref={}", AI_AUTHORSHIP_REFNAME));
+    args.push(format!("--ref={}", namespace));
     args.push("merge".to_string());
     args.push("-s".to_string());
     args.push("ours".to_string());
@@ -461,8 +622,9 @@ pub fn merge_notes_from_ref(repo: &Repository, source_ref: &str) -> Result<(), G
     args.push(source_ref.to_string());
 
     debug_log(&format!(
-        "Merging notes from {} into refs/notes/ai",
-        source_ref
+        "Merging notes from {} into {}",
+        source_ref,
+        notes_ref_for_namespace(namespace)
     ));
     exec_git(&args)?;
     Ok(())
@@ -481,14 +643,23 @@ pub fn copy_ref(repo: &Repository, source_ref: &str, dest_ref: &str) -> Result<(
 }
 
 /// Search AI notes for a pattern and return matching commit SHAs ordered by commit date (newest first)
-/// Uses git grep to search through refs/notes/ai
+/// Uses git grep to search through every namespace from `read_notes_namespaces`
 pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, GitAiError> {
+    let mut notes_refs: Vec<String> = read_notes_namespaces(repo)
+        .iter()
+        .map(|namespace| notes_ref_for_namespace(namespace))
+        .collect();
+    // git grep fails outright on a missing tree-ish, so only search namespaces that exist
+    if notes_refs.len() > 1 {
+        notes_refs.retain(|notes_ref| ref_exists(repo, notes_ref));
+    }
+
     let mut args = repo.global_args_for_exec();
     args.push("--no-pager".to_string());
     args.push("grep".to_string());
     args.push("-nI".to_string());
     args.push(pattern.to_string());
-    args.push("refs/notes/ai".to_string());
+    args.extend(notes_refs.iter().cloned());
 
     let output = exec_git(&args)?;
     let stdout = String::from_utf8(output.stdout)
@@ -498,8 +669,10 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
     // Extract the commit SHA from the path
     let mut shas = HashSet::new();
     for line in stdout.lines() {
-        if let Some(path_and_rest) = line.strip_prefix("refs/notes/ai:")
-            && let Some(path_end) = path_and_rest.find(':')
+        if let Some(path_and_rest) = notes_refs.iter().find_map(|notes_ref| {
+            line.strip_prefix(notes_ref.as_str())
+                .and_then(|rest| rest.strip_prefix(':'))
+        }) && let Some(path_end) = path_and_rest.find(':')
         {
             let path = &path_and_rest[..path_end];
             // Path is in format "ab/cdef123..." - combine to get full SHA
@@ -511,12 +684,16 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
     // If we have multiple results, sort by commit date (newest first)
     if shas.len() > 1 {
         let sha_vec: Vec<String> = shas.into_iter().collect();
-        
-        eprintln!("[DEBUG] Found {} commits with pattern '{}' in git notes", sha_vec.len(), pattern);
+
+        eprintln!(
+            "[DEBUG] Found {} commits with pattern '{}' in git notes",
+            sha_vec.len(),
+            pattern
+        );
         for sha in &sha_vec {
             eprintln!("[DEBUG]   - {}", sha);
         }
-        
+
         // FILTER OUT NON-EXISTENT COMMITS using git cat-file -e
         let existing_shas: Vec<String> = sha_vec
             .into_iter()
@@ -525,7 +702,7 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
                 args.push("cat-file".to_string());
                 args.push("-e".to_string());
                 args.push(sha.clone());
-                
+
                 let exists = exec_git(&args).is_ok();
                 if !exists {
                     eprintln!("[DEBUG] Filtering out non-existent commit: {}", sha);
@@ -533,22 +710,25 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
                 exists
             })
             .collect();
-        
-        eprintln!("[DEBUG] After filtering: {} existing commits", existing_shas.len());
+
+        eprintln!(
+            "[DEBUG] After filtering: {} existing commits",
+            existing_shas.len()
+        );
         for sha in &existing_shas {
             eprintln!("[DEBUG]   - {}", sha);
         }
-        
+
         if existing_shas.is_empty() {
             return Err(GitAiError::Generic(
-                "No existing commits found in git notes for pattern".to_string()
+                "No existing commits found in git notes for pattern".to_string(),
             ));
         }
-        
+
         if existing_shas.len() == 1 {
             return Ok(existing_shas);
         }
-        
+
         // Sort only existing commits by date
         let mut args = repo.global_args_for_exec();
         args.push("log".to_string());
@@ -573,13 +753,13 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
             args.push("cat-file".to_string());
             args.push("-e".to_string());
             args.push(sha.clone());
-            
+
             if exec_git(&args).is_ok() {
                 Ok(sha_vec)
             } else {
                 eprintln!("[DEBUG] Single commit {} does not exist", sha);
                 Err(GitAiError::Generic(
-                    "Commit referenced in git notes does not exist".to_string()
+                    "Commit referenced in git notes does not exist".to_string(),
                 ))
             }
         } else {
@@ -726,24 +906,48 @@ mod tests {
     }
 
     #[test]
-    fn test_tracking_ref_for_remote() {
+    fn test_tracking_ref_for_namespace() {
         assert_eq!(
-            tracking_ref_for_remote("origin"),
+            tracking_ref_for_namespace(AI_AUTHORSHIP_REFNAME, "origin"),
             "refs/notes/ai-remote/origin"
         );
         assert_eq!(
-            tracking_ref_for_remote("upstream"),
+            tracking_ref_for_namespace(AI_AUTHORSHIP_REFNAME, "upstream"),
             "refs/notes/ai-remote/upstream"
         );
         assert_eq!(
-            tracking_ref_for_remote("my-fork"),
+            tracking_ref_for_namespace(AI_AUTHORSHIP_REFNAME, "my-fork"),
             "refs/notes/ai-remote/my-fork"
         );
         // Special characters get sanitized
         assert_eq!(
-            tracking_ref_for_remote("remote/with/slashes"),
+            tracking_ref_for_namespace(AI_AUTHORSHIP_REFNAME, "remote/with/slashes"),
             "refs/notes/ai-remote/remote_with_slashes"
         );
+        assert_eq!(
+            tracking_ref_for_namespace("ai-internal", "origin"),
+            "refs/notes/ai-internal-remote/origin"
+        );
+    }
+
+    #[test]
+    fn test_normalize_notes_namespace() {
+        assert_eq!(
+            normalize_notes_namespace("ai-internal"),
+            Some("ai-internal".to_string())
+        );
+        assert_eq!(
+            normalize_notes_namespace(" refs/notes/ai-internal "),
+            Some("ai-internal".to_string())
+        );
+        assert_eq!(
+            normalize_notes_namespace("team/ai"),
+            Some("team/ai".to_string())
+        );
+        assert_eq!(normalize_notes_namespace(""), None);
+        assert_eq!(normalize_notes_namespace("refs/notes/"), None);
+        assert_eq!(normalize_notes_namespace("a..b"), None);
+        assert_eq!(normalize_notes_namespace("ai:evil"), None);
     }
 
     #[test]
@@ -824,7 +1028,12 @@ mod tests {
         let initial_note_c = show_authorship_note(tmp_repo.gitai_repo(), &commit_c);
 
         // Merge notes from refs/notes/test into refs/notes/ai
-        merge_notes_from_ref(tmp_repo.gitai_repo(), "refs/notes/test").expect("merge notes");
+        merge_notes_from_ref(
+            tmp_repo.gitai_repo(),
+            "refs/notes/test",
+            AI_AUTHORSHIP_REFNAME,
+        )
+        .expect("merge notes");
 
         // After merge, commit C should have a note on refs/notes/ai
         let final_note_c = show_authorship_note(tmp_repo.gitai_repo(), &commit_c);
@@ -1060,7 +1269,7 @@ mod tests {
     #[test]
     fn test_flat_note_pathspec_for_commit() {
         let sha = "abcdef1234567890abcdef1234567890abcdef12";
-        let pathspec = flat_note_pathspec_for_commit(sha);
+        let pathspec = flat_note_pathspec_for_commit(AI_AUTHORSHIP_REFNAME, sha);
         assert_eq!(
             pathspec,
             "refs/notes/ai:abcdef1234567890abcdef1234567890abcdef12"
@@ -1070,7 +1279,7 @@ mod tests {
     #[test]
     fn test_fanout_note_pathspec_for_commit() {
         let sha = "abcdef1234567890abcdef1234567890abcdef12";
-        let pathspec = fanout_note_pathspec_for_commit(sha);
+        let pathspec = fanout_note_pathspec_for_commit(AI_AUTHORSHIP_REFNAME, sha);
         assert_eq!(
             pathspec,
             "refs/notes/ai:ab/cdef1234567890abcdef1234567890abcdef12"
@@ -1181,4 +1390,25 @@ mod tests {
             panic!("Expected version mismatch error");
         }
     }
+
+    #[test]
+    fn test_get_reference_as_authorship_log_v3_reads_3_0_0_notes() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+
+        tmp_repo
+            .write_file("test.txt", "content\n", true)
+            .expect("write file");
+        tmp_repo.commit_with_message("Commit").expect("commit");
+        let commit_sha = tmp_repo.get_head_commit_sha().expect("head");
+
+        let mut log = AuthorshipLog::new();
+        log.metadata.schema_version = "authorship/3.0.0".to_string();
+        let note_content = log.serialize_to_string().expect("serialize");
+        notes_add(tmp_repo.gitai_repo(), &commit_sha, &note_content).expect("add note");
+
+        let result = get_reference_as_authorship_log_v3(tmp_repo.gitai_repo(), &commit_sha)
+            .expect("3.0.0 notes should still be readable");
+        assert_eq!(result.metadata.schema_version, "authorship/3.0.0");
+        assert!(result.metadata.line_states.is_empty());
+    }
 }
@@ -1,14 +1,17 @@
 use crate::authorship::attribution_tracker::LineAttribution;
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::authorship_log_serialization::generate_short_hash;
-use crate::authorship::working_log::{CHECKPOINT_API_VERSION, Checkpoint, CheckpointKind};
+use crate::authorship::working_log::{
+    CHECKPOINT_API_VERSION, Checkpoint, CheckpointKind, WorkingLogEntry,
+};
 use crate::error::GitAiError;
 use crate::git::rewrite_log::{RewriteLogEvent, append_event_to_file};
 use crate::utils::{debug_log, normalize_to_posix};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 /// Initial attributions data structure stored in the INITIAL file
@@ -20,6 +23,64 @@ pub struct InitialAttributions {
     pub prompts: HashMap<String, PromptRecord>,
 }
 
+/// Working logs are compacted once checkpoints.jsonl has doubled since the last compaction,
+/// and never below this size
+const COMPACTION_MIN_BYTES: u64 = 1024 * 1024;
+
+/// Bytes before the indexed length that are hashed to detect a rewritten log
+const INDEX_TAIL_WINDOW: u64 = 4096;
+
+/// The latest state of one file in the working log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedFile {
+    /// Most recent entry for the file
+    pub latest: WorkingLogEntry,
+    /// Touched by an AiAgent or AiTab checkpoint
+    pub ai_checkpoint: bool,
+    /// Touched by an AI checkpoint or carrying non-human attributions in any entry
+    pub ai_attributed: bool,
+}
+
+/// Index of file -> latest entry stored next to checkpoints.jsonl, so checkpoints don't have
+/// to reread the whole log. It covers the first `log_len` bytes of the log and is caught up
+/// (or rebuilt if the log was rewritten) whenever it is read.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CheckpointIndex {
+    log_len: u64,
+    log_tail_hash: String,
+    compacted_len: u64,
+    pub checkpoint_count: usize,
+    pub has_ai_checkpoints: bool,
+    pub files: HashMap<String, IndexedFile>,
+}
+
+impl CheckpointIndex {
+    fn record(&mut self, checkpoint: &Checkpoint) {
+        let is_ai = matches!(
+            checkpoint.kind,
+            CheckpointKind::AiAgent | CheckpointKind::AiTab
+        );
+        self.checkpoint_count += 1;
+        self.has_ai_checkpoints |= is_ai;
+
+        for entry in &checkpoint.entries {
+            let (ai_checkpoint, ai_attributed) = self
+                .files
+                .get(&entry.file)
+                .map(|f| (f.ai_checkpoint, f.ai_attributed))
+                .unwrap_or_default();
+            self.files.insert(
+                entry.file.clone(),
+                IndexedFile {
+                    latest: entry.clone(),
+                    ai_checkpoint: ai_checkpoint || is_ai,
+                    ai_attributed: ai_attributed || is_ai || entry.has_non_human_attribution(),
+                },
+            );
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RepoStorage {
     pub repo_path: PathBuf,
@@ -27,6 +88,7 @@ pub struct RepoStorage {
     pub working_logs: PathBuf,
     pub rewrite_log: PathBuf,
     pub logs: PathBuf,
+    pub blame_cache: PathBuf,
 }
 
 impl RepoStorage {
@@ -35,6 +97,7 @@ impl RepoStorage {
         let working_logs_dir = ai_dir.join("working_logs");
         let rewrite_log_file = ai_dir.join("rewrite_log");
         let logs_dir = ai_dir.join("logs");
+        let blame_cache_dir = ai_dir.join("blame_cache");
 
         let config = RepoStorage {
             repo_path: repo_path.to_path_buf(),
@@ -42,6 +105,7 @@ impl RepoStorage {
             working_logs: working_logs_dir,
             rewrite_log: rewrite_log_file,
             logs: logs_dir,
+            blame_cache: blame_cache_dir,
         };
 
         config.ensure_config_directory().unwrap();
@@ -204,8 +268,11 @@ impl PersistedWorkingLog {
         }
 
         // Clear checkpoints by truncating the JSONL file
-        let checkpoints_file = self.dir.join("checkpoints.jsonl");
-        fs::write(&checkpoints_file, "")?;
+        fs::write(self.checkpoints_file(), "")?;
+        let index_file = self.index_file();
+        if index_file.exists() {
+            fs::remove_file(&index_file)?;
+        }
 
         // Clear INITIAL attributions file so stale attributions from a
         // previous working state do not persist across resets
@@ -321,9 +388,6 @@ impl PersistedWorkingLog {
 
     /* append checkpoint */
     pub fn append_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), GitAiError> {
-        // Read existing checkpoints
-        let mut checkpoints = self.read_all_checkpoints().unwrap_or_default();
-
         // Create a copy, potentially without transcript to reduce storage size.
         // Transcripts are refetched in update_prompts_to_latest() before post-commit
         // using tool-specific sources (transcript_path for Claude, cursor_db_path for Cursor, etc.)
@@ -348,8 +412,10 @@ impl PersistedWorkingLog {
             // For other tools, check if they have the necessary metadata for refetching
             // cursor can always refetch from its database
             "cursor" => false,
-            // claude, codex, gemini, continue-cli need transcript_path
-            "claude" | "codex" | "gemini" | "continue-cli" => metadata
+            // claude, codex, gemini, continue-cli and the other session-file agents need
+            // transcript_path
+            "claude" | "codex" | "gemini" | "continue-cli" | "aider" | "windsurf" | "amp"
+            | "junie" | "rovodev" => metadata
                 .as_ref()
                 .and_then(|m| m.get("transcript_path"))
                 .is_none(),
@@ -366,34 +432,63 @@ impl PersistedWorkingLog {
             storage_checkpoint.transcript = None;
         }
 
-        // Add the new checkpoint
-        checkpoints.push(storage_checkpoint);
+        // Append a single line; existing checkpoints are never rewritten here
+        let mut line = serde_json::to_string(&storage_checkpoint)?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(self.checkpoints_file())?;
+        let len = file.metadata()?.len();
+        if len > 0 {
+            let mut last_byte = [0u8; 1];
+            file.seek(SeekFrom::Start(len - 1))?;
+            file.read_exact(&mut last_byte)?;
+            if last_byte[0] != b'\n' {
+                line.insert(0, '\n');
+            }
+        }
+        file.write_all(line.as_bytes())?;
+        drop(file);
+
+        // Catch the index up with the new line, and compact once the log has doubled
+        match self.read_checkpoint_index() {
+            Ok(index) if index.log_len > COMPACTION_MIN_BYTES.max(index.compacted_len * 2) => {
+                self.compact_checkpoints()?;
+            }
+            Ok(_) => {}
+            Err(e) => debug_log(&format!("Failed to update checkpoint index: {}", e)),
+        }
 
-        // Prune char-level attributions from older checkpoints for the same files
-        // Only the most recent checkpoint per file needs char-level precision
-        self.prune_old_char_attributions(&mut checkpoints);
+        Ok(())
+    }
 
-        // Write all checkpoints back
+    /// Rewrite checkpoints.jsonl without the char-level attributions of superseded entries.
+    /// Only the most recent checkpoint per file needs char-level precision.
+    pub fn compact_checkpoints(&self) -> Result<(), GitAiError> {
+        let checkpoints = self.read_all_checkpoints()?;
         self.write_all_checkpoints(&checkpoints)
     }
 
     pub fn read_all_checkpoints(&self) -> Result<Vec<Checkpoint>, GitAiError> {
-        let checkpoints_file = self.dir.join("checkpoints.jsonl");
+        let checkpoints_file = self.checkpoints_file();
 
         if !checkpoints_file.exists() {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(&checkpoints_file)?;
+        let reader = BufReader::new(File::open(&checkpoints_file)?);
         let mut checkpoints = Vec::new();
 
         // Parse JSONL file - each line is a separate JSON object
-        for line in content.lines() {
+        for line in reader.lines() {
+            let line = line?;
             if line.trim().is_empty() {
                 continue;
             }
 
-            let checkpoint: Checkpoint = serde_json::from_str(line)
+            let checkpoint: Checkpoint = serde_json::from_str(&line)
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
 
             if checkpoint.api_version != CHECKPOINT_API_VERSION {
@@ -451,9 +546,108 @@ impl PersistedWorkingLog {
             migrated_checkpoints.push(checkpoint);
         }
 
+        // The log is append-only between compactions, so superseded entries may still
+        // carry char-level attributions on disk
+        self.prune_old_char_attributions(&mut migrated_checkpoints);
+
         Ok(migrated_checkpoints)
     }
 
+    /// Read the file -> latest entry index, catching it up with checkpoints appended since it
+    /// was saved. The index is rebuilt from scratch if the log was rewritten underneath it.
+    pub fn read_checkpoint_index(&self) -> Result<CheckpointIndex, GitAiError> {
+        let checkpoints_file = self.checkpoints_file();
+        let log_len = fs::metadata(&checkpoints_file)
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let stored = fs::read_to_string(self.index_file())
+            .ok()
+            .and_then(|content| serde_json::from_str::<CheckpointIndex>(&content).ok())
+            .filter(|index| {
+                index.log_len <= log_len
+                    && self
+                        .log_tail_hash(index.log_len)
+                        .is_ok_and(|hash| hash == index.log_tail_hash)
+            });
+        let mut index = match stored {
+            Some(index) if index.log_len == log_len => return Ok(index),
+            Some(index) => index,
+            None => CheckpointIndex::default(),
+        };
+
+        if log_len > index.log_len {
+            let mut reader = BufReader::new(File::open(&checkpoints_file)?);
+            reader.seek(SeekFrom::Start(index.log_len))?;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                let read = reader.read_line(&mut line)?;
+                if read == 0 {
+                    break;
+                }
+                if !line.trim().is_empty() {
+                    match serde_json::from_str::<Checkpoint>(line.trim()) {
+                        Ok(checkpoint) => {
+                            if checkpoint.api_version == CHECKPOINT_API_VERSION {
+                                index.record(&checkpoint);
+                            }
+                        }
+                        // A concurrent append that hasn't finished writing its line yet
+                        Err(_) if !line.ends_with('\n') => break,
+                        Err(e) => {
+                            return Err(
+                                std::io::Error::new(std::io::ErrorKind::InvalidData, e).into()
+                            );
+                        }
+                    }
+                }
+                index.log_len += read as u64;
+            }
+        }
+
+        index.log_tail_hash = self.log_tail_hash(index.log_len)?;
+        self.save_checkpoint_index(&index);
+        Ok(index)
+    }
+
+    fn save_checkpoint_index(&self, index: &CheckpointIndex) {
+        let index_file = self.index_file();
+        let tmp_file = index_file.with_extension(format!("json.{}.tmp", std::process::id()));
+        let result = serde_json::to_vec(index)
+            .map_err(GitAiError::from)
+            .and_then(|json| fs::write(&tmp_file, json).map_err(GitAiError::from))
+            .and_then(|_| fs::rename(&tmp_file, &index_file).map_err(GitAiError::from));
+        if let Err(e) = result {
+            debug_log(&format!("Failed to save checkpoint index: {}", e));
+            let _ = fs::remove_file(&tmp_file);
+        }
+    }
+
+    /// Hash of the log bytes just before `len`, used to tell whether the log an index was
+    /// built from has since been rewritten
+    fn log_tail_hash(&self, len: u64) -> Result<String, GitAiError> {
+        let mut hasher = Sha256::new();
+        hasher.update(len.to_le_bytes());
+        if len > 0 {
+            let start = len.saturating_sub(INDEX_TAIL_WINDOW);
+            let mut file = File::open(self.checkpoints_file())?;
+            file.seek(SeekFrom::Start(start))?;
+            let mut tail = vec![0u8; (len - start) as usize];
+            file.read_exact(&mut tail)?;
+            hasher.update(&tail);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    fn checkpoints_file(&self) -> PathBuf {
+        self.dir.join("checkpoints.jsonl")
+    }
+
+    fn index_file(&self) -> PathBuf {
+        self.dir.join("checkpoints.index.json")
+    }
+
     /// Remove char-level attributions from all but the most recent checkpoint per file.
     /// This reduces storage size while preserving precision for the entries that matter.
     /// Only the most recent checkpoint entry for each file is used when computing new entries.
@@ -487,55 +681,43 @@ impl PersistedWorkingLog {
     /// by post-commit after transcripts have been refetched and need to be preserved
     /// for from_just_working_log() to read them.
     pub fn write_all_checkpoints(&self, checkpoints: &[Checkpoint]) -> Result<(), GitAiError> {
-        let checkpoints_file = self.dir.join("checkpoints.jsonl");
+        let checkpoints_file = self.checkpoints_file();
 
-        // Serialize all checkpoints to JSONL
-        let mut lines = Vec::new();
+        // Serialize all checkpoints to JSONL, indexing them as we go
+        let mut content = String::new();
+        let mut index = CheckpointIndex::default();
         for checkpoint in checkpoints {
-            let json_line = serde_json::to_string(checkpoint)?;
-            lines.push(json_line);
+            content.push_str(&serde_json::to_string(checkpoint)?);
+            content.push('\n');
+            index.record(checkpoint);
         }
 
         // Write all lines to file
-        let content = lines.join("\n");
-        if !content.is_empty() {
-            fs::write(&checkpoints_file, format!("{}\n", content))?;
-        } else {
-            fs::write(&checkpoints_file, "")?;
-        }
+        fs::write(&checkpoints_file, &content)?;
+
+        index.log_len = content.len() as u64;
+        index.compacted_len = index.log_len;
+        index.log_tail_hash = self.log_tail_hash(index.log_len)?;
+        self.save_checkpoint_index(&index);
 
         Ok(())
     }
 
     pub fn all_touched_files(&self) -> Result<HashSet<String>, GitAiError> {
-        let checkpoints = self.read_all_checkpoints()?;
-        let mut touched_files = HashSet::new();
-        for checkpoint in checkpoints {
-            for entry in checkpoint.entries {
-                touched_files.insert(entry.file);
-            }
-        }
-        Ok(touched_files)
+        let index = self.read_checkpoint_index()?;
+        Ok(index.files.into_keys().collect())
     }
 
     #[allow(dead_code)]
     pub fn all_ai_touched_files(&self) -> Result<HashSet<String>, GitAiError> {
-        let checkpoints = self.read_all_checkpoints()?;
-        let mut touched_files = HashSet::new();
-        for checkpoint in checkpoints {
-            // Only include files from AI checkpoints (AiAgent or AiTab)
-            match checkpoint.kind {
-                CheckpointKind::AiAgent | CheckpointKind::AiTab => {
-                    for entry in checkpoint.entries {
-                        touched_files.insert(entry.file);
-                    }
-                }
-                CheckpointKind::Human => {
-                    // Skip human checkpoints
-                }
-            }
-        }
-        Ok(touched_files)
+        // Only include files from AI checkpoints (AiAgent or AiTab)
+        let index = self.read_checkpoint_index()?;
+        Ok(index
+            .files
+            .into_iter()
+            .filter(|(_, file)| file.ai_checkpoint)
+            .map(|(path, _)| path)
+            .collect())
     }
 
     /* INITIAL attributions file */
@@ -899,6 +1081,147 @@ mod tests {
         );
     }
 
+    fn checkpoint_for_file(kind: CheckpointKind, file: &str, blob_sha: &str) -> Checkpoint {
+        use crate::authorship::attribution_tracker::Attribution;
+
+        let author_id = if kind == CheckpointKind::Human {
+            kind.to_str()
+        } else {
+            "ai-session".to_string()
+        };
+        let entry = WorkingLogEntry::new(
+            file.to_string(),
+            blob_sha.to_string(),
+            vec![Attribution::new(0, 10, author_id, 1)],
+            vec![],
+        );
+        Checkpoint::new(
+            kind,
+            blob_sha.to_string(),
+            "author".to_string(),
+            vec![entry],
+        )
+    }
+
+    #[test]
+    fn test_append_checkpoint_only_appends() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+        let checkpoints_file = working_log.dir.join("checkpoints.jsonl");
+
+        working_log
+            .append_checkpoint(&checkpoint_for_file(
+                CheckpointKind::AiAgent,
+                "a.rs",
+                "sha-1",
+            ))
+            .unwrap();
+        let before = fs::read(&checkpoints_file).unwrap();
+
+        working_log
+            .append_checkpoint(&checkpoint_for_file(CheckpointKind::Human, "a.rs", "sha-2"))
+            .unwrap();
+        let after = fs::read(&checkpoints_file).unwrap();
+
+        // Superseded entries keep their char-level attributions on disk until compaction,
+        // but readers only see them on the latest entry
+        assert!(after.starts_with(&before));
+        let checkpoints = working_log.read_all_checkpoints().unwrap();
+        assert!(checkpoints[0].entries[0].attributions.is_empty());
+        assert_eq!(checkpoints[1].entries[0].attributions.len(), 1);
+
+        working_log.compact_checkpoints().unwrap();
+        let compacted = fs::read(&checkpoints_file).unwrap();
+        assert!(compacted.len() < after.len());
+        assert_eq!(working_log.read_all_checkpoints().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_checkpoint_index_tracks_latest_entry_per_file() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+
+        working_log
+            .append_checkpoint(&checkpoint_for_file(
+                CheckpointKind::AiAgent,
+                "a.rs",
+                "sha-1",
+            ))
+            .unwrap();
+        working_log
+            .append_checkpoint(&checkpoint_for_file(CheckpointKind::Human, "b.rs", "sha-2"))
+            .unwrap();
+        working_log
+            .append_checkpoint(&checkpoint_for_file(CheckpointKind::Human, "a.rs", "sha-3"))
+            .unwrap();
+
+        let index = working_log.read_checkpoint_index().unwrap();
+        assert_eq!(index.checkpoint_count, 3);
+        assert!(index.has_ai_checkpoints);
+        assert_eq!(index.files["a.rs"].latest.blob_sha, "sha-3");
+        assert!(index.files["a.rs"].ai_checkpoint);
+        assert!(!index.files["b.rs"].ai_attributed);
+
+        assert_eq!(
+            working_log.all_ai_touched_files().unwrap(),
+            HashSet::from(["a.rs".to_string()])
+        );
+        assert_eq!(working_log.all_touched_files().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_checkpoint_index_follows_external_log_changes() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+        let checkpoints_file = working_log.dir.join("checkpoints.jsonl");
+
+        working_log
+            .append_checkpoint(&checkpoint_for_file(CheckpointKind::Human, "a.rs", "sha-1"))
+            .unwrap();
+        assert_eq!(
+            working_log
+                .read_checkpoint_index()
+                .unwrap()
+                .checkpoint_count,
+            1
+        );
+
+        // Lines appended by another process are picked up incrementally
+        let external =
+            serde_json::to_string(&checkpoint_for_file(CheckpointKind::AiTab, "b.rs", "sha-2"))
+                .unwrap();
+        let mut content = fs::read_to_string(&checkpoints_file).unwrap();
+        content.push_str(&external);
+        content.push('\n');
+        fs::write(&checkpoints_file, &content).unwrap();
+
+        let index = working_log.read_checkpoint_index().unwrap();
+        assert_eq!(index.checkpoint_count, 2);
+        assert!(index.files["b.rs"].ai_checkpoint);
+
+        // A rewritten log invalidates the index
+        fs::write(&checkpoints_file, format!("{}\n", external)).unwrap();
+        let index = working_log.read_checkpoint_index().unwrap();
+        assert_eq!(index.checkpoint_count, 1);
+        assert!(!index.files.contains_key("a.rs"));
+
+        // A reset clears it
+        working_log.reset_working_log().unwrap();
+        assert!(
+            working_log
+                .read_checkpoint_index()
+                .unwrap()
+                .files
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_working_log_for_base_commit_creates_directory() {
         // Create a temporary repository
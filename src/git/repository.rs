@@ -16,6 +16,7 @@ use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
+use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(windows)]
@@ -992,6 +993,8 @@ pub struct Repository {
     /// Canonical (absolute, resolved) version of workdir for reliable path comparisons
     /// On Windows, this uses the \\?\ UNC prefix format
     canonical_workdir: PathBuf,
+    /// Notes namespace authorship is written to, resolved from config on first use
+    notes_namespace: OnceLock<String>,
 }
 
 impl Repository {
@@ -1197,6 +1200,29 @@ impl Repository {
         }
     }
 
+    /// Get every value of a multi-valued config key, in config file order.
+    pub fn config_get_all_str(&self, key: &str) -> Result<Vec<String>, GitAiError> {
+        let git_config_file = self.get_git_config_file()?;
+        Ok(git_config_file
+            .strings(key)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect())
+    }
+
+    /// Notes namespace (the `<name>` in `refs/notes/<name>`) that authorship logs are
+    /// written to: `git-ai.notesRef`, or `ai` when unset.
+    pub fn notes_namespace(&self) -> &str {
+        self.notes_namespace.get_or_init(|| {
+            self.config_get_str(crate::git::refs::NOTES_NAMESPACE_CONFIG_KEY)
+                .ok()
+                .flatten()
+                .and_then(|value| crate::git::refs::normalize_notes_namespace(&value))
+                .unwrap_or_else(|| crate::git::refs::AI_AUTHORSHIP_REFNAME.to_string())
+        })
+    }
+
     /// Get all config values matching a regex pattern.
     ///
     /// Regular expression matching is currently case-sensitive
@@ -2162,10 +2188,12 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         pre_reset_target_commit: None,
         workdir,
         canonical_workdir,
+        notes_namespace: OnceLock::new(),
     })
 }
 
-fn resolve_command_base_dir(global_args: &[String]) -> Result<PathBuf, GitAiError> {
+/// Directory a git invocation runs in: the current directory with any `-C` args applied
+pub(crate) fn resolve_command_base_dir(global_args: &[String]) -> Result<PathBuf, GitAiError> {
     let mut base = std::env::current_dir().map_err(GitAiError::IoError)?;
     let mut idx = 0usize;
 
@@ -2209,6 +2237,7 @@ pub fn from_bare_repository(git_dir: &Path) -> Result<Repository, GitAiError> {
         pre_reset_target_commit: None,
         workdir,
         canonical_workdir,
+        notes_namespace: OnceLock::new(),
     })
 }
 
@@ -2509,7 +2538,9 @@ fn parse_git_version(version_str: &str) -> Option<(u32, u32, u32)> {
 ///
 /// This means: old file line 10 (2 lines), new file line 15 (5 lines)
 /// We extract the "new file" line numbers to know which lines were added.
-fn parse_diff_added_lines(diff_output: &str) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+pub(crate) fn parse_diff_added_lines(
+    diff_output: &str,
+) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
     let mut result: HashMap<String, Vec<u32>> = HashMap::new();
     let mut current_file: Option<String> = None;
 
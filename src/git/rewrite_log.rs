@@ -29,9 +29,27 @@ pub enum RewriteLogEvent {
     CherryPickAbort {
         cherry_pick_abort: CherryPickAbortEvent,
     },
+    RevertStart {
+        revert_start: RevertStartEvent,
+    },
+    RevertComplete {
+        revert_complete: RevertCompleteEvent,
+    },
+    RevertAbort {
+        revert_abort: RevertAbortEvent,
+    },
     RevertMixed {
         revert_mixed: RevertMixedEvent,
     },
+    AmStart {
+        am_start: AmStartEvent,
+    },
+    AmComplete {
+        am_complete: AmCompleteEvent,
+    },
+    AmAbort {
+        am_abort: AmAbortEvent,
+    },
     Reset {
         reset: ResetEvent,
     },
@@ -111,13 +129,42 @@ impl RewriteLogEvent {
         }
     }
 
-    #[allow(dead_code)]
+    pub fn revert_start(event: RevertStartEvent) -> Self {
+        Self::RevertStart {
+            revert_start: event,
+        }
+    }
+
+    pub fn revert_complete(event: RevertCompleteEvent) -> Self {
+        Self::RevertComplete {
+            revert_complete: event,
+        }
+    }
+
+    pub fn revert_abort(event: RevertAbortEvent) -> Self {
+        Self::RevertAbort {
+            revert_abort: event,
+        }
+    }
+
     pub fn revert_mixed(event: RevertMixedEvent) -> Self {
         Self::RevertMixed {
             revert_mixed: event,
         }
     }
 
+    pub fn am_start(event: AmStartEvent) -> Self {
+        Self::AmStart { am_start: event }
+    }
+
+    pub fn am_complete(event: AmCompleteEvent) -> Self {
+        Self::AmComplete { am_complete: event }
+    }
+
+    pub fn am_abort(event: AmAbortEvent) -> Self {
+        Self::AmAbort { am_abort: event }
+    }
+
     #[allow(dead_code)]
     pub fn reset(event: ResetEvent) -> Self {
         Self::Reset { reset: event }
@@ -320,24 +367,144 @@ impl CherryPickAbortEvent {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevertStartEvent {
+    pub original_head: String,
+    pub reverted_commits: Vec<String>,
+}
+
+impl RevertStartEvent {
+    pub fn new(original_head: String, reverted_commits: Vec<String>) -> Self {
+        Self {
+            original_head,
+            reverted_commits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevertCompleteEvent {
+    pub original_head: String,
+    pub new_head: String,
+    pub reverted_commits: Vec<String>,
+    pub new_commits: Vec<String>,
+}
+
+impl RevertCompleteEvent {
+    pub fn new(
+        original_head: String,
+        new_head: String,
+        reverted_commits: Vec<String>,
+        new_commits: Vec<String>,
+    ) -> Self {
+        Self {
+            original_head,
+            new_head,
+            reverted_commits,
+            new_commits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevertAbortEvent {
+    pub original_head: String,
+}
+
+impl RevertAbortEvent {
+    pub fn new(original_head: String) -> Self {
+        Self { original_head }
+    }
+}
+
+/// A `git revert --no-commit`: the inverse changes are left in the index and
+/// working tree on top of `base_head` instead of being committed
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RevertMixedEvent {
-    pub reverted_commit: String,
+    pub base_head: String,
+    pub reverted_commits: Vec<String>,
     pub success: bool,
     pub affected_files: Vec<String>,
 }
 
 impl RevertMixedEvent {
-    #[allow(dead_code)]
-    pub fn new(reverted_commit: String, success: bool, affected_files: Vec<String>) -> Self {
+    pub fn new(
+        base_head: String,
+        reverted_commits: Vec<String>,
+        success: bool,
+        affected_files: Vec<String>,
+    ) -> Self {
         Self {
-            reverted_commit,
+            base_head,
+            reverted_commits,
             success,
             affected_files,
         }
     }
 }
 
+/// One message of a mailbox produced by `git format-patch`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmPatch {
+    /// Commit the patch was generated from (the sha in the mbox "From " line)
+    pub source_commit: String,
+    /// Subject with the `[PATCH n/m]` prefix removed, which `git am` uses as the summary
+    pub subject: String,
+    /// Authorship payload embedded by git-ai when the patch was formatted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorship: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmStartEvent {
+    pub original_head: String,
+    pub patches: Vec<AmPatch>,
+}
+
+impl AmStartEvent {
+    pub fn new(original_head: String, patches: Vec<AmPatch>) -> Self {
+        Self {
+            original_head,
+            patches,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmCompleteEvent {
+    pub original_head: String,
+    pub new_head: String,
+    pub patches: Vec<AmPatch>,
+    pub new_commits: Vec<String>,
+}
+
+impl AmCompleteEvent {
+    pub fn new(
+        original_head: String,
+        new_head: String,
+        patches: Vec<AmPatch>,
+        new_commits: Vec<String>,
+    ) -> Self {
+        Self {
+            original_head,
+            new_head,
+            patches,
+            new_commits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmAbortEvent {
+    pub original_head: String,
+}
+
+impl AmAbortEvent {
+    pub fn new(original_head: String) -> Self {
+        Self { original_head }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ResetKind {
@@ -621,6 +788,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_revert_events_serialization() {
+        let start = RewriteLogEvent::revert_start(RevertStartEvent::new(
+            "abc123".to_string(),
+            vec!["def456".to_string()],
+        ));
+        let complete = RewriteLogEvent::revert_complete(RevertCompleteEvent::new(
+            "abc123".to_string(),
+            "ghi789".to_string(),
+            vec!["def456".to_string()],
+            vec!["ghi789".to_string()],
+        ));
+        let mixed = RewriteLogEvent::revert_mixed(RevertMixedEvent::new(
+            "abc123".to_string(),
+            vec!["def456".to_string()],
+            true,
+            vec!["file.txt".to_string()],
+        ));
+
+        let jsonl = serialize_events_to_jsonl(&[start, complete, mixed]).unwrap();
+        let deserialized = deserialize_events_from_jsonl(&jsonl).unwrap();
+        assert_eq!(deserialized.len(), 3);
+
+        match &deserialized[0] {
+            RewriteLogEvent::RevertStart { revert_start } => {
+                assert_eq!(revert_start.reverted_commits, vec!["def456"]);
+            }
+            _ => panic!("Expected RevertStart event"),
+        }
+        match &deserialized[1] {
+            RewriteLogEvent::RevertComplete { revert_complete } => {
+                assert_eq!(revert_complete.new_commits, vec!["ghi789"]);
+            }
+            _ => panic!("Expected RevertComplete event"),
+        }
+        match &deserialized[2] {
+            RewriteLogEvent::RevertMixed { revert_mixed } => {
+                assert_eq!(revert_mixed.base_head, "abc123");
+                assert_eq!(revert_mixed.affected_files, vec!["file.txt"]);
+            }
+            _ => panic!("Expected RevertMixed event"),
+        }
+    }
+
     #[test]
     fn test_commit_amend_event_serialization() {
         let event =
@@ -688,4 +899,41 @@ mod tests {
             _ => panic!("Expected Merge event"),
         }
     }
+
+    #[test]
+    fn test_am_events_serialization() {
+        let patch = AmPatch {
+            source_commit: "def456".to_string(),
+            subject: "Add parser".to_string(),
+            authorship: Some("parser.rs\n  abc1234 1-2\n---\n{}".to_string()),
+        };
+        let start =
+            RewriteLogEvent::am_start(AmStartEvent::new("abc123".to_string(), vec![patch.clone()]));
+        let complete = RewriteLogEvent::am_complete(AmCompleteEvent::new(
+            "abc123".to_string(),
+            "ghi789".to_string(),
+            vec![patch.clone()],
+            vec!["ghi789".to_string()],
+        ));
+        let abort = RewriteLogEvent::am_abort(AmAbortEvent::new("abc123".to_string()));
+
+        let jsonl = serialize_events_to_jsonl(&[start, complete, abort]).unwrap();
+        let deserialized = deserialize_events_from_jsonl(&jsonl).unwrap();
+        assert_eq!(deserialized.len(), 3);
+
+        match &deserialized[0] {
+            RewriteLogEvent::AmStart { am_start } => assert_eq!(am_start.patches[0], patch),
+            _ => panic!("Expected AmStart event"),
+        }
+        match &deserialized[1] {
+            RewriteLogEvent::AmComplete { am_complete } => {
+                assert_eq!(am_complete.new_commits, vec!["ghi789"]);
+            }
+            _ => panic!("Expected AmComplete event"),
+        }
+        match &deserialized[2] {
+            RewriteLogEvent::AmAbort { am_abort } => assert_eq!(am_abort.original_head, "abc123"),
+            _ => panic!("Expected AmAbort event"),
+        }
+    }
 }
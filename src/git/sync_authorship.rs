@@ -1,5 +1,9 @@
+use crate::git::prompt_refs::{
+    PROMPTS_REF, merge_prompt_blobs_from_ref, prompts_push_refspec, prompts_tracking_ref,
+};
 use crate::git::refs::{
-    AI_AUTHORSHIP_PUSH_REFSPEC, copy_ref, merge_notes_from_ref, ref_exists, tracking_ref_for_remote,
+    copy_ref, merge_notes_from_ref, notes_ref_for_namespace, push_refspec_for_namespace,
+    ref_exists, remote_notes_namespaces, tracking_ref_for_namespace,
 };
 use crate::{
     error::GitAiError,
@@ -71,24 +75,71 @@ pub fn fetch_remote_from_args(
 // Returns Ok(NotesExistence::Found) if notes were found and fetched,
 // Ok(NotesExistence::NotFound) if confirmed no notes exist on remote,
 // Err(...) for actual errors (network, permissions, etc.)
+//
+// Every namespace configured for the remote (see `remote_notes_namespaces`) is fetched;
+// notes are reported as found if any namespace had them. When notes were found, the prompt
+// blobs ref (refs/ai-prompts/blobs) is fetched and merged too (best-effort).
 pub fn fetch_authorship_notes(
     repository: &Repository,
     remote_name: &str,
 ) -> Result<NotesExistence, GitAiError> {
+    let mut existence = NotesExistence::NotFound;
+    for namespace in remote_notes_namespaces(repository, remote_name) {
+        if fetch_authorship_notes_namespace(repository, remote_name, &namespace)?
+            == NotesExistence::Found
+        {
+            existence = NotesExistence::Found;
+        }
+    }
+
+    if existence == NotesExistence::Found {
+        fetch_and_merge_prompt_blobs(repository, remote_name);
+    }
+    Ok(existence)
+}
+
+/// Fetch the remote's prompt blobs ref into its tracking ref and merge it into
+/// refs/ai-prompts/blobs. Failures are logged, not returned.
+fn fetch_and_merge_prompt_blobs(repository: &Repository, remote_name: &str) {
+    let tracking_ref = prompts_tracking_ref(remote_name);
+    let fetch_prompts = build_authorship_fetch_args(
+        repository.global_args_for_exec(),
+        remote_name,
+        &format!("+{}:{}", PROMPTS_REF, tracking_ref),
+    );
+    debug_log(&format!("prompt blobs fetch: {:?}", fetch_prompts));
+    if let Err(e) = exec_git(&fetch_prompts) {
+        // The remote may not have any prompt blobs yet
+        debug_log(&format!("prompt blobs fetch failed: {}", e));
+        return;
+    }
+    if ref_exists(repository, &tracking_ref)
+        && let Err(e) = merge_prompt_blobs_from_ref(repository, &tracking_ref)
+    {
+        debug_log(&format!("prompt blobs merge failed: {}", e));
+    }
+}
+
+fn fetch_authorship_notes_namespace(
+    repository: &Repository,
+    remote_name: &str,
+    namespace: &str,
+) -> Result<NotesExistence, GitAiError> {
+    let notes_ref = notes_ref_for_namespace(namespace);
     // Generate tracking ref for this remote
-    let tracking_ref = tracking_ref_for_remote(remote_name);
+    let tracking_ref = tracking_ref_for_namespace(namespace, remote_name);
 
     debug_log(&format!(
-        "fetching authorship notes for remote '{}' to tracking ref '{}'",
-        remote_name, tracking_ref
+        "fetching authorship notes {} for remote '{}' to tracking ref '{}'",
+        notes_ref, remote_name, tracking_ref
     ));
 
-    // First, check if the remote has refs/notes/ai using ls-remote
+    // First, check if the remote has the notes ref using ls-remote
     // This is important for bare repos where the refmap might not be configured
     let mut ls_remote_args = repository.global_args_for_exec();
     ls_remote_args.push("ls-remote".to_string());
     ls_remote_args.push(remote_name.to_string());
-    ls_remote_args.push("refs/notes/ai".to_string());
+    ls_remote_args.push(notes_ref.clone());
 
     debug_log(&format!("ls-remote command: {:?}", ls_remote_args));
 
@@ -103,14 +154,14 @@ pub fn fetch_authorship_notes(
 
             if result.trim().is_empty() {
                 debug_log(&format!(
-                    "no authorship notes found on remote '{}', nothing to sync",
-                    remote_name
+                    "no authorship notes {} found on remote '{}', nothing to sync",
+                    notes_ref, remote_name
                 ));
                 return Ok(NotesExistence::NotFound);
             }
             debug_log(&format!(
-                "found authorship notes on remote '{}'",
-                remote_name
+                "found authorship notes {} on remote '{}'",
+                notes_ref, remote_name
             ));
         }
         Err(e) => {
@@ -124,7 +175,7 @@ pub fn fetch_authorship_notes(
     }
 
     // Now fetch the notes to the tracking ref with explicit refspec
-    let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
+    let fetch_refspec = format!("+{}:{}", notes_ref, tracking_ref);
 
     // Build the internal authorship fetch with explicit flags and disabled hooks.
     // IMPORTANT: use repository.global_args_for_exec() to ensure -C flag is present for bare repos.
@@ -153,31 +204,9 @@ pub fn fetch_authorship_notes(
         }
     }
 
-    // After successful fetch, merge the tracking ref into refs/notes/ai
-    let local_notes_ref = "refs/notes/ai";
-
-    if crate::git::refs::ref_exists(repository, &tracking_ref) {
-        if crate::git::refs::ref_exists(repository, local_notes_ref) {
-            // Both exist - merge them
-            debug_log(&format!(
-                "merging authorship notes from {} into {}",
-                tracking_ref, local_notes_ref
-            ));
-            if let Err(e) = merge_notes_from_ref(repository, &tracking_ref) {
-                debug_log(&format!("notes merge failed: {}", e));
-                // Don't fail on merge errors, just log and continue
-            }
-        } else {
-            // Only tracking ref exists - copy it to local
-            debug_log(&format!(
-                "initializing {} from tracking ref {}",
-                local_notes_ref, tracking_ref
-            ));
-            if let Err(e) = copy_ref(repository, &tracking_ref, local_notes_ref) {
-                debug_log(&format!("notes copy failed: {}", e));
-                // Don't fail on copy errors, just log and continue
-            }
-        }
+    // After successful fetch, merge the tracking ref into the local notes ref
+    if ref_exists(repository, &tracking_ref) {
+        merge_tracking_ref(repository, &tracking_ref, namespace);
     } else {
         debug_log(&format!(
             "tracking ref {} was not created after fetch",
@@ -187,55 +216,82 @@ pub fn fetch_authorship_notes(
 
     Ok(NotesExistence::Found)
 }
+
+/// Merge a fetched tracking ref into refs/notes/<namespace>, or initialize the local ref
+/// from it. Failures are logged, not returned.
+fn merge_tracking_ref(repository: &Repository, tracking_ref: &str, namespace: &str) {
+    let local_notes_ref = notes_ref_for_namespace(namespace);
+
+    if ref_exists(repository, &local_notes_ref) {
+        // Both exist - merge them
+        debug_log(&format!(
+            "merging authorship notes from {} into {}",
+            tracking_ref, local_notes_ref
+        ));
+        if let Err(e) = merge_notes_from_ref(repository, tracking_ref, namespace) {
+            debug_log(&format!("notes merge failed: {}", e));
+            // Don't fail on merge errors, just log and continue
+        }
+    } else {
+        // Only tracking ref exists - copy it to local
+        debug_log(&format!(
+            "initializing {} from tracking ref {}",
+            local_notes_ref, tracking_ref
+        ));
+        if let Err(e) = copy_ref(repository, tracking_ref, &local_notes_ref) {
+            debug_log(&format!("notes copy failed: {}", e));
+            // Don't fail on copy errors, just log and continue
+        }
+    }
+}
+
 // for use with post-push hook
 pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Result<(), GitAiError> {
-    // STEP 1: Fetch remote notes into tracking ref and merge before pushing
-    // This ensures we don't lose notes from other branches/clones
-    let tracking_ref = tracking_ref_for_remote(remote_name);
-    let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
-
-    let fetch_before_push = build_authorship_fetch_args(
-        repository.global_args_for_exec(),
-        remote_name,
-        &fetch_refspec,
-    );
+    let namespaces = remote_notes_namespaces(repository, remote_name);
 
-    debug_log(&format!(
-        "pre-push authorship fetch: {:?}",
-        &fetch_before_push
-    ));
+    // STEP 1: Fetch remote notes and prompt blobs into tracking refs and merge before pushing
+    // This ensures we don't lose notes from other branches/clones
+    for namespace in &namespaces {
+        let tracking_ref = tracking_ref_for_namespace(namespace, remote_name);
+        let fetch_refspec = format!("+{}:{}", notes_ref_for_namespace(namespace), tracking_ref);
+
+        let fetch_before_push = build_authorship_fetch_args(
+            repository.global_args_for_exec(),
+            remote_name,
+            &fetch_refspec,
+        );
 
-    // Fetch is best-effort; if it fails (e.g., no remote notes yet), continue
-    if exec_git(&fetch_before_push).is_ok() {
-        // Merge fetched notes into local refs/notes/ai
-        let local_notes_ref = "refs/notes/ai";
+        debug_log(&format!(
+            "pre-push authorship fetch: {:?}",
+            &fetch_before_push
+        ));
 
-        if ref_exists(repository, &tracking_ref) {
-            if ref_exists(repository, local_notes_ref) {
-                // Both exist - merge them
-                debug_log(&format!(
-                    "pre-push: merging {} into {}",
-                    tracking_ref, local_notes_ref
-                ));
-                if let Err(e) = merge_notes_from_ref(repository, &tracking_ref) {
-                    debug_log(&format!("pre-push notes merge failed: {}", e));
-                }
-            } else {
-                // Only tracking ref exists - copy it to local
-                debug_log(&format!(
-                    "pre-push: initializing {} from {}",
-                    local_notes_ref, tracking_ref
-                ));
-                if let Err(e) = copy_ref(repository, &tracking_ref, local_notes_ref) {
-                    debug_log(&format!("pre-push notes copy failed: {}", e));
-                }
-            }
+        // Fetch is best-effort; if it fails (e.g., no remote notes yet), continue
+        if exec_git(&fetch_before_push).is_ok() && ref_exists(repository, &tracking_ref) {
+            merge_tracking_ref(repository, &tracking_ref, namespace);
         }
     }
+    let push_prompts = ref_exists(repository, PROMPTS_REF);
+    if push_prompts {
+        fetch_and_merge_prompt_blobs(repository, remote_name);
+    }
+
+    // STEP 2: Push notes without force (requires fast-forward), along with any prompt blobs
+    let mut refspecs: Vec<String> = namespaces
+        .iter()
+        .filter(|namespace| ref_exists(repository, &notes_ref_for_namespace(namespace)))
+        .map(|namespace| push_refspec_for_namespace(namespace))
+        .collect();
+    if !refspecs.is_empty() && push_prompts {
+        refspecs.push(prompts_push_refspec());
+    }
+    if refspecs.is_empty() {
+        debug_log("no local authorship notes to push");
+        return Ok(());
+    }
 
-    // STEP 2: Push notes without force (requires fast-forward)
     let push_authorship =
-        build_authorship_push_args(repository.global_args_for_exec(), remote_name);
+        build_authorship_push_args(repository.global_args_for_exec(), remote_name, &refspecs);
 
     debug_log(&format!(
         "pushing authorship refs (no force): {:?}",
@@ -324,7 +380,11 @@ fn build_authorship_fetch_args(
     args
 }
 
-fn build_authorship_push_args(global_args: Vec<String>, remote_name: &str) -> Vec<String> {
+fn build_authorship_push_args(
+    global_args: Vec<String>,
+    remote_name: &str,
+    refspecs: &[String],
+) -> Vec<String> {
     let mut args = with_disabled_hooks(global_args);
     args.push("push".to_string());
     args.push("--quiet".to_string());
@@ -332,7 +392,7 @@ fn build_authorship_push_args(global_args: Vec<String>, remote_name: &str) -> Ve
     args.push("--no-verify".to_string());
     args.push("--no-signed".to_string());
     args.push(remote_name.to_string());
-    args.push(AI_AUTHORSHIP_PUSH_REFSPEC.to_string());
+    args.extend(refspecs.iter().cloned());
     args
 }
 
@@ -359,8 +419,11 @@ mod tests {
     #[test]
     fn authorship_push_args_always_disable_hooks() {
         let disabled_hooks = disabled_hooks_config();
-        let args =
-            build_authorship_push_args(vec!["-C".to_string(), "/tmp/repo".to_string()], "origin");
+        let args = build_authorship_push_args(
+            vec!["-C".to_string(), "/tmp/repo".to_string()],
+            "origin",
+            &["refs/notes/ai:refs/notes/ai".to_string()],
+        );
 
         assert!(
             args.windows(2)
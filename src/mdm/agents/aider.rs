@@ -0,0 +1,437 @@
+use crate::error::GitAiError;
+use crate::mdm::hook_installer::{HookCheckResult, HookInstaller, HookInstallerParams};
+use crate::mdm::utils::{binary_exists, generate_diff, home_dir, write_atomic};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BLOCK_BEGIN: &str = "# >>> git-ai >>>";
+const BLOCK_END: &str = "# <<< git-ai <<<";
+
+/// Aider has no edit hooks, so git-ai rides on its config instead:
+///
+/// - `lint-cmd` runs after every edit with the edited file appended, which makes it the
+///   checkpoint hook (`git-ai checkpoint aider --chain-lint-cmd <file>`). git-ai first records
+///   the file as it was before the edit (rebuilt from the reply in Aider's chat history) as a
+///   human checkpoint, then the edit itself as an AI checkpoint, and finally runs the
+///   `lint-cmd` the block shadows so the user's linter keeps working.
+/// - `auto-commits` is left alone. Aider commits *before* running the lint command, which
+///   commits AI edits ahead of their checkpoint, so the block documents turning it off per
+///   repo rather than changing it for every repo on the machine.
+///
+/// The settings live in a marked block at the end of `~/.aider.conf.yml`. YAML keeps the
+/// last value of a repeated key, so the block wins over earlier settings and removing it on
+/// uninstall restores them.
+pub struct AiderInstaller;
+
+impl AiderInstaller {
+    /// Tells `git-ai checkpoint aider` to run the user's own `lint-cmd` after checkpointing
+    pub const CHAIN_LINT_CMD_FLAG: &'static str = "--chain-lint-cmd";
+
+    fn config_path() -> PathBuf {
+        home_dir().join(".aider.conf.yml")
+    }
+
+    fn desired_block(binary_path: &Path) -> String {
+        let lint_cmd = format!(
+            "{} checkpoint aider {}",
+            binary_path.display(),
+            Self::CHAIN_LINT_CMD_FLAG
+        );
+        [
+            BLOCK_BEGIN.to_string(),
+            "# git-ai checkpoints every Aider edit from lint-cmd, then runs the lint-cmd set above."
+                .to_string(),
+            "# Aider commits its edits before linting them: add `auto-commits: false` to a repo's"
+                .to_string(),
+            "# .aider.conf.yml to have git-ai attribute Aider's edits in that repo.".to_string(),
+            "auto-lint: true".to_string(),
+            format!("lint-cmd: {}", serde_json::Value::String(lint_cmd)),
+            BLOCK_END.to_string(),
+        ]
+        .join("\n")
+    }
+
+    /// The user's own `lint-cmd` entries in `~/.aider.conf.yml`, which the git-ai block
+    /// shadows and `git-ai checkpoint aider --chain-lint-cmd` runs after checkpointing
+    pub fn user_lint_cmds() -> Vec<String> {
+        fs::read_to_string(Self::config_path())
+            .map(|content| Self::parse_lint_cmds(&Self::remove_block(&content)))
+            .unwrap_or_default()
+    }
+
+    /// Read the top-level `lint-cmd` key: a single command or a list of them.
+    ///
+    /// Only the YAML forms Aider documents are understood (plain or quoted scalars, block and
+    /// flow lists); a repeated key replaces the earlier value, as it does in YAML.
+    fn parse_lint_cmds(content: &str) -> Vec<String> {
+        let mut cmds = Vec::new();
+        let mut lines = content.lines().peekable();
+        while let Some(line) = lines.next() {
+            let Some(value) = line.strip_prefix("lint-cmd:") else {
+                continue;
+            };
+            cmds.clear();
+            let value = strip_yaml_comment(value).trim();
+            if value.is_empty() {
+                while let Some(item) = lines
+                    .peek()
+                    .and_then(|next| next.trim_start().strip_prefix("- "))
+                {
+                    cmds.push(yaml_scalar(item));
+                    lines.next();
+                }
+            } else if let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                cmds.extend(items.split(',').map(yaml_scalar));
+            } else {
+                cmds.push(yaml_scalar(value));
+            }
+        }
+        cmds.retain(|cmd| !cmd.is_empty());
+        cmds
+    }
+
+    /// Pick the lint command Aider would run for `file`: a command without a language applies
+    /// to every file (the last one wins), otherwise the one for the file's language
+    pub fn lint_cmd_for_file(lint_cmds: &[String], file: &str) -> Option<String> {
+        let mut all_files = None;
+        let mut by_language = HashMap::new();
+        for lint_cmd in lint_cmds {
+            let (language, cmd) = split_lint_cmd(lint_cmd);
+            if cmd.is_empty() {
+                continue;
+            }
+            match language {
+                Some(language) => {
+                    by_language.insert(language, cmd);
+                }
+                None => all_files = Some(cmd),
+            }
+        }
+        all_files
+            .or_else(|| {
+                let language = language_for_file(file)?;
+                by_language.get(language).copied()
+            })
+            .map(str::to_string)
+    }
+
+    /// Byte range of the git-ai block (including its trailing newline), if present
+    fn block_range(content: &str) -> Option<(usize, usize)> {
+        let start = content.find(BLOCK_BEGIN)?;
+        let end_marker = start + content[start..].find(BLOCK_END)?;
+        let mut end = end_marker + BLOCK_END.len();
+        if content[end..].starts_with('\n') {
+            end += 1;
+        }
+        Some((start, end))
+    }
+
+    fn existing_block(content: &str) -> Option<&str> {
+        Self::block_range(content).map(|(start, end)| content[start..end].trim_end())
+    }
+
+    fn apply_block(content: &str, block: &str) -> String {
+        let without = Self::remove_block(content);
+        let mut updated = without.trim_end().to_string();
+        if !updated.is_empty() {
+            updated.push_str("\n\n");
+        }
+        updated.push_str(block);
+        updated.push('\n');
+        updated
+    }
+
+    fn remove_block(content: &str) -> String {
+        match Self::block_range(content) {
+            Some((start, end)) => {
+                let before = content[..start].trim_end_matches('\n');
+                let after = &content[end..];
+                if before.is_empty() {
+                    after.to_string()
+                } else if after.is_empty() {
+                    format!("{}\n", before)
+                } else {
+                    format!("{}\n{}", before, after)
+                }
+            }
+            None => content.to_string(),
+        }
+    }
+}
+
+/// Split Aider's `<language>: <cmd>` form; anything else applies to every language
+fn split_lint_cmd(lint_cmd: &str) -> (Option<&str>, &str) {
+    if let Some((language, cmd)) = lint_cmd.split_once(':')
+        && !language.is_empty()
+        && language.bytes().all(|b| b.is_ascii_lowercase())
+    {
+        return (Some(language), cmd.trim());
+    }
+    (None, lint_cmd.trim())
+}
+
+/// The language names Aider uses for `lint-cmd`, for the common extensions
+fn language_for_file(file: &str) -> Option<&'static str> {
+    let extension = Path::new(file).extension()?.to_str()?.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "py" | "pyi" => "python",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "rs" => "rust",
+        "go" => "go",
+        "rb" => "ruby",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "php" => "php",
+        "sh" | "bash" => "bash",
+        "lua" => "lua",
+        "scala" => "scala",
+        "ex" | "exs" => "elixir",
+        "hs" => "haskell",
+        "dart" => "dart",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "md" => "markdown",
+        _ => return None,
+    };
+    Some(language)
+}
+
+fn strip_yaml_comment(value: &str) -> &str {
+    let trimmed = value.trim_start();
+    if trimmed.starts_with('"') || trimmed.starts_with('\'') {
+        return value;
+    }
+    match value.find(" #") {
+        Some(idx) => &value[..idx],
+        None => value,
+    }
+}
+
+fn yaml_scalar(value: &str) -> String {
+    let value = strip_yaml_comment(value).trim();
+    if value.starts_with('"') {
+        return serde_json::from_str::<String>(value)
+            .unwrap_or_else(|_| value.trim_matches('"').to_string());
+    }
+    if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        return inner.replace("''", "'");
+    }
+    value.to_string()
+}
+
+impl HookInstaller for AiderInstaller {
+    fn name(&self) -> &str {
+        "Aider"
+    }
+
+    fn id(&self) -> &str {
+        "aider"
+    }
+
+    fn check_hooks(&self, params: &HookInstallerParams) -> Result<HookCheckResult, GitAiError> {
+        let config_path = Self::config_path();
+        let has_binary = binary_exists("aider");
+
+        if !has_binary && !config_path.exists() {
+            return Ok(HookCheckResult {
+                tool_installed: false,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let content = if config_path.exists() {
+            fs::read_to_string(&config_path)?
+        } else {
+            String::new()
+        };
+        let existing = Self::existing_block(&content);
+
+        Ok(HookCheckResult {
+            tool_installed: true,
+            hooks_installed: existing.is_some(),
+            hooks_up_to_date: existing == Some(Self::desired_block(&params.binary_path).as_str()),
+        })
+    }
+
+    fn install_hooks(
+        &self,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let config_path = Self::config_path();
+        let existing_content = if config_path.exists() {
+            fs::read_to_string(&config_path)?
+        } else {
+            String::new()
+        };
+
+        let new_content =
+            Self::apply_block(&existing_content, &Self::desired_block(&params.binary_path));
+        if new_content == existing_content {
+            return Ok(None);
+        }
+
+        let diff_output = generate_diff(&config_path, &existing_content, &new_content);
+        if !dry_run {
+            write_atomic(&config_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+
+    fn uninstall_hooks(
+        &self,
+        _params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let config_path = Self::config_path();
+        if !config_path.exists() {
+            return Ok(None);
+        }
+
+        let existing_content = fs::read_to_string(&config_path)?;
+        if Self::block_range(&existing_content).is_none() {
+            return Ok(None);
+        }
+
+        let new_content = Self::remove_block(&existing_content);
+        let diff_output = generate_diff(&config_path, &existing_content, &new_content);
+        if !dry_run {
+            write_atomic(&config_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_binary_path() -> PathBuf {
+        PathBuf::from("/usr/local/bin/git-ai")
+    }
+
+    #[test]
+    fn test_desired_block_sets_lint_cmd() {
+        let block = AiderInstaller::desired_block(&test_binary_path());
+        assert!(
+            block.contains("lint-cmd: \"/usr/local/bin/git-ai checkpoint aider --chain-lint-cmd\"")
+        );
+        assert!(block.contains("auto-lint: true"));
+        assert!(block.starts_with(BLOCK_BEGIN));
+        assert!(block.ends_with(BLOCK_END));
+    }
+
+    #[test]
+    fn test_desired_block_leaves_commit_settings_alone() {
+        let block = AiderInstaller::desired_block(&test_binary_path());
+        let settings: Vec<&str> = block
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect();
+        assert!(
+            settings
+                .iter()
+                .all(|line| !line.starts_with("auto-commits:")
+                    && !line.starts_with("dirty-commits:"))
+        );
+    }
+
+    #[test]
+    fn test_parse_lint_cmds_ignores_git_ai_block() {
+        let block = AiderInstaller::desired_block(&test_binary_path());
+        let single = AiderInstaller::apply_block("lint-cmd: ruff check # fast\n", &block);
+        assert_eq!(
+            AiderInstaller::parse_lint_cmds(&AiderInstaller::remove_block(&single)),
+            vec!["ruff check"]
+        );
+
+        let list =
+            "model: sonnet\nlint-cmd:\n  - \"python: flake8 --select=E9\"\n  - 'go: go vet'\n";
+        assert_eq!(
+            AiderInstaller::parse_lint_cmds(list),
+            vec!["python: flake8 --select=E9", "go: go vet"]
+        );
+        assert_eq!(
+            AiderInstaller::parse_lint_cmds("lint-cmd: [\"python: flake8\", eslint]\n"),
+            vec!["python: flake8", "eslint"]
+        );
+        assert_eq!(
+            AiderInstaller::parse_lint_cmds(&block),
+            vec!["/usr/local/bin/git-ai checkpoint aider --chain-lint-cmd"]
+        );
+        assert!(AiderInstaller::parse_lint_cmds("model: sonnet\n").is_empty());
+    }
+
+    #[test]
+    fn test_lint_cmd_for_file_follows_aider_precedence() {
+        let by_language = vec!["python: flake8".to_string(), "go: go vet".to_string()];
+        assert_eq!(
+            AiderInstaller::lint_cmd_for_file(&by_language, "src/math.py").as_deref(),
+            Some("flake8")
+        );
+        assert_eq!(
+            AiderInstaller::lint_cmd_for_file(&by_language, "main.go").as_deref(),
+            Some("go vet")
+        );
+        assert_eq!(
+            AiderInstaller::lint_cmd_for_file(&by_language, "README.md"),
+            None
+        );
+
+        // A command without a language wins for every file, as it does in Aider
+        let with_all = vec!["python: flake8".to_string(), "make lint".to_string()];
+        assert_eq!(
+            AiderInstaller::lint_cmd_for_file(&with_all, "src/math.py").as_deref(),
+            Some("make lint")
+        );
+    }
+
+    #[test]
+    fn test_apply_block_appends_and_is_idempotent() {
+        let block = AiderInstaller::desired_block(&test_binary_path());
+        let original = "model: sonnet\nauto-commits: true\n";
+
+        let installed = AiderInstaller::apply_block(original, &block);
+        assert!(installed.starts_with(original));
+        assert_eq!(
+            AiderInstaller::existing_block(&installed),
+            Some(block.as_str())
+        );
+        assert_eq!(AiderInstaller::apply_block(&installed, &block), installed);
+    }
+
+    #[test]
+    fn test_apply_block_replaces_outdated_block() {
+        let old_block = AiderInstaller::desired_block(Path::new("/old/git-ai"));
+        let new_block = AiderInstaller::desired_block(&test_binary_path());
+        let installed = AiderInstaller::apply_block("model: sonnet\n", &old_block);
+
+        let updated = AiderInstaller::apply_block(&installed, &new_block);
+        assert_eq!(updated.matches(BLOCK_BEGIN).count(), 1);
+        assert!(!updated.contains("/old/git-ai"));
+    }
+
+    #[test]
+    fn test_remove_block_restores_original_config() {
+        let block = AiderInstaller::desired_block(&test_binary_path());
+        let original = "model: sonnet\nauto-commits: true\n";
+        let installed = AiderInstaller::apply_block(original, &block);
+
+        assert_eq!(AiderInstaller::remove_block(&installed), original);
+        assert_eq!(AiderInstaller::remove_block(original), original);
+    }
+}
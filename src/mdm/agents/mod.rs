@@ -1,3 +1,4 @@
+mod aider;
 mod claude_code;
 mod codex;
 mod cursor;
@@ -7,7 +8,9 @@ mod github_copilot;
 mod jetbrains;
 mod opencode;
 mod vscode;
+mod windsurf;
 
+pub use aider::AiderInstaller;
 pub use claude_code::ClaudeCodeInstaller;
 pub use codex::CodexInstaller;
 pub use cursor::CursorInstaller;
@@ -17,6 +20,7 @@ pub use github_copilot::GitHubCopilotInstaller;
 pub use jetbrains::JetBrainsInstaller;
 pub use opencode::OpenCodeInstaller;
 pub use vscode::VSCodeInstaller;
+pub use windsurf::WindsurfInstaller;
 
 use super::hook_installer::HookInstaller;
 
@@ -32,5 +36,7 @@ pub fn get_all_installers() -> Vec<Box<dyn HookInstaller>> {
         Box::new(GeminiInstaller),
         Box::new(DroidInstaller),
         Box::new(JetBrainsInstaller),
+        Box::new(AiderInstaller),
+        Box::new(WindsurfInstaller),
     ]
 }
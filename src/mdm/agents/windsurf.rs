@@ -0,0 +1,366 @@
+use crate::error::GitAiError;
+use crate::mdm::hook_installer::{HookCheckResult, HookInstaller, HookInstallerParams};
+use crate::mdm::utils::{
+    binary_exists, generate_diff, home_dir, is_git_ai_checkpoint_command, write_atomic,
+};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+
+const WINDSURF_CHECKPOINT_CMD: &str = "checkpoint windsurf --hook-input stdin";
+
+// Cascade actions that bracket every file write
+const WINDSURF_HOOK_EVENTS: &[&str] = &["pre_user_prompt", "pre_write_code", "post_write_code"];
+
+pub struct WindsurfInstaller;
+
+impl WindsurfInstaller {
+    fn windsurf_dir() -> PathBuf {
+        home_dir().join(".codeium").join("windsurf")
+    }
+
+    fn hooks_path() -> PathBuf {
+        Self::windsurf_dir().join("hooks.json")
+    }
+
+    fn desired_command(params: &HookInstallerParams) -> String {
+        format!(
+            "{} {}",
+            params.binary_path.display(),
+            WINDSURF_CHECKPOINT_CMD
+        )
+    }
+
+    fn git_ai_commands(config: &Value, event: &str) -> Vec<String> {
+        config
+            .get("hooks")
+            .and_then(|h| h.get(event))
+            .and_then(|v| v.as_array())
+            .map(|hooks| {
+                hooks
+                    .iter()
+                    .filter_map(|hook| hook.get("command").and_then(|c| c.as_str()))
+                    .filter(|cmd| is_git_ai_checkpoint_command(cmd))
+                    .map(|cmd| cmd.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Add (or update) exactly one git-ai hook per event, keeping the user's other hooks
+    fn apply_hooks(config: &Value, desired_cmd: &str) -> Value {
+        let mut merged = config.clone();
+        if !merged.is_object() {
+            merged = json!({});
+        }
+        let mut hooks_obj = merged.get("hooks").cloned().unwrap_or_else(|| json!({}));
+
+        for event in WINDSURF_HOOK_EVENTS {
+            let mut hooks_array = hooks_obj
+                .get(*event)
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+
+            let mut kept_git_ai_hook = false;
+            hooks_array.retain_mut(|hook| {
+                let is_git_ai = hook
+                    .get("command")
+                    .and_then(|c| c.as_str())
+                    .map(is_git_ai_checkpoint_command)
+                    .unwrap_or(false);
+                if !is_git_ai {
+                    return true;
+                }
+                if kept_git_ai_hook {
+                    return false;
+                }
+                kept_git_ai_hook = true;
+                if let Some(obj) = hook.as_object_mut() {
+                    obj.insert("command".to_string(), json!(desired_cmd));
+                }
+                true
+            });
+            if !kept_git_ai_hook {
+                hooks_array.push(json!({
+                    "command": desired_cmd,
+                    "show_output": false
+                }));
+            }
+
+            if let Some(obj) = hooks_obj.as_object_mut() {
+                obj.insert(event.to_string(), Value::Array(hooks_array));
+            }
+        }
+
+        if let Some(root) = merged.as_object_mut() {
+            root.insert("hooks".to_string(), hooks_obj);
+        }
+        merged
+    }
+
+    /// Remove git-ai hooks, returning `None` if there were none
+    fn remove_hooks(config: &Value) -> Option<Value> {
+        let mut merged = config.clone();
+        let hooks_obj = merged.get_mut("hooks")?.as_object_mut()?;
+
+        let mut changed = false;
+        for event in WINDSURF_HOOK_EVENTS {
+            if let Some(hooks_array) = hooks_obj.get_mut(*event).and_then(|v| v.as_array_mut()) {
+                let original_len = hooks_array.len();
+                hooks_array.retain(|hook| {
+                    !hook
+                        .get("command")
+                        .and_then(|c| c.as_str())
+                        .map(is_git_ai_checkpoint_command)
+                        .unwrap_or(false)
+                });
+                changed |= hooks_array.len() != original_len;
+            }
+        }
+
+        changed.then_some(merged)
+    }
+}
+
+impl HookInstaller for WindsurfInstaller {
+    fn name(&self) -> &str {
+        "Windsurf"
+    }
+
+    fn id(&self) -> &str {
+        "windsurf"
+    }
+
+    fn check_hooks(&self, params: &HookInstallerParams) -> Result<HookCheckResult, GitAiError> {
+        let has_binary = binary_exists("windsurf");
+        let has_dotfiles = Self::windsurf_dir().exists();
+
+        if !has_binary && !has_dotfiles {
+            return Ok(HookCheckResult {
+                tool_installed: false,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let hooks_path = Self::hooks_path();
+        if !hooks_path.exists() {
+            return Ok(HookCheckResult {
+                tool_installed: true,
+                hooks_installed: false,
+                hooks_up_to_date: false,
+            });
+        }
+
+        let content = fs::read_to_string(&hooks_path)?;
+        let existing: Value = serde_json::from_str(&content).unwrap_or_else(|_| json!({}));
+        let desired_cmd = Self::desired_command(params);
+
+        let hooks_installed = WINDSURF_HOOK_EVENTS
+            .iter()
+            .any(|event| !Self::git_ai_commands(&existing, event).is_empty());
+        let hooks_up_to_date = WINDSURF_HOOK_EVENTS
+            .iter()
+            .all(|event| Self::git_ai_commands(&existing, event) == [desired_cmd.clone()]);
+
+        Ok(HookCheckResult {
+            tool_installed: true,
+            hooks_installed,
+            hooks_up_to_date,
+        })
+    }
+
+    fn install_hooks(
+        &self,
+        params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let hooks_path = Self::hooks_path();
+
+        let existing_content = if hooks_path.exists() {
+            fs::read_to_string(&hooks_path)?
+        } else {
+            String::new()
+        };
+
+        let existing: Value = if existing_content.trim().is_empty() {
+            json!({})
+        } else {
+            serde_json::from_str(&existing_content)?
+        };
+
+        let merged = Self::apply_hooks(&existing, &Self::desired_command(params));
+        if existing == merged {
+            return Ok(None);
+        }
+
+        let new_content = serde_json::to_string_pretty(&merged)?;
+        let diff_output = generate_diff(&hooks_path, &existing_content, &new_content);
+
+        if !dry_run {
+            if let Some(dir) = hooks_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            write_atomic(&hooks_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+
+    fn uninstall_hooks(
+        &self,
+        _params: &HookInstallerParams,
+        dry_run: bool,
+    ) -> Result<Option<String>, GitAiError> {
+        let hooks_path = Self::hooks_path();
+
+        if !hooks_path.exists() {
+            return Ok(None);
+        }
+
+        let existing_content = fs::read_to_string(&hooks_path)?;
+        let existing: Value = serde_json::from_str(&existing_content)?;
+
+        let Some(merged) = Self::remove_hooks(&existing) else {
+            return Ok(None);
+        };
+
+        let new_content = serde_json::to_string_pretty(&merged)?;
+        let diff_output = generate_diff(&hooks_path, &existing_content, &new_content);
+
+        if !dry_run {
+            write_atomic(&hooks_path, new_content.as_bytes())?;
+        }
+
+        Ok(Some(diff_output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    const DESIRED_CMD: &str = "/usr/local/bin/git-ai checkpoint windsurf --hook-input stdin";
+
+    fn test_params() -> HookInstallerParams {
+        HookInstallerParams {
+            binary_path: PathBuf::from("/usr/local/bin/git-ai"),
+        }
+    }
+
+    fn with_temp_home<F: FnOnce(&Path)>(f: F) {
+        let temp = tempdir().unwrap();
+        let home = temp.path().to_path_buf();
+
+        let prev_home = std::env::var_os("HOME");
+        let prev_userprofile = std::env::var_os("USERPROFILE");
+
+        // SAFETY: tests are serialized via #[serial], so mutating process env is safe.
+        unsafe {
+            std::env::set_var("HOME", &home);
+            std::env::set_var("USERPROFILE", &home);
+        }
+
+        f(&home);
+
+        // SAFETY: tests are serialized via #[serial], so restoring process env is safe.
+        unsafe {
+            match prev_home {
+                Some(v) => std::env::set_var("HOME", v),
+                None => std::env::remove_var("HOME"),
+            }
+            match prev_userprofile {
+                Some(v) => std::env::set_var("USERPROFILE", v),
+                None => std::env::remove_var("USERPROFILE"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_hooks_adds_all_events_and_preserves_user_hooks() {
+        let existing = json!({
+            "hooks": {
+                "post_write_code": [
+                    { "command": "prettier --write", "show_output": true }
+                ]
+            }
+        });
+
+        let merged = WindsurfInstaller::apply_hooks(&existing, DESIRED_CMD);
+        for event in WINDSURF_HOOK_EVENTS {
+            assert_eq!(
+                WindsurfInstaller::git_ai_commands(&merged, event),
+                vec![DESIRED_CMD.to_string()]
+            );
+        }
+        assert_eq!(
+            merged["hooks"]["post_write_code"][0]["command"],
+            "prettier --write"
+        );
+    }
+
+    #[test]
+    fn test_apply_hooks_updates_outdated_command_and_dedupes() {
+        let old_cmd = "/old/git-ai checkpoint windsurf --hook-input stdin";
+        let existing = json!({
+            "hooks": {
+                "pre_write_code": [
+                    { "command": old_cmd, "show_output": false },
+                    { "command": old_cmd, "show_output": false }
+                ]
+            }
+        });
+
+        let merged = WindsurfInstaller::apply_hooks(&existing, DESIRED_CMD);
+        assert_eq!(
+            WindsurfInstaller::git_ai_commands(&merged, "pre_write_code"),
+            vec![DESIRED_CMD.to_string()]
+        );
+        assert_eq!(WindsurfInstaller::apply_hooks(&merged, DESIRED_CMD), merged);
+    }
+
+    #[test]
+    fn test_remove_hooks_only_removes_git_ai_hooks() {
+        let installed = WindsurfInstaller::apply_hooks(
+            &json!({ "hooks": { "pre_run_command": [{ "command": "audit" }] } }),
+            DESIRED_CMD,
+        );
+
+        let removed = WindsurfInstaller::remove_hooks(&installed).expect("hooks removed");
+        for event in WINDSURF_HOOK_EVENTS {
+            assert!(WindsurfInstaller::git_ai_commands(&removed, event).is_empty());
+        }
+        assert_eq!(removed["hooks"]["pre_run_command"][0]["command"], "audit");
+        assert!(WindsurfInstaller::remove_hooks(&removed).is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_install_check_and_uninstall_hooks() {
+        with_temp_home(|home| {
+            fs::create_dir_all(home.join(".codeium").join("windsurf")).unwrap();
+            let installer = WindsurfInstaller;
+            let params = test_params();
+
+            let dry_run = installer.install_hooks(&params, true).unwrap();
+            assert!(dry_run.is_some(), "dry-run should produce a diff");
+            assert!(!WindsurfInstaller::hooks_path().exists());
+
+            assert!(installer.install_hooks(&params, false).unwrap().is_some());
+            assert!(installer.install_hooks(&params, false).unwrap().is_none());
+
+            let check = installer.check_hooks(&params).unwrap();
+            assert!(check.tool_installed);
+            assert!(check.hooks_installed);
+            assert!(check.hooks_up_to_date);
+
+            assert!(installer.uninstall_hooks(&params, false).unwrap().is_some());
+            let check = installer.check_hooks(&params).unwrap();
+            assert!(!check.hooks_installed);
+        });
+    }
+}
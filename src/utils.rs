@@ -192,6 +192,16 @@ pub fn spawn_internal_git_ai_subcommand(
         .is_ok()
 }
 
+/// Run a git-ai subcommand with this binary and wait for it, sharing this process's stdio
+pub fn run_internal_git_ai_subcommand(
+    subcommand: &str,
+    extra_args: &[String],
+) -> Result<std::process::ExitStatus, GitAiError> {
+    let exe = current_git_ai_exe()?;
+    let mut cmd = internal_git_ai_command_with_exe(exe, subcommand);
+    Ok(cmd.args(extra_args).status()?)
+}
+
 pub fn is_interactive_terminal() -> bool {
     *IS_TERMINAL.get_or_init(|| std::io::stdin().is_terminal())
 }
@@ -0,0 +1,359 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::CheckpointKind;
+use git_ai::commands::checkpoint_agent::agent_presets::{
+    AgentCheckpointFlags, AgentCheckpointPreset,
+};
+use git_ai::commands::checkpoint_agent::aider_preset::{
+    AIDER_CHAT_HISTORY_FILE, AIDER_INPUT_HISTORY_FILE, AiderPreset,
+};
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+use test_utils::fixture_path;
+
+fn aider_session_fixture_path() -> std::path::PathBuf {
+    fixture_path("aider-session")
+}
+
+fn run_aider_preset(
+    hook_input: serde_json::Value,
+) -> git_ai::commands::checkpoint_agent::agent_presets::AgentRunResult {
+    AiderPreset
+        .run(AgentCheckpointFlags {
+            hook_input: Some(hook_input.to_string()),
+        })
+        .expect("Failed to run AiderPreset")
+}
+
+#[test]
+fn test_parse_aider_chat_history_uses_last_session() {
+    let session_dir = aider_session_fixture_path();
+    let chat_history = session_dir.join(AIDER_CHAT_HISTORY_FILE);
+    let input_history = session_dir.join(AIDER_INPUT_HISTORY_FILE);
+
+    let (transcript, model, session_started_at) =
+        AiderPreset::transcript_and_model_from_aider_history(
+            chat_history.to_str().unwrap(),
+            Some(input_history.to_str().unwrap()),
+        )
+        .expect("Failed to parse Aider chat history");
+
+    assert_eq!(model.as_deref(), Some("claude-3-5-sonnet-20241022"));
+    assert_eq!(session_started_at.as_deref(), Some("2025-03-11 14:02:45"));
+
+    let user_messages: Vec<&String> = transcript
+        .messages()
+        .iter()
+        .filter_map(|m| match m {
+            Message::User { text, .. } => Some(text),
+            _ => None,
+        })
+        .collect();
+    // `/add` is Aider housekeeping; `/ask` carries a prompt; the earlier session is ignored
+    assert_eq!(
+        user_messages,
+        vec![
+            "add a function that multiplies two numbers\nand include a docstring",
+            "what does add do?",
+        ]
+    );
+
+    let assistant_text = transcript
+        .messages()
+        .iter()
+        .find_map(|m| match m {
+            Message::Assistant { text, .. } => Some(text.clone()),
+            _ => None,
+        })
+        .expect("Should have an assistant message");
+    assert!(assistant_text.starts_with("I'll add a `multiply` function"));
+    assert!(assistant_text.contains(">>>>>>> REPLACE"));
+
+    let edits: Vec<&serde_json::Value> = transcript
+        .messages()
+        .iter()
+        .filter_map(|m| match m {
+            Message::ToolUse { name, input, .. } if name == "edit" => Some(input),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(edits, vec![&json!({ "file_path": "src/math.py" })]);
+}
+
+#[test]
+fn test_parse_aider_chat_history_timestamps_user_messages() {
+    let session_dir = aider_session_fixture_path();
+    let chat_history = session_dir.join(AIDER_CHAT_HISTORY_FILE);
+    let input_history = session_dir.join(AIDER_INPUT_HISTORY_FILE);
+
+    let (transcript, _, _) = AiderPreset::transcript_and_model_from_aider_history(
+        chat_history.to_str().unwrap(),
+        Some(input_history.to_str().unwrap()),
+    )
+    .unwrap();
+    let user_timestamps: Vec<Option<&String>> = transcript
+        .messages()
+        .iter()
+        .filter(|m| matches!(m, Message::User { .. }))
+        .map(|m| m.timestamp())
+        .collect();
+    assert_eq!(user_timestamps.len(), 2);
+    assert!(user_timestamps.iter().all(|ts| ts.is_some()));
+
+    // Without the input history the transcript is the same, just without timestamps
+    let (without_inputs, _, _) =
+        AiderPreset::transcript_and_model_from_aider_history(chat_history.to_str().unwrap(), None)
+            .unwrap();
+    assert_eq!(without_inputs.messages().len(), transcript.messages().len());
+    assert!(
+        without_inputs
+            .messages()
+            .iter()
+            .all(|m| m.timestamp().is_none())
+    );
+}
+
+#[test]
+fn test_aider_preset_after_edit_is_ai_checkpoint() {
+    let session_dir = aider_session_fixture_path();
+    let hook_input = json!({
+        "hook_event_name": "after_edit",
+        "cwd": session_dir.to_string_lossy(),
+        "edited_filepaths": ["src/math.py"],
+    });
+
+    let result = run_aider_preset(hook_input.clone());
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.tool, "aider");
+    assert_eq!(result.agent_id.model, "claude-3-5-sonnet-20241022");
+    assert_eq!(result.agent_id.id.len(), 16);
+    assert_eq!(
+        result.edited_filepaths,
+        Some(vec!["src/math.py".to_string()])
+    );
+    assert!(result.transcript.is_some());
+
+    let metadata = result.agent_metadata.expect("Should store history paths");
+    assert_eq!(
+        metadata.get("transcript_path").map(String::as_str),
+        Some(
+            session_dir
+                .join(AIDER_CHAT_HISTORY_FILE)
+                .to_string_lossy()
+                .as_ref()
+        )
+    );
+
+    // Every checkpoint in the same session shares the session id
+    assert_eq!(run_aider_preset(hook_input).agent_id.id, result.agent_id.id);
+}
+
+#[test]
+fn test_aider_preset_before_edit_is_human_checkpoint() {
+    let hook_input = json!({
+        "hook_event_name": "before_edit",
+        "cwd": aider_session_fixture_path().to_string_lossy(),
+        "edited_filepaths": ["src/math.py"],
+    });
+
+    let result = run_aider_preset(hook_input);
+    assert_eq!(result.checkpoint_kind, CheckpointKind::Human);
+    assert!(result.transcript.is_none());
+    assert_eq!(
+        result.will_edit_filepaths,
+        Some(vec!["src/math.py".to_string()])
+    );
+}
+
+#[test]
+fn test_aider_preset_without_history_defaults_to_unknown_model() {
+    let temp = tempfile::tempdir().unwrap();
+    let hook_input: serde_json::Value =
+        serde_json::from_str(&AiderPreset::hook_input_from_args(temp.path(), &[])).unwrap();
+
+    let result = run_aider_preset(hook_input);
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, "unknown");
+    assert!(result.edited_filepaths.is_none());
+    assert!(result.transcript.unwrap().messages().is_empty());
+}
+
+#[test]
+fn test_aider_lint_cmd_checkpoint_attributes_ai_lines() {
+    let repo = TestRepo::new();
+    let session_dir = aider_session_fixture_path();
+    for history_file in [AIDER_CHAT_HISTORY_FILE, AIDER_INPUT_HISTORY_FILE] {
+        fs::copy(
+            session_dir.join(history_file),
+            repo.path().join(history_file),
+        )
+        .unwrap();
+    }
+    fs::write(
+        repo.path().join(".git").join("info").join("exclude"),
+        ".aider*\n",
+    )
+    .unwrap();
+
+    let mut file = repo.filename("math.py");
+    file.set_contents(lines!["def add(a, b):", "    return a + b", "# end"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Aider edits the file, then runs its lint command with the edited file appended
+    fs::write(
+        repo.path().join("math.py"),
+        "def add(a, b):\n    return a + b\ndef multiply(a, b):\n    return a * b\n# end",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "aider", "math.py"]).unwrap();
+
+    let commit = repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "def add(a, b):".human(),
+        "    return a + b".human(),
+        "def multiply(a, b):".ai(),
+        "    return a * b".ai(),
+        "# end".human(),
+    ]);
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("Should record the Aider session");
+    assert_eq!(prompt.agent_id.tool, "aider");
+    assert_eq!(prompt.agent_id.model, "claude-3-5-sonnet-20241022");
+}
+
+/// `src/math.py` after the edit in the fixture's last session
+const MATH_AFTER_EDIT: &str = "def add(a, b):\n    return a + b\n\n\ndef multiply(a, b):\n    \"\"\"Return the product of a and b.\"\"\"\n    return a * b\n# human note\n";
+
+#[test]
+fn test_aider_pre_edit_contents_undo_last_reply() {
+    let temp = tempfile::tempdir().unwrap();
+    fs::create_dir_all(temp.path().join("src")).unwrap();
+    fs::write(temp.path().join("src/math.py"), MATH_AFTER_EDIT).unwrap();
+    let chat_history =
+        fs::read_to_string(aider_session_fixture_path().join(AIDER_CHAT_HISTORY_FILE)).unwrap();
+
+    let contents = AiderPreset::pre_edit_contents(
+        &chat_history,
+        temp.path(),
+        &["src/math.py".to_string(), "src/old.py".to_string()],
+    );
+    // `src/old.py` was only edited in an earlier session
+    assert_eq!(contents.len(), 1);
+    assert_eq!(
+        contents.get("src/math.py").map(String::as_str),
+        Some("def add(a, b):\n    return a + b\n# human note\n")
+    );
+
+    // An edit that isn't in the file any more can't be undone, so nothing is claimed
+    fs::write(
+        temp.path().join("src/math.py"),
+        "def add(a, b):\n    return b + a\n",
+    )
+    .unwrap();
+    assert!(
+        AiderPreset::pre_edit_contents(&chat_history, temp.path(), &["src/math.py".to_string()])
+            .is_empty()
+    );
+}
+
+fn aider_repo_with_history() -> TestRepo {
+    let repo = TestRepo::new();
+    let session_dir = aider_session_fixture_path();
+    for history_file in [AIDER_CHAT_HISTORY_FILE, AIDER_INPUT_HISTORY_FILE] {
+        fs::copy(
+            session_dir.join(history_file),
+            repo.path().join(history_file),
+        )
+        .unwrap();
+    }
+    fs::write(
+        repo.path().join(".git").join("info").join("exclude"),
+        ".aider*\nlint-ran\n",
+    )
+    .unwrap();
+    repo
+}
+
+#[test]
+fn test_aider_lint_cmd_checkpoints_human_edits_before_the_ai_edit() {
+    let repo = aider_repo_with_history();
+    let mut file = repo.filename("src/math.py");
+    file.set_contents(lines!["def add(a, b):", "    return a + b"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // The human's note is still pending when Aider edits the file and runs its lint command
+    fs::write(repo.path().join("src/math.py"), MATH_AFTER_EDIT).unwrap();
+    repo.git_ai(&["checkpoint", "aider", "src/math.py"])
+        .unwrap();
+
+    repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "def add(a, b):".human(),
+        "    return a + b".human(),
+        "".ai(),
+        "".ai(),
+        "def multiply(a, b):".ai(),
+        "    \"\"\"Return the product of a and b.\"\"\"".ai(),
+        "    return a * b".ai(),
+        "# human note".human(),
+    ]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_aider_chained_lint_cmd_runs_users_lint_cmd_after_checkpoint() {
+    let repo = aider_repo_with_history();
+    let mut file = repo.filename("src/math.py");
+    file.set_contents(lines!["def add(a, b):", "    return a + b"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let home = tempfile::tempdir().unwrap();
+    let home_path = home.path().to_str().unwrap();
+    let installed_config = "lint-cmd: touch lint-ran\n\n# >>> git-ai >>>\nauto-lint: true\nlint-cmd: \"git-ai checkpoint aider --chain-lint-cmd\"\n# <<< git-ai <<<\n";
+    fs::write(home.path().join(".aider.conf.yml"), installed_config).unwrap();
+
+    fs::write(repo.path().join("src/math.py"), MATH_AFTER_EDIT).unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "aider", "--chain-lint-cmd", "src/math.py"],
+        &[("HOME", home_path)],
+    )
+    .unwrap();
+    assert!(repo.path().join("lint-ran").exists());
+
+    repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "def add(a, b):".human(),
+        "    return a + b".human(),
+        "".ai(),
+        "".ai(),
+        "def multiply(a, b):".ai(),
+        "    \"\"\"Return the product of a and b.\"\"\"".ai(),
+        "    return a * b".ai(),
+        "# human note".human(),
+    ]);
+
+    // Aider reads the lint command's exit code, so a failing linter still fails
+    fs::write(
+        home.path().join(".aider.conf.yml"),
+        installed_config.replace("touch lint-ran", "false"),
+    )
+    .unwrap();
+    assert!(
+        repo.git_ai_with_env(
+            &["checkpoint", "aider", "--chain-lint-cmd", "src/math.py"],
+            &[("HOME", home_path)],
+        )
+        .is_err()
+    );
+}
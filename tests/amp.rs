@@ -0,0 +1,203 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::CheckpointKind;
+use git_ai::commands::checkpoint_agent::agent_presets::{
+    AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+};
+use git_ai::commands::checkpoint_agent::amp_preset::AmpPreset;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+use test_utils::fixture_path;
+
+const THREAD_ID: &str = "T-7f3c9a12-5b4e-4d8a-b6c1-2e9f0a7d3b58";
+
+fn run_amp_preset(
+    hook_input: serde_json::Value,
+) -> Result<AgentRunResult, git_ai::error::GitAiError> {
+    AmpPreset.run(AgentCheckpointFlags {
+        hook_input: Some(hook_input.to_string()),
+    })
+}
+
+#[test]
+fn test_parse_amp_thread() {
+    let fixture = fixture_path("amp-thread.json");
+    let (transcript, model) =
+        AmpPreset::transcript_and_model_from_amp_thread(fixture.to_str().unwrap())
+            .expect("Failed to parse Amp thread");
+
+    // The latest assistant turn's model wins
+    assert_eq!(model.as_deref(), Some("claude-opus-4-20250514"));
+
+    let messages = transcript.messages();
+    assert!(matches!(
+        &messages[0],
+        Message::User { text, timestamp: Some(ts) }
+            if text == "Add a multiply helper to math.ts" && ts.starts_with("2025-06-06T09:00:01")
+    ));
+    assert!(matches!(&messages[1], Message::Thinking { .. }));
+    assert!(
+        matches!(&messages[2], Message::Assistant { text, .. } if text == "I'll read `math.ts` first.")
+    );
+    assert!(matches!(&messages[3], Message::ToolUse { name, .. } if name == "read_file"));
+    match &messages[4] {
+        Message::ToolUse { name, input, .. } => {
+            assert_eq!(name, "edit_file");
+            assert_eq!(input["path"], "/workspace/app/math.ts");
+        }
+        other => panic!("Expected edit_file tool use, got {:?}", other),
+    }
+    assert!(matches!(&messages[5], Message::Assistant { .. }));
+
+    // tool_result blocks are not user prompts
+    assert_eq!(messages.len(), 6);
+}
+
+#[test]
+fn test_amp_preset_pre_tool_use_is_human_checkpoint() {
+    let result = run_amp_preset(json!({
+        "hook_event_name": "PreToolUse",
+        "thread_id": THREAD_ID,
+        "cwd": "/workspace/app",
+        "tool_name": "edit_file",
+        "tool_input": { "path": "/workspace/app/math.ts" }
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::Human);
+    assert_eq!(result.agent_id.tool, "amp");
+    assert_eq!(result.agent_id.id, THREAD_ID);
+    assert_eq!(
+        result.will_edit_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert!(result.transcript.is_none());
+}
+
+#[test]
+fn test_amp_preset_post_tool_use_reads_thread() {
+    let fixture = fixture_path("amp-thread.json");
+    let result = run_amp_preset(json!({
+        "hook_event_name": "PostToolUse",
+        "thread_id": THREAD_ID,
+        "cwd": "/workspace/app",
+        "tool_name": "edit_file",
+        "tool_input": { "path": "/workspace/app/math.ts" },
+        "transcript_path": fixture.to_string_lossy(),
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, "claude-opus-4-20250514");
+    assert_eq!(
+        result.edited_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert_eq!(result.transcript.unwrap().messages().len(), 6);
+    assert_eq!(
+        result
+            .agent_metadata
+            .unwrap()
+            .get("transcript_path")
+            .map(String::as_str),
+        Some(fixture.to_string_lossy().as_ref())
+    );
+}
+
+#[test]
+fn test_amp_preset_missing_thread_falls_back_to_empty_transcript() {
+    let result = run_amp_preset(json!({
+        "hook_event_name": "PostToolUse",
+        "thread_id": THREAD_ID,
+        "cwd": "/workspace/app",
+        "transcript_path": "/nonexistent/thread.json",
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, "unknown");
+    assert!(result.transcript.unwrap().messages().is_empty());
+
+    let err = run_amp_preset(json!({ "hook_event_name": "PostToolUse", "cwd": "/workspace" }));
+    assert!(err.is_err(), "thread_id is required");
+}
+
+#[test]
+fn test_amp_hooks_attribute_thread_edits() {
+    let repo = TestRepo::new();
+    let threads_dir = tempfile::tempdir().unwrap();
+    fs::copy(
+        fixture_path("amp-thread.json"),
+        threads_dir.path().join(format!("{}.json", THREAD_ID)),
+    )
+    .unwrap();
+    let env = [(
+        "GIT_AI_AMP_THREADS_DIR",
+        threads_dir.path().to_str().unwrap(),
+    )];
+
+    let mut file = repo.filename("math.ts");
+    file.set_contents(lines!["export const add = (a, b) => a + b;", "// end"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let file_path = repo.canonical_path().join("math.ts");
+    let hook_input = |event: &str| {
+        json!({
+            "hook_event_name": event,
+            "thread_id": THREAD_ID,
+            "cwd": repo.canonical_path().to_string_lossy(),
+            "tool_name": "edit_file",
+            "tool_input": { "path": file_path.to_string_lossy() }
+        })
+        .to_string()
+    };
+
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "amp",
+            "--hook-input",
+            &hook_input("PreToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+    fs::write(
+        &file_path,
+        "export const add = (a, b) => a + b;\nexport const multiply = (a, b) => a * b;\n// end",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "amp",
+            "--hook-input",
+            &hook_input("PostToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "export const add = (a, b) => a + b;".human(),
+        "export const multiply = (a, b) => a * b;".ai(),
+        "// end".human(),
+    ]);
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("Should record the Amp thread");
+    assert_eq!(prompt.agent_id.tool, "amp");
+    assert_eq!(prompt.agent_id.id, THREAD_ID);
+    assert_eq!(prompt.agent_id.model, "claude-opus-4-20250514");
+}
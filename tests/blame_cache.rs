@@ -0,0 +1,108 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+use std::path::PathBuf;
+
+fn setup_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(1, lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("Add AI functions").unwrap();
+    repo
+}
+
+fn cache_dir(repo: &TestRepo) -> PathBuf {
+    repo.path().join(".git").join("ai").join("blame_cache")
+}
+
+fn cache_entries(repo: &TestRepo) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(cache_dir(repo)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".json"))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_blame_json_is_cached_and_reused() {
+    let repo = setup_repo();
+
+    let first = repo.git_ai(&["blame", "--json", "lib.rs"]).unwrap();
+    let entries = cache_entries(&repo);
+    assert_eq!(entries.len(), 1, "{entries:?}");
+
+    let second = repo.git_ai(&["blame", "--json", "lib.rs"]).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(cache_entries(&repo), entries);
+
+    // Another range is another entry
+    repo.git_ai(&["blame", "--json", "-L", "2,3", "lib.rs"])
+        .unwrap();
+    assert_eq!(cache_entries(&repo).len(), 2);
+}
+
+#[test]
+fn test_blame_cache_invalidated_by_notes_update() {
+    let repo = setup_repo();
+    let output = repo.git_ai(&["blame", "lib.rs"]).unwrap();
+    assert!(output.contains("mock_ai"), "{output}");
+    assert_eq!(cache_entries(&repo).len(), 0, "text output is not cached");
+    repo.git_ai(&["blame", "--json", "lib.rs"]).unwrap();
+    assert_eq!(cache_entries(&repo).len(), 1);
+
+    // Rewrite the note so the prompt belongs to another tool
+    let note = repo.git(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    let note = note.replace("\"mock_ai\"", "\"edited_ai\"");
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", &note, "HEAD"])
+        .unwrap();
+
+    let output = repo.git_ai(&["blame", "--json", "lib.rs"]).unwrap();
+    assert!(output.contains("edited_ai"), "{output}");
+    assert!(!output.contains("mock_ai"), "{output}");
+    assert_eq!(cache_entries(&repo).len(), 1, "stale entries are pruned");
+}
+
+#[test]
+fn test_blame_cache_skips_uncommitted_changes() {
+    let repo = setup_repo();
+    let path = repo.path().join("lib.rs");
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::write(&path, format!("// wip\n{}", contents)).unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--json", "--include-uncommitted", "lib.rs"])
+        .unwrap();
+    assert!(output.contains("\"3-4\""), "{output}");
+    assert_eq!(cache_entries(&repo).len(), 0);
+
+    // Buffers matching HEAD are cached even when the working tree is dirty
+    let contents_path = repo.path().join("buffer.rs");
+    fs::write(&contents_path, &contents).unwrap();
+    repo.git_ai(&[
+        "blame",
+        "--json",
+        "--contents",
+        contents_path.to_str().unwrap(),
+        "lib.rs",
+    ])
+    .unwrap();
+    assert_eq!(cache_entries(&repo).len(), 1);
+}
+
+#[test]
+fn test_blame_no_cache_flag() {
+    let repo = setup_repo();
+    repo.git_ai(&["blame", "--json", "--no-cache", "lib.rs"])
+        .unwrap();
+    assert!(!cache_dir(&repo).exists());
+}
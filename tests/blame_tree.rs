@@ -0,0 +1,90 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// README.md (2 human lines), src/lib.rs (1 human + 2 AI lines) and a binary file
+fn setup_repo() -> TestRepo {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project", "Docs"]);
+    fs::write(repo.path().join("logo.bin"), [0u8, 1, 2, 0, 3]).unwrap();
+    let mut lib = repo.filename("src/lib.rs");
+    lib.set_contents(lines!["pub fn base() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    lib.insert_at(
+        1,
+        lines!["pub fn ai_one() {}".ai(), "pub fn ai_two() {}".ai()],
+    );
+    repo.stage_all_and_commit("Add AI functions").unwrap();
+    repo
+}
+
+fn parse_json(output: &str) -> serde_json::Value {
+    serde_json::from_str(output).unwrap_or_else(|e| panic!("{}: {}", e, output))
+}
+
+#[test]
+fn test_blame_tree_json_for_whole_repository() {
+    let repo = setup_repo();
+
+    let output = repo.git_ai(&["blame", "--tree", "--json"]).unwrap();
+    let coverage = parse_json(&output);
+
+    assert_eq!(coverage["path"], "");
+    assert_eq!(coverage["totals"]["total_lines"], 5);
+    assert_eq!(coverage["totals"]["ai_lines"], 2);
+    assert_eq!(coverage["totals"]["human_lines"], 3);
+    assert_eq!(coverage["totals"]["by_tool"]["mock_ai"], 2);
+    assert_eq!(coverage["skipped"], serde_json::json!(["logo.bin"]));
+
+    let files = coverage["files"].as_array().unwrap();
+    let paths: Vec<&str> = files.iter().map(|f| f["path"].as_str().unwrap()).collect();
+    assert_eq!(paths, vec!["README.md", "src/lib.rs"]);
+    assert_eq!(files[1]["ai_lines"], 2);
+    assert_eq!(files[1]["total_lines"], 3);
+}
+
+#[test]
+fn test_blame_tree_limits_to_path() {
+    let repo = setup_repo();
+
+    let output = repo.git_ai(&["blame", "--tree", "src", "--json"]).unwrap();
+    let coverage = parse_json(&output);
+    assert_eq!(coverage["path"], "src");
+    assert_eq!(coverage["files"].as_array().unwrap().len(), 1);
+    assert_eq!(coverage["totals"]["ai_lines"], 2);
+
+    // Without a path, the current directory is used
+    let output = repo
+        .git_ai_from_working_dir(
+            &repo.path().join("src"),
+            &["blame", "--tree", "--json", "--jobs", "2"],
+        )
+        .unwrap();
+    assert_eq!(parse_json(&output)["path"], "src");
+}
+
+#[test]
+fn test_blame_tree_text_output() {
+    let repo = setup_repo();
+
+    let output = repo.git_ai(&["blame", "--tree"]).unwrap();
+    let row = |name: &str| {
+        output
+            .lines()
+            .find(|line| line.trim_start().starts_with(name))
+            .unwrap_or_else(|| panic!("no row for {} in:\n{}", name, output))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(row("src/"), vec!["src/", "3", "2", "67%", "mock_ai", "2"]);
+    assert_eq!(row("README.md"), vec!["README.md", "2", "0", "0%"]);
+    assert!(output.contains("Tools: mock_ai 2"), "{}", output);
+    assert!(output.contains("Skipped 1 binary files"), "{}", output);
+}
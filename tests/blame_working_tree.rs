@@ -0,0 +1,157 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// Commit one AI line, then checkpoint two more AI lines without committing them
+fn setup_pending_ai_lines(repo: &TestRepo) {
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["def main():", "    pass"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["    committed_ai()".ai()]);
+    repo.stage_all_and_commit("Add committed AI line").unwrap();
+
+    file.insert_at(
+        2,
+        lines!["    pending_ai_one()".ai(), "    pending_ai_two()".ai()],
+    );
+}
+
+fn blame_line<'a>(output: &'a str, contents: &str) -> &'a str {
+    output
+        .lines()
+        .find(|line| line.ends_with(contents))
+        .unwrap_or_else(|| panic!("no blame line for {:?} in:\n{}", contents, output))
+}
+
+#[test]
+fn test_blame_shows_uncommitted_ai_lines() {
+    let repo = TestRepo::new();
+    setup_pending_ai_lines(&repo);
+
+    // A human edit made after the last checkpoint shifts the pending lines down
+    let path = repo.path().join("app.py");
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::write(&path, format!("# header\n{}", contents)).unwrap();
+
+    let output = repo.git_ai(&["blame", "app.py"]).unwrap();
+    assert!(
+        blame_line(&output, "pending_ai_one()").contains("mock_ai"),
+        "{output}"
+    );
+    assert!(
+        blame_line(&output, "pending_ai_two()").contains("mock_ai"),
+        "{output}"
+    );
+    assert!(
+        blame_line(&output, "committed_ai()").contains("mock_ai"),
+        "{output}"
+    );
+    assert!(
+        blame_line(&output, "# header").contains("Not Committed Yet"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_blame_json_defaults_to_head() {
+    let repo = TestRepo::new();
+    setup_pending_ai_lines(&repo);
+
+    let output = repo.git_ai(&["blame", "--json", "app.py"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    // Only the three committed lines are blamed; the pending ones aren't in HEAD
+    let line_status = json["line_status"].as_object().unwrap();
+    assert!(
+        line_status.values().all(|status| status == "committed"),
+        "{output}"
+    );
+    let lines = json["lines"].as_object().unwrap();
+    assert_eq!(lines.keys().collect::<Vec<_>>(), vec!["2"], "{output}");
+}
+
+#[test]
+fn test_blame_json_marks_pending_lines() {
+    let repo = TestRepo::new();
+    setup_pending_ai_lines(&repo);
+
+    let output = repo
+        .git_ai(&["blame", "--json", "--include-uncommitted", "app.py"])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let line_status = json["line_status"].as_object().unwrap();
+    assert_eq!(line_status["2"], "committed", "{output}");
+    assert_eq!(line_status["3-4"], "pending", "{output}");
+
+    let lines = json["lines"].as_object().unwrap();
+    assert_eq!(lines.len(), 2, "{output}");
+    let pending_prompt = lines["3-4"].as_str().unwrap();
+    let prompt = &json["prompts"][pending_prompt];
+    assert_eq!(prompt["agent_id"]["tool"], "mock_ai", "{output}");
+}
+
+#[test]
+fn test_blame_json_pending_lines_become_committed() {
+    let repo = TestRepo::new();
+    setup_pending_ai_lines(&repo);
+    repo.stage_all_and_commit("Add pending AI lines").unwrap();
+
+    let output = repo.git_ai(&["blame", "--json", "app.py"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let line_status = json["line_status"].as_object().unwrap();
+    assert!(
+        line_status.values().all(|status| status == "committed"),
+        "{output}"
+    );
+    let lines = json["lines"].as_object().unwrap();
+    assert!(lines.keys().eq(line_status.keys()), "{output}");
+}
+
+#[test]
+fn test_blame_pending_lines_keep_the_uncommitted_hash() {
+    let repo = TestRepo::new();
+    setup_pending_ai_lines(&repo);
+
+    // Pending lines name their agent but are still blamed to the all-zero commit
+    let output = repo.git_ai(&["blame", "app.py"]).unwrap();
+    for contents in ["pending_ai_one()", "pending_ai_two()"] {
+        let line = blame_line(&output, contents);
+        assert!(line.starts_with("0000000"), "{output}");
+        assert!(line.contains("mock_ai"), "{output}");
+    }
+    assert!(
+        !blame_line(&output, "committed_ai()").starts_with("0000000"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_blame_unstaged_ai_lines_stay_pending_after_partial_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["def main():", "    pass"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["    staged_ai()".ai()]);
+    file.stage();
+    file.insert_at(3, lines!["unstaged_ai()".ai()]);
+    repo.commit("Commit staged AI line").unwrap();
+
+    let output = repo.git_ai(&["blame", "app.py"]).unwrap();
+    let unstaged = blame_line(&output, "unstaged_ai()");
+    assert!(unstaged.starts_with("0000000"), "{output}");
+    assert!(unstaged.contains("mock_ai"), "{output}");
+
+    let output = repo
+        .git_ai(&["blame", "--json", "--include-uncommitted", "app.py"])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["line_status"]["4"], "pending", "{output}");
+    assert_eq!(json["line_status"]["2"], "committed", "{output}");
+}
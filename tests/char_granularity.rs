@@ -0,0 +1,116 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// AI writes two lines, then a human renames one call on the first line
+fn repo_with_mixed_line() -> (TestRepo, String) {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("calc.rs");
+
+    fs::write(&file_path, "fn main() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(
+        &file_path,
+        "fn main() {}\nlet total = compute(values);\nlet count = values.len();\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    fs::write(
+        &file_path,
+        "fn main() {}\nlet total = checked_sum(values);\nlet count = values.len();\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+
+    let commit = repo.stage_all_and_commit("Add totals").unwrap();
+    (repo, commit.commit_sha)
+}
+
+fn authors_on_line(spans: &serde_json::Value, line: u64) -> Vec<String> {
+    spans
+        .as_array()
+        .expect("char_spans should be an array")
+        .iter()
+        .filter(|span| span["line"] == line)
+        .map(|span| span["author"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_blame_json_char_granularity_emits_mixed_line_spans() {
+    let (repo, _) = repo_with_mixed_line();
+
+    let output = repo
+        .git_ai(&["blame", "--json", "--granularity=char", "calc.rs"])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let spans = &json["char_spans"];
+    let authors = authors_on_line(spans, 2);
+    assert!(authors.contains(&"human".to_string()), "{}", output);
+    let ai_author = authors
+        .iter()
+        .find(|author| *author != "human")
+        .unwrap_or_else(|| panic!("Line 2 should have an AI span: {}", output));
+    assert!(
+        json["prompts"].get(ai_author).is_some(),
+        "Span prompts should be included: {}",
+        output
+    );
+
+    // Single-author lines stay line-level
+    assert!(authors_on_line(spans, 3).is_empty());
+
+    // Spans cover the whole line without overlapping
+    let line_spans: Vec<&serde_json::Value> = spans
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|span| span["line"] == 2)
+        .collect();
+    assert_eq!(line_spans.first().unwrap()["start"], 0);
+    for pair in line_spans.windows(2) {
+        assert_eq!(pair[0]["end"], pair[1]["start"]);
+    }
+    assert_eq!(
+        line_spans.last().unwrap()["end"],
+        "let total = checked_sum(values);".chars().count()
+    );
+}
+
+#[test]
+fn test_blame_json_line_granularity_has_no_spans() {
+    let (repo, _) = repo_with_mixed_line();
+
+    let output = repo.git_ai(&["blame", "--json", "calc.rs"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(json.get("char_spans").is_none());
+
+    let result = repo.git_ai(&["blame", "--granularity=char", "calc.rs"]);
+    assert!(result.is_err(), "--granularity=char requires --json");
+}
+
+#[test]
+fn test_diff_json_char_granularity_emits_mixed_line_spans() {
+    let (repo, commit_sha) = repo_with_mixed_line();
+
+    let output = repo
+        .git_ai(&["diff", &commit_sha, "--json", "--granularity=char"])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let spans = &json["files"]["calc.rs"]["char_spans"];
+    let authors = authors_on_line(spans, 2);
+    assert!(authors.contains(&"human".to_string()), "{}", output);
+    let ai_author = authors.iter().find(|author| *author != "human").unwrap();
+    assert!(json["prompts"].get(ai_author).is_some(), "{}", output);
+
+    let output = repo.git_ai(&["diff", &commit_sha, "--json"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(json["files"]["calc.rs"].get("char_spans").is_none());
+}
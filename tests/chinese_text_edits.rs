@@ -91,6 +91,9 @@ fn test_chinese_partial_staging() {
         "甲: 更新".ai(),
         "乙: 更新".ai(),
         // "丙: 三" is not committed because the unstaged insert adds a newline.
+        // The unstaged AI lines are still pending in the working log, so blame shows their agent
+        "新增: 四".ai(),
+        "新增: 五".ai(),
     ]);
 }
 
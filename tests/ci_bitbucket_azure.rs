@@ -0,0 +1,282 @@
+#[macro_use]
+mod repos;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use std::thread::JoinHandle;
+
+/// Serve `body` as a JSON response to a single HTTP request. Returns the server URL.
+fn serve_json_once(body: String) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    });
+    (url, handle)
+}
+
+fn read_remote_authorship_note(upstream: &TestRepo, commit_sha: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args([
+            "--git-dir",
+            upstream.path().to_str().unwrap(),
+            "notes",
+            "--ref=ai",
+            "show",
+            commit_sha,
+        ])
+        .output()
+        .unwrap();
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// The CI clone has no user config, so the notes commit needs an identity from the
+/// environment (the workflow templates set one globally).
+const CI_IDENTITY: [(&str, &str); 4] = [
+    ("GIT_AUTHOR_NAME", "CI Bot"),
+    ("GIT_AUTHOR_EMAIL", "ci@example.com"),
+    ("GIT_COMMITTER_NAME", "CI Bot"),
+    ("GIT_COMMITTER_EMAIL", "ci@example.com"),
+];
+
+struct SquashMergedPr {
+    base_sha: String,
+    head_sha: String,
+    merge_sha: String,
+}
+
+/// Push an AI-authored feature branch, then squash it into main without git-ai hooks,
+/// leaving the merge commit without authorship like a hosted "squash and merge" would.
+fn setup_squash_merged_pr(local: &TestRepo) -> SquashMergedPr {
+    let mut file = local.filename("parser.rs");
+    file.set_contents(lines!["fn parse() {}".human()]);
+    let base_sha = local
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+    local.git(&["push", "-u", "origin", "main"]).unwrap();
+
+    local.git(&["checkout", "-b", "feature/parser"]).unwrap();
+    file.set_contents(lines![
+        "fn parse() {}".human(),
+        "fn parse_expr() {}".ai(),
+        "fn parse_stmt() {}".ai()
+    ]);
+    let head_sha = local.stage_all_and_commit("Add parser").unwrap().commit_sha;
+    local
+        .git(&["push", "-u", "origin", "feature/parser"])
+        .unwrap();
+
+    local.git_og(&["checkout", "main"]).unwrap();
+    fs::write(
+        local.path().join("parser.rs"),
+        "fn parse() {}\nfn parse_expr() {}\nfn parse_stmt() {}",
+    )
+    .unwrap();
+    local.git_og(&["add", "-A"]).unwrap();
+    local.git_og(&["commit", "-m", "Add parser (#1)"]).unwrap();
+    let merge_sha = local
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+    local.git_og(&["push", "origin", "main"]).unwrap();
+
+    SquashMergedPr {
+        base_sha,
+        head_sha,
+        merge_sha,
+    }
+}
+
+#[test]
+fn test_ci_bitbucket_run_rewrites_squash_merge() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let pr = setup_squash_merged_pr(&local);
+    assert!(read_remote_authorship_note(&upstream, &pr.merge_sha).is_none());
+
+    let body = serde_json::json!({
+        "values": [{
+            "id": 1,
+            "title": "Add parser",
+            "source": {"branch": {"name": "feature/parser"}, "commit": {"hash": &pr.head_sha[..12]}},
+            "destination": {"branch": {"name": "main"}, "commit": {"hash": &pr.base_sha[..12]}},
+            "merge_commit": {"hash": &pr.merge_sha[..12]}
+        }]
+    });
+    let (api_url, server) = serve_json_once(body.to_string());
+
+    let output = local
+        .git_ai_with_env(
+            &["ci", "bitbucket", "run"],
+            &[
+                ("BITBUCKET_COMMIT", pr.merge_sha.as_str()),
+                ("BITBUCKET_WORKSPACE", "acme"),
+                ("BITBUCKET_REPO_SLUG", "parser"),
+                (
+                    "BITBUCKET_GIT_HTTP_ORIGIN",
+                    upstream.path().to_str().unwrap(),
+                ),
+                ("BITBUCKET_API_URL", api_url.as_str()),
+                ("BITBUCKET_ACCESS_TOKEN", "secret-token"),
+                CI_IDENTITY[0],
+                CI_IDENTITY[1],
+                CI_IDENTITY[2],
+                CI_IDENTITY[3],
+            ],
+        )
+        .expect("ci bitbucket run should succeed");
+
+    let request = server.join().unwrap();
+    assert!(
+        request.starts_with("GET /repositories/acme/parser/pullrequests?state=MERGED"),
+        "{request}"
+    );
+    assert!(
+        request.contains("Authorization: Bearer secret-token"),
+        "{request}"
+    );
+    assert!(output.contains("Found matching PR #1"), "{output}");
+
+    let note = read_remote_authorship_note(&upstream, &pr.merge_sha)
+        .expect("authorship should be pushed for the squash commit");
+    assert!(note.contains("parser.rs"), "{note}");
+    assert!(!local.path().join("git-ai-ci-clone").exists());
+}
+
+#[test]
+fn test_ci_bitbucket_run_skips_unmatched_commit() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let pr = setup_squash_merged_pr(&local);
+
+    let (api_url, server) = serve_json_once(r#"{"values": []}"#.to_string());
+    let output = local
+        .git_ai_with_env(
+            &["ci", "bitbucket", "run"],
+            &[
+                ("BITBUCKET_COMMIT", pr.merge_sha.as_str()),
+                ("BITBUCKET_WORKSPACE", "acme"),
+                ("BITBUCKET_REPO_SLUG", "parser"),
+                (
+                    "BITBUCKET_GIT_HTTP_ORIGIN",
+                    upstream.path().to_str().unwrap(),
+                ),
+                ("BITBUCKET_API_URL", api_url.as_str()),
+            ],
+        )
+        .expect("an unmatched commit is not an error");
+    server.join().unwrap();
+
+    assert!(output.contains("No recent PR found"), "{output}");
+    assert!(read_remote_authorship_note(&upstream, &pr.merge_sha).is_none());
+}
+
+#[test]
+fn test_ci_bitbucket_run_requires_environment() {
+    let repo = TestRepo::new();
+    let err = repo
+        .git_ai(&["ci", "bitbucket", "run"])
+        .expect_err("missing environment should fail");
+    assert!(
+        err.contains("BITBUCKET_COMMIT environment variable not set"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_ci_azure_run_rewrites_squash_merge() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let pr = setup_squash_merged_pr(&local);
+
+    let body = serde_json::json!({
+        "count": 1,
+        "value": [{
+            "pullRequestId": 1,
+            "title": "Add parser",
+            "status": "completed",
+            "sourceRefName": "refs/heads/feature/parser",
+            "targetRefName": "refs/heads/main",
+            "lastMergeSourceCommit": {"commitId": &pr.head_sha},
+            "lastMergeTargetCommit": {"commitId": &pr.base_sha},
+            "lastMergeCommit": {"commitId": &pr.merge_sha}
+        }]
+    });
+    let (api_url, server) = serve_json_once(body.to_string());
+
+    let output = local
+        .git_ai_with_env(
+            &["ci", "azure", "run"],
+            &[
+                ("BUILD_SOURCEVERSION", pr.merge_sha.as_str()),
+                ("SYSTEM_COLLECTIONURI", &format!("{}/acme/", api_url)),
+                ("SYSTEM_TEAMPROJECT", "Parser"),
+                ("BUILD_REPOSITORY_ID", "repo-guid"),
+                ("BUILD_REPOSITORY_URI", upstream.path().to_str().unwrap()),
+                ("SYSTEM_ACCESSTOKEN", "secret-token"),
+                CI_IDENTITY[0],
+                CI_IDENTITY[1],
+                CI_IDENTITY[2],
+                CI_IDENTITY[3],
+            ],
+        )
+        .expect("ci azure run should succeed");
+
+    let request = server.join().unwrap();
+    assert!(
+        request.starts_with(
+            "GET /acme/Parser/_apis/git/repositories/repo-guid/pullrequests?searchCriteria.status=completed"
+        ),
+        "{request}"
+    );
+    assert!(output.contains("Found matching PR !1"), "{output}");
+
+    let note = read_remote_authorship_note(&upstream, &pr.merge_sha)
+        .expect("authorship should be pushed for the squash commit");
+    assert!(note.contains("parser.rs"), "{note}");
+}
+
+#[test]
+fn test_ci_azure_install_writes_pipeline() {
+    let repo = TestRepo::new();
+    let output = repo
+        .git_ai(&["ci", "azure", "install"])
+        .expect("install should succeed");
+    assert!(output.contains("azure-pipelines-git-ai.yml"), "{output}");
+
+    let pipeline = fs::read_to_string(repo.path().join("azure-pipelines-git-ai.yml")).unwrap();
+    assert!(pipeline.contains("git-ai ci azure run"));
+    assert!(pipeline.contains("SYSTEM_ACCESSTOKEN: $(System.AccessToken)"));
+}
+
+#[test]
+fn test_ci_bitbucket_install_prints_snippet() {
+    let repo = TestRepo::new();
+    let output = repo
+        .git_ai(&["ci", "bitbucket", "install"])
+        .expect("install should succeed");
+    assert!(output.contains("bitbucket-pipelines.yml"), "{output}");
+    assert!(output.contains("git-ai ci bitbucket run"), "{output}");
+}
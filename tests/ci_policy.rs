@@ -0,0 +1,163 @@
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// Human base commit followed by an AI commit touching `src/crypto/` and `migrations/`.
+/// Returns the base commit sha.
+fn setup_repo(repo: &TestRepo) -> String {
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project".human()]);
+    let base = repo
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+
+    let mut aes = repo.filename("src/crypto/aes.rs");
+    aes.set_contents(lines![
+        "fn encrypt() {}".ai(),
+        "fn decrypt() {}".ai(),
+        "// reviewed".human()
+    ]);
+    let mut migration = repo.filename("migrations/001.sql");
+    migration.set_contents(lines!["CREATE TABLE users (id INT);".ai()]);
+    repo.stage_all_and_commit("Add crypto and migration")
+        .unwrap();
+
+    base
+}
+
+fn write_policy(repo: &TestRepo, policy: &str) -> String {
+    let path = repo.path().join(".git-ai-policy.toml");
+    fs::write(&path, policy).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_ci_check_fails_on_violated_rules() {
+    let repo = TestRepo::new();
+    let base = setup_repo(&repo);
+    write_policy(
+        &repo,
+        r#"
+[[rule]]
+name = "no-ai-migrations"
+paths = ["migrations/**"]
+max_ai_lines = 0
+
+[[rule]]
+name = "crypto-ai-share"
+paths = ["src/crypto/**"]
+max_ai_share = 50.0
+"#,
+    );
+
+    let err = repo
+        .git_ai(&["ci", "check", "--base", &base])
+        .expect_err("policy should fail");
+    assert!(err.contains("✗ no-ai-migrations: 1 AI lines"), "{err}");
+    assert!(
+        err.contains("migrations/001.sql (1 of 1 added lines AI)"),
+        "{err}"
+    );
+    assert!(err.contains("✗ crypto-ai-share: 66.7% AI lines"), "{err}");
+    assert!(
+        err.contains("src/crypto/aes.rs (2 of 3 added lines AI)"),
+        "{err}"
+    );
+    assert!(err.contains("2 violation(s)"), "{err}");
+}
+
+#[test]
+fn test_ci_check_passes_within_limits() {
+    let repo = TestRepo::new();
+    let base = setup_repo(&repo);
+    let policy = write_policy(
+        &repo,
+        r#"
+[[rule]]
+name = "crypto-ai-share"
+paths = ["src/crypto/**"]
+max_ai_share = 70.0
+
+[[rule]]
+name = "docs-human-only"
+paths = ["docs/**"]
+max_ai_lines = 0
+"#,
+    );
+
+    let output = repo
+        .git_ai(&["ci", "check", "--base", &base, "--policy", &policy])
+        .expect("policy should pass");
+    assert!(output.contains("AI authorship policy passed"), "{output}");
+
+    let json = repo
+        .git_ai(&["ci", "check", "--base", &base, "--json"])
+        .expect("policy should pass");
+    let start = json.find('{').unwrap();
+    let end = json.rfind('}').unwrap();
+    let result: serde_json::Value = serde_json::from_str(&json[start..=end]).unwrap();
+    assert_eq!(result["passed"], true);
+    assert_eq!(result["violations"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn test_ci_check_only_counts_commits_after_base() {
+    let repo = TestRepo::new();
+    setup_repo(&repo);
+    let head = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    let mut notes = repo.filename("NOTES.md");
+    notes.set_contents(lines!["human notes".human()]);
+    repo.stage_all_and_commit("Human follow-up").unwrap();
+
+    write_policy(&repo, "[[rule]]\nname = \"no-ai\"\nmax_ai_lines = 0\n");
+    repo.git_ai(&["ci", "check", "--base", &head])
+        .expect("AI lines before the base are not part of the range");
+    assert!(
+        repo.git_ai(&["ci", "check", "--base", &format!("{}~1", head)])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_ci_check_reports_invalid_policy() {
+    let repo = TestRepo::new();
+    let base = setup_repo(&repo);
+    write_policy(&repo, "[[rule]]\nname = \"empty\"\n");
+
+    let err = repo
+        .git_ai(&["ci", "check", "--base", &base])
+        .expect_err("invalid policy should fail");
+    assert!(err.contains("has no constraint"), "{err}");
+}
+
+#[test]
+fn test_ci_check_counts_lines_rewritten_in_the_range_once_with_their_final_author() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project".human()]);
+    let base = repo
+        .stage_all_and_commit("Initial commit")
+        .unwrap()
+        .commit_sha;
+
+    let mut lib = repo.filename("src/lib.rs");
+    lib.set_contents(lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("AI draft").unwrap();
+
+    // A human rewrites the first AI line and the AI revises the second
+    lib.set_contents(lines![
+        "fn human_one() {}".human(),
+        "fn ai_two_v2() {}".ai()
+    ]);
+    repo.stage_all_and_commit("Review fixes").unwrap();
+
+    write_policy(&repo, "[[rule]]\nname = \"ai-lines\"\nmax_ai_lines = 0\n");
+    let err = repo
+        .git_ai(&["ci", "check", "--base", &base])
+        .expect_err("the revised AI line is still in the final diff");
+    assert!(err.contains("✗ ai-lines: 1 AI lines"), "{err}");
+    assert!(err.contains("src/lib.rs (1 of 2 added lines AI)"), "{err}");
+}
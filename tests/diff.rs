@@ -1019,3 +1019,89 @@ fn test_diff_ignores_git_diff_opts_env_for_internal_diff() {
         ai_diff
     );
 }
+
+#[test]
+fn test_diff_sarif_output() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["print('base')".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(lines![
+        "print('base')".human(),
+        "print('ai one')".ai(),
+        "print('ai two')".ai(),
+        "print('human')".human()
+    ]);
+    let commit = repo.stage_all_and_commit("Add lines").unwrap();
+
+    let output = repo
+        .git_ai(&["diff", &commit.commit_sha, "--sarif"])
+        .expect("git-ai diff --sarif should succeed");
+    let sarif: serde_json::Value =
+        serde_json::from_str(&output).expect("Output should be valid SARIF JSON");
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1, "One contiguous AI range: {}", output);
+
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "app.py");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["endLine"], 3);
+
+    let prompt_hash = results[0]["properties"]["promptHash"].as_str().unwrap();
+    assert!(
+        commit
+            .authorship_log
+            .metadata
+            .prompts
+            .contains_key(prompt_hash),
+        "SARIF prompt hash should come from the authorship log"
+    );
+}
+
+#[test]
+fn test_diff_github_annotations_output() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(lines![
+        "fn base() {}".human(),
+        "fn generated() {}".ai(),
+        "fn written() {}".human()
+    ]);
+    let commit = repo.stage_all_and_commit("Add functions").unwrap();
+
+    let output = repo
+        .git_ai(&["diff", &commit.commit_sha, "--github-annotations"])
+        .expect("git-ai diff --github-annotations should succeed");
+    let notices: Vec<&str> = output
+        .lines()
+        .filter(|line| line.starts_with("::notice "))
+        .collect();
+
+    assert_eq!(notices.len(), 1, "Expected one notice, got: {}", output);
+    assert!(
+        notices[0].starts_with("::notice file=lib.rs,line=2,endLine=2,title=AI-authored ("),
+        "Unexpected notice: {}",
+        notices[0]
+    );
+    assert!(notices[0].contains("::AI-authored by "));
+}
+
+#[test]
+fn test_diff_rejects_multiple_output_formats() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("a.txt");
+    file.set_contents(lines!["a".human()]);
+    let commit = repo.stage_all_and_commit("Initial").unwrap();
+
+    let result = repo.git_ai(&["diff", &commit.commit_sha, "--json", "--sarif"]);
+    assert!(result.is_err(), "Conflicting format flags should fail");
+}
@@ -0,0 +1,269 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn read_authorship_note(repo: &TestRepo, commit_sha: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args([
+            "-C",
+            repo.path().to_str().expect("valid repo path"),
+            "--no-pager",
+            "notes",
+            "--ref=ai",
+            "show",
+            commit_sha,
+        ])
+        .output()
+        .expect("failed to run git notes show");
+
+    if output.status.success() {
+        let note = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if note.is_empty() { None } else { Some(note) }
+    } else {
+        None
+    }
+}
+
+fn head_sha(repo: &TestRepo) -> String {
+    repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string()
+}
+
+/// Format the commits in `range` into `<repo>/patches` and return the sorted patch paths
+fn format_patches(repo: &TestRepo, range: &str) -> Vec<PathBuf> {
+    repo.git(&["format-patch", "-o", "patches", range]).unwrap();
+    let mut patches: Vec<PathBuf> = fs::read_dir(repo.path().join("patches"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    patches.sort();
+    patches
+}
+
+fn git_am(repo: &TestRepo, patches: &[PathBuf]) -> Result<String, String> {
+    let mut args = vec!["am".to_string()];
+    args.extend(patches.iter().map(|p| p.to_string_lossy().to_string()));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    repo.git(&args)
+}
+
+#[test]
+fn test_format_patch_embeds_authorship_payload() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("Add AI functions").unwrap();
+
+    let mut human = repo.filename("README.md");
+    human.set_contents(lines!["# Readme".human()]);
+    repo.stage_all_and_commit("Add readme").unwrap();
+
+    let patches = format_patches(&repo, "HEAD~2");
+    assert_eq!(patches.len(), 2);
+
+    let ai_patch = fs::read_to_string(&patches[0]).unwrap();
+    assert!(
+        ai_patch.contains("---\nGit-AI-Authorship-Begin\nlib.rs\n"),
+        "{ai_patch}"
+    );
+    assert!(ai_patch.contains("Git-AI-Authorship-End"), "{ai_patch}");
+
+    let human_patch = fs::read_to_string(&patches[1]).unwrap();
+    assert!(!human_patch.contains("Git-AI-Authorship-Begin"));
+
+    // Running format-patch again must not embed the payload twice
+    format_patches(&repo, "HEAD~2");
+    let ai_patch = fs::read_to_string(&patches[0]).unwrap();
+    assert_eq!(ai_patch.matches("Git-AI-Authorship-Begin").count(), 1);
+}
+
+#[test]
+fn test_format_patch_only_rewrites_the_patches_it_wrote() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai()]);
+    repo.stage_all_and_commit("Add AI function").unwrap();
+
+    // A patch already in the output directory, written without git-ai
+    repo.git_og(&["format-patch", "-o", "patches", "HEAD~1"])
+        .unwrap();
+    let patches_dir = repo.path().join("patches");
+    let kept_patch = patches_dir.join("9999-kept.patch");
+    let written = fs::read_dir(&patches_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    fs::rename(&written, &kept_patch).unwrap();
+    let kept_content = fs::read_to_string(&kept_patch).unwrap();
+
+    let patches = format_patches(&repo, "HEAD~1");
+    assert_eq!(patches.len(), 2);
+    let new_patch = fs::read_to_string(&patches[0]).unwrap();
+    assert!(new_patch.contains("Git-AI-Authorship-Begin"), "{new_patch}");
+    assert_eq!(fs::read_to_string(&kept_patch).unwrap(), kept_content);
+}
+
+#[test]
+fn test_format_patch_stdout_warns_that_authorship_is_not_embedded() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai()]);
+    repo.stage_all_and_commit("Add AI function").unwrap();
+
+    let output = repo.git(&["format-patch", "--stdout", "HEAD~1"]).unwrap();
+    assert!(
+        output.contains("Subject: [PATCH] Add AI function"),
+        "{output}"
+    );
+    assert!(!output.contains("Git-AI-Authorship-Begin"));
+    assert!(
+        output.contains("does not embed AI authorship in `format-patch --stdout`"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_am_in_same_repo_preserves_authorship() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(1, lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("Add AI functions").unwrap();
+
+    let patches = format_patches(&repo, &main_branch);
+    repo.git(&["checkout", &main_branch]).unwrap();
+
+    // Move main ahead so the patch applies to a different commit
+    let mut other = repo.filename("other.rs");
+    other.set_contents(lines!["fn other() {}".human()]);
+    repo.stage_all_and_commit("Unrelated change").unwrap();
+
+    git_am(&repo, &patches).unwrap();
+    let applied = head_sha(&repo);
+
+    assert!(read_authorship_note(&repo, &applied).is_some());
+    file.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+        "fn tail() {}".human(),
+    ]);
+}
+
+#[test]
+fn test_am_in_other_repo_restores_authorship_from_payload() {
+    let source = TestRepo::new();
+    let mut file = source.filename("lib.rs");
+    file.set_contents(lines![
+        "// a",
+        "// b",
+        "// c",
+        "// d",
+        "fn base() {}",
+        "fn tail() {}"
+    ]);
+    source.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(5, lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    source.stage_all_and_commit("Add AI functions").unwrap();
+    file.insert_at(8, lines!["fn human() {}".human(), "fn ai_three() {}".ai()]);
+    source.stage_all_and_commit("Add more functions").unwrap();
+
+    let patches = format_patches(&source, "HEAD~2");
+    assert_eq!(patches.len(), 2);
+
+    // The target has extra lines at the top, so every hunk applies at an offset
+    let target = TestRepo::new();
+    let mut target_file = target.filename("lib.rs");
+    target_file.set_contents(lines![
+        "// header one",
+        "// header two",
+        "// a",
+        "// b",
+        "// c",
+        "// d",
+        "fn base() {}",
+        "fn tail() {}"
+    ]);
+    target.stage_all_and_commit("Initial commit").unwrap();
+    let original_head = head_sha(&target);
+
+    git_am(&target, &patches).unwrap();
+
+    let new_commits = target
+        .git(&["rev-list", &format!("{}..HEAD", original_head)])
+        .unwrap();
+    for commit in new_commits.lines() {
+        let note = read_authorship_note(&target, commit)
+            .unwrap_or_else(|| panic!("applied commit {} should have a note", commit));
+        assert!(note.contains("lib.rs"), "{note}");
+    }
+
+    target_file.assert_lines_and_blame(lines![
+        "// header one".human(),
+        "// header two".human(),
+        "// a".human(),
+        "// b".human(),
+        "// c".human(),
+        "// d".human(),
+        "fn base() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+        "fn tail() {}".human(),
+        "fn human() {}".human(),
+        "fn ai_three() {}".ai(),
+    ]);
+}
+
+#[test]
+fn test_am_with_conflict_and_continue() {
+    let source = TestRepo::new();
+    let mut file = source.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    source.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai()]);
+    source.stage_all_and_commit("Add AI function").unwrap();
+    let patches = format_patches(&source, "HEAD~1");
+
+    let target = TestRepo::new();
+    let mut target_file = target.filename("lib.rs");
+    target_file.set_contents(lines!["fn base_renamed() {}", "fn tail() {}"]);
+    target.stage_all_and_commit("Initial commit").unwrap();
+
+    assert!(git_am(&target, &patches).is_err(), "Should have conflict");
+
+    fs::write(
+        target.path().join("lib.rs"),
+        "fn base_renamed() {}\nfn ai_one() {}\nfn tail() {}",
+    )
+    .unwrap();
+    target.git(&["add", "lib.rs"]).unwrap();
+    target.git(&["am", "--continue"]).unwrap();
+
+    let applied = head_sha(&target);
+    assert!(read_authorship_note(&target, &applied).is_some());
+    target_file.assert_lines_and_blame(lines![
+        "fn base_renamed() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn tail() {}".human(),
+    ]);
+}
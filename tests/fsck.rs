@@ -0,0 +1,102 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn read_authorship_note(repo: &TestRepo, commit_sha: &str) -> String {
+    repo.git(&["notes", "--ref=ai", "show", commit_sha])
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+fn write_authorship_note(repo: &TestRepo, commit_sha: &str, content: &str) {
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", content, commit_sha])
+        .unwrap();
+}
+
+fn commit_ai_lines(repo: &TestRepo) -> String {
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("Add AI functions").unwrap();
+    repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string()
+}
+
+#[test]
+fn test_fsck_passes_on_valid_notes() {
+    let repo = TestRepo::new();
+    commit_ai_lines(&repo);
+
+    let output = repo.git_ai(&["fsck"]).unwrap();
+    assert!(
+        output.contains("Checked 2 notes: 0 issues found"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_fsck_reports_and_repairs_invalid_notes() {
+    let repo = TestRepo::new();
+    let head = commit_ai_lines(&repo);
+
+    let note = read_authorship_note(&repo, &head);
+    let (attestations, metadata) = note.split_once("\n---\n").unwrap();
+    let hash = attestations
+        .lines()
+        .nth(1)
+        .unwrap()
+        .split_whitespace()
+        .next()
+        .unwrap();
+
+    // Stretch the range past the end of the file and add an orphaned prompt record
+    let mut metadata: serde_json::Value = serde_json::from_str(metadata).unwrap();
+    let prompt = metadata["prompts"][hash].clone();
+    metadata["prompts"]["0123456789abcdef"] = prompt;
+    let corrupted = format!(
+        "lib.rs\n  {} 2-40\nmissing.rs\n  {} 1\n---\n{}",
+        hash,
+        hash,
+        serde_json::to_string_pretty(&metadata).unwrap()
+    );
+    write_authorship_note(&repo, &head, &corrupted);
+
+    assert!(repo.git_ai(&["fsck"]).is_err(), "fsck should fail");
+
+    let output = repo.git_ai(&["fsck", "--repair", "--json"]).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let kinds: Vec<&str> = report["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec!["line_out_of_range", "missing_file", "orphaned_prompt"]
+    );
+    assert_eq!(report["notes_repaired"], 1);
+
+    let repaired = read_authorship_note(&repo, &head);
+    assert!(
+        repaired.starts_with(&format!("lib.rs\n  {} 2-4\n---", hash)),
+        "{repaired}"
+    );
+    assert!(!repaired.contains("0123456789abcdef"), "{repaired}");
+
+    let output = repo.git_ai(&["fsck"]).unwrap();
+    assert!(output.contains("0 issues found"), "{output}");
+}
+
+#[test]
+fn test_fsck_reports_unparseable_notes() {
+    let repo = TestRepo::new();
+    let head = commit_ai_lines(&repo);
+    write_authorship_note(&repo, &head, "not an authorship log");
+
+    assert!(repo.git_ai(&["fsck", "--repair"]).is_err());
+    assert_eq!(read_authorship_note(&repo, &head), "not an authorship log");
+}
@@ -896,6 +896,7 @@ fn test_gemini_e2e_partial_staging() {
         "line2".human(),
         "ai_line3".ai(),
         "ai_line4".ai(),
-        // ai_line5 is not committed because it's unstaged
+        // ai_line5 is unstaged but still pending in the working log, so blame shows its agent
+        "ai_line5".ai(),
     ]);
 }
@@ -0,0 +1,190 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::CheckpointKind;
+use git_ai::commands::checkpoint_agent::agent_presets::{
+    AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+};
+use git_ai::commands::checkpoint_agent::junie_preset::JuniePreset;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+use test_utils::fixture_path;
+
+const SESSION_ID: &str = "junie-3d1b7e2a";
+
+fn run_junie_preset(
+    hook_input: serde_json::Value,
+) -> Result<AgentRunResult, git_ai::error::GitAiError> {
+    JuniePreset.run(AgentCheckpointFlags {
+        hook_input: Some(hook_input.to_string()),
+    })
+}
+
+#[test]
+fn test_parse_junie_session() {
+    let fixture = fixture_path("junie-session.jsonl");
+    let (transcript, model) =
+        JuniePreset::transcript_and_model_from_junie_session(fixture.to_str().unwrap())
+            .expect("Failed to parse Junie session");
+
+    // Switching models mid-session records the latest one
+    assert_eq!(model.as_deref(), Some("gpt-5"));
+
+    let messages = transcript.messages();
+    assert!(matches!(
+        &messages[0],
+        Message::User { text, timestamp: Some(_) } if text == "Add a multiply helper to math.ts"
+    ));
+    assert!(matches!(&messages[1], Message::Thinking { .. }));
+    assert!(
+        matches!(&messages[2], Message::Plan { text, .. } if text.starts_with("1. Open math.ts"))
+    );
+    assert!(matches!(&messages[3], Message::ToolUse { name, .. } if name == "open"));
+    match &messages[4] {
+        Message::ToolUse { name, input, .. } => {
+            assert_eq!(name, "edit_file");
+            assert_eq!(input["path"], "/workspace/app/math.ts");
+        }
+        other => panic!("Expected edit_file tool use, got {:?}", other),
+    }
+    assert!(matches!(
+        &messages[5],
+        Message::Assistant { text, .. } if text == "Added `multiply` to `math.ts`."
+    ));
+
+    // Tool results and session bookkeeping are skipped
+    assert_eq!(messages.len(), 6);
+}
+
+#[test]
+fn test_junie_preset_pre_tool_use_is_human_checkpoint() {
+    let result = run_junie_preset(json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": SESSION_ID,
+        "cwd": "/workspace/app",
+        "tool_input": { "path": "/workspace/app/math.ts" }
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::Human);
+    assert_eq!(result.agent_id.tool, "junie");
+    assert_eq!(result.agent_id.id, SESSION_ID);
+    assert_eq!(
+        result.will_edit_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert!(result.transcript.is_none());
+}
+
+#[test]
+fn test_junie_preset_post_tool_use_reads_session() {
+    let fixture = fixture_path("junie-session.jsonl");
+    let result = run_junie_preset(json!({
+        "hookEventName": "PostToolUse",
+        "sessionId": SESSION_ID,
+        "cwd": "/workspace/app",
+        "tool_input": { "file_path": "/workspace/app/math.ts" },
+        "transcript_path": fixture.to_string_lossy(),
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, "gpt-5");
+    assert_eq!(
+        result.edited_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert_eq!(result.transcript.unwrap().messages().len(), 6);
+}
+
+#[test]
+fn test_junie_preset_missing_session_falls_back_to_empty_transcript() {
+    let result = run_junie_preset(json!({
+        "hook_event_name": "PostToolUse",
+        "session_id": SESSION_ID,
+        "cwd": "/workspace/app",
+        "transcript_path": "/nonexistent/session.jsonl",
+    }))
+    .unwrap();
+
+    assert_eq!(result.agent_id.model, "unknown");
+    assert!(result.transcript.unwrap().messages().is_empty());
+}
+
+#[test]
+fn test_junie_hooks_attribute_session_edits() {
+    let repo = TestRepo::new();
+    let sessions_dir = tempfile::tempdir().unwrap();
+    fs::copy(
+        fixture_path("junie-session.jsonl"),
+        sessions_dir.path().join(format!("{}.jsonl", SESSION_ID)),
+    )
+    .unwrap();
+    let env = [(
+        "GIT_AI_JUNIE_SESSIONS_DIR",
+        sessions_dir.path().to_str().unwrap(),
+    )];
+
+    let mut file = repo.filename("math.ts");
+    file.set_contents(lines!["export const add = (a, b) => a + b;", "// end"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let file_path = repo.canonical_path().join("math.ts");
+    let hook_input = |event: &str| {
+        json!({
+            "hook_event_name": event,
+            "session_id": SESSION_ID,
+            "cwd": repo.canonical_path().to_string_lossy(),
+            "tool_input": { "path": file_path.to_string_lossy() }
+        })
+        .to_string()
+    };
+
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "junie",
+            "--hook-input",
+            &hook_input("PreToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+    fs::write(
+        &file_path,
+        "export const add = (a, b) => a + b;\nexport const multiply = (a, b) => a * b;\n// end",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "junie",
+            "--hook-input",
+            &hook_input("PostToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "export const add = (a, b) => a + b;".human(),
+        "export const multiply = (a, b) => a * b;".ai(),
+        "// end".human(),
+    ]);
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("Should record the Junie session");
+    assert_eq!(prompt.agent_id.tool, "junie");
+    assert_eq!(prompt.agent_id.id, SESSION_ID);
+    assert_eq!(prompt.agent_id.model, "gpt-5");
+}
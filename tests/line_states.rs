@@ -0,0 +1,96 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::authorship_log::LineRange;
+use git_ai::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, LineState};
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+#[test]
+fn test_commit_records_ai_and_human_edited_line_states() {
+    let repo = TestRepo::new();
+    let file_path = repo.path().join("calc.rs");
+
+    fs::write(&file_path, "fn main() {}\n").unwrap();
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(
+        &file_path,
+        "fn main() {}\nlet a = 1;\nlet b = 2;\nlet c = 3;\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    // A human rewrites the middle AI line before committing
+    fs::write(
+        &file_path,
+        "fn main() {}\nlet a = 1;\nlet b = a + 41;\nlet c = 3;\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+
+    let commit = repo.stage_all_and_commit("Add values").unwrap();
+    let metadata = &commit.authorship_log.metadata;
+    assert_eq!(metadata.schema_version, AUTHORSHIP_LOG_VERSION);
+
+    let states = metadata
+        .line_states
+        .get("calc.rs")
+        .expect("calc.rs should have line states");
+    let ai = states
+        .iter()
+        .find(|state| state.state == LineState::Ai)
+        .expect("untouched AI lines should be recorded");
+    let edited = states
+        .iter()
+        .find(|state| state.state == LineState::AiHumanEdited)
+        .expect("the human-edited AI line should be recorded");
+
+    assert_eq!(ai.hash, edited.hash);
+    assert!(metadata.prompts.contains_key(&ai.hash));
+    assert_eq!(ai.lines, vec![LineRange::Single(2), LineRange::Single(4)]);
+    assert!(ai.ai_timestamp.is_some());
+    assert!(ai.human_author.is_none());
+
+    assert_eq!(edited.lines, vec![LineRange::Single(3)]);
+    assert_eq!(edited.human_author.as_deref(), Some("Test User"));
+    assert!(edited.human_edit_timestamp.unwrap() >= edited.ai_timestamp.unwrap());
+
+    // Human-edited lines stay out of the attestations, as in 3.0.0
+    let attested: Vec<&LineRange> = commit
+        .authorship_log
+        .attestations
+        .iter()
+        .flat_map(|file| file.entries.iter().flat_map(|entry| &entry.line_ranges))
+        .collect();
+    assert!(!attested.iter().any(|range| range.contains(3)));
+}
+
+#[test]
+fn test_blame_reads_3_0_0_notes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("legacy.txt");
+    file.set_contents(lines!["human line", "ai line".ai()]);
+    let commit = repo.stage_all_and_commit("Add legacy note").unwrap();
+
+    // Rewrite the note as an older client would have written it
+    let mut log = commit.authorship_log.clone();
+    log.metadata.schema_version = "authorship/3.0.0".to_string();
+    log.metadata.line_states.clear();
+    let note = log.serialize_to_string().unwrap();
+    repo.git(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-m",
+        &note,
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    let output = repo.git_ai(&["blame", "legacy.txt"]).unwrap();
+    assert!(output.contains("mock_ai"), "{}", output);
+}
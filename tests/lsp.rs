@@ -0,0 +1,154 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::{Value, json};
+
+/// lib.rs: a human function followed by a function written by mock_ai
+fn setup_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn human() {", "    1", "}", ""]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(
+        4,
+        lines![
+            "fn generated() -> u32 {".ai(),
+            "    let x = 2;".ai(),
+            "    x * 2".ai(),
+            "}"
+        ],
+    );
+    repo.stage_all_and_commit("Add generated function").unwrap();
+    repo
+}
+
+/// Send `messages` (ending with shutdown and exit) and return the responses by request id
+fn run_session(repo: &TestRepo, messages: &[Value]) -> Vec<Value> {
+    let mut input = Vec::new();
+    for message in messages.iter().chain([
+        &json!({ "jsonrpc": "2.0", "id": 999, "method": "shutdown" }),
+        &json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]) {
+        let body = message.to_string();
+        input.extend(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).into_bytes());
+    }
+    let output = repo.git_ai_with_stdin(&["lsp"], &input).unwrap();
+
+    let mut responses = Vec::new();
+    let mut rest = output.as_str();
+    while let Some(header_end) = rest.find("\r\n\r\n") {
+        let length: usize = rest[..header_end]
+            .trim()
+            .strip_prefix("Content-Length: ")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let body = &rest[header_end + 4..header_end + 4 + length];
+        responses.push(serde_json::from_str(body).unwrap());
+        rest = &rest[header_end + 4 + length..];
+    }
+    responses
+}
+
+fn response(responses: &[Value], id: u64) -> &Value {
+    responses
+        .iter()
+        .find(|response| response["id"] == id)
+        .unwrap_or_else(|| panic!("no response {} in {:?}", id, responses))
+}
+
+#[test]
+fn test_lsp_hover_code_lens_and_blame() {
+    let repo = setup_repo();
+    let uri = url::Url::from_file_path(repo.canonical_path().join("lib.rs"))
+        .unwrap()
+        .to_string();
+    let document = json!({ "textDocument": { "uri": uri } });
+    let hover = |line: u32| {
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": 4 },
+        })
+    };
+
+    let responses = run_session(
+        &repo,
+        &[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": hover(5) }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/hover", "params": hover(1) }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "textDocument/codeLens", "params": document }),
+            json!({ "jsonrpc": "2.0", "id": 5, "method": "git-ai/blame", "params": document }),
+            json!({ "jsonrpc": "2.0", "id": 6, "method": "unknown/method", "params": {} }),
+        ],
+    );
+
+    let capabilities = &response(&responses, 1)["result"]["capabilities"];
+    assert_eq!(capabilities["hoverProvider"], true);
+
+    let hover = response(&responses, 2)["result"]["contents"]["value"]
+        .as_str()
+        .unwrap();
+    assert!(hover.contains("**mock_ai**"), "{hover}");
+    assert!(hover.contains("Human: Test User"), "{hover}");
+    assert_eq!(response(&responses, 3)["result"], Value::Null);
+
+    let lenses = response(&responses, 4)["result"].as_array().unwrap();
+    let titles: Vec<(u64, &str)> = lenses
+        .iter()
+        .map(|lens| {
+            (
+                lens["range"]["start"]["line"].as_u64().unwrap(),
+                lens["command"]["title"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            (0, "AI 0% (0/3 lines)"),
+            (4, "AI 75% (3/4 lines) · mock_ai")
+        ]
+    );
+
+    let blame = &response(&responses, 5)["result"];
+    assert_eq!(blame["lines"].as_object().unwrap().len(), 1, "{blame}");
+    assert_eq!(response(&responses, 6)["error"]["code"], -32601);
+    assert_eq!(response(&responses, 999)["result"], Value::Null);
+}
+
+#[test]
+fn test_lsp_blames_unsaved_buffer() {
+    let repo = setup_repo();
+    let path = repo.canonical_path().join("lib.rs");
+    let uri = url::Url::from_file_path(&path).unwrap().to_string();
+    let text = format!("// edited\n{}", std::fs::read_to_string(&path).unwrap());
+
+    let responses = run_session(
+        &repo,
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": { "uri": uri, "languageId": "rust", "version": 1, "text": text },
+                },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "textDocument/hover",
+                "params": { "textDocument": { "uri": uri }, "position": { "line": 6, "character": 0 } },
+            }),
+        ],
+    );
+
+    let hover = response(&responses, 1)["result"]["contents"]["value"]
+        .as_str()
+        .unwrap();
+    assert!(hover.contains("**mock_ai**"), "{hover}");
+}
@@ -0,0 +1,159 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::{Value, json};
+
+/// lib.rs: a human function followed by a function written by mock_ai
+fn setup_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn human() {", "    1", "}", ""]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(
+        4,
+        lines![
+            "fn generated() -> u32 {".ai(),
+            "    let x = 2;".ai(),
+            "    x * 2".ai(),
+            "}"
+        ],
+    );
+    repo.stage_all_and_commit("Add generated function").unwrap();
+    repo
+}
+
+/// Send `messages` and return the responses by request id
+fn run_session(repo: &TestRepo, messages: &[Value]) -> Vec<Value> {
+    let mut input = String::new();
+    for message in messages {
+        input.push_str(&message.to_string());
+        input.push('\n');
+    }
+    let output = repo.git_ai_with_stdin(&["mcp"], input.as_bytes()).unwrap();
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn response(responses: &[Value], id: u64) -> &Value {
+    responses
+        .iter()
+        .find(|response| response["id"] == id)
+        .unwrap_or_else(|| panic!("no response {} in {:?}", id, responses))
+}
+
+fn call(id: u64, name: &str, arguments: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    })
+}
+
+/// Text content of a successful tool call, parsed as JSON
+fn tool_output(responses: &[Value], id: u64) -> Value {
+    let result = &response(responses, id)["result"];
+    assert_eq!(result["isError"], false, "{result}");
+    serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn test_mcp_initialize_and_list_tools() {
+    let repo = setup_repo();
+    let responses = run_session(
+        &repo,
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": "2025-03-26",
+                    "capabilities": {},
+                    "clientInfo": { "name": "test", "version": "1.0" },
+                },
+            }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }),
+        ],
+    );
+    assert_eq!(responses.len(), 3, "{responses:?}");
+
+    let result = &response(&responses, 1)["result"];
+    assert_eq!(result["protocolVersion"], "2025-03-26");
+    assert_eq!(result["serverInfo"]["name"], "git-ai");
+
+    let tools = response(&responses, 2)["result"]["tools"]
+        .as_array()
+        .unwrap();
+    assert_eq!(tools.len(), 4);
+    assert!(
+        tools
+            .iter()
+            .all(|tool| tool["inputSchema"]["type"] == "object")
+    );
+    assert_eq!(response(&responses, 3)["error"]["code"], -32601);
+}
+
+#[test]
+fn test_mcp_blame_show_prompt_and_stats() {
+    let repo = setup_repo();
+    let responses = run_session(
+        &repo,
+        &[
+            call(1, "blame_lines", json!({ "file": "lib.rs" })),
+            call(
+                2,
+                "blame_lines",
+                json!({ "file": "lib.rs", "start_line": 1, "end_line": 3 }),
+            ),
+            call(3, "commit_ai_stats", json!({})),
+            call(4, "search_prompts", json!({ "commit": "HEAD" })),
+            call(
+                5,
+                "search_prompts",
+                json!({ "commit": "HEAD", "tool": "other_tool" }),
+            ),
+            call(6, "blame_lines", json!({ "file": "missing.rs" })),
+        ],
+    );
+
+    let blame = tool_output(&responses, 1);
+    let lines = blame["lines"].as_object().unwrap();
+    assert_eq!(lines.len(), 1, "{blame}");
+    let (range, prompt_id) = lines.iter().next().unwrap();
+    assert_eq!(range, "4-7");
+    let prompt_id = prompt_id.as_str().unwrap();
+    assert_eq!(blame["prompts"][prompt_id]["agent_id"]["tool"], "mock_ai");
+
+    let human_only = tool_output(&responses, 2);
+    assert!(human_only["lines"].as_object().unwrap().is_empty());
+
+    let stats = tool_output(&responses, 3);
+    let head = repo.git(&["rev-parse", "HEAD"]).unwrap();
+    assert_eq!(stats["commit"], head.trim());
+    assert_eq!(stats["stats"]["ai_additions"], 4, "{stats}");
+    assert_eq!(stats["stats"]["human_additions"], 1, "{stats}");
+
+    let search = tool_output(&responses, 4);
+    assert!(search.to_string().contains(prompt_id), "{search}");
+    let filtered = tool_output(&responses, 5);
+    assert!(!filtered.to_string().contains(prompt_id), "{filtered}");
+
+    assert_eq!(response(&responses, 6)["result"]["isError"], true);
+
+    let responses = run_session(
+        &repo,
+        &[call(1, "show_prompt", json!({ "prompt_id": prompt_id }))],
+    );
+    let prompt = tool_output(&responses, 1);
+    assert_eq!(prompt["prompt_id"], prompt_id);
+    assert_eq!(prompt["commit"], head.trim());
+    assert_eq!(prompt["prompt"]["agent_id"]["tool"], "mock_ai");
+}
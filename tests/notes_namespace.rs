@@ -0,0 +1,115 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn head_sha(repo: &TestRepo) -> String {
+    repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string()
+}
+
+fn has_note(repo: &TestRepo, namespace: &str, commit_sha: &str) -> bool {
+    repo.git_og(&["notes", &format!("--ref={}", namespace), "show", commit_sha])
+        .is_ok()
+}
+
+fn remote_refs(repo: &TestRepo, remote: &str) -> String {
+    repo.git_og(&["ls-remote", remote]).unwrap()
+}
+
+#[test]
+fn test_notes_written_to_configured_namespace() {
+    let repo = TestRepo::new();
+    repo.git_og(&["config", "git-ai.notesRef", "refs/notes/ai-internal"])
+        .unwrap();
+
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(1, lines!["fn ai_one() {}".ai()]);
+    repo.stage_all_and_commit("Add AI function").unwrap();
+
+    let head = head_sha(&repo);
+    assert!(has_note(&repo, "ai-internal", &head));
+    assert!(!has_note(&repo, "ai", &head));
+
+    file.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn tail() {}".human(),
+    ]);
+}
+
+#[test]
+fn test_blame_merges_read_namespaces() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Public attribution in refs/notes/ai
+    file.insert_at(1, lines!["fn public_ai() {}".ai()]);
+    repo.stage_all_and_commit("Add public AI function").unwrap();
+
+    // Internal attribution in refs/notes/ai-internal
+    repo.git_og(&["config", "git-ai.notesRef", "ai-internal"])
+        .unwrap();
+    file.insert_at(2, lines!["fn internal_ai() {}".ai()]);
+    repo.stage_all_and_commit("Add internal AI function")
+        .unwrap();
+
+    // Without the public namespace in the read set, its lines look human
+    file.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn public_ai() {}".human(),
+        "fn internal_ai() {}".ai(),
+        "fn tail() {}".human(),
+    ]);
+
+    repo.git_og(&["config", "--add", "git-ai.readNotesRef", "ai"])
+        .unwrap();
+    file.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn public_ai() {}".ai(),
+        "fn internal_ai() {}".ai(),
+        "fn tail() {}".human(),
+    ]);
+}
+
+#[test]
+fn test_push_only_syncs_namespaces_configured_for_remote() {
+    let (repo, _upstream) = TestRepo::new_with_remote();
+    let public = TestRepo::new_bare();
+    repo.git_og(&["remote", "add", "public", public.path().to_str().unwrap()])
+        .unwrap();
+
+    repo.git_og(&["config", "git-ai.notesRef", "ai-internal"])
+        .unwrap();
+    repo.git_og(&["config", "--add", "remote.origin.gitAiNotesRef", "ai"])
+        .unwrap();
+    repo.git_og(&[
+        "config",
+        "--add",
+        "remote.origin.gitAiNotesRef",
+        "ai-internal",
+    ])
+    .unwrap();
+
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn ai_one() {}".ai()]);
+    repo.stage_all_and_commit("Add AI function").unwrap();
+
+    repo.git(&["push", "origin", "HEAD:refs/heads/main"])
+        .unwrap();
+    repo.git(&["push", "public", "HEAD:refs/heads/main"])
+        .unwrap();
+
+    let origin_refs = remote_refs(&repo, "origin");
+    assert!(
+        origin_refs.contains("refs/notes/ai-internal"),
+        "{origin_refs}"
+    );
+
+    let public_refs = remote_refs(&repo, "public");
+    assert!(public_refs.contains("refs/heads/main"), "{public_refs}");
+    assert!(!public_refs.contains("refs/notes/"), "{public_refs}");
+}
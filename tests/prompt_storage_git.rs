@@ -0,0 +1,158 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+use test_utils::fixture_path;
+
+const SESSION_ID: &str = "junie-3d1b7e2a";
+
+fn use_git_prompt_storage(repo: &mut TestRepo) {
+    repo.patch_git_ai_config(|patch| {
+        patch.prompt_storage = Some("git".to_string());
+    });
+}
+
+/// Commit a Junie edit to math.ts and return the authorship log of the new commit
+fn commit_junie_edit(repo: &TestRepo) -> AuthorshipLog {
+    let sessions_dir = tempfile::tempdir().unwrap();
+    fs::copy(
+        fixture_path("junie-session.jsonl"),
+        sessions_dir.path().join(format!("{}.jsonl", SESSION_ID)),
+    )
+    .unwrap();
+    let env = [(
+        "GIT_AI_JUNIE_SESSIONS_DIR",
+        sessions_dir.path().to_str().unwrap(),
+    )];
+
+    let mut file = repo.filename("math.ts");
+    file.set_contents(lines!["export const add = (a, b) => a + b;"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let file_path = repo.canonical_path().join("math.ts");
+    let hook_input = |event: &str| {
+        json!({
+            "hook_event_name": event,
+            "session_id": SESSION_ID,
+            "cwd": repo.canonical_path().to_string_lossy(),
+            "tool_input": { "path": file_path.to_string_lossy() }
+        })
+        .to_string()
+    };
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "junie",
+            "--hook-input",
+            &hook_input("PreToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+    fs::write(
+        &file_path,
+        "export const add = (a, b) => a + b;\nexport const multiply = (a, b) => a * b;\n",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "junie",
+            "--hook-input",
+            &hook_input("PostToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+
+    repo.stage_all_and_commit("Add multiply")
+        .unwrap()
+        .authorship_log
+}
+
+/// Prompt blob paths in the tree of `refs/ai-prompts/blobs`
+fn prompt_blob_paths(repo: &TestRepo) -> String {
+    repo.git(&["ls-tree", "-r", "--name-only", "refs/ai-prompts/blobs"])
+        .unwrap()
+}
+
+fn blob_path(oid: &str) -> String {
+    format!("{}/{}", &oid[..2], &oid[2..])
+}
+
+fn show_prompt_message_count(repo: &TestRepo, prompt_id: &str) -> usize {
+    let output = repo.git_ai(&["show-prompt", prompt_id]).unwrap();
+    let json_start = output.find('{').expect("show-prompt should print JSON");
+    // Output may be followed by stderr lines; read just the JSON document
+    let parsed: serde_json::Value = serde_json::Deserializer::from_str(&output[json_start..])
+        .into_iter()
+        .next()
+        .unwrap()
+        .unwrap();
+    parsed["prompt"]["messages"].as_array().unwrap().len()
+}
+
+#[test]
+fn test_git_prompt_storage_adds_blobs_to_one_ref() {
+    let mut repo = TestRepo::new();
+    use_git_prompt_storage(&mut repo);
+
+    let log = commit_junie_edit(&repo);
+    let (prompt_id, prompt) = log.metadata.prompts.iter().next().unwrap();
+
+    // The note references the blob instead of inlining the transcript
+    assert!(prompt.messages.is_empty());
+    let oid = prompt
+        .messages_url
+        .as_deref()
+        .and_then(|url| url.strip_prefix("git-blob:"))
+        .expect("messages_url should reference a prompt blob");
+
+    let refs = repo
+        .git(&["for-each-ref", "--format=%(refname)", "refs/ai-prompts/"])
+        .unwrap();
+    assert_eq!(refs.trim(), "refs/ai-prompts/blobs");
+    assert_eq!(prompt_blob_paths(&repo).trim(), blob_path(oid));
+    let blob = repo.git(&["cat-file", "-p", oid]).unwrap();
+    assert!(blob.contains("Add a multiply helper to math.ts"));
+
+    assert_eq!(show_prompt_message_count(&repo, prompt_id), 6);
+}
+
+#[test]
+fn test_git_prompt_storage_travels_with_push_and_fetch() {
+    let (mut local, upstream) = TestRepo::new_with_remote();
+    use_git_prompt_storage(&mut local);
+
+    let log = commit_junie_edit(&local);
+    let (prompt_id, prompt) = log.metadata.prompts.iter().next().unwrap();
+    let oid = prompt
+        .messages_url
+        .as_deref()
+        .and_then(|url| url.strip_prefix("git-blob:"))
+        .unwrap();
+
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+    let upstream_blobs = prompt_blob_paths(&upstream);
+    assert!(
+        upstream_blobs.contains(&blob_path(oid)),
+        "{}",
+        upstream_blobs
+    );
+
+    // A fresh clone has an empty prompt database, so messages can only come from the blob
+    let mut other = TestRepo::new();
+    use_git_prompt_storage(&mut other);
+    other
+        .git(&["remote", "add", "origin", upstream.path().to_str().unwrap()])
+        .unwrap();
+    other.git(&["fetch", "origin"]).unwrap();
+
+    let fetched_blobs = prompt_blob_paths(&other);
+    assert!(fetched_blobs.contains(&blob_path(oid)), "{}", fetched_blobs);
+    assert_eq!(show_prompt_message_count(&other, prompt_id), 6);
+}
@@ -0,0 +1,119 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+fn read_authorship_note(repo: &TestRepo, commit_sha: &str) -> Option<String> {
+    repo.git(&["notes", "--ref=ai", "show", commit_sha])
+        .ok()
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty())
+}
+
+fn rev_list(repo: &TestRepo, branch: &str) -> Vec<String> {
+    repo.git(&["rev-list", "--reverse", branch])
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rewrite `branch` with filter-branch (no hooks) and write a filter-repo style commit map
+fn rewrite_history(repo: &TestRepo, branch: &str, tree_filter: &str) -> String {
+    let before = rev_list(repo, branch);
+    repo.git_og(&[
+        "filter-branch",
+        "-f",
+        "--tree-filter",
+        tree_filter,
+        "--",
+        branch,
+    ])
+    .unwrap();
+    let after = rev_list(repo, branch);
+    assert_eq!(before.len(), after.len());
+
+    let mut map = String::from("old                                      new\n");
+    for (old, new) in before.iter().zip(after.iter()) {
+        map.push_str(&format!("{} {}\n", old, new));
+    }
+    let map_path = repo.path().join(".git").join("commit-map");
+    fs::write(&map_path, map).unwrap();
+    map_path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_remap_notes_after_path_rename_and_file_removal() {
+    let repo = TestRepo::new();
+    let mut lib = repo.filename("src/lib.rs");
+    lib.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    lib.insert_at(1, lines!["fn ai_one() {}".ai()]);
+    let mut secret = repo.filename("secret.txt");
+    secret.set_contents(lines!["TOKEN=abc".ai()]);
+    repo.stage_all_and_commit("Add AI code and secret").unwrap();
+
+    lib.insert_at(2, lines!["fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("More AI code").unwrap();
+
+    let branch = repo.current_branch();
+    let map_path = rewrite_history(
+        &repo,
+        &branch,
+        "rm -f secret.txt; if [ -d src ]; then mv src core; fi",
+    );
+
+    let output = repo
+        .git_ai(&[
+            "remap-notes",
+            "--commit-map",
+            &map_path,
+            "--path-rename",
+            "src/:core/",
+        ])
+        .unwrap();
+    assert!(output.contains("1 file attestations dropped"), "{output}");
+
+    let new_commits = rev_list(&repo, &branch);
+    let note = read_authorship_note(&repo, &new_commits[1]).expect("rewritten commit has a note");
+    assert!(note.contains("core/lib.rs"), "{note}");
+    assert!(!note.contains("secret.txt"), "{note}");
+    assert!(!note.contains("src/lib.rs"), "{note}");
+    assert!(read_authorship_note(&repo, &new_commits[2]).is_some());
+
+    let mut core = repo.filename("core/lib.rs");
+    core.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+        "fn tail() {}".human(),
+    ]);
+}
+
+#[test]
+fn test_remap_notes_copies_unchanged_notes_and_supports_dry_run() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai()]);
+    repo.stage_all_and_commit("Add AI code").unwrap();
+    let old_head = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let old_note = read_authorship_note(&repo, &old_head).unwrap();
+
+    let branch = repo.current_branch();
+    let map_path = rewrite_history(&repo, &branch, "echo extra > extra.txt");
+    let new_head = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    assert_ne!(old_head, new_head);
+
+    repo.git_ai(&["remap-notes", "--commit-map", &map_path, "--dry-run"])
+        .unwrap();
+    assert!(read_authorship_note(&repo, &new_head).is_none());
+
+    repo.git_ai(&["remap-notes", "--commit-map", &map_path])
+        .unwrap();
+    assert_eq!(read_authorship_note(&repo, &new_head), Some(old_note));
+}
@@ -0,0 +1,286 @@
+mod repos;
+use git_ai::authorship::stats::CommitStats;
+use git_ai::commands::report::AuthorshipReport;
+use git_ai::git::authorship_traversal::load_authorship_logs_for_commits;
+use git_ai::git::find_repository_in_path;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// Extract the first complete JSON object from mixed stdout/stderr output.
+fn extract_json_object(output: &str) -> String {
+    let start = output.find('{').unwrap_or(0);
+    let end = output.rfind('}').unwrap_or(output.len().saturating_sub(1));
+    output[start..=end].to_string()
+}
+
+fn report_from_args(repo: &TestRepo, args: &[&str]) -> AuthorshipReport {
+    let raw = repo.git_ai(args).expect("git-ai report should succeed");
+    serde_json::from_str(&extract_json_object(&raw)).expect("valid report json")
+}
+
+fn stats_for_commit(repo: &TestRepo, sha: &str) -> CommitStats {
+    let raw = repo
+        .git_ai(&["stats", sha, "--json"])
+        .expect("git-ai stats should succeed");
+    serde_json::from_str(&extract_json_object(&raw)).expect("valid stats json")
+}
+
+fn commit_as(repo: &TestRepo, message: &str, author: &str, date: &str) -> String {
+    let email = format!("{}@example.com", author.to_lowercase());
+    repo.git(&["add", "-A"]).expect("add --all should succeed");
+    repo.commit_with_env(
+        message,
+        &[
+            ("GIT_AUTHOR_NAME", author),
+            ("GIT_AUTHOR_EMAIL", &email),
+            ("GIT_AUTHOR_DATE", date),
+            ("GIT_COMMITTER_DATE", date),
+        ],
+        None,
+    )
+    .expect("commit should succeed")
+    .commit_sha
+}
+
+/// Three commits by two authors across three ISO weeks:
+/// - Alice, 2024-W02: src/app.rs (2 AI, 1 human) and README.md (human)
+/// - Bob, 2024-W03: docs/guide.md (human) and src/util.rs (AI)
+/// - Alice, 2024-W04: appends AI lines to src/app.rs
+fn build_history(repo: &TestRepo) -> Vec<String> {
+    let mut app = repo.filename("src/app.rs");
+    app.set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human()
+    ]);
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project".human()]);
+    let first = commit_as(repo, "Add app", "Alice", "2024-01-10T12:00:00Z");
+
+    let mut guide = repo.filename("docs/guide.md");
+    guide.set_contents(lines!["Guide".human(), "More guide".human()]);
+    let mut util = repo.filename("src/util.rs");
+    util.set_contents(lines!["fn util() {}".ai()]);
+    let second = commit_as(repo, "Add docs and util", "Bob", "2024-01-17T12:00:00Z");
+
+    app.set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human(),
+        "fn d() {}".ai(),
+        "fn e() {}".ai()
+    ]);
+    let third = commit_as(repo, "Extend app", "Alice", "2024-01-24T12:00:00Z");
+
+    vec![first, second, third]
+}
+
+fn assert_line_stats_match(label: &str, actual: &CommitStats, expected: &[&CommitStats]) {
+    let sum = |f: fn(&CommitStats) -> u32| expected.iter().map(|s| f(s)).sum::<u32>();
+    assert_eq!(
+        actual.git_diff_added_lines,
+        sum(|s| s.git_diff_added_lines),
+        "{label}: added lines"
+    );
+    assert_eq!(
+        actual.git_diff_deleted_lines,
+        sum(|s| s.git_diff_deleted_lines),
+        "{label}: deleted lines"
+    );
+    assert_eq!(
+        actual.ai_accepted,
+        sum(|s| s.ai_accepted),
+        "{label}: ai_accepted"
+    );
+    assert_eq!(
+        actual.ai_additions,
+        sum(|s| s.ai_additions),
+        "{label}: ai_additions"
+    );
+    assert_eq!(
+        actual.human_additions,
+        sum(|s| s.human_additions),
+        "{label}: human_additions"
+    );
+    assert_eq!(
+        actual.mixed_additions,
+        sum(|s| s.mixed_additions),
+        "{label}: mixed_additions"
+    );
+}
+
+#[test]
+fn test_report_matches_stats_per_commit() {
+    let repo = TestRepo::new();
+    let shas = build_history(&repo);
+    let stats: Vec<CommitStats> = shas
+        .iter()
+        .map(|sha| stats_for_commit(&repo, sha))
+        .collect();
+    assert_eq!(stats[0].ai_accepted, 2);
+    assert_eq!(stats[1].ai_accepted, 1);
+    assert_eq!(stats[2].ai_accepted, 2);
+
+    let report = report_from_args(&repo, &["report", "--json"]);
+    assert_eq!(report.total_commits, 3);
+    assert_eq!(report.commits_with_authorship, 3);
+    assert_line_stats_match("totals", &report.totals, &[&stats[0], &stats[1], &stats[2]]);
+
+    let alice = &report.by_author["Alice <alice@example.com>"];
+    assert_eq!(alice.commits, 2);
+    assert_line_stats_match("alice", &alice.stats, &[&stats[0], &stats[2]]);
+    let bob = &report.by_author["Bob <bob@example.com>"];
+    assert_eq!(bob.commits, 1);
+    assert_line_stats_match("bob", &bob.stats, &[&stats[1]]);
+
+    for (week, stats) in ["2024-W02", "2024-W03", "2024-W04"].iter().zip(&stats) {
+        let bucket = &report.by_week[*week];
+        assert_eq!(bucket.commits, 1);
+        assert_line_stats_match(week, &bucket.stats, &[stats]);
+    }
+
+    // Every tool/model bucket sums the accepted lines `stats` attributes to it
+    for (tool_model, bucket) in &report.by_tool_model {
+        let expected: u32 = stats
+            .iter()
+            .filter_map(|s| s.tool_model_breakdown.get(tool_model))
+            .map(|t| t.ai_accepted)
+            .sum();
+        assert_eq!(bucket.stats.ai_accepted, expected, "tool {tool_model}");
+    }
+    let tool_accepted: u32 = report
+        .by_tool_model
+        .values()
+        .map(|b| b.stats.ai_accepted)
+        .sum();
+    assert_eq!(tool_accepted, report.totals.ai_accepted);
+
+    // All AI lines live under src/, docs/ and the root are human only
+    let src = &report.by_directory["src"];
+    assert_eq!(src.commits, 3);
+    assert_eq!(src.stats.ai_accepted, report.totals.ai_accepted);
+    assert_eq!(
+        src.stats.human_additions,
+        src.stats.git_diff_added_lines - src.stats.ai_accepted
+    );
+    assert_eq!(report.by_directory["docs"].stats.ai_accepted, 0);
+    assert_eq!(report.by_directory["docs"].stats.human_additions, 2);
+    assert_eq!(report.by_directory["."].stats.human_additions, 1);
+    let dir_added: u32 = report
+        .by_directory
+        .values()
+        .map(|b| b.stats.git_diff_added_lines)
+        .sum();
+    assert_eq!(dir_added, report.totals.git_diff_added_lines);
+}
+
+#[test]
+fn test_report_filters() {
+    let repo = TestRepo::new();
+    let shas = build_history(&repo);
+
+    let since = report_from_args(&repo, &["report", "--since", "2024-01-15", "--json"]);
+    assert_eq!(since.total_commits, 2);
+    assert_line_stats_match(
+        "since",
+        &since.totals,
+        &[
+            &stats_for_commit(&repo, &shas[1]),
+            &stats_for_commit(&repo, &shas[2]),
+        ],
+    );
+
+    let bob = report_from_args(&repo, &["report", "--author", "Bob", "--json"]);
+    assert_eq!(bob.total_commits, 1);
+    assert_eq!(bob.by_author.len(), 1);
+    assert_line_stats_match("author", &bob.totals, &[&stats_for_commit(&repo, &shas[1])]);
+
+    let docs = report_from_args(&repo, &["report", "--path", "docs", "--json"]);
+    assert_eq!(docs.total_commits, 1);
+    assert_eq!(docs.totals.git_diff_added_lines, 2);
+    assert_eq!(docs.totals.ai_accepted, 0);
+    assert_eq!(docs.by_directory.keys().collect::<Vec<_>>(), vec!["docs"]);
+
+    let src = report_from_args(&repo, &["report", "--path", "src", "--json"]);
+    let full = report_from_args(&repo, &["report", "--json"]);
+    assert_eq!(src.total_commits, 3);
+    assert_eq!(
+        src.totals.git_diff_added_lines,
+        full.by_directory["src"].stats.git_diff_added_lines
+    );
+    assert_eq!(src.totals.ai_accepted, 5);
+    assert_eq!(src.totals.ai_accepted, full.totals.ai_accepted);
+}
+
+#[test]
+fn test_report_csv() {
+    let repo = TestRepo::new();
+    build_history(&repo);
+
+    let csv = repo
+        .git_ai(&["report", "--csv"])
+        .expect("git-ai report --csv should succeed");
+    let lines: Vec<&str> = csv
+        .lines()
+        .skip_while(|line| !line.starts_with("dimension,"))
+        .collect();
+    assert_eq!(
+        lines[0],
+        "dimension,key,commits,added_lines,deleted_lines,ai_additions,human_additions,mixed_additions,ai_accepted,total_ai_additions,ai_share"
+    );
+
+    let row = |prefix: &str| -> Vec<String> {
+        lines
+            .iter()
+            .find(|line| line.starts_with(prefix))
+            .unwrap_or_else(|| panic!("missing csv row {prefix}"))
+            .split(',')
+            .map(str::to_string)
+            .collect()
+    };
+
+    let report = report_from_args(&repo, &["report", "--json"]);
+    let total = row("total,");
+    assert_eq!(total[2], "3");
+    assert_eq!(total[3], report.totals.git_diff_added_lines.to_string());
+    assert_eq!(total[8], report.totals.ai_accepted.to_string());
+
+    let src = row("directory,src,");
+    let src_stats = &report.by_directory["src"].stats;
+    assert_eq!(src[3], src_stats.git_diff_added_lines.to_string());
+    assert_eq!(
+        src[5], "",
+        "directory rows leave prompt-level columns empty"
+    );
+    assert_eq!(src[6], src_stats.human_additions.to_string());
+    assert_eq!(src[8], "5");
+
+    assert_eq!(row("author,Bob <bob@example.com>,")[2], "1");
+    assert_eq!(row("week,2024-W04,")[8], "2");
+}
+
+#[test]
+fn test_load_authorship_logs_for_commits() {
+    let repo = TestRepo::new();
+    let shas = build_history(&repo);
+
+    repo.filename("notes.txt")
+        .set_contents(lines!["no authorship".human()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git_og(&["commit", "-m", "Committed without git-ai"])
+        .unwrap();
+    let untracked = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    let git_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    let mut requested = shas.clone();
+    requested.push(untracked.clone());
+    let logs = load_authorship_logs_for_commits(&git_repo, &requested).unwrap();
+
+    assert_eq!(logs.len(), 3);
+    assert!(!logs.contains_key(&untracked));
+    for sha in &shas {
+        let log = &logs[sha];
+        assert_eq!(&log.metadata.base_commit_sha, sha);
+        assert!(!log.attestations.is_empty());
+    }
+}
@@ -14,6 +14,11 @@ const AI_AUTHOR_NAMES: &[&str] = &[
     "cursor",
     "codex",
     "gemini",
+    "aider",
+    "windsurf",
+    "amp",
+    "junie",
+    "rovodev",
 ];
 
 #[derive(Debug, Clone, PartialEq)]
@@ -311,7 +316,8 @@ impl<'a> TestFile<'a> {
         }
     }
 
-    /// Assert only committed lines (filters out uncommitted lines)
+    /// Assert only attributed lines (filters out "Not Committed Yet" lines; uncommitted AI lines
+    /// still in the working log show their agent and are kept)
     /// Useful for partial staging tests where some lines aren't committed yet
     pub fn assert_committed_lines<T: Into<ExpectedLine>>(&mut self, lines: Vec<T>) {
         let expected_lines: Vec<ExpectedLine> = lines.into_iter().map(|l| l.into()).collect();
@@ -0,0 +1,239 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::process::Command;
+
+fn read_authorship_note(repo: &TestRepo, commit_sha: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args([
+            "-C",
+            repo.path().to_str().expect("valid repo path"),
+            "--no-pager",
+            "notes",
+            "--ref=ai",
+            "show",
+            commit_sha,
+        ])
+        .output()
+        .expect("failed to run git notes show");
+
+    if output.status.success() {
+        let note = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if note.is_empty() { None } else { Some(note) }
+    } else {
+        None
+    }
+}
+
+fn head_sha(repo: &TestRepo) -> String {
+    repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string()
+}
+
+/// Test reverting an AI-authored commit writes a note with no AI lines
+#[test]
+fn test_revert_ai_commit() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line A".ai(), "AI line B".ai()]);
+    repo.stage_all_and_commit("Add AI lines").unwrap();
+    let ai_commit = head_sha(&repo);
+
+    repo.git(&["revert", "--no-edit", &ai_commit]).unwrap();
+    let revert_commit = head_sha(&repo);
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "Line 2".human()]);
+
+    let note = read_authorship_note(&repo, &revert_commit)
+        .expect("revert commit should have an authorship note");
+    assert!(!note.contains("file.txt"), "{note}");
+}
+
+/// Test reverting a revert restores the original AI attribution
+#[test]
+fn test_revert_of_revert_restores_ai_attribution() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line A".ai(), "AI line B".ai()]);
+    repo.stage_all_and_commit("Add AI lines").unwrap();
+    let ai_commit = head_sha(&repo);
+
+    repo.git(&["revert", "--no-edit", &ai_commit]).unwrap();
+    let revert_commit = head_sha(&repo);
+    repo.git(&["revert", "--no-edit", &revert_commit]).unwrap();
+
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line A".ai(),
+        "AI line B".ai(),
+        "Line 2".human(),
+    ]);
+
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.ai_accepted, 2);
+}
+
+/// Test reverting a human deletion brings the deleted AI lines back as AI
+#[test]
+fn test_revert_restores_deleted_ai_lines() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines![
+        "Line 1",
+        "AI line A".ai(),
+        "AI line B".ai(),
+        "Line 2"
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.delete_at(1);
+    repo.stage_all_and_commit("Remove AI line A").unwrap();
+    let deletion_commit = head_sha(&repo);
+
+    repo.git(&["revert", "--no-edit", &deletion_commit])
+        .unwrap();
+
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line A".ai(),
+        "AI line B".ai(),
+        "Line 2".human(),
+    ]);
+}
+
+/// Test reverting several commits in one invocation
+#[test]
+fn test_revert_multiple_commits() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "AI line A".ai(), "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["AI other".ai()]);
+    repo.stage_all_and_commit("Add other file").unwrap();
+    let add_other = head_sha(&repo);
+
+    file.delete_at(1);
+    repo.stage_all_and_commit("Remove AI line A").unwrap();
+    let deletion_commit = head_sha(&repo);
+    let original_head = deletion_commit.clone();
+
+    repo.git(&["revert", "--no-edit", &deletion_commit, &add_other])
+        .unwrap();
+
+    let new_commits = repo
+        .git(&["rev-list", &format!("{}..HEAD", original_head)])
+        .unwrap();
+    let new_commits: Vec<&str> = new_commits.lines().collect();
+    assert_eq!(new_commits.len(), 2);
+    for commit in &new_commits {
+        assert!(
+            read_authorship_note(&repo, commit).is_some(),
+            "revert commit {} should have an authorship note",
+            commit
+        );
+    }
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI line A".ai(), "Line 2".human(),]);
+    assert!(!repo.path().join("other.txt").exists());
+}
+
+/// Test `git revert --no-commit` followed by a manual commit
+#[test]
+fn test_revert_no_commit_then_commit() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines![
+        "Line 1",
+        "AI line A".ai(),
+        "AI line B".ai(),
+        "Line 2"
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.delete_range(1, 3);
+    repo.stage_all_and_commit("Remove AI lines").unwrap();
+    let deletion_commit = head_sha(&repo);
+    let head_before = deletion_commit.clone();
+
+    repo.git(&["revert", "--no-commit", &deletion_commit])
+        .unwrap();
+    assert_eq!(head_sha(&repo), head_before, "--no-commit must not commit");
+
+    repo.git(&["commit", "-m", "Restore AI lines"]).unwrap();
+
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line A".ai(),
+        "AI line B".ai(),
+        "Line 2".human(),
+    ]);
+}
+
+/// Test a conflicting revert resolved with --continue
+#[test]
+fn test_revert_with_conflict_and_continue() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.replace_at(1, "AI version".ai());
+    repo.stage_all_and_commit("AI change").unwrap();
+    let ai_commit = head_sha(&repo);
+
+    file.replace_at(1, "Human version".human());
+    repo.stage_all_and_commit("Human change").unwrap();
+
+    assert!(
+        repo.git(&["revert", "--no-edit", &ai_commit]).is_err(),
+        "Should have conflict"
+    );
+
+    std::fs::write(repo.path().join("file.txt"), "Line 1\nLine 2\nLine 3").unwrap();
+    repo.git(&["add", "file.txt"]).unwrap();
+    repo.git_with_env(&["revert", "--continue"], &[("GIT_EDITOR", "true")], None)
+        .unwrap();
+
+    let revert_commit = head_sha(&repo);
+    assert!(read_authorship_note(&repo, &revert_commit).is_some());
+    file.assert_lines_and_blame(lines!["Line 1".human(), "Line 2".human(), "Line 3".human()]);
+}
+
+/// Test revert --abort leaves no authorship behind
+#[test]
+fn test_revert_abort() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.replace_at(1, "AI version".ai());
+    repo.stage_all_and_commit("AI change").unwrap();
+    let ai_commit = head_sha(&repo);
+
+    file.replace_at(1, "Human version".human());
+    repo.stage_all_and_commit("Human change").unwrap();
+    let head_before = head_sha(&repo);
+
+    assert!(repo.git(&["revert", "--no-edit", &ai_commit]).is_err());
+    repo.git(&["revert", "--abort"]).unwrap();
+
+    assert_eq!(head_sha(&repo), head_before);
+    file.assert_lines_and_blame(lines!["Line 1".human(), "Human version".human()]);
+}
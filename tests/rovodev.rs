@@ -0,0 +1,199 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::CheckpointKind;
+use git_ai::commands::checkpoint_agent::agent_presets::{
+    AgentCheckpointFlags, AgentCheckpointPreset, AgentRunResult,
+};
+use git_ai::commands::checkpoint_agent::rovodev_preset::RovoDevPreset;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+use test_utils::fixture_path;
+
+const SESSION_ID: &str = "4c2e8f1a-9b7d-4a3e-8c5f-1d6b2a9e7f30";
+const MODEL: &str = "anthropic:claude-sonnet-4@20250514";
+
+fn run_rovodev_preset(
+    hook_input: serde_json::Value,
+) -> Result<AgentRunResult, git_ai::error::GitAiError> {
+    RovoDevPreset.run(AgentCheckpointFlags {
+        hook_input: Some(hook_input.to_string()),
+    })
+}
+
+#[test]
+fn test_parse_rovodev_session_context() {
+    let fixture = fixture_path("rovodev-session-context.json");
+    let (transcript, model) =
+        RovoDevPreset::transcript_and_model_from_rovodev_session(fixture.to_str().unwrap())
+            .expect("Failed to parse Rovo Dev session");
+
+    assert_eq!(model.as_deref(), Some(MODEL));
+
+    let messages = transcript.messages();
+    // System prompts are skipped
+    assert!(matches!(
+        &messages[0],
+        Message::User { text, timestamp: Some(_) } if text == "Add a multiply helper to math.ts"
+    ));
+    assert!(matches!(&messages[1], Message::Thinking { .. }));
+    assert!(
+        matches!(&messages[2], Message::Assistant { text, .. } if text == "Let me look at `math.ts`.")
+    );
+
+    // String args are decoded, object args are kept as-is
+    match &messages[3] {
+        Message::ToolUse { name, input, .. } => {
+            assert_eq!(name, "open_files");
+            assert_eq!(input, &json!({ "file_paths": ["math.ts"] }));
+        }
+        other => panic!("Expected open_files tool use, got {:?}", other),
+    }
+    match &messages[4] {
+        Message::ToolUse { name, input, .. } => {
+            assert_eq!(name, "find_and_replace_code");
+            assert_eq!(input["file_path"], "math.ts");
+        }
+        other => panic!("Expected find_and_replace_code tool use, got {:?}", other),
+    }
+
+    // Multi-part prompts are joined; tool returns are not prompts
+    assert!(matches!(
+        &messages[5],
+        Message::User { text, .. } if text == "Also keep it exported\nfrom the index"
+    ));
+    assert!(matches!(&messages[6], Message::Assistant { .. }));
+    assert_eq!(messages.len(), 7);
+}
+
+#[test]
+fn test_rovodev_preset_pre_tool_use_is_human_checkpoint() {
+    let result = run_rovodev_preset(json!({
+        "hook_event_name": "PreToolUse",
+        "session_id": SESSION_ID,
+        "cwd": "/workspace/app",
+        "tool_name": "find_and_replace_code",
+        "tool_input": { "file_path": "/workspace/app/math.ts" }
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::Human);
+    assert_eq!(result.agent_id.tool, "rovodev");
+    assert_eq!(result.agent_id.id, SESSION_ID);
+    assert_eq!(
+        result.will_edit_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+}
+
+#[test]
+fn test_rovodev_preset_post_tool_use_reads_session_context() {
+    let fixture = fixture_path("rovodev-session-context.json");
+    let result = run_rovodev_preset(json!({
+        "hook_event_name": "PostToolUse",
+        "session_id": SESSION_ID,
+        "cwd": "/workspace/app",
+        "tool_input": { "file_path": "/workspace/app/math.ts" },
+        "transcript_path": fixture.to_string_lossy(),
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, MODEL);
+    assert_eq!(
+        result.edited_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert_eq!(result.transcript.unwrap().messages().len(), 7);
+
+    let missing = run_rovodev_preset(json!({
+        "hook_event_name": "PostToolUse",
+        "session_id": SESSION_ID,
+        "cwd": "/workspace/app",
+        "transcript_path": "/nonexistent/session_context.json",
+    }))
+    .unwrap();
+    assert_eq!(missing.agent_id.model, "unknown");
+    assert!(missing.transcript.unwrap().messages().is_empty());
+}
+
+#[test]
+fn test_rovodev_hooks_attribute_session_edits() {
+    let repo = TestRepo::new();
+    let sessions_dir = tempfile::tempdir().unwrap();
+    let session_dir = sessions_dir.path().join(SESSION_ID);
+    fs::create_dir_all(&session_dir).unwrap();
+    fs::copy(
+        fixture_path("rovodev-session-context.json"),
+        session_dir.join("session_context.json"),
+    )
+    .unwrap();
+    let env = [(
+        "GIT_AI_ROVODEV_SESSIONS_DIR",
+        sessions_dir.path().to_str().unwrap(),
+    )];
+
+    let mut file = repo.filename("math.ts");
+    file.set_contents(lines!["export const add = (a, b) => a + b;", "// end"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let file_path = repo.canonical_path().join("math.ts");
+    let hook_input = |event: &str| {
+        json!({
+            "hook_event_name": event,
+            "session_id": SESSION_ID,
+            "cwd": repo.canonical_path().to_string_lossy(),
+            "tool_name": "find_and_replace_code",
+            "tool_input": { "file_path": file_path.to_string_lossy() }
+        })
+        .to_string()
+    };
+
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "rovodev",
+            "--hook-input",
+            &hook_input("PreToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+    fs::write(
+        &file_path,
+        "export const add = (a, b) => a + b;\nexport const multiply = (a, b) => a * b;\n// end",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "rovodev",
+            "--hook-input",
+            &hook_input("PostToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "export const add = (a, b) => a + b;".human(),
+        "export const multiply = (a, b) => a * b;".ai(),
+        "// end".human(),
+    ]);
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("Should record the Rovo Dev session");
+    assert_eq!(prompt.agent_id.tool, "rovodev");
+    assert_eq!(prompt.agent_id.id, SESSION_ID);
+    assert_eq!(prompt.agent_id.model, MODEL);
+}
@@ -0,0 +1,248 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::api::{
+    ApiClient, ApiContext, BundleData, CasMessagesObject, CasObject, CasUploadRequest,
+    CreateBundleRequest,
+};
+use git_ai::authorship::authorship_log::PromptRecord;
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::AgentId;
+use git_ai::commands::serve::{ServeStore, Server};
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use test_utils::fixture_path;
+
+/// Start `git-ai serve` on a free localhost port in a background thread
+fn start_server(api_key: Option<&str>) -> (tempfile::TempDir, PathBuf, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("serve.db");
+    let server = Server::bind("127.0.0.1:0", ServeStore::open(&db_path).unwrap())
+        .unwrap()
+        .with_api_key(api_key.map(str::to_string));
+    let url = server.public_url().to_string();
+    std::thread::spawn(move || server.run());
+    (dir, db_path, url)
+}
+
+fn client(url: &str, api_key: Option<&str>) -> ApiClient {
+    let mut context = ApiContext::without_auth(Some(url.to_string()));
+    context.api_key = api_key.map(str::to_string);
+    ApiClient::new(context)
+}
+
+fn cas_object(content: serde_json::Value) -> CasObject {
+    let canonical = serde_json_canonicalizer::to_string(&content).unwrap();
+    CasObject {
+        hash: format!("{:x}", Sha256::digest(canonical.as_bytes())),
+        content,
+        metadata: HashMap::from([("kind".to_string(), "prompt".to_string())]),
+    }
+}
+
+#[test]
+fn test_serve_cas_upload_and_read() {
+    let (_dir, _db, url) = start_server(None);
+    let client = client(&url, None);
+
+    let good = cas_object(json!({ "messages": [{ "type": "user", "text": "hello" }] }));
+    let mut tampered = cas_object(json!({ "messages": [] }));
+    tampered.hash = "0".repeat(64);
+
+    let upload = client
+        .upload_cas(CasUploadRequest {
+            objects: vec![good.clone(), tampered.clone()],
+        })
+        .unwrap();
+    assert_eq!(upload.success_count, 1);
+    assert_eq!(upload.failure_count, 1);
+    assert_eq!(upload.results[0].status, "ok");
+    assert_eq!(upload.results[1].status, "error");
+    assert!(
+        upload.results[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Hash mismatch")
+    );
+
+    let missing = "f".repeat(64);
+    let read = client
+        .read_ca_prompt_store(&[&good.hash, &missing])
+        .unwrap();
+    assert_eq!(read.success_count, 1);
+    assert_eq!(read.failure_count, 1);
+    assert_eq!(read.results[0].content.as_ref(), Some(&good.content));
+    assert_eq!(read.results[1].status, "error");
+
+    // Nothing found at all is reported like the hosted API does, as a 404
+    let read = client.read_ca_prompt_store(&[&missing]).unwrap();
+    assert_eq!(read.success_count, 0);
+
+    // messages_url points at {api_base_url}/cas/{hash}
+    let response = minreq::get(format!("{}/cas/{}", url, good.hash))
+        .send()
+        .unwrap();
+    assert_eq!(response.status_code, 200);
+    let content: serde_json::Value = serde_json::from_str(response.as_str().unwrap()).unwrap();
+    assert_eq!(content, good.content);
+}
+
+#[test]
+fn test_serve_create_bundle() {
+    let (_dir, _db, url) = start_server(None);
+    let client = client(&url, None);
+
+    let prompt = PromptRecord {
+        agent_id: AgentId {
+            tool: "claude".to_string(),
+            id: "session-1".to_string(),
+            model: "claude-sonnet-4".to_string(),
+        },
+        human_author: Some("Test User".to_string()),
+        messages: vec![Message::user("Add a multiply helper".to_string(), None)],
+        total_additions: 3,
+        total_deletions: 0,
+        accepted_lines: 3,
+        overriden_lines: 0,
+        messages_url: None,
+    };
+    let request = CreateBundleRequest {
+        title: "Multiply helper".to_string(),
+        data: BundleData {
+            prompts: HashMap::from([("abcd1234abcd1234".to_string(), prompt)]),
+            files: HashMap::new(),
+        },
+    };
+
+    let response = client.create_bundle(request.clone()).unwrap();
+    assert!(response.success);
+    assert_eq!(response.url, format!("{}/api/bundles/{}", url, response.id));
+
+    let stored = minreq::get(&response.url).send().unwrap();
+    assert_eq!(stored.status_code, 200);
+    let stored: CreateBundleRequest = serde_json::from_str(stored.as_str().unwrap()).unwrap();
+    assert_eq!(stored, request);
+
+    let empty = CreateBundleRequest {
+        title: "Empty".to_string(),
+        data: BundleData {
+            prompts: HashMap::new(),
+            files: HashMap::new(),
+        },
+    };
+    let err = client.create_bundle(empty).unwrap_err();
+    assert!(err.to_string().contains("at least one prompt"), "{}", err);
+}
+
+#[test]
+fn test_serve_requires_api_key() {
+    let (_dir, _db, url) = start_server(Some("team-secret"));
+    let object = cas_object(json!({ "messages": [] }));
+
+    let err = client(&url, None)
+        .upload_cas(CasUploadRequest {
+            objects: vec![object.clone()],
+        })
+        .unwrap_err();
+    assert!(err.to_string().contains("401"), "{}", err);
+
+    let upload = client(&url, Some("team-secret"))
+        .upload_cas(CasUploadRequest {
+            objects: vec![object],
+        })
+        .unwrap();
+    assert_eq!(upload.success_count, 1);
+}
+
+#[test]
+fn test_default_prompt_storage_against_local_server() {
+    let (_dir, db_path, url) = start_server(None);
+
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.prompt_storage = Some("default".to_string());
+    });
+
+    let sessions_dir = tempfile::tempdir().unwrap();
+    let session_id = "junie-3d1b7e2a";
+    fs::copy(
+        fixture_path("junie-session.jsonl"),
+        sessions_dir.path().join(format!("{}.jsonl", session_id)),
+    )
+    .unwrap();
+    let env = [
+        (
+            "GIT_AI_JUNIE_SESSIONS_DIR",
+            sessions_dir.path().to_str().unwrap(),
+        ),
+        ("GIT_AI_API_BASE_URL", url.as_str()),
+    ];
+
+    let mut file = repo.filename("math.ts");
+    file.set_contents(lines!["export const add = (a, b) => a + b;"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let file_path = repo.canonical_path().join("math.ts");
+    let hook_input = |event: &str| {
+        json!({
+            "hook_event_name": event,
+            "session_id": session_id,
+            "cwd": repo.canonical_path().to_string_lossy(),
+            "tool_input": { "path": file_path.to_string_lossy() }
+        })
+        .to_string()
+    };
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "junie",
+            "--hook-input",
+            &hook_input("PreToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+    fs::write(
+        &file_path,
+        "export const add = (a, b) => a + b;\nexport const multiply = (a, b) => a * b;\n",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "junie",
+            "--hook-input",
+            &hook_input("PostToolUse"),
+        ],
+        &env,
+    )
+    .unwrap();
+    repo.git(&["add", "-A"]).unwrap();
+    let note = repo
+        .commit_with_env("Add multiply", &env, None)
+        .unwrap()
+        .authorship_log;
+    let prompt = note.metadata.prompts.values().next().unwrap();
+    assert!(prompt.messages.is_empty());
+    let messages_url = prompt.messages_url.clone().unwrap();
+    let hash = messages_url
+        .strip_prefix(&format!("{}/cas/", url))
+        .expect("messages_url should point at the local server")
+        .to_string();
+
+    repo.git_ai_with_env(&["flush-cas"], &env).unwrap();
+
+    let stored = ServeStore::open(&db_path)
+        .unwrap()
+        .get_cas_object(&hash)
+        .unwrap()
+        .expect("flush-cas should upload the prompt messages");
+    let stored: CasMessagesObject = serde_json::from_value(stored).unwrap();
+    assert_eq!(stored.messages.len(), 6);
+}
@@ -275,6 +275,9 @@ fn test_partial_staging_filters_unstaged_lines() {
         "line1".human(),
         "ai_modified2".ai(),
         // ai_modified3 is ai, but it's not considered committed, because adding the subsequent uncommitted lines also added a newline char to this line
+        // The unstaged AI lines are still pending in the working log, so blame shows their agent
+        "unstaged_line1".ai(),
+        "unstaged_line2".ai(),
     ]);
 }
 
@@ -356,6 +359,10 @@ fn test_multiple_ai_sessions_with_partial_staging() {
         "ai1_line1".ai(),
         "ai1_line2".ai(),
         // ai1_line3 is ai, but it's not considered committed, because adding the subsequent uncommitted lines also added a newline char to this line
+        // The second session's lines are still pending in the working log, so blame shows their agent
+        "ai2_line1".ai(),
+        "ai2_line2".ai(),
+        "ai2_line3".ai(),
     ]);
 }
 
@@ -429,6 +436,10 @@ fn test_ai_edits_with_partial_staging() {
         "line3".human(),
         "ai_modified_line4".ai(),
         // line5 is human, but it's not considered committed, because adding line 6+ also added a newline char to line 5
+        // The unstaged AI lines are still pending in the working log, so blame shows their agent
+        "ai_line6".ai(),
+        "ai_line7".ai(),
+        "ai_line8".ai(),
     ]);
 }
 
@@ -461,6 +472,9 @@ fn test_unstaged_changes_not_committed() {
         "line3".human(),
         "ai_line4".ai(),
         // line 5 is ai, but it's not considered committed, because adding line 6+ also added a newline char to line 5
+        // The unstaged AI lines are still pending in the working log, so blame shows their agent
+        "unstaged_line6".ai(),
+        "unstaged_line7".ai(),
     ]);
 }
 
@@ -0,0 +1,195 @@
+//! Memory ceiling for transcript parsing on very large sessions.
+//!
+//! This test binary installs a counting allocator, so every test here is `#[serial]` to keep
+//! the peak measurements from overlapping.
+
+use git_ai::authorship::transcript::Message;
+use git_ai::commands::checkpoint_agent::agent_presets::{ClaudePreset, CodexPreset, DroidPreset};
+use git_ai::commands::checkpoint_agent::transcript_stream::DEFAULT_MAX_MESSAGES;
+use serde_json::json;
+use serial_test::serial;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct PeakAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+const TRANSCRIPT_BYTES: u64 = 200 * 1024 * 1024;
+const MEMORY_CEILING: usize = 64 * 1024 * 1024;
+
+/// Write ~200 MB of JSONL: mostly large tool inputs, a prompt every few lines and one
+/// line bigger than the parser's line limit
+fn write_synthetic_transcript(path: &Path, line_for: impl Fn(usize, &str) -> serde_json::Value) {
+    let mut writer = BufWriter::new(File::create(path).unwrap());
+    let file_content = "x".repeat(60 * 1024);
+    let mut written = 0u64;
+    let mut i = 0;
+    while written < TRANSCRIPT_BYTES {
+        let line = if i == 100 {
+            line_for(i, &"y".repeat(20 * 1024 * 1024))
+        } else {
+            line_for(i, &file_content)
+        };
+        let mut bytes = serde_json::to_vec(&line).unwrap();
+        bytes.push(b'\n');
+        writer.write_all(&bytes).unwrap();
+        written += bytes.len() as u64;
+        i += 1;
+    }
+    writer.flush().unwrap();
+}
+
+/// Run `parse` and return its result with the peak heap growth it caused
+fn measure_peak<T>(parse: impl FnOnce() -> T) -> (T, usize) {
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    let result = parse();
+    (result, PEAK.load(Ordering::SeqCst).saturating_sub(baseline))
+}
+
+fn assert_bounded(messages: &[Message], peak: usize) {
+    assert!(
+        peak < MEMORY_CEILING,
+        "Parsing a {} MB transcript peaked at {} MB (ceiling {} MB)",
+        TRANSCRIPT_BYTES / (1024 * 1024),
+        peak / (1024 * 1024),
+        MEMORY_CEILING / (1024 * 1024)
+    );
+    assert!(!messages.is_empty());
+    assert!(messages.len() <= DEFAULT_MAX_MESSAGES);
+
+    // The newest messages are kept, with giant tool inputs truncated
+    assert!(matches!(messages.last(), Some(Message::ToolUse { .. })));
+    for message in messages {
+        if let Message::ToolUse { input, .. } = message {
+            assert!(input.to_string().len() < 80 * 1024);
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_claude_transcript_parsing_has_memory_ceiling() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    write_synthetic_transcript(&path, |i, content| {
+        if i % 10 == 0 {
+            json!({
+                "type": "user",
+                "timestamp": "2025-06-01T10:00:00.000Z",
+                "message": { "role": "user", "content": format!("prompt {i}") }
+            })
+        } else {
+            json!({
+                "type": "assistant",
+                "timestamp": "2025-06-01T10:00:01.000Z",
+                "message": {
+                    "model": "claude-sonnet-4-20250514",
+                    "role": "assistant",
+                    "content": [{
+                        "type": "tool_use",
+                        "id": format!("toolu_{i}"),
+                        "name": "Write",
+                        "input": { "file_path": format!("src/file_{i}.rs"), "content": content }
+                    }]
+                }
+            })
+        }
+    });
+
+    let (result, peak) = measure_peak(|| {
+        ClaudePreset::transcript_and_model_from_claude_code_jsonl(path.to_str().unwrap())
+    });
+    let (transcript, model) = result.unwrap();
+
+    assert_eq!(model.as_deref(), Some("claude-sonnet-4-20250514"));
+    assert_bounded(transcript.messages(), peak);
+}
+
+#[test]
+#[serial]
+fn test_codex_rollout_parsing_has_memory_ceiling() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rollout.jsonl");
+    write_synthetic_transcript(&path, |i, content| {
+        if i % 10 == 0 {
+            json!({
+                "timestamp": "2025-06-01T10:00:00.000Z",
+                "type": "turn_context",
+                "payload": { "model": "gpt-5-codex" }
+            })
+        } else {
+            json!({
+                "timestamp": "2025-06-01T10:00:01.000Z",
+                "type": "response_item",
+                "payload": {
+                    "type": "custom_tool_call",
+                    "name": "apply_patch",
+                    "input": content
+                }
+            })
+        }
+    });
+
+    let (result, peak) = measure_peak(|| {
+        CodexPreset::transcript_and_model_from_codex_rollout_jsonl(path.to_str().unwrap())
+    });
+    let (transcript, model) = result.unwrap();
+
+    assert_eq!(model.as_deref(), Some("gpt-5-codex"));
+    assert_bounded(transcript.messages(), peak);
+}
+
+#[test]
+#[serial]
+fn test_droid_transcript_parsing_has_memory_ceiling() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    write_synthetic_transcript(&path, |i, content| {
+        json!({
+            "type": "message",
+            "timestamp": "2025-06-01T10:00:01.000Z",
+            "message": {
+                "role": "assistant",
+                "content": [
+                    { "type": "text", "text": format!("step {i}") },
+                    {
+                        "type": "tool_use",
+                        "name": "Create",
+                        "input": { "file_path": format!("src/file_{i}.rs"), "content": content }
+                    }
+                ]
+            }
+        })
+    });
+
+    let (result, peak) =
+        measure_peak(|| DroidPreset::transcript_and_model_from_droid_jsonl(path.to_str().unwrap()));
+    let (transcript, _) = result.unwrap();
+
+    assert_bounded(transcript.messages(), peak);
+}
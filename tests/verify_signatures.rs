@@ -0,0 +1,215 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::{TestRepo, get_binary_path};
+use std::fs;
+use std::process::Command;
+
+fn head_sha(repo: &TestRepo) -> String {
+    repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string()
+}
+
+/// Generate an ed25519 key inside the repo's .git dir and configure ssh note signing
+fn configure_ssh_signing(repo: &TestRepo) {
+    let git_dir = repo.path().join(".git");
+    let key_path = git_dir.join("signing_key");
+    let status = Command::new("ssh-keygen")
+        .args([
+            "-q",
+            "-t",
+            "ed25519",
+            "-N",
+            "",
+            "-C",
+            "test@example.com",
+            "-f",
+        ])
+        .arg(&key_path)
+        .status()
+        .expect("failed to run ssh-keygen");
+    assert!(status.success());
+
+    let public_key = fs::read_to_string(key_path.with_extension("pub")).unwrap();
+    let allowed_signers = git_dir.join("allowed_signers");
+    fs::write(
+        &allowed_signers,
+        format!("test@example.com {}", public_key.trim()),
+    )
+    .unwrap();
+
+    repo.git(&["config", "gpg.format", "ssh"]).unwrap();
+    repo.git(&["config", "user.signingkey", key_path.to_str().unwrap()])
+        .unwrap();
+    repo.git(&[
+        "config",
+        "gpg.ssh.allowedSignersFile",
+        allowed_signers.to_str().unwrap(),
+    ])
+    .unwrap();
+    repo.git(&["config", "git-ai.signNotes", "true"]).unwrap();
+}
+
+fn commit_ai_lines(repo: &TestRepo) {
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}", "fn tail() {}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["fn ai_one() {}".ai(), "fn ai_two() {}".ai()]);
+    repo.stage_all_and_commit("Add AI functions").unwrap();
+}
+
+#[test]
+fn test_verify_reports_valid_signature() {
+    let repo = TestRepo::new();
+    configure_ssh_signing(&repo);
+    commit_ai_lines(&repo);
+
+    let note = repo
+        .git(&["notes", "--ref=ai", "show", &head_sha(&repo)])
+        .unwrap();
+    assert!(
+        note.contains("\"signature\": {\"format\":\"ssh\""),
+        "{note}"
+    );
+
+    let output = repo.git_ai(&["verify", "HEAD"]).unwrap();
+    assert!(output.contains("signed by test@example.com"), "{output}");
+
+    let output = repo.git_ai(&["verify", "HEAD~1..HEAD"]).unwrap();
+    assert!(
+        output.contains("1 valid, 0 invalid, 0 missing signatures"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_verify_reports_tampered_note_as_invalid() {
+    let repo = TestRepo::new();
+    configure_ssh_signing(&repo);
+    commit_ai_lines(&repo);
+    let head = head_sha(&repo);
+
+    let note = repo.git(&["notes", "--ref=ai", "show", &head]).unwrap();
+    let tampered = note.replacen(" 2-3\n", " 1-3\n", 1);
+    assert_ne!(note, tampered, "{note}");
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", &tampered, &head])
+        .unwrap();
+
+    // verify exits non-zero on invalid signatures, so run it directly to read the report
+    let output = Command::new(get_binary_path())
+        .args(["verify", "--json", "HEAD"])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["invalid"], 1);
+    assert_eq!(report["commits"][0]["signature"]["status"], "invalid");
+}
+
+/// Run verify directly, since it exits non-zero on invalid signatures, and parse the report
+fn verify_json(repo: &TestRepo, spec: &str) -> (bool, serde_json::Value) {
+    let output = Command::new(get_binary_path())
+        .args(["verify", "--json", spec])
+        .current_dir(repo.path())
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        serde_json::from_slice(&output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_verify_reports_note_copied_to_another_commit_as_invalid() {
+    let repo = TestRepo::new();
+    configure_ssh_signing(&repo);
+    commit_ai_lines(&repo);
+    let head = head_sha(&repo);
+
+    repo.git_og(&["notes", "--ref=ai", "copy", "-f", &head, "HEAD~1"])
+        .unwrap();
+
+    let (success, report) = verify_json(&repo, "HEAD~1");
+    assert!(!success);
+    let signature = &report["commits"][0]["signature"];
+    assert_eq!(signature["status"], "invalid", "{report}");
+    assert_eq!(
+        signature["reason"],
+        format!("note was signed for commit {}", head)
+    );
+}
+
+#[test]
+fn test_verify_without_allowed_signers_is_untrusted() {
+    let repo = TestRepo::new();
+    configure_ssh_signing(&repo);
+    commit_ai_lines(&repo);
+    repo.git(&["config", "--unset", "gpg.ssh.allowedSignersFile"])
+        .unwrap();
+
+    let (success, report) = verify_json(&repo, "HEAD");
+    assert!(success);
+    assert_eq!(report["valid"], 0, "{report}");
+    assert_eq!(report["untrusted"], 1, "{report}");
+    assert_eq!(report["commits"][0]["signature"]["status"], "untrusted");
+}
+
+#[test]
+fn test_verify_rejects_commit_namespace_signature() {
+    let repo = TestRepo::new();
+    configure_ssh_signing(&repo);
+    commit_ai_lines(&repo);
+    let head = head_sha(&repo);
+    let note = repo.git(&["notes", "--ref=ai", "show", &head]).unwrap();
+
+    // Re-sign the same payload in git's commit signature namespace
+    let (payload, _) = note.trim_end().rsplit_once(",\n  \"signature\": ").unwrap();
+    let payload = format!("{}\n}}", payload);
+    let key = repo.path().join(".git").join("signing_key");
+    let mut child = Command::new("ssh-keygen")
+        .args(["-Y", "sign", "-n", "git", "-f"])
+        .arg(&key)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    use std::io::Write;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(payload.as_bytes())
+        .unwrap();
+    let signature = String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap();
+    let forged = format!(
+        "{},\n  \"signature\": {}\n}}",
+        payload.strip_suffix("\n}").unwrap(),
+        serde_json::json!({ "format": "ssh", "signature": signature })
+    );
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", &forged, &head])
+        .unwrap();
+
+    let (success, report) = verify_json(&repo, "HEAD");
+    assert!(!success);
+    assert_eq!(
+        report["commits"][0]["signature"]["status"], "invalid",
+        "{report}"
+    );
+}
+
+#[test]
+fn test_verify_reports_unsigned_notes_as_missing() {
+    let repo = TestRepo::new();
+    commit_ai_lines(&repo);
+
+    let output = repo.git_ai(&["verify", "HEAD~1..HEAD"]).unwrap();
+    assert!(
+        output.contains(&format!("missing {}", head_sha(&repo))),
+        "{output}"
+    );
+    assert!(
+        output.contains("0 valid, 0 invalid, 1 missing signatures"),
+        "{output}"
+    );
+}
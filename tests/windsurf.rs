@@ -0,0 +1,218 @@
+#[macro_use]
+mod repos;
+mod test_utils;
+
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::CheckpointKind;
+use git_ai::commands::checkpoint_agent::agent_presets::{
+    AgentCheckpointFlags, AgentCheckpointPreset, WindsurfPreset,
+};
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::json;
+use std::fs;
+use test_utils::fixture_path;
+
+const TRAJECTORY_ID: &str = "6f1c2a9e-3b7d-4e8a-9c01-5d2f7b8e4a10";
+
+fn run_windsurf_preset(
+    hook_input: serde_json::Value,
+) -> Result<
+    git_ai::commands::checkpoint_agent::agent_presets::AgentRunResult,
+    git_ai::error::GitAiError,
+> {
+    WindsurfPreset.run(AgentCheckpointFlags {
+        hook_input: Some(hook_input.to_string()),
+    })
+}
+
+#[test]
+fn test_parse_windsurf_trajectory() {
+    let steps =
+        WindsurfPreset::fetch_trajectory_payload(&fixture_path("windsurf-trajectory.jsonl"))
+            .expect("Failed to read Windsurf trajectory");
+    let (transcript, model) = WindsurfPreset::transcript_and_model_from_trajectory(&steps);
+
+    assert_eq!(model.as_deref(), Some("claude-sonnet-4"));
+
+    let messages = transcript.messages();
+    assert!(matches!(
+        &messages[0],
+        Message::User { text, timestamp: Some(_) } if text == "Add a multiply helper to math.ts"
+    ));
+    assert!(matches!(&messages[1], Message::Thinking { .. }));
+    assert!(matches!(
+        &messages[2],
+        Message::Assistant { text, .. } if text == "I'll add a `multiply` function to `math.ts`."
+    ));
+    assert!(matches!(&messages[3], Message::ToolUse { name, .. } if name == "view_file"));
+
+    // Edits keep only the path, not the new file contents
+    match &messages[4] {
+        Message::ToolUse { name, input, .. } => {
+            assert_eq!(name, "code_action");
+            assert_eq!(input, &json!({ "file_path": "/workspace/app/math.ts" }));
+        }
+        other => panic!("Expected code_action tool use, got {:?}", other),
+    }
+
+    // Unknown step types such as checkpoints are skipped
+    assert_eq!(messages.len(), 6);
+}
+
+#[test]
+fn test_windsurf_preset_pre_write_code_is_human_checkpoint() {
+    let result = run_windsurf_preset(json!({
+        "agent_action_name": "pre_write_code",
+        "trajectory_id": TRAJECTORY_ID,
+        "execution_id": "exec-1",
+        "timestamp": "2025-06-02T10:15:06.000Z",
+        "tool_info": { "file_path": "/workspace/app/math.ts" }
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::Human);
+    assert_eq!(result.agent_id.tool, "windsurf");
+    assert_eq!(result.agent_id.id, TRAJECTORY_ID);
+    assert_eq!(
+        result.will_edit_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert_eq!(result.repo_working_dir.as_deref(), Some("/workspace/app"));
+    assert!(result.transcript.is_none());
+}
+
+#[test]
+fn test_windsurf_preset_post_write_code_reads_trajectory() {
+    let transcript_path = fixture_path("windsurf-trajectory.jsonl");
+    let result = run_windsurf_preset(json!({
+        "agent_action_name": "post_write_code",
+        "trajectory_id": TRAJECTORY_ID,
+        "tool_info": {
+            "file_path": "/workspace/app/math.ts",
+            "transcript_path": transcript_path.to_string_lossy(),
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, "claude-sonnet-4");
+    assert_eq!(
+        result.edited_filepaths,
+        Some(vec!["/workspace/app/math.ts".to_string()])
+    );
+    assert_eq!(result.transcript.unwrap().messages().len(), 6);
+    assert_eq!(
+        result
+            .agent_metadata
+            .unwrap()
+            .get("transcript_path")
+            .map(String::as_str),
+        Some(transcript_path.to_string_lossy().as_ref())
+    );
+}
+
+#[test]
+fn test_windsurf_preset_missing_trajectory_falls_back_to_empty_transcript() {
+    let result = run_windsurf_preset(json!({
+        "agent_action_name": "post_write_code",
+        "trajectory_id": TRAJECTORY_ID,
+        "model_name": "swe-1",
+        "tool_info": {
+            "file_path": "/workspace/app/math.ts",
+            "transcript_path": "/nonexistent/trajectory.jsonl",
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(result.checkpoint_kind, CheckpointKind::AiAgent);
+    assert_eq!(result.agent_id.model, "swe-1");
+    assert!(result.transcript.unwrap().messages().is_empty());
+}
+
+#[test]
+fn test_windsurf_preset_rejects_unsupported_actions() {
+    let err = run_windsurf_preset(json!({
+        "agent_action_name": "post_run_command",
+        "trajectory_id": TRAJECTORY_ID,
+    }));
+    assert!(err.is_err());
+
+    let err = run_windsurf_preset(json!({ "agent_action_name": "post_write_code" }));
+    assert!(err.is_err(), "trajectory_id is required");
+}
+
+#[test]
+fn test_windsurf_hooks_attribute_cascade_edits() {
+    let repo = TestRepo::new();
+    let transcripts_dir = tempfile::tempdir().unwrap();
+    fs::copy(
+        fixture_path("windsurf-trajectory.jsonl"),
+        transcripts_dir
+            .path()
+            .join(format!("{}.jsonl", TRAJECTORY_ID)),
+    )
+    .unwrap();
+    let env = [(
+        "GIT_AI_WINDSURF_TRANSCRIPTS_DIR",
+        transcripts_dir.path().to_str().unwrap(),
+    )];
+
+    let mut file = repo.filename("math.ts");
+    file.set_contents(lines!["export const add = (a, b) => a + b;", "// end"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let file_path = repo.canonical_path().join("math.ts");
+    let hook_input = |action: &str| {
+        json!({
+            "agent_action_name": action,
+            "trajectory_id": TRAJECTORY_ID,
+            "tool_info": { "file_path": file_path.to_string_lossy() }
+        })
+        .to_string()
+    };
+
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "windsurf",
+            "--hook-input",
+            &hook_input("pre_write_code"),
+        ],
+        &env,
+    )
+    .unwrap();
+    fs::write(
+        &file_path,
+        "export const add = (a, b) => a + b;\nexport const multiply = (a, b) => a * b;\n// end",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &[
+            "checkpoint",
+            "windsurf",
+            "--hook-input",
+            &hook_input("post_write_code"),
+        ],
+        &env,
+    )
+    .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add multiply").unwrap();
+    file.assert_lines_and_blame(lines![
+        "export const add = (a, b) => a + b;".human(),
+        "export const multiply = (a, b) => a * b;".ai(),
+        "// end".human(),
+    ]);
+
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("Should record the Cascade trajectory");
+    assert_eq!(prompt.agent_id.tool, "windsurf");
+    assert_eq!(prompt.agent_id.id, TRAJECTORY_ID);
+    assert_eq!(prompt.agent_id.model, "claude-sonnet-4");
+}